@@ -59,13 +59,13 @@ fn get_args() -> ArgMatches {
         .short('H')
         .long("hinting")
         .value_parser(hinting_value_parser)
-        .value_names(&["TYPE"]);
+        .value_names(["TYPE"]);
     let transform_arg = Arg::new("transform")
         .help("Transform to apply to glyph when rendering")
         .long("transform")
         .num_args(4);
     let rasterization_mode_group =
-        ArgGroup::new("rasterization-mode").args(&["grayscale", "bilevel", "subpixel"]);
+        ArgGroup::new("rasterization-mode").args(["grayscale", "bilevel", "subpixel"]);
     Command::new("render-glyph")
         .version("0.1")
         .author("The Pathfinder Project Developers")
@@ -104,7 +104,7 @@ fn main() {
         .unwrap();
 
     let (canvas_format, rasterization_options) = if matches.get_flag("bilevel") {
-        (Format::A8, RasterizationOptions::Bilevel)
+        (Format::A1, RasterizationOptions::Bilevel)
     } else if matches.get_flag("subpixel") {
         (Format::Rgb24, RasterizationOptions::SubpixelAa)
     } else {
@@ -159,7 +159,11 @@ fn main() {
         let row = &canvas.pixels[row_start..row_end];
         for x in 0..raster_rect.width() {
             match canvas.format {
-                Format::Rgba32 => unimplemented!(),
+                Format::Rgba32 | Format::Rgba32Straight | Format::Bgra32 => {
+                    let shade = shade(row[x as usize * 4 + 3]);
+                    line.push(shade);
+                    line.push(shade);
+                }
                 Format::Rgb24 => {
                     write!(
                         &mut line,
@@ -175,6 +179,12 @@ fn main() {
                     line.push(shade);
                     line.push(shade);
                 }
+                Format::A1 => {
+                    let set = row[x as usize / 8] & (0x80 >> (x % 8)) != 0;
+                    let shade = shade(if set { 0xff } else { 0 });
+                    line.push(shade);
+                    line.push(shade);
+                }
             }
         }
         println!("{}", line);
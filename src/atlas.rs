@@ -0,0 +1,612 @@
+// font-kit/src/atlas.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Packing rasterized glyphs into texture atlas pages, for renderers that draw text from a GPU
+//! texture.
+//!
+//! Glyphs are rasterized on first use and packed into `Canvas` pages with a shelf packer. When the
+//! pages are full, the least recently used glyphs are evicted to make room, and the space they
+//! leave behind is reused by later glyphs. The regions of each page that changed since they were
+//! last uploaded are tracked so that only those need to be sent to the GPU again.
+
+use pathfinder_geometry::rect::{RectF, RectI};
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::{Vector2F, Vector2I};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::canvas::{Canvas, Format, RasterizationOptions};
+use crate::error::AtlasError;
+use crate::font::Font;
+use crate::hinting::HintingOptions;
+use crate::loader::Loader;
+
+/// Identifies a font that has been added to a `GlyphAtlas`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FontId(u32);
+
+/// Everything that determines how a glyph is rasterized, used to look it up in a `GlyphAtlas`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphKey {
+    /// The font, as returned by `GlyphAtlas::add_font()`.
+    pub font: FontId,
+    /// The ID of the glyph in the font.
+    pub glyph_id: u32,
+    /// The size of the glyph, in points.
+    pub point_size: f32,
+    /// The fractional part of the pen position, in pixels.
+    ///
+    /// This is rounded down to the nearest multiple of one over `AtlasOptions::subpixel_steps`,
+    /// so that glyphs drawn at nearby positions share an atlas entry.
+    pub subpixel_offset: Vector2F,
+    /// The hinting to apply.
+    pub hinting_options: HintingOptions,
+    /// The antialiasing strategy to use.
+    pub rasterization_options: RasterizationOptions,
+}
+
+/// Configures the pages of a `GlyphAtlas`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasOptions {
+    /// The size of each page, in pixels.
+    pub page_size: Vector2I,
+    /// The pixel format of each page.
+    pub format: Format,
+    /// The number of blank pixels kept around each glyph, so that texture filtering doesn't
+    /// pick up its neighbors.
+    pub padding: i32,
+    /// The most pages that the atlas will create before it starts evicting glyphs.
+    pub max_pages: usize,
+    /// The number of distinct subpixel offsets per pixel, horizontally and vertically.
+    pub subpixel_steps: u32,
+}
+
+impl Default for AtlasOptions {
+    #[inline]
+    fn default() -> AtlasOptions {
+        AtlasOptions {
+            page_size: Vector2I::splat(1024),
+            format: Format::A8,
+            padding: 1,
+            max_pages: 1,
+            subpixel_steps: 4,
+        }
+    }
+}
+
+/// Where a glyph is stored in a `GlyphAtlas`, and how to place it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasEntry {
+    /// The index of the page that holds the glyph.
+    pub page: usize,
+    /// The pixels of the page that hold the glyph, without padding.
+    pub rect: RectI,
+    /// `rect` in texture coordinates, from 0.0 to 1.0 across the page.
+    pub uv_rect: RectF,
+    /// The offset of the top left corner of the glyph image from the pen position, rounded down
+    /// to whole pixels, with y pointing down.
+    pub bearing: Vector2I,
+}
+
+impl AtlasEntry {
+    /// Returns true if the glyph has no pixels, as for a space. Nothing needs to be drawn.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rect.width() <= 0 || self.rect.height() <= 0
+    }
+}
+
+/// A region of a page that has changed since dirty regions were last taken.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirtyRegion {
+    /// The index of the page that changed.
+    pub page: usize,
+    /// The pixels of the page that changed.
+    pub rect: RectI,
+}
+
+/// A cache of rasterized glyphs packed into `Canvas` pages.
+///
+/// Call `begin_frame()` at the start of each frame and `get()` for each glyph drawn. Glyphs used
+/// during the current frame are never evicted, so every entry returned during a frame stays valid
+/// until the next call to `begin_frame()`.
+#[derive(Debug)]
+pub struct GlyphAtlas<F = Font>
+where
+    F: Loader,
+{
+    options: AtlasOptions,
+    fonts: Vec<F>,
+    pages: Vec<Page>,
+    entries: HashMap<CacheKey, CachedGlyph>,
+    // The glyphs that occupy space in the pages, ordered from least to most recently used.
+    lru: BTreeMap<u64, CacheKey>,
+    // Increases every time a glyph is used.
+    generation: u64,
+    // The generation at the start of the current frame.
+    frame_start: u64,
+}
+
+impl<F> GlyphAtlas<F>
+where
+    F: Loader,
+{
+    /// Creates an empty atlas with the given options. Pages are created as they are needed.
+    pub fn new(options: AtlasOptions) -> GlyphAtlas<F> {
+        GlyphAtlas {
+            options,
+            fonts: vec![],
+            pages: vec![],
+            entries: HashMap::new(),
+            lru: BTreeMap::new(),
+            generation: 0,
+            frame_start: 0,
+        }
+    }
+
+    /// Returns the options that the atlas was created with.
+    #[inline]
+    pub fn options(&self) -> &AtlasOptions {
+        &self.options
+    }
+
+    /// Adds a font to the atlas and returns the ID to use in glyph keys.
+    pub fn add_font(&mut self, font: F) -> FontId {
+        self.fonts.push(font);
+        FontId(self.fonts.len() as u32 - 1)
+    }
+
+    /// Returns the font with the given ID, if it was added to this atlas.
+    #[inline]
+    pub fn font(&self, font_id: FontId) -> Option<&F> {
+        self.fonts.get(font_id.0 as usize)
+    }
+
+    /// Starts a new frame. Glyphs that haven't been used since this call may be evicted.
+    #[inline]
+    pub fn begin_frame(&mut self) {
+        self.frame_start = self.generation;
+    }
+
+    /// Returns the entry for a glyph, rasterizing and packing it first if it isn't in the atlas.
+    ///
+    /// If there is no room left, the least recently used glyphs from earlier frames are evicted
+    /// until there is. `AtlasError::Full` is returned if that isn't enough.
+    pub fn get(&mut self, key: &GlyphKey) -> Result<AtlasEntry, AtlasError> {
+        let subpixel_offset = self.quantize_subpixel_offset(key.subpixel_offset);
+        let cache_key = CacheKey::new(key, subpixel_offset);
+        if let Some(glyph) = self.entries.get_mut(&cache_key) {
+            if glyph.slot.is_some() {
+                self.lru.remove(&glyph.generation);
+                glyph.generation = self.generation;
+                self.lru.insert(self.generation, cache_key);
+                self.generation += 1;
+            }
+            return Ok(glyph.entry);
+        }
+
+        let font = self
+            .fonts
+            .get(key.font.0 as usize)
+            .ok_or(AtlasError::NoSuchFont)?;
+        let transform = Transform2F::from_translation(subpixel_offset);
+        let bounds = font.raster_bounds(
+            key.glyph_id,
+            key.point_size,
+            transform,
+            key.hinting_options,
+            key.rasterization_options,
+        )?;
+
+        if bounds.width() <= 0 || bounds.height() <= 0 {
+            let entry = AtlasEntry {
+                page: 0,
+                rect: RectI::default(),
+                uv_rect: RectF::default(),
+                bearing: bounds.origin(),
+            };
+            let glyph = CachedGlyph {
+                entry,
+                slot: None,
+                generation: self.generation,
+            };
+            self.entries.insert(cache_key, glyph);
+            return Ok(entry);
+        }
+
+        let mut canvas = Canvas::new(bounds.size(), self.options.format);
+        font.rasterize_glyph(
+            &mut canvas,
+            key.glyph_id,
+            key.point_size,
+            Transform2F::from_translation(-bounds.origin().to_f32()) * transform,
+            key.hinting_options,
+            key.rasterization_options,
+        )?;
+
+        let padding = self.options.padding.max(0);
+        let slot = self.allocate(bounds.size() + Vector2I::splat(padding * 2))?;
+        let rect = RectI::new(slot.rect.origin() + Vector2I::splat(padding), bounds.size());
+        let page = &mut self.pages[slot.page];
+        page.clear(slot.rect);
+        page.canvas.blit_from(
+            rect.origin(),
+            &canvas.pixels,
+            canvas.size,
+            canvas.stride,
+            canvas.format,
+        );
+        page.mark_dirty(slot.rect);
+
+        let entry = AtlasEntry {
+            page: slot.page,
+            rect,
+            uv_rect: rect.to_f32() * (Vector2F::splat(1.0) / self.options.page_size.to_f32()),
+            bearing: bounds.origin(),
+        };
+        let glyph = CachedGlyph {
+            entry,
+            slot: Some(slot),
+            generation: self.generation,
+        };
+        self.entries.insert(cache_key, glyph);
+        self.lru.insert(self.generation, cache_key);
+        self.generation += 1;
+        Ok(entry)
+    }
+
+    /// Returns the number of glyphs in the atlas.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the atlas holds no glyphs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of pages that have been created.
+    #[inline]
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Returns the page with the given index.
+    #[inline]
+    pub fn page(&self, index: usize) -> Option<&Canvas> {
+        self.pages.get(index).map(|page| &page.canvas)
+    }
+
+    /// Returns the regions of the pages that have changed since the last call, one per page at
+    /// most, and marks every page clean.
+    ///
+    /// New pages start out blank, so only the regions that glyphs were written to are reported.
+    pub fn take_dirty_regions(&mut self) -> Vec<DirtyRegion> {
+        self.pages
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, page)| {
+                page.dirty
+                    .take()
+                    .map(|rect| DirtyRegion { page: index, rect })
+            })
+            .collect()
+    }
+
+    /// Removes every glyph from the atlas. The pages are kept and cleared.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.lru.clear();
+        for page in &mut self.pages {
+            page.shelves.clear();
+            let rect = RectI::new(Vector2I::default(), page.canvas.size);
+            page.clear(rect);
+            page.mark_dirty(rect);
+        }
+    }
+
+    fn quantize_subpixel_offset(&self, offset: Vector2F) -> Vector2F {
+        let steps = self.options.subpixel_steps.max(1) as f32;
+        let fract = offset - offset.floor();
+        (fract * steps).floor().min(Vector2F::splat(steps - 1.0)) * (1.0 / steps)
+    }
+
+    // Finds room for a slot of the given size, creating pages and evicting glyphs as needed.
+    fn allocate(&mut self, size: Vector2I) -> Result<Slot, AtlasError> {
+        let page_size = self.options.page_size;
+        if size.x() > page_size.x() || size.y() > page_size.y() {
+            return Err(AtlasError::GlyphTooLarge);
+        }
+
+        loop {
+            for (index, page) in self.pages.iter_mut().enumerate() {
+                if let Some(rect) = page.allocate(size) {
+                    return Ok(Slot { page: index, rect });
+                }
+            }
+
+            if self.pages.len() < self.options.max_pages {
+                self.pages.push(Page::new(page_size, self.options.format));
+                continue;
+            }
+
+            match self.lru.first_key_value() {
+                Some((&generation, &victim)) if generation < self.frame_start => {
+                    self.lru.remove(&generation);
+                    self.evict(&victim);
+                }
+                _ => return Err(AtlasError::Full),
+            }
+        }
+    }
+
+    fn evict(&mut self, key: &CacheKey) {
+        if let Some(CachedGlyph {
+            slot: Some(slot), ..
+        }) = self.entries.remove(key)
+        {
+            self.pages[slot.page].free(slot.rect);
+        }
+    }
+}
+
+// A glyph in the cache, and the space it occupies.
+#[derive(Clone, Copy, Debug)]
+struct CachedGlyph {
+    entry: AtlasEntry,
+    slot: Option<Slot>,
+    generation: u64,
+}
+
+// The padded space that a glyph occupies in a page.
+#[derive(Clone, Copy, Debug)]
+struct Slot {
+    page: usize,
+    rect: RectI,
+}
+
+// A hashable form of `GlyphKey`, with the subpixel offset quantized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    font: FontId,
+    glyph_id: u32,
+    point_size: u32,
+    subpixel_offset: (u32, u32),
+    hinting_options: (u8, u32),
+    rasterization_options: u8,
+}
+
+impl CacheKey {
+    fn new(key: &GlyphKey, subpixel_offset: Vector2F) -> CacheKey {
+        let hinting_options = match key.hinting_options {
+            HintingOptions::None => (0, 0),
+            HintingOptions::Vertical(size) => (1, size.to_bits()),
+            HintingOptions::VerticalSubpixel(size) => (2, size.to_bits()),
+            HintingOptions::Full(size) => (3, size.to_bits()),
+        };
+        let rasterization_options = match key.rasterization_options {
+            RasterizationOptions::Bilevel => 0,
+            RasterizationOptions::GrayscaleAa => 1,
+            RasterizationOptions::SubpixelAa => 2,
+        };
+        CacheKey {
+            font: key.font,
+            glyph_id: key.glyph_id,
+            point_size: key.point_size.to_bits(),
+            subpixel_offset: (subpixel_offset.x().to_bits(), subpixel_offset.y().to_bits()),
+            hinting_options,
+            rasterization_options,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Page {
+    canvas: Canvas,
+    shelves: Vec<Shelf>,
+    dirty: Option<RectI>,
+}
+
+// A row of glyphs, filled from left to right. Shelves are kept in order from the top of the page
+// with no space between them.
+#[derive(Clone, Debug)]
+struct Shelf {
+    y: i32,
+    height: i32,
+    x: i32,
+    glyph_count: usize,
+    // Space left of `x` that evicted glyphs gave back, as sorted, disjoint ranges of x.
+    gaps: Vec<(i32, i32)>,
+}
+
+impl Shelf {
+    fn new(y: i32, height: i32) -> Shelf {
+        Shelf {
+            y,
+            height,
+            x: 0,
+            glyph_count: 0,
+            gaps: vec![],
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.glyph_count == 0
+    }
+
+    // Returns the left edge of the narrowest gap that a slot of the given width fits in, or of the
+    // free space at the end of the shelf.
+    fn find_space(&self, width: i32, page_width: i32) -> Option<i32> {
+        self.gaps
+            .iter()
+            .filter(|&&(min_x, max_x)| max_x - min_x >= width)
+            .min_by_key(|&&(min_x, max_x)| max_x - min_x)
+            .map(|&(min_x, _)| min_x)
+            .or(Some(self.x).filter(|&x| page_width - x >= width))
+    }
+
+    fn insert(&mut self, min_x: i32, width: i32) {
+        self.glyph_count += 1;
+        if min_x == self.x {
+            self.x += width;
+            return;
+        }
+        let index = self
+            .gaps
+            .iter()
+            .position(|&(gap_min_x, _)| gap_min_x == min_x)
+            .unwrap();
+        if self.gaps[index].1 - min_x == width {
+            self.gaps.remove(index);
+        } else {
+            self.gaps[index].0 += width;
+        }
+    }
+
+    fn remove(&mut self, min_x: i32, max_x: i32) {
+        self.glyph_count -= 1;
+        if self.glyph_count == 0 {
+            self.x = 0;
+            self.gaps.clear();
+            return;
+        }
+
+        let index = self
+            .gaps
+            .partition_point(|&(_, gap_max_x)| gap_max_x <= min_x);
+        let (mut min_x, mut max_x) = (min_x, max_x);
+        if index < self.gaps.len() && self.gaps[index].0 == max_x {
+            max_x = self.gaps.remove(index).1;
+        }
+        if index > 0 && self.gaps[index - 1].1 == min_x {
+            min_x = self.gaps.remove(index - 1).0;
+        }
+        if max_x == self.x {
+            self.x = min_x;
+        } else {
+            let index = self
+                .gaps
+                .partition_point(|&(_, gap_max_x)| gap_max_x <= min_x);
+            self.gaps.insert(index, (min_x, max_x));
+        }
+    }
+}
+
+impl Page {
+    fn new(size: Vector2I, format: Format) -> Page {
+        Page {
+            canvas: Canvas::new(size, format),
+            shelves: vec![],
+            dirty: None,
+        }
+    }
+
+    // Puts a slot on the shortest shelf that it fits on, unless that would waste much of the
+    // shelf's height and there's room to start a new shelf instead. New shelves are cut from
+    // empty ones where possible, and otherwise start below the last shelf.
+    fn allocate(&mut self, size: Vector2I) -> Option<RectI> {
+        let page_size = self.canvas.size;
+        let best = self
+            .shelves
+            .iter()
+            .enumerate()
+            .filter(|(_, shelf)| !shelf.is_empty() && shelf.height >= size.y())
+            .filter_map(|(index, shelf)| {
+                let x = shelf.find_space(size.x(), page_size.x())?;
+                Some((index, x, shelf.height))
+            })
+            .min_by_key(|&(_, _, height)| height);
+        if let Some((index, x, height)) = best {
+            if height * 2 <= size.y() * 3 {
+                return Some(self.insert(index, x, size));
+            }
+        }
+
+        let empty_shelf = self
+            .shelves
+            .iter()
+            .enumerate()
+            .filter(|(_, shelf)| shelf.is_empty() && shelf.height >= size.y())
+            .min_by_key(|(_, shelf)| shelf.height)
+            .map(|(index, _)| index);
+        if let Some(index) = empty_shelf {
+            let shelf = &mut self.shelves[index];
+            let rest = shelf.height - size.y();
+            shelf.height = size.y();
+            if rest > 0 {
+                let y = shelf.y + size.y();
+                self.shelves.insert(index + 1, Shelf::new(y, rest));
+            }
+            return Some(self.insert(index, 0, size));
+        }
+
+        let bottom = self
+            .shelves
+            .last()
+            .map_or(0, |shelf| shelf.y + shelf.height);
+        if page_size.y() - bottom >= size.y() {
+            self.shelves.push(Shelf::new(bottom, size.y()));
+            return Some(self.insert(self.shelves.len() - 1, 0, size));
+        }
+
+        best.map(|(index, x, _)| self.insert(index, x, size))
+    }
+
+    fn insert(&mut self, index: usize, x: i32, size: Vector2I) -> RectI {
+        let shelf = &mut self.shelves[index];
+        shelf.insert(x, size.x());
+        RectI::new(Vector2I::new(x, shelf.y), size)
+    }
+
+    // Releases a slot so that its space can be reused. Shelves that become empty are merged with
+    // empty neighbors so that taller glyphs fit, and empty shelves at the bottom of the page are
+    // removed.
+    fn free(&mut self, rect: RectI) {
+        let mut index = self
+            .shelves
+            .binary_search_by_key(&rect.min_y(), |shelf| shelf.y)
+            .unwrap();
+        self.shelves[index].remove(rect.min_x(), rect.max_x());
+        if !self.shelves[index].is_empty() {
+            return;
+        }
+
+        if index + 1 < self.shelves.len() && self.shelves[index + 1].is_empty() {
+            self.shelves[index].height += self.shelves.remove(index + 1).height;
+        }
+        if index > 0 && self.shelves[index - 1].is_empty() {
+            self.shelves[index - 1].height += self.shelves.remove(index).height;
+            index -= 1;
+        }
+        if index + 1 == self.shelves.len() {
+            self.shelves.pop();
+        }
+    }
+    fn clear(&mut self, rect: RectI) {
+        // Blitting empty coverage clears pixels in any format, including those packed into `A1`
+        // bytes.
+        let width = rect.width() as usize;
+        let empty = vec![0; width * rect.height() as usize];
+        self.canvas
+            .blit_from(rect.origin(), &empty, rect.size(), width, Format::A8);
+    }
+
+    fn mark_dirty(&mut self, rect: RectI) {
+        self.dirty = Some(match self.dirty {
+            Some(dirty) => RectI::from_points(
+                dirty.origin().min(rect.origin()),
+                dirty.lower_right().max(rect.lower_right()),
+            ),
+            None => rect,
+        });
+    }
+}
@@ -0,0 +1,734 @@
+// font-kit/src/bitmap.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bitmap glyphs, such as those embedded in the `CBDT`/`CBLC`, `EBDT`/`EBLC`, and `sbix` tables.
+//!
+//! Color emoji fonts and bitmap fonts store their glyphs as images grouped into *strikes*, each of
+//! which is designed for one size in pixels per em. To draw a glyph at another size, the strike
+//! that best matches the requested size is chosen and its image is scaled to fit.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use float_ord::FloatOrd;
+use pathfinder_geometry::rect::{RectF, RectI};
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::{Vector2F, Vector2I};
+use std::cmp::Reverse;
+use std::ops::Range;
+
+use crate::canvas::{Canvas, Format};
+use crate::color::RgbaColor;
+use crate::loader::Loader;
+
+pub(crate) const CBDT_TABLE_TAG: u32 = 0x43424454;
+pub(crate) const CBLC_TABLE_TAG: u32 = 0x43424c43;
+pub(crate) const EBLC_TABLE_TAG: u32 = 0x45424c43;
+pub(crate) const SBIX_TABLE_TAG: u32 = 0x73626978;
+
+const SBIX_GRAPHIC_TYPE_DUPE: u32 = 0x64757065;
+const SBIX_GRAPHIC_TYPE_JPG: u32 = 0x6a706720;
+const SBIX_GRAPHIC_TYPE_PNG: u32 = 0x706e6720;
+const SBIX_GRAPHIC_TYPE_TIFF: u32 = 0x74696666;
+
+const BITMAP_SIZE_RECORD_LENGTH: usize = 48;
+
+// Shrinking an image by more than this factor samples only some of its pixels.
+const MAX_SAMPLES_PER_AXIS: f32 = 8.0;
+
+/// The encoding of an embedded bitmap image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EmbeddedImageFormat {
+    /// A PNG file.
+    Png,
+    /// A JPEG file. `font-kit` does not decode these.
+    Jpeg,
+    /// A TIFF file. `font-kit` does not decode these.
+    Tiff,
+    /// Uncompressed pixels, stored row by row from the top.
+    ///
+    /// Bit depths of 1, 2, 4, and 8 store coverage values, which are drawn in the foreground
+    /// color, and a bit depth of 32 stores premultiplied BGRA colors.
+    Raw {
+        /// The number of bits per pixel.
+        bit_depth: u8,
+        /// True if rows are packed together without padding; false if each row starts on a byte
+        /// boundary.
+        bit_aligned: bool,
+    },
+}
+
+/// A set of embedded bitmaps that were designed for one size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BitmapStrike {
+    /// The horizontal and vertical number of pixels per em.
+    pub pixels_per_em: Vector2I,
+    /// The pixel density that the strike was designed for. This is always 72 for `CBLC` strikes.
+    pub pixels_per_inch: u16,
+    /// The number of bits per pixel of uncompressed images in the strike.
+    pub bit_depth: u8,
+}
+
+/// A size at which a font provides bitmap glyphs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixedSize {
+    /// The horizontal and vertical number of pixels per em.
+    pub pixels_per_em: Vector2F,
+    /// The distance from the top of the strike's line to its bottom, in pixels.
+    ///
+    /// For `sbix` strikes, which don't record it, this is the number of pixels per em.
+    pub height: i32,
+}
+
+/// An image that a font embeds for a glyph, along with the strike that it belongs to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EmbeddedImage {
+    /// How the image data is encoded.
+    pub format: EmbeddedImageFormat,
+    /// The image data, exactly as it is stored in the font.
+    pub data: Vec<u8>,
+    /// The size of the image in pixels.
+    ///
+    /// This is zero for JPEG and TIFF images from the `sbix` table, as their sizes are only stored
+    /// inside the image data.
+    pub size: Vector2I,
+    /// The position of the top left corner of the image relative to the glyph origin, in pixels of
+    /// the strike. The Y axis points down.
+    pub origin: Vector2I,
+    /// The strike that the image belongs to.
+    pub strike: BitmapStrike,
+}
+
+/// Finds the embedded image for a glyph in the strike that best matches the given size.
+///
+/// Strikes are tried in the order of `strike_preference()`, skipping those that don't contain the
+/// glyph. `CBDT` images take precedence over `sbix` ones.
+pub(crate) fn find_embedded_image<L>(
+    loader: &L,
+    glyph_id: u32,
+    pixels_per_em: f32,
+) -> Option<EmbeddedImage>
+where
+    L: Loader,
+{
+    if let (Some(cblc), Some(cbdt)) = (
+        loader.load_font_table(CBLC_TABLE_TAG),
+        loader.load_font_table(CBDT_TABLE_TAG),
+    ) {
+        if let Some(image) = find_cbdt_image(&cblc, &cbdt, glyph_id, pixels_per_em) {
+            return Some(image);
+        }
+    }
+
+    let sbix = loader.load_font_table(SBIX_TABLE_TAG)?;
+    find_sbix_image(&sbix, glyph_id, loader.glyph_count(), pixels_per_em)
+}
+
+/// Lists the strikes of the `CBLC`, `EBLC`, or `sbix` table, in the order that they're stored.
+pub(crate) fn read_fixed_sizes<L>(loader: &L) -> Vec<FixedSize>
+where
+    L: Loader,
+{
+    for table_tag in [CBLC_TABLE_TAG, EBLC_TABLE_TAG] {
+        if let Some(sizes) = loader
+            .load_font_table(table_tag)
+            .and_then(|table| read_bitmap_location_sizes(&table))
+        {
+            return sizes;
+        }
+    }
+    loader
+        .load_font_table(SBIX_TABLE_TAG)
+        .and_then(|sbix| read_sbix_sizes(&sbix))
+        .unwrap_or_default()
+}
+
+/// Draws an embedded image over an `Rgba32` canvas, scaled from its strike to `point_size`.
+///
+/// Returns false if the image is in a format that cannot be decoded.
+pub(crate) fn rasterize_embedded_image(
+    canvas: &mut Canvas,
+    image: &EmbeddedImage,
+    point_size: f32,
+    transform: Transform2F,
+    foreground_color: RgbaColor,
+) -> bool {
+    assert_eq!(canvas.format, Format::Rgba32);
+
+    let pixels_per_em = image.strike.pixels_per_em;
+    if pixels_per_em.x() == 0 || pixels_per_em.y() == 0 {
+        return false;
+    }
+    let decoded_image = match DecodedImage::new(image, foreground_color) {
+        Some(decoded_image) => decoded_image,
+        None => return false,
+    };
+
+    let scale = Vector2F::splat(point_size) / pixels_per_em.to_f32();
+    let image_to_device = transform
+        * Transform2F::from_scale(scale)
+        * Transform2F::from_translation(image.origin.to_f32());
+    decoded_image.draw(canvas, image_to_device);
+    true
+}
+
+/// Returns the indices of the given strike sizes, in pixels per em, in order of preference for the
+/// requested size.
+///
+/// The nearest strike comes first, and the larger of two equally near strikes. Every loader picks
+/// strikes this way, whether it draws color images or the glyphs of bitmap-only fonts, so a glyph
+/// is drawn from the same strike however it is rasterized.
+pub(crate) fn strike_preference(sizes: &[f32], pixels_per_em: f32) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&index| {
+        let size = sizes[index];
+        (
+            FloatOrd((size - pixels_per_em).abs()),
+            Reverse(FloatOrd(size)),
+        )
+    });
+    order
+}
+
+/// Reads the sizes of the strikes in a `CBLC` or `EBLC` table, which share a layout.
+fn read_bitmap_location_sizes(table: &[u8]) -> Option<Vec<FixedSize>> {
+    let mut reader = table;
+    let _major_version = reader.read_u16::<BigEndian>().ok()?;
+    let _minor_version = reader.read_u16::<BigEndian>().ok()?;
+    let size_count = reader.read_u32::<BigEndian>().ok()? as usize;
+    let records = reader.get(..(size_count * BITMAP_SIZE_RECORD_LENGTH))?;
+    Some(
+        records
+            .chunks(BITMAP_SIZE_RECORD_LENGTH)
+            .map(|record| {
+                // The horizontal line metrics start with the ascender and descender at offset 16,
+                // and the pixels per em are at offset 44.
+                let (ascender, descender) = (record[16] as i8, record[17] as i8);
+                FixedSize {
+                    pixels_per_em: Vector2F::new(record[44] as f32, record[45] as f32),
+                    height: ascender as i32 - descender as i32,
+                }
+            })
+            .collect(),
+    )
+}
+
+fn read_sbix_sizes(sbix: &[u8]) -> Option<Vec<FixedSize>> {
+    let mut reader = sbix;
+    let _version = reader.read_u16::<BigEndian>().ok()?;
+    let _flags = reader.read_u16::<BigEndian>().ok()?;
+    let strike_count = reader.read_u32::<BigEndian>().ok()? as usize;
+    let mut sizes = vec![];
+    for _ in 0..strike_count {
+        let strike_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+        let mut strike = sbix.get(strike_offset..)?;
+        let ppem = strike.read_u16::<BigEndian>().ok()?;
+        sizes.push(FixedSize {
+            pixels_per_em: Vector2F::splat(ppem as f32),
+            height: ppem as i32,
+        });
+    }
+    Some(sizes)
+}
+
+fn find_cbdt_image(
+    cblc: &[u8],
+    cbdt: &[u8],
+    glyph_id: u32,
+    pixels_per_em: f32,
+) -> Option<EmbeddedImage> {
+    let mut reader = cblc;
+    let _major_version = reader.read_u16::<BigEndian>().ok()?;
+    let _minor_version = reader.read_u16::<BigEndian>().ok()?;
+    let size_count = reader.read_u32::<BigEndian>().ok()? as usize;
+    let records = reader.get(..(size_count * BITMAP_SIZE_RECORD_LENGTH))?;
+
+    // The vertical pixels per em of each strike is at offset 45 of its `BitmapSize` record.
+    let sizes: Vec<f32> = records
+        .chunks(BITMAP_SIZE_RECORD_LENGTH)
+        .map(|record| record[45] as f32)
+        .collect();
+    strike_preference(&sizes, pixels_per_em)
+        .into_iter()
+        .find_map(|index| {
+            let start = index * BITMAP_SIZE_RECORD_LENGTH;
+            let record = &records[start..(start + BITMAP_SIZE_RECORD_LENGTH)];
+            read_cbdt_glyph(cblc, cbdt, record, glyph_id)
+        })
+}
+
+/// Looks up a glyph in the strike described by a `BitmapSize` record of the `CBLC` table.
+fn read_cbdt_glyph(
+    cblc: &[u8],
+    cbdt: &[u8],
+    mut record: &[u8],
+    glyph_id: u32,
+) -> Option<EmbeddedImage> {
+    let subtable_array_offset = record.read_u32::<BigEndian>().ok()? as usize;
+    let _index_tables_size = record.read_u32::<BigEndian>().ok()?;
+    let subtable_count = record.read_u32::<BigEndian>().ok()? as usize;
+    let _color_ref = record.read_u32::<BigEndian>().ok()?;
+    // Skip the horizontal and vertical line metrics.
+    let mut record = record.get(24..)?;
+    let start_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+    let end_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+    let ppem_x = record.read_u8().ok()?;
+    let ppem_y = record.read_u8().ok()?;
+    let bit_depth = record.read_u8().ok()?;
+    if glyph_id < start_glyph_id || glyph_id > end_glyph_id {
+        return None;
+    }
+    let strike = BitmapStrike {
+        pixels_per_em: Vector2I::new(ppem_x as i32, ppem_y as i32),
+        pixels_per_inch: 72,
+        bit_depth,
+    };
+
+    let subtable_array =
+        cblc.get(subtable_array_offset..(subtable_array_offset + subtable_count * 8))?;
+    for mut entry in subtable_array.chunks(8) {
+        let first_glyph_id = entry.read_u16::<BigEndian>().ok()? as u32;
+        let last_glyph_id = entry.read_u16::<BigEndian>().ok()? as u32;
+        let additional_offset = entry.read_u32::<BigEndian>().ok()? as usize;
+        if glyph_id < first_glyph_id || glyph_id > last_glyph_id {
+            continue;
+        }
+
+        let mut subtable = cblc.get((subtable_array_offset + additional_offset)..)?;
+        let index_format = subtable.read_u16::<BigEndian>().ok()?;
+        let image_format = subtable.read_u16::<BigEndian>().ok()?;
+        let image_data_offset = subtable.read_u32::<BigEndian>().ok()? as usize;
+        let (range, index_metrics) =
+            read_index_subtable(subtable, index_format, glyph_id, first_glyph_id)?;
+
+        // Glyphs without images have zero-length data.
+        if range.start >= range.end {
+            return None;
+        }
+        let data = cbdt.get((image_data_offset + range.start)..(image_data_offset + range.end))?;
+        return read_cbdt_image(data, image_format, index_metrics, strike);
+    }
+    None
+}
+
+/// Finds the range of a glyph's image data within the image data of an index subtable, along with
+/// the glyph metrics if the subtable stores them.
+///
+/// Formats 1, 2, and 3 store the data of every glyph from `first_glyph_id` on, in order, while
+/// formats 4 and 5 list the IDs of the glyphs that they store.
+fn read_index_subtable(
+    mut subtable: &[u8],
+    index_format: u16,
+    glyph_id: u32,
+    first_glyph_id: u32,
+) -> Option<(Range<usize>, Option<GlyphMetrics>)> {
+    let glyph_index = (glyph_id - first_glyph_id) as usize;
+    match index_format {
+        1 => {
+            let mut offsets = subtable.get((glyph_index * 4)..)?;
+            let start = offsets.read_u32::<BigEndian>().ok()? as usize;
+            let end = offsets.read_u32::<BigEndian>().ok()? as usize;
+            Some((start..end, None))
+        }
+        2 => {
+            let image_size = subtable.read_u32::<BigEndian>().ok()? as usize;
+            let metrics = GlyphMetrics::read_big(&mut subtable)?;
+            let start = glyph_index * image_size;
+            Some((start..(start + image_size), Some(metrics)))
+        }
+        3 => {
+            let mut offsets = subtable.get((glyph_index * 2)..)?;
+            let start = offsets.read_u16::<BigEndian>().ok()? as usize;
+            let end = offsets.read_u16::<BigEndian>().ok()? as usize;
+            Some((start..end, None))
+        }
+        4 => {
+            // Only the glyphs listed in the subtable have images.
+            let glyph_count = subtable.read_u32::<BigEndian>().ok()? as usize;
+            let pairs = subtable.get(..((glyph_count + 1) * 4))?;
+            let position = pairs
+                .chunks(4)
+                .take(glyph_count)
+                .position(|pair| u16::from_be_bytes([pair[0], pair[1]]) as u32 == glyph_id)?;
+            let mut pairs = &pairs[(position * 4)..];
+            let _glyph_id = pairs.read_u16::<BigEndian>().ok()?;
+            let start = pairs.read_u16::<BigEndian>().ok()? as usize;
+            let _next_glyph_id = pairs.read_u16::<BigEndian>().ok()?;
+            let end = pairs.read_u16::<BigEndian>().ok()? as usize;
+            Some((start..end, None))
+        }
+        5 => {
+            let image_size = subtable.read_u32::<BigEndian>().ok()? as usize;
+            let metrics = GlyphMetrics::read_big(&mut subtable)?;
+            let glyph_count = subtable.read_u32::<BigEndian>().ok()? as usize;
+            let glyph_ids = subtable.get(..(glyph_count * 2))?;
+            let position = glyph_ids
+                .chunks(2)
+                .position(|id| u16::from_be_bytes([id[0], id[1]]) as u32 == glyph_id)?;
+            let start = position * image_size;
+            Some((start..(start + image_size), Some(metrics)))
+        }
+        _ => None,
+    }
+}
+
+/// Reads one glyph's image from the `CBDT` table.
+fn read_cbdt_image(
+    mut data: &[u8],
+    image_format: u16,
+    index_metrics: Option<GlyphMetrics>,
+    strike: BitmapStrike,
+) -> Option<EmbeddedImage> {
+    let raw = |bit_aligned| EmbeddedImageFormat::Raw {
+        bit_depth: strike.bit_depth,
+        bit_aligned,
+    };
+    let (metrics, format) = match image_format {
+        1 => (GlyphMetrics::read_small(&mut data)?, raw(false)),
+        2 => (GlyphMetrics::read_small(&mut data)?, raw(true)),
+        5 => (index_metrics?, raw(true)),
+        6 => (GlyphMetrics::read_big(&mut data)?, raw(false)),
+        7 => (GlyphMetrics::read_big(&mut data)?, raw(true)),
+        17 => (
+            GlyphMetrics::read_small(&mut data)?,
+            EmbeddedImageFormat::Png,
+        ),
+        18 => (GlyphMetrics::read_big(&mut data)?, EmbeddedImageFormat::Png),
+        19 => (index_metrics?, EmbeddedImageFormat::Png),
+        // Composite bitmaps (formats 8 and 9) aren't supported.
+        _ => return None,
+    };
+    if format == EmbeddedImageFormat::Png {
+        let length = data.read_u32::<BigEndian>().ok()? as usize;
+        data = data.get(..length)?;
+    }
+
+    Some(EmbeddedImage {
+        format,
+        data: data.to_vec(),
+        size: metrics.size,
+        origin: Vector2I::new(metrics.bearing.x(), -metrics.bearing.y()),
+        strike,
+    })
+}
+
+/// The size and horizontal bearing of an embedded bitmap, in pixels.
+#[derive(Clone, Copy)]
+struct GlyphMetrics {
+    size: Vector2I,
+    bearing: Vector2I,
+}
+
+impl GlyphMetrics {
+    /// Reads a `SmallGlyphMetrics` record.
+    fn read_small(reader: &mut &[u8]) -> Option<GlyphMetrics> {
+        let height = reader.read_u8().ok()? as i32;
+        let width = reader.read_u8().ok()? as i32;
+        let bearing_x = reader.read_i8().ok()? as i32;
+        let bearing_y = reader.read_i8().ok()? as i32;
+        let _advance = reader.read_u8().ok()?;
+        Some(GlyphMetrics {
+            size: Vector2I::new(width, height),
+            bearing: Vector2I::new(bearing_x, bearing_y),
+        })
+    }
+
+    /// Reads a `BigGlyphMetrics` record, discarding the vertical metrics.
+    fn read_big(reader: &mut &[u8]) -> Option<GlyphMetrics> {
+        let metrics = GlyphMetrics::read_small(reader)?;
+        *reader = reader.get(3..)?;
+        Some(metrics)
+    }
+}
+
+fn find_sbix_image(
+    sbix: &[u8],
+    glyph_id: u32,
+    glyph_count: u32,
+    pixels_per_em: f32,
+) -> Option<EmbeddedImage> {
+    let mut reader = sbix;
+    let _version = reader.read_u16::<BigEndian>().ok()?;
+    let _flags = reader.read_u16::<BigEndian>().ok()?;
+    let strike_count = reader.read_u32::<BigEndian>().ok()? as usize;
+    let (mut strikes, mut sizes) = (vec![], vec![]);
+    for _ in 0..strike_count {
+        let strike_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+        let strike = sbix.get(strike_offset..)?;
+        let mut header = strike;
+        sizes.push(header.read_u16::<BigEndian>().ok()? as f32);
+        strikes.push(strike);
+    }
+
+    strike_preference(&sizes, pixels_per_em)
+        .into_iter()
+        .find_map(|index| read_sbix_glyph(strikes[index], glyph_id, glyph_count, true))
+}
+
+/// Looks up a glyph in an `sbix` strike. `dupe` images are followed once if `follow_dupes` is set.
+fn read_sbix_glyph(
+    strike: &[u8],
+    glyph_id: u32,
+    glyph_count: u32,
+    follow_dupes: bool,
+) -> Option<EmbeddedImage> {
+    if glyph_id >= glyph_count {
+        return None;
+    }
+    let mut header = strike;
+    let ppem = header.read_u16::<BigEndian>().ok()?;
+    let ppi = header.read_u16::<BigEndian>().ok()?;
+    let mut offsets = header.get((glyph_id as usize * 4)..)?;
+    let start = offsets.read_u32::<BigEndian>().ok()? as usize;
+    let end = offsets.read_u32::<BigEndian>().ok()? as usize;
+
+    // Glyphs without images have zero-length data.
+    if start >= end {
+        return None;
+    }
+    let mut data = strike.get(start..end)?;
+    let origin_offset_x = data.read_i16::<BigEndian>().ok()? as i32;
+    let origin_offset_y = data.read_i16::<BigEndian>().ok()? as i32;
+    let format = match data.read_u32::<BigEndian>().ok()? {
+        SBIX_GRAPHIC_TYPE_PNG => EmbeddedImageFormat::Png,
+        SBIX_GRAPHIC_TYPE_JPG => EmbeddedImageFormat::Jpeg,
+        SBIX_GRAPHIC_TYPE_TIFF => EmbeddedImageFormat::Tiff,
+        SBIX_GRAPHIC_TYPE_DUPE if follow_dupes => {
+            let original_glyph_id = data.read_u16::<BigEndian>().ok()? as u32;
+            return read_sbix_glyph(strike, original_glyph_id, glyph_count, false);
+        }
+        _ => return None,
+    };
+
+    let size = match format {
+        EmbeddedImageFormat::Png => png_size(data)?,
+        _ => Vector2I::zero(),
+    };
+    Some(EmbeddedImage {
+        format,
+        data: data.to_vec(),
+        size,
+        // The origin offset locates the bottom left corner of the image.
+        origin: Vector2I::new(origin_offset_x, -(origin_offset_y + size.y())),
+        strike: BitmapStrike {
+            pixels_per_em: Vector2I::splat(ppem as i32),
+            pixels_per_inch: ppi,
+            bit_depth: 32,
+        },
+    })
+}
+
+/// Reads the size of a PNG image from its `IHDR` chunk, which must come first.
+fn png_size(data: &[u8]) -> Option<Vector2I> {
+    let mut reader = data.get(16..)?;
+    let width = reader.read_u32::<BigEndian>().ok()?;
+    let height = reader.read_u32::<BigEndian>().ok()?;
+    Some(Vector2I::new(width as i32, height as i32))
+}
+
+/// An embedded image decoded to premultiplied RGBA.
+struct DecodedImage {
+    size: Vector2I,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl DecodedImage {
+    fn new(image: &EmbeddedImage, foreground_color: RgbaColor) -> Option<DecodedImage> {
+        match image.format {
+            EmbeddedImageFormat::Png => DecodedImage::from_png(&image.data),
+            EmbeddedImageFormat::Raw {
+                bit_depth,
+                bit_aligned,
+            } => DecodedImage::from_raw(
+                &image.data,
+                image.size,
+                bit_depth,
+                bit_aligned,
+                foreground_color,
+            ),
+            EmbeddedImageFormat::Jpeg | EmbeddedImageFormat::Tiff => None,
+        }
+    }
+
+    fn from_png(data: &[u8]) -> Option<DecodedImage> {
+        let mut decoder = png::Decoder::new(data);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().ok()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).ok()?;
+
+        let (width, height) = (info.width as usize, info.height as usize);
+        let samples = info.color_type.samples();
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in buffer.chunks(info.line_size).take(height) {
+            for pixel in row[..(width * samples)].chunks(samples) {
+                let (gray_or_red, green, blue, alpha) = match *pixel {
+                    [gray] => (gray, gray, gray, 255),
+                    [gray, alpha] => (gray, gray, gray, alpha),
+                    [red, green, blue] => (red, green, blue, 255),
+                    [red, green, blue, alpha] => (red, green, blue, alpha),
+                    _ => return None,
+                };
+                let alpha = alpha as f32 / 255.0;
+                pixels.push([
+                    gray_or_red as f32 / 255.0 * alpha,
+                    green as f32 / 255.0 * alpha,
+                    blue as f32 / 255.0 * alpha,
+                    alpha,
+                ]);
+            }
+        }
+        Some(DecodedImage {
+            size: Vector2I::new(width as i32, height as i32),
+            pixels,
+        })
+    }
+
+    fn from_raw(
+        data: &[u8],
+        size: Vector2I,
+        bit_depth: u8,
+        bit_aligned: bool,
+        foreground_color: RgbaColor,
+    ) -> Option<DecodedImage> {
+        let (width, height) = (size.x() as usize, size.y() as usize);
+        let bit_depth = bit_depth as usize;
+        if ![1, 2, 4, 8, 32].contains(&bit_depth) {
+            return None;
+        }
+        let row_bits = width * bit_depth;
+        let row_stride_bits = if bit_aligned {
+            row_bits
+        } else {
+            row_bits.div_ceil(8) * 8
+        };
+        if data.len() * 8 < row_stride_bits * height {
+            return None;
+        }
+
+        let foreground_alpha = foreground_color.a as f32 / 255.0;
+        let foreground = [
+            foreground_color.r as f32 / 255.0 * foreground_alpha,
+            foreground_color.g as f32 / 255.0 * foreground_alpha,
+            foreground_color.b as f32 / 255.0 * foreground_alpha,
+            foreground_alpha,
+        ];
+        let max_value = ((1u32 << bit_depth.min(8)) - 1) as f32;
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let bit = y * row_stride_bits + x * bit_depth;
+                let byte_index = bit / 8;
+                if bit_depth == 32 {
+                    let bgra = &data[byte_index..(byte_index + 4)];
+                    pixels.push([
+                        bgra[2] as f32 / 255.0,
+                        bgra[1] as f32 / 255.0,
+                        bgra[0] as f32 / 255.0,
+                        bgra[3] as f32 / 255.0,
+                    ]);
+                } else {
+                    // Pixels are packed from the most significant bit down.
+                    let shift = 8 - bit_depth - bit % 8;
+                    let value = (data[byte_index] >> shift) as u32 & ((1 << bit_depth) - 1);
+                    let coverage = value as f32 / max_value;
+                    pixels.push(foreground.map(|channel| channel * coverage));
+                }
+            }
+        }
+        Some(DecodedImage { size, pixels })
+    }
+
+    /// Draws the image over an `Rgba32` canvas. `image_to_device` maps image pixel coordinates to
+    /// canvas pixel coordinates.
+    fn draw(&self, canvas: &mut Canvas, image_to_device: Transform2F) {
+        let image_rect = RectF::new(Vector2F::zero(), self.size.to_f32());
+        let device_rect = (image_to_device * image_rect).round_out().to_i32();
+        let device_rect = match device_rect.intersection(RectI::new(Vector2I::zero(), canvas.size))
+        {
+            Some(device_rect) => device_rect,
+            None => return,
+        };
+
+        // Take several bilinear samples per canvas pixel when shrinking the image, so that every
+        // image pixel contributes to the result.
+        let scale = image_to_device.matrix.det().abs().sqrt();
+        if scale == 0.0 {
+            return;
+        }
+        let samples_per_axis = (1.0 / scale).ceil().clamp(1.0, MAX_SAMPLES_PER_AXIS) as usize;
+        let sample_weight = 1.0 / (samples_per_axis * samples_per_axis) as f32;
+        let device_to_image = image_to_device.inverse();
+
+        for y in device_rect.min_y()..device_rect.max_y() {
+            for x in device_rect.min_x()..device_rect.max_x() {
+                let mut color = [0.0; 4];
+                for sample_y in 0..samples_per_axis {
+                    for sample_x in 0..samples_per_axis {
+                        let offset = (Vector2F::new(sample_x as f32, sample_y as f32)
+                            + Vector2F::splat(0.5))
+                            / samples_per_axis as f32;
+                        let point = Vector2F::new(x as f32, y as f32) + offset;
+                        let sample = self.sample(device_to_image * point);
+                        for channel in 0..4 {
+                            color[channel] += sample[channel] * sample_weight;
+                        }
+                    }
+                }
+                if color[3] <= 0.0 {
+                    continue;
+                }
+
+                let start = y as usize * canvas.stride + x as usize * 4;
+                let dest = &mut canvas.pixels[start..(start + 4)];
+                for channel in 0..4 {
+                    let backdrop = dest[channel] as f32 / 255.0;
+                    let result = color[channel] + backdrop * (1.0 - color[3]);
+                    dest[channel] = (result.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+            }
+        }
+    }
+
+    /// Samples the image at a point with bilinear filtering. The image is transparent outside its
+    /// bounds.
+    fn sample(&self, point: Vector2F) -> [f32; 4] {
+        let point = point - Vector2F::splat(0.5);
+        let origin = point.floor();
+        let fraction = point - origin;
+        let (x0, y0) = (origin.x() as i32, origin.y() as i32);
+
+        let mut color = [0.0; 4];
+        for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            let weight_x = if dx == 0 {
+                1.0 - fraction.x()
+            } else {
+                fraction.x()
+            };
+            let weight_y = if dy == 0 {
+                1.0 - fraction.y()
+            } else {
+                fraction.y()
+            };
+            let weight = weight_x * weight_y;
+            if weight == 0.0 {
+                continue;
+            }
+            let (x, y) = (x0 + dx, y0 + dy);
+            if x < 0 || y < 0 || x >= self.size.x() || y >= self.size.y() {
+                continue;
+            }
+            let pixel = self.pixels[y as usize * self.size.x() as usize + x as usize];
+            for channel in 0..4 {
+                color[channel] += pixel[channel] * weight;
+            }
+        }
+        color
+    }
+}
@@ -10,11 +10,13 @@
 
 //! An in-memory bitmap surface for glyph rasterization.
 
-use pathfinder_geometry::rect::RectI;
-use pathfinder_geometry::vector::Vector2I;
+use pathfinder_geometry::rect::{RectF, RectI};
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::{Vector2F, Vector2I};
 use std::cmp;
 use std::fmt;
 
+use crate::color::RgbaColor;
 use crate::utils;
 
 static BITMAP_1BPP_TO_8BPP_LUT: [[u8; 8]; 256] = {
@@ -58,11 +60,7 @@ impl Canvas {
     /// The canvas is initialized with transparent black (all values 0).
     #[inline]
     pub fn new(size: Vector2I, format: Format) -> Canvas {
-        Canvas::with_stride(
-            size,
-            size.x() as usize * format.bytes_per_pixel() as usize,
-            format,
-        )
+        Canvas::with_stride(size, format.stride_for_width(size.x() as usize), format)
     }
 
     /// Creates a new blank canvas with the given pixel size, stride (number of bytes between
@@ -78,7 +76,6 @@ impl Canvas {
         }
     }
 
-    #[allow(dead_code)]
     pub(crate) fn blit_from_canvas(&mut self, src: &Canvas) {
         self.blit_from(
             Vector2I::default(),
@@ -93,6 +90,20 @@ impl Canvas {
     /// If the target area overlaps the boundaries of the canvas, only the drawable region is blitted.
     /// `dst_point` and `src_size` are specified in pixels. `src_stride` is specified in bytes.
     /// `src_stride` must be equal or larger than the actual data length.
+    ///
+    /// Pixels are converted from `src_format` to the format of the canvas as follows:
+    ///
+    /// * `A8` and `A1` coverage becomes white with the coverage as its alpha in the RGBA formats,
+    ///   and gray in `Rgb24`.
+    ///
+    /// * `Rgb24` subpixel coverage becomes opaque in the RGBA formats, and its green channel
+    ///   becomes the coverage in `A8` and `A1`.
+    ///
+    /// * The RGBA formats become their alpha in `A8` and `A1`, and their color over black in
+    ///   `Rgb24`. Between each other, they are premultiplied or unpremultiplied as needed, and
+    ///   formats with the same kind of alpha only have their channels reordered.
+    ///
+    /// * `A1` pixels are set where the coverage is at least one half.
     #[allow(dead_code)]
     pub(crate) fn blit_from(
         &mut self,
@@ -108,7 +119,7 @@ impl Canvas {
             "Number of pixels in src_bytes does not match stride and size."
         );
         assert!(
-            src_stride >= src_size.x() as usize * src_format.bytes_per_pixel() as usize,
+            src_stride >= src_format.stride_for_width(src_size.x() as usize),
             "src_stride must be >= than src_size.x()"
         );
 
@@ -118,98 +129,275 @@ impl Canvas {
             Some(dst_rect) => dst_rect,
             None => return,
         };
+        // Where the part of the source that lands on the canvas starts.
+        let src_origin = dst_rect.origin() - dst_point;
 
         match (self.format, src_format) {
             (Format::A8, Format::A8)
             | (Format::Rgb24, Format::Rgb24)
-            | (Format::Rgba32, Format::Rgba32) => {
-                self.blit_from_with::<BlitMemcpy>(dst_rect, src_bytes, src_stride, src_format)
+            | (Format::Rgba32, Format::Rgba32)
+            | (Format::Rgba32Straight, Format::Rgba32Straight)
+            | (Format::Bgra32, Format::Bgra32) => self.blit_from_with::<BlitMemcpy>(
+                dst_rect, src_origin, src_bytes, src_stride, src_format,
+            ),
+            (Format::Rgba32Straight, Format::Bgra32) | (Format::Bgra32, Format::Rgba32Straight) => {
+                self.blit_from_with::<BlitSwapRedBlue>(
+                    dst_rect, src_origin, src_bytes, src_stride, src_format,
+                )
+            }
+            (Format::A8, Format::Rgb24) => self.blit_from_with::<BlitRgb24ToA8>(
+                dst_rect, src_origin, src_bytes, src_stride, src_format,
+            ),
+            (Format::Rgb24, Format::A8) => self.blit_from_with::<BlitA8ToRgb24>(
+                dst_rect, src_origin, src_bytes, src_stride, src_format,
+            ),
+            (Format::Rgb24, Format::Rgba32) => self.blit_from_with::<BlitRgba32ToRgb24>(
+                dst_rect, src_origin, src_bytes, src_stride, src_format,
+            ),
+            (Format::Rgba32, Format::Rgb24) | (Format::Rgba32Straight, Format::Rgb24) => {
+                self.blit_from_with::<BlitRgb24ToRgba32>(
+                    dst_rect, src_origin, src_bytes, src_stride, src_format,
+                )
+            }
+            (Format::A8, Format::Rgba32)
+            | (Format::A8, Format::Rgba32Straight)
+            | (Format::A8, Format::Bgra32) => self.blit_from_with::<BlitRgba32ToA8>(
+                dst_rect, src_origin, src_bytes, src_stride, src_format,
+            ),
+            (Format::Rgba32, Format::A8) => self.blit_from_with::<BlitA8ToRgba32>(
+                dst_rect, src_origin, src_bytes, src_stride, src_format,
+            ),
+            (Format::A8, Format::A1) if src_origin.x() % 8 == 0 => self
+                .blit_from_with::<BlitA1ToA8>(
+                    dst_rect, src_origin, src_bytes, src_stride, src_format,
+                ),
+            _ => self.blit_from_converted(dst_rect, src_origin, src_bytes, src_stride, src_format),
+        }
+    }
+
+    /// Composites `color` over this `Rgba32` canvas, using the `A8` canvas `mask` as coverage.
+    ///
+    /// As `Rgba32` is premultiplied, the straight alpha `color` is premultiplied by its alpha and
+    /// the coverage before it is composited.
+    ///
+    /// Both canvases must be the same size.
+    pub(crate) fn fill_with_mask(&mut self, mask: &Canvas, color: RgbaColor) {
+        assert_eq!(self.format, Format::Rgba32);
+        assert_eq!(mask.format, Format::A8);
+        assert_eq!(self.size, mask.size);
+
+        let width = self.size.x() as usize;
+        for y in 0..(self.size.y() as usize) {
+            let dest_row_start = y * self.stride;
+            let mask_row_start = y * mask.stride;
+            let dest_row_pixels = &mut self.pixels[dest_row_start..(dest_row_start + width * 4)];
+            let mask_row_pixels = &mask.pixels[mask_row_start..(mask_row_start + width)];
+            for (dest, &coverage) in dest_row_pixels.chunks_mut(4).zip(mask_row_pixels.iter()) {
+                if coverage == 0 {
+                    continue;
+                }
+                let src_alpha = mul_div_255(color.a, coverage);
+                let inv_src_alpha = 255 - src_alpha;
+                dest[0] = mul_div_255(color.r, src_alpha) + mul_div_255(dest[0], inv_src_alpha);
+                dest[1] = mul_div_255(color.g, src_alpha) + mul_div_255(dest[1], inv_src_alpha);
+                dest[2] = mul_div_255(color.b, src_alpha) + mul_div_255(dest[2], inv_src_alpha);
+                dest[3] = src_alpha + mul_div_255(dest[3], inv_src_alpha);
             }
-            (Format::A8, Format::Rgb24) => {
-                self.blit_from_with::<BlitRgb24ToA8>(dst_rect, src_bytes, src_stride, src_format)
+        }
+    }
+
+    /// Draws `src` with its origin at `dst_point`, keeping what is already drawn underneath.
+    ///
+    /// Coverage formats keep the larger coverage of each channel, and the RGBA formats composite
+    /// `src` over this canvas. If `src` has a different format, it is converted first, as
+    /// `blit_from()` does.
+    pub(crate) fn composite_from(&mut self, dst_point: Vector2I, src: &Canvas) {
+        if src.format != self.format {
+            let mut converted_src = Canvas::new(src.size, self.format);
+            converted_src.blit_from_canvas(src);
+            self.composite_from(dst_point, &converted_src);
+            return;
+        }
+
+        let dst_rect = RectI::new(dst_point, src.size);
+        let dst_rect = match dst_rect.intersection(RectI::new(Vector2I::default(), self.size)) {
+            Some(dst_rect) => dst_rect,
+            None => return,
+        };
+        let src_origin = dst_rect.origin() - dst_point;
+
+        if self.format == Format::A1 {
+            for y in 0..(dst_rect.height() as usize) {
+                let dest_row_start = (dst_rect.origin_y() as usize + y) * self.stride;
+                let src_row_start = (src_origin.y() as usize + y) * src.stride;
+                let dest_row_pixels = &mut self.pixels[dest_row_start..][..self.stride];
+                let src_row_pixels = &src.pixels[src_row_start..][..src.stride];
+                for x in 0..(dst_rect.width() as usize) {
+                    let pixel = read_pixel(src_row_pixels, src_origin.x() as usize + x, src.format);
+                    if pixel[3] != 0 {
+                        let dest_x = dst_rect.origin_x() as usize + x;
+                        write_pixel(dest_row_pixels, dest_x, self.format, pixel);
+                    }
+                }
             }
-            (Format::Rgb24, Format::A8) => {
-                self.blit_from_with::<BlitA8ToRgb24>(dst_rect, src_bytes, src_stride, src_format)
+            return;
+        }
+
+        let bytes_per_pixel = self.format.bytes_per_pixel() as usize;
+        let row_length = dst_rect.width() as usize * bytes_per_pixel;
+        for y in 0..(dst_rect.height() as usize) {
+            let dest_row_start = (dst_rect.origin_y() as usize + y) * self.stride
+                + dst_rect.origin_x() as usize * bytes_per_pixel;
+            let src_row_start = (src_origin.y() as usize + y) * src.stride
+                + src_origin.x() as usize * bytes_per_pixel;
+            let dest_row_pixels = &mut self.pixels[dest_row_start..(dest_row_start + row_length)];
+            let src_row_pixels = &src.pixels[src_row_start..(src_row_start + row_length)];
+            match self.format {
+                Format::A8 | Format::Rgb24 => {
+                    for (dest, &src) in dest_row_pixels.iter_mut().zip(src_row_pixels.iter()) {
+                        *dest = cmp::max(*dest, src);
+                    }
+                }
+                Format::Rgba32 => {
+                    for (dest, src) in dest_row_pixels.chunks_mut(4).zip(src_row_pixels.chunks(4)) {
+                        let inv_src_alpha = 255 - src[3];
+                        for (dest, &src) in dest.iter_mut().zip(src.iter()) {
+                            *dest = src + mul_div_255(*dest, inv_src_alpha);
+                        }
+                    }
+                }
+                Format::Rgba32Straight | Format::Bgra32 => {
+                    // The order of the color channels doesn't matter here, only where alpha is.
+                    for (dest, src) in dest_row_pixels.chunks_mut(4).zip(src_row_pixels.chunks(4)) {
+                        match src[3] {
+                            0 => {}
+                            255 => dest.copy_from_slice(src),
+                            _ => {
+                                let src = premultiply([src[0], src[1], src[2], src[3]]);
+                                let mut result = premultiply([dest[0], dest[1], dest[2], dest[3]]);
+                                let inv_src_alpha = 255 - src[3];
+                                for (result, &src) in result.iter_mut().zip(src.iter()) {
+                                    *result = src + mul_div_255(*result, inv_src_alpha);
+                                }
+                                dest.copy_from_slice(&unpremultiply(result));
+                            }
+                        }
+                    }
+                }
+                Format::A1 => unreachable!(),
             }
-            (Format::Rgb24, Format::Rgba32) => self
-                .blit_from_with::<BlitRgba32ToRgb24>(dst_rect, src_bytes, src_stride, src_format),
-            (Format::Rgba32, Format::Rgb24) => self
-                .blit_from_with::<BlitRgb24ToRgba32>(dst_rect, src_bytes, src_stride, src_format),
-            (Format::Rgba32, Format::A8) | (Format::A8, Format::Rgba32) => unimplemented!(),
         }
     }
 
+    /// Blits an `A8` bitmap to this canvas through `src_to_dst`, a transform from pixel
+    /// coordinates in the bitmap to pixel coordinates in the canvas. Each canvas pixel takes the
+    /// value of the nearest bitmap pixel, which keeps scaled bitmap glyphs sharp.
     #[allow(dead_code)]
-    pub(crate) fn blit_from_bitmap_1bpp(
+    pub(crate) fn blit_from_transformed(
         &mut self,
-        dst_point: Vector2I,
         src_bytes: &[u8],
         src_size: Vector2I,
         src_stride: usize,
+        src_to_dst: Transform2F,
     ) {
-        if self.format != Format::A8 {
-            unimplemented!()
+        if src_to_dst.matrix.det() == 0.0 {
+            return;
         }
-
-        let dst_rect = RectI::new(dst_point, src_size);
-        let dst_rect = dst_rect.intersection(RectI::new(Vector2I::default(), self.size));
-        let dst_rect = match dst_rect {
+        let src_rect = RectF::new(Vector2F::zero(), src_size.to_f32());
+        let dst_rect = (src_to_dst * src_rect).round_out().to_i32();
+        let dst_rect = match dst_rect.intersection(RectI::new(Vector2I::default(), self.size)) {
             Some(dst_rect) => dst_rect,
             None => return,
         };
 
-        let size = dst_rect.size();
-
-        let dest_bytes_per_pixel = self.format.bytes_per_pixel() as usize;
-        let dest_row_stride = size.x() as usize * dest_bytes_per_pixel;
-        let src_row_stride = utils::div_round_up(size.x() as usize, 8);
-
-        for y in 0..size.y() {
-            let (dest_row_start, src_row_start) = (
-                (y + dst_rect.origin_y()) as usize * self.stride
-                    + dst_rect.origin_x() as usize * dest_bytes_per_pixel,
-                y as usize * src_stride,
-            );
-            let dest_row_end = dest_row_start + dest_row_stride;
-            let src_row_end = src_row_start + src_row_stride;
-            let dest_row_pixels = &mut self.pixels[dest_row_start..dest_row_end];
-            let src_row_pixels = &src_bytes[src_row_start..src_row_end];
-            for x in 0..src_row_stride {
-                let pattern = &BITMAP_1BPP_TO_8BPP_LUT[src_row_pixels[x] as usize];
-                let dest_start = x * 8;
-                let dest_end = cmp::min(dest_start + 8, dest_row_stride);
-                let src = &pattern[0..(dest_end - dest_start)];
-                dest_row_pixels[dest_start..dest_end].clone_from_slice(src);
+        let dst_to_src = src_to_dst.inverse();
+        let width = dst_rect.width() as usize;
+        let mut buffer = vec![0; width * dst_rect.height() as usize];
+        for y in 0..dst_rect.height() {
+            for x in 0..dst_rect.width() {
+                let dst_point = (dst_rect.origin() + Vector2I::new(x, y)).to_f32();
+                let src_point = (dst_to_src * (dst_point + Vector2F::splat(0.5)))
+                    .floor()
+                    .to_i32();
+                if src_point.x() >= 0
+                    && src_point.y() >= 0
+                    && src_point.x() < src_size.x()
+                    && src_point.y() < src_size.y()
+                {
+                    buffer[y as usize * width + x as usize] =
+                        src_bytes[src_point.y() as usize * src_stride + src_point.x() as usize];
+                }
             }
         }
+        self.blit_from(
+            dst_rect.origin(),
+            &buffer,
+            dst_rect.size(),
+            width,
+            Format::A8,
+        );
     }
 
-    /// Blits to area `rect` using the data given in the buffer `src_bytes`.
+    /// Blits to area `rect` using the data given in the buffer `src_bytes`, starting at
+    /// `src_origin` in it.
     /// `src_stride` must be specified in bytes.
     /// The dimensions of `rect` must be in pixels.
+    /// The source rows must start on a byte boundary.
     fn blit_from_with<B: Blit>(
         &mut self,
         rect: RectI,
+        src_origin: Vector2I,
         src_bytes: &[u8],
         src_stride: usize,
         src_format: Format,
     ) {
-        let src_bytes_per_pixel = src_format.bytes_per_pixel() as usize;
+        let src_row_offset = src_origin.x() as usize * src_format.bits_per_pixel() as usize / 8;
         let dest_bytes_per_pixel = self.format.bytes_per_pixel() as usize;
 
         for y in 0..rect.height() {
             let (dest_row_start, src_row_start) = (
                 (y + rect.origin_y()) as usize * self.stride
                     + rect.origin_x() as usize * dest_bytes_per_pixel,
-                y as usize * src_stride,
+                (y + src_origin.y()) as usize * src_stride + src_row_offset,
             );
             let dest_row_end = dest_row_start + rect.width() as usize * dest_bytes_per_pixel;
-            let src_row_end = src_row_start + rect.width() as usize * src_bytes_per_pixel;
+            let src_row_end = src_row_start + src_format.stride_for_width(rect.width() as usize);
             let dest_row_pixels = &mut self.pixels[dest_row_start..dest_row_end];
             let src_row_pixels = &src_bytes[src_row_start..src_row_end];
             B::blit(dest_row_pixels, src_row_pixels)
         }
     }
+
+    /// Blits to area `rect` like `blit_from_with()`, converting one pixel at a time. This handles
+    /// every pair of formats, including `A1` rows that don't start on a byte boundary.
+    fn blit_from_converted(
+        &mut self,
+        rect: RectI,
+        src_origin: Vector2I,
+        src_bytes: &[u8],
+        src_stride: usize,
+        src_format: Format,
+    ) {
+        for y in 0..(rect.height() as usize) {
+            let dest_row_start = (rect.origin_y() as usize + y) * self.stride;
+            let src_row_start = (src_origin.y() as usize + y) * src_stride;
+            let dest_row_pixels = &mut self.pixels[dest_row_start..][..self.stride];
+            let src_row_pixels = &src_bytes[src_row_start..][..src_stride];
+            for x in 0..(rect.width() as usize) {
+                let mut pixel = read_pixel(src_row_pixels, src_origin.x() as usize + x, src_format);
+                // Subpixel coverage has no alpha, so its green channel stands in for the coverage.
+                if src_format == Format::Rgb24 && !self.format.is_rgba() {
+                    pixel[3] = pixel[1];
+                }
+                write_pixel(
+                    dest_row_pixels,
+                    rect.origin_x() as usize + x,
+                    self.format,
+                    pixel,
+                );
+            }
+        }
+    }
 }
 
 impl fmt::Debug for Canvas {
@@ -232,6 +420,13 @@ pub enum Format {
     Rgb24,
     /// A8.
     A8,
+    /// R8G8B8A8, little-endian, with straight (not premultiplied) alpha.
+    Rgba32Straight,
+    /// B8G8R8A8, little-endian, with straight (not premultiplied) alpha.
+    Bgra32,
+    /// A1, with eight pixels packed into each byte, the leftmost in the most significant bit.
+    /// Each row starts on a new byte.
+    A1,
 }
 
 impl Format {
@@ -239,9 +434,10 @@ impl Format {
     #[inline]
     pub fn bits_per_pixel(self) -> u8 {
         match self {
-            Format::Rgba32 => 32,
+            Format::Rgba32 | Format::Rgba32Straight | Format::Bgra32 => 32,
             Format::Rgb24 => 24,
             Format::A8 => 8,
+            Format::A1 => 1,
         }
     }
 
@@ -249,9 +445,9 @@ impl Format {
     #[inline]
     pub fn components_per_pixel(self) -> u8 {
         match self {
-            Format::Rgba32 => 4,
+            Format::Rgba32 | Format::Rgba32Straight | Format::Bgra32 => 4,
             Format::Rgb24 => 3,
-            Format::A8 => 1,
+            Format::A8 | Format::A1 => 1,
         }
     }
 
@@ -262,10 +458,30 @@ impl Format {
     }
 
     /// Returns the number of bytes per pixel that this image format corresponds to.
+    ///
+    /// This is 0 for `A1`, which packs eight pixels into each byte. Use `stride_for_width()` to
+    /// find the length of a row in any format.
     #[inline]
     pub fn bytes_per_pixel(self) -> u8 {
         self.bits_per_pixel() / 8
     }
+
+    /// Returns the smallest stride, in bytes, of a row of `width` pixels in this image format.
+    ///
+    /// `A1` rows are rounded up to a whole number of bytes.
+    #[inline]
+    pub fn stride_for_width(self, width: usize) -> usize {
+        utils::div_round_up(width * self.bits_per_pixel() as usize, 8)
+    }
+
+    /// Returns true if this image format has color and alpha channels, rather than coverage.
+    #[inline]
+    pub(crate) fn is_rgba(self) -> bool {
+        matches!(
+            self,
+            Format::Rgba32 | Format::Rgba32Straight | Format::Bgra32
+        )
+    }
 }
 
 /// The antialiasing strategy that should be used when rasterizing glyphs.
@@ -279,6 +495,85 @@ pub enum RasterizationOptions {
     SubpixelAa,
 }
 
+/// Computes `a * b / 255`, rounded to the nearest integer.
+#[inline]
+fn mul_div_255(a: u8, b: u8) -> u8 {
+    let product = a as u32 * b as u32 + 128;
+    ((product + (product >> 8)) >> 8) as u8
+}
+
+/// Premultiplies the color of an RGBA pixel by its alpha.
+#[inline]
+fn premultiply(pixel: [u8; 4]) -> [u8; 4] {
+    let alpha = pixel[3];
+    [
+        mul_div_255(pixel[0], alpha),
+        mul_div_255(pixel[1], alpha),
+        mul_div_255(pixel[2], alpha),
+        alpha,
+    ]
+}
+
+/// Divides the color of a premultiplied RGBA pixel by its alpha. Transparent pixels become
+/// transparent black.
+#[inline]
+fn unpremultiply(pixel: [u8; 4]) -> [u8; 4] {
+    let alpha = pixel[3] as u32;
+    if alpha == 0 {
+        return [0; 4];
+    }
+    let divide = |value: u8| cmp::min((value as u32 * 255 + alpha / 2) / alpha, 255) as u8;
+    [
+        divide(pixel[0]),
+        divide(pixel[1]),
+        divide(pixel[2]),
+        pixel[3],
+    ]
+}
+
+/// Reads pixel `x` of a row in the given format, as premultiplied RGBA.
+fn read_pixel(row: &[u8], x: usize, format: Format) -> [u8; 4] {
+    match format {
+        Format::A1 => {
+            let value = if row[x / 8] & (0x80 >> (x % 8)) != 0 {
+                0xff
+            } else {
+                0
+            };
+            [value; 4]
+        }
+        Format::A8 => [row[x]; 4],
+        Format::Rgb24 => [row[x * 3], row[x * 3 + 1], row[x * 3 + 2], 255],
+        Format::Rgba32Straight => {
+            premultiply([row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]])
+        }
+        Format::Rgba32 => [row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]],
+        Format::Bgra32 => premultiply([row[x * 4 + 2], row[x * 4 + 1], row[x * 4], row[x * 4 + 3]]),
+    }
+}
+
+/// Writes premultiplied RGBA `pixel` to pixel `x` of a row in the given format.
+fn write_pixel(row: &mut [u8], x: usize, format: Format, pixel: [u8; 4]) {
+    match format {
+        Format::A1 => {
+            let mask = 0x80 >> (x % 8);
+            if pixel[3] >= 0x80 {
+                row[x / 8] |= mask;
+            } else {
+                row[x / 8] &= !mask;
+            }
+        }
+        Format::A8 => row[x] = pixel[3],
+        Format::Rgb24 => row[(x * 3)..(x * 3 + 3)].copy_from_slice(&pixel[0..3]),
+        Format::Rgba32Straight => row[(x * 4)..(x * 4 + 4)].copy_from_slice(&unpremultiply(pixel)),
+        Format::Rgba32 => row[(x * 4)..(x * 4 + 4)].copy_from_slice(&pixel),
+        Format::Bgra32 => {
+            let [red, green, blue, alpha] = unpremultiply(pixel);
+            row[(x * 4)..(x * 4 + 4)].copy_from_slice(&[blue, green, red, alpha]);
+        }
+    }
+}
+
 trait Blit {
     fn blit(dest: &mut [u8], src: &[u8]);
 }
@@ -292,6 +587,17 @@ impl Blit for BlitMemcpy {
     }
 }
 
+struct BlitSwapRedBlue;
+
+impl Blit for BlitSwapRedBlue {
+    #[inline]
+    fn blit(dest: &mut [u8], src: &[u8]) {
+        for (dest, src) in dest.chunks_mut(4).zip(src.chunks(4)) {
+            dest.copy_from_slice(&[src[2], src[1], src[0], src[3]]);
+        }
+    }
+}
+
 struct BlitRgb24ToA8;
 
 impl Blit for BlitRgb24ToA8 {
@@ -329,6 +635,41 @@ impl Blit for BlitRgba32ToRgb24 {
     }
 }
 
+struct BlitRgba32ToA8;
+
+impl Blit for BlitRgba32ToA8 {
+    #[inline]
+    fn blit(dest: &mut [u8], src: &[u8]) {
+        for (dest, src) in dest.iter_mut().zip(src.chunks(4)) {
+            *dest = src[3]
+        }
+    }
+}
+
+struct BlitA8ToRgba32;
+
+impl Blit for BlitA8ToRgba32 {
+    #[inline]
+    fn blit(dest: &mut [u8], src: &[u8]) {
+        // Coverage becomes premultiplied white.
+        for (dest, &src) in dest.chunks_mut(4).zip(src.iter()) {
+            dest.copy_from_slice(&[src; 4]);
+        }
+    }
+}
+
+struct BlitA1ToA8;
+
+impl Blit for BlitA1ToA8 {
+    #[inline]
+    fn blit(dest: &mut [u8], src: &[u8]) {
+        for (dest, &src) in dest.chunks_mut(8).zip(src.iter()) {
+            let pattern = &BITMAP_1BPP_TO_8BPP_LUT[src as usize];
+            dest.copy_from_slice(&pattern[0..dest.len()]);
+        }
+    }
+}
+
 struct BlitRgb24ToRgba32;
 
 impl Blit for BlitRgb24ToRgba32 {
@@ -341,3 +682,29 @@ impl Blit for BlitRgb24ToRgba32 {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pathfinder_geometry::vector::Vector2I;
+
+    use crate::canvas::{Canvas, Format};
+
+    #[test]
+    fn convert_straight_alpha_formats() {
+        // Color under a low alpha doesn't survive premultiplication, so the channels must only be
+        // reordered.
+        let straight = [200, 100, 50, 3];
+        let mut bgra = Canvas::new(Vector2I::splat(1), Format::Bgra32);
+        bgra.blit_from(
+            Vector2I::zero(),
+            &straight,
+            Vector2I::splat(1),
+            4,
+            Format::Rgba32Straight,
+        );
+        assert_eq!(bgra.pixels, [50, 100, 200, 3]);
+        let mut rgba = Canvas::new(Vector2I::splat(1), Format::Rgba32Straight);
+        rgba.blit_from_canvas(&bgra);
+        assert_eq!(rgba.pixels, straight);
+    }
+}
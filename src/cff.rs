@@ -0,0 +1,1283 @@
+// font-kit/src/cff.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Glyph outlines and names from the `CFF ` table of fonts with PostScript outlines.
+//!
+//! Outlines are built by interpreting Type 2 charstrings. Hints are skipped.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use pathfinder_geometry::line_segment::LineSegment2F;
+use pathfinder_geometry::vector::Vector2F;
+use std::convert::TryFrom;
+
+use crate::error::GlyphLoadingError;
+use crate::outline::OutlineSink;
+
+pub(crate) const CFF_TABLE_TAG: u32 = 0x43464620;
+
+const TOP_DICT_CHARSET: u16 = 15;
+const TOP_DICT_CHAR_STRINGS: u16 = 17;
+const TOP_DICT_PRIVATE: u16 = 18;
+const TOP_DICT_ROS: u16 = 0x0c00 | 30;
+const TOP_DICT_FD_ARRAY: u16 = 0x0c00 | 36;
+const TOP_DICT_FD_SELECT: u16 = 0x0c00 | 37;
+const PRIVATE_DICT_SUBRS: u16 = 19;
+
+// The predefined charsets, which are given by offset instead of being stored in the table.
+const ISO_ADOBE_CHARSET: usize = 0;
+const EXPERT_CHARSET: usize = 1;
+const EXPERT_SUBSET_CHARSET: usize = 2;
+
+const MAX_ARGUMENTS: usize = 48;
+const MAX_SUBROUTINE_DEPTH: u8 = 10;
+
+// The strings that string IDs below 391 refer to, which aren't stored in the table.
+const STANDARD_STRINGS: [&str; 391] = [
+    ".notdef",
+    "space",
+    "exclam",
+    "quotedbl",
+    "numbersign",
+    "dollar",
+    "percent",
+    "ampersand",
+    "quoteright",
+    "parenleft",
+    "parenright",
+    "asterisk",
+    "plus",
+    "comma",
+    "hyphen",
+    "period",
+    "slash",
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "colon",
+    "semicolon",
+    "less",
+    "equal",
+    "greater",
+    "question",
+    "at",
+    "A",
+    "B",
+    "C",
+    "D",
+    "E",
+    "F",
+    "G",
+    "H",
+    "I",
+    "J",
+    "K",
+    "L",
+    "M",
+    "N",
+    "O",
+    "P",
+    "Q",
+    "R",
+    "S",
+    "T",
+    "U",
+    "V",
+    "W",
+    "X",
+    "Y",
+    "Z",
+    "bracketleft",
+    "backslash",
+    "bracketright",
+    "asciicircum",
+    "underscore",
+    "quoteleft",
+    "a",
+    "b",
+    "c",
+    "d",
+    "e",
+    "f",
+    "g",
+    "h",
+    "i",
+    "j",
+    "k",
+    "l",
+    "m",
+    "n",
+    "o",
+    "p",
+    "q",
+    "r",
+    "s",
+    "t",
+    "u",
+    "v",
+    "w",
+    "x",
+    "y",
+    "z",
+    "braceleft",
+    "bar",
+    "braceright",
+    "asciitilde",
+    "exclamdown",
+    "cent",
+    "sterling",
+    "fraction",
+    "yen",
+    "florin",
+    "section",
+    "currency",
+    "quotesingle",
+    "quotedblleft",
+    "guillemotleft",
+    "guilsinglleft",
+    "guilsinglright",
+    "fi",
+    "fl",
+    "endash",
+    "dagger",
+    "daggerdbl",
+    "periodcentered",
+    "paragraph",
+    "bullet",
+    "quotesinglbase",
+    "quotedblbase",
+    "quotedblright",
+    "guillemotright",
+    "ellipsis",
+    "perthousand",
+    "questiondown",
+    "grave",
+    "acute",
+    "circumflex",
+    "tilde",
+    "macron",
+    "breve",
+    "dotaccent",
+    "dieresis",
+    "ring",
+    "cedilla",
+    "hungarumlaut",
+    "ogonek",
+    "caron",
+    "emdash",
+    "AE",
+    "ordfeminine",
+    "Lslash",
+    "Oslash",
+    "OE",
+    "ordmasculine",
+    "ae",
+    "dotlessi",
+    "lslash",
+    "oslash",
+    "oe",
+    "germandbls",
+    "onesuperior",
+    "logicalnot",
+    "mu",
+    "trademark",
+    "Eth",
+    "onehalf",
+    "plusminus",
+    "Thorn",
+    "onequarter",
+    "divide",
+    "brokenbar",
+    "degree",
+    "thorn",
+    "threequarters",
+    "twosuperior",
+    "registered",
+    "minus",
+    "eth",
+    "multiply",
+    "threesuperior",
+    "copyright",
+    "Aacute",
+    "Acircumflex",
+    "Adieresis",
+    "Agrave",
+    "Aring",
+    "Atilde",
+    "Ccedilla",
+    "Eacute",
+    "Ecircumflex",
+    "Edieresis",
+    "Egrave",
+    "Iacute",
+    "Icircumflex",
+    "Idieresis",
+    "Igrave",
+    "Ntilde",
+    "Oacute",
+    "Ocircumflex",
+    "Odieresis",
+    "Ograve",
+    "Otilde",
+    "Scaron",
+    "Uacute",
+    "Ucircumflex",
+    "Udieresis",
+    "Ugrave",
+    "Yacute",
+    "Ydieresis",
+    "Zcaron",
+    "aacute",
+    "acircumflex",
+    "adieresis",
+    "agrave",
+    "aring",
+    "atilde",
+    "ccedilla",
+    "eacute",
+    "ecircumflex",
+    "edieresis",
+    "egrave",
+    "iacute",
+    "icircumflex",
+    "idieresis",
+    "igrave",
+    "ntilde",
+    "oacute",
+    "ocircumflex",
+    "odieresis",
+    "ograve",
+    "otilde",
+    "scaron",
+    "uacute",
+    "ucircumflex",
+    "udieresis",
+    "ugrave",
+    "yacute",
+    "ydieresis",
+    "zcaron",
+    "exclamsmall",
+    "Hungarumlautsmall",
+    "dollaroldstyle",
+    "dollarsuperior",
+    "ampersandsmall",
+    "Acutesmall",
+    "parenleftsuperior",
+    "parenrightsuperior",
+    "twodotenleader",
+    "onedotenleader",
+    "zerooldstyle",
+    "oneoldstyle",
+    "twooldstyle",
+    "threeoldstyle",
+    "fouroldstyle",
+    "fiveoldstyle",
+    "sixoldstyle",
+    "sevenoldstyle",
+    "eightoldstyle",
+    "nineoldstyle",
+    "commasuperior",
+    "threequartersemdash",
+    "periodsuperior",
+    "questionsmall",
+    "asuperior",
+    "bsuperior",
+    "centsuperior",
+    "dsuperior",
+    "esuperior",
+    "isuperior",
+    "lsuperior",
+    "msuperior",
+    "nsuperior",
+    "osuperior",
+    "rsuperior",
+    "ssuperior",
+    "tsuperior",
+    "ff",
+    "ffi",
+    "ffl",
+    "parenleftinferior",
+    "parenrightinferior",
+    "Circumflexsmall",
+    "hyphensuperior",
+    "Gravesmall",
+    "Asmall",
+    "Bsmall",
+    "Csmall",
+    "Dsmall",
+    "Esmall",
+    "Fsmall",
+    "Gsmall",
+    "Hsmall",
+    "Ismall",
+    "Jsmall",
+    "Ksmall",
+    "Lsmall",
+    "Msmall",
+    "Nsmall",
+    "Osmall",
+    "Psmall",
+    "Qsmall",
+    "Rsmall",
+    "Ssmall",
+    "Tsmall",
+    "Usmall",
+    "Vsmall",
+    "Wsmall",
+    "Xsmall",
+    "Ysmall",
+    "Zsmall",
+    "colonmonetary",
+    "onefitted",
+    "rupiah",
+    "Tildesmall",
+    "exclamdownsmall",
+    "centoldstyle",
+    "Lslashsmall",
+    "Scaronsmall",
+    "Zcaronsmall",
+    "Dieresissmall",
+    "Brevesmall",
+    "Caronsmall",
+    "Dotaccentsmall",
+    "Macronsmall",
+    "figuredash",
+    "hypheninferior",
+    "Ogoneksmall",
+    "Ringsmall",
+    "Cedillasmall",
+    "questiondownsmall",
+    "oneeighth",
+    "threeeighths",
+    "fiveeighths",
+    "seveneighths",
+    "onethird",
+    "twothirds",
+    "zerosuperior",
+    "foursuperior",
+    "fivesuperior",
+    "sixsuperior",
+    "sevensuperior",
+    "eightsuperior",
+    "ninesuperior",
+    "zeroinferior",
+    "oneinferior",
+    "twoinferior",
+    "threeinferior",
+    "fourinferior",
+    "fiveinferior",
+    "sixinferior",
+    "seveninferior",
+    "eightinferior",
+    "nineinferior",
+    "centinferior",
+    "dollarinferior",
+    "periodinferior",
+    "commainferior",
+    "Agravesmall",
+    "Aacutesmall",
+    "Acircumflexsmall",
+    "Atildesmall",
+    "Adieresissmall",
+    "Aringsmall",
+    "AEsmall",
+    "Ccedillasmall",
+    "Egravesmall",
+    "Eacutesmall",
+    "Ecircumflexsmall",
+    "Edieresissmall",
+    "Igravesmall",
+    "Iacutesmall",
+    "Icircumflexsmall",
+    "Idieresissmall",
+    "Ethsmall",
+    "Ntildesmall",
+    "Ogravesmall",
+    "Oacutesmall",
+    "Ocircumflexsmall",
+    "Otildesmall",
+    "Odieresissmall",
+    "OEsmall",
+    "Oslashsmall",
+    "Ugravesmall",
+    "Uacutesmall",
+    "Ucircumflexsmall",
+    "Udieresissmall",
+    "Yacutesmall",
+    "Thornsmall",
+    "Ydieresissmall",
+    "001.000",
+    "001.001",
+    "001.002",
+    "001.003",
+    "Black",
+    "Bold",
+    "Book",
+    "Light",
+    "Medium",
+    "Regular",
+    "Roman",
+    "Semibold",
+];
+
+// The string IDs of the glyphs in the Standard Encoding that `seac` accented characters are
+// composed from, as (first code, first string ID, count) runs.
+const STANDARD_ENCODING_RUNS: [(u8, u16, u8); 14] = [
+    (32, 1, 95),
+    (161, 96, 15),
+    (177, 111, 4),
+    (182, 115, 8),
+    (191, 123, 1),
+    (193, 124, 8),
+    (202, 132, 2),
+    (205, 134, 4),
+    (225, 138, 1),
+    (227, 139, 1),
+    (232, 140, 4),
+    (241, 144, 1),
+    (245, 145, 1),
+    (248, 146, 4),
+];
+
+/// The location of an INDEX structure, which stores an array of variable-length objects.
+#[derive(Clone, Copy, Debug, Default)]
+struct Index {
+    count: usize,
+    offset_size: usize,
+    offsets_start: usize,
+    // Offsets in the INDEX are relative to the byte before the data.
+    data_base: usize,
+    end: usize,
+}
+
+impl Index {
+    fn read(table: &[u8], offset: usize) -> Option<Index> {
+        let count = table.get(offset..)?.read_u16::<BigEndian>().ok()? as usize;
+        if count == 0 {
+            return Some(Index {
+                end: offset + 2,
+                ..Index::default()
+            });
+        }
+        let offset_size = *table.get(offset + 2)? as usize;
+        if !(1..=4).contains(&offset_size) {
+            return None;
+        }
+        let offsets_start = offset + 3;
+        let data_base = offsets_start + (count + 1) * offset_size - 1;
+        let mut index = Index {
+            count,
+            offset_size,
+            offsets_start,
+            data_base,
+            end: 0,
+        };
+        index.end = data_base + index.offset(table, count)?;
+        if index.end > table.len() {
+            return None;
+        }
+        Some(index)
+    }
+
+    fn offset(&self, table: &[u8], index: usize) -> Option<usize> {
+        let start = self.offsets_start + index * self.offset_size;
+        let bytes = table.get(start..(start + self.offset_size))?;
+        Some(
+            bytes
+                .iter()
+                .fold(0, |offset, &byte| (offset << 8) | byte as usize),
+        )
+    }
+
+    fn get<'a>(&self, table: &'a [u8], index: usize) -> Option<&'a [u8]> {
+        if index >= self.count {
+            return None;
+        }
+        let start = self.data_base + self.offset(table, index)?;
+        let end = self.data_base + self.offset(table, index + 1)?;
+        table.get(start..end)
+    }
+
+    // The bias added to subroutine numbers, which lets more subroutines be called with short
+    // operands.
+    fn subroutine_bias(&self) -> i32 {
+        if self.count < 1240 {
+            107
+        } else if self.count < 33900 {
+            1131
+        } else {
+            32768
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Charset {
+    Predefined(usize),
+    Custom(usize),
+}
+
+#[derive(Clone, Debug)]
+enum Keying {
+    // A name-keyed font, with its local subroutines.
+    Names(Option<Index>),
+    // A CID-keyed font, with the `FDSelect` offset and the local subroutines of each font DICT.
+    Cids(usize, Vec<Option<Index>>),
+}
+
+/// The parsed structure of a `CFF ` table.
+#[derive(Clone, Debug)]
+pub(crate) struct Cff {
+    strings: Index,
+    global_subrs: Index,
+    char_strings: Index,
+    charset: Charset,
+    keying: Keying,
+}
+
+impl Cff {
+    /// Parses the header and the top-level structures of a `CFF ` table.
+    ///
+    /// Only the first font of the table is used, as OpenType requires.
+    pub(crate) fn new(table: &[u8]) -> Option<Cff> {
+        let major_version = *table.first()?;
+        if major_version != 1 {
+            return None;
+        }
+        let header_size = *table.get(2)? as usize;
+        let names = Index::read(table, header_size)?;
+        let top_dicts = Index::read(table, names.end)?;
+        let strings = Index::read(table, top_dicts.end)?;
+        let global_subrs = Index::read(table, strings.end)?;
+
+        let top_dict = read_dict(top_dicts.get(table, 0)?)?;
+        let char_strings_offset = dict_operand(&top_dict, TOP_DICT_CHAR_STRINGS, 0)? as usize;
+        let char_strings = Index::read(table, char_strings_offset)?;
+        let charset = match dict_operand(&top_dict, TOP_DICT_CHARSET, 0).unwrap_or(0.0) as usize {
+            offset @ (ISO_ADOBE_CHARSET | EXPERT_CHARSET | EXPERT_SUBSET_CHARSET) => {
+                Charset::Predefined(offset)
+            }
+            offset => Charset::Custom(offset),
+        };
+
+        let keying = if dict_operand(&top_dict, TOP_DICT_ROS, 0).is_some() {
+            let fd_select = dict_operand(&top_dict, TOP_DICT_FD_SELECT, 0)? as usize;
+            let fd_array_offset = dict_operand(&top_dict, TOP_DICT_FD_ARRAY, 0)? as usize;
+            let fd_array = Index::read(table, fd_array_offset)?;
+            let local_subrs = (0..fd_array.count)
+                .map(|fd_index| {
+                    let font_dict = read_dict(fd_array.get(table, fd_index)?)?;
+                    read_local_subrs(table, &font_dict)
+                })
+                .collect();
+            Keying::Cids(fd_select, local_subrs)
+        } else {
+            Keying::Names(read_local_subrs(table, &top_dict))
+        };
+
+        Some(Cff {
+            strings,
+            global_subrs,
+            char_strings,
+            charset,
+            keying,
+        })
+    }
+
+    /// Returns the number of glyphs in the font.
+    #[inline]
+    pub(crate) fn glyph_count(&self) -> u32 {
+        self.char_strings.count as u32
+    }
+
+    /// Sends the outline of a glyph to a sink, in font units.
+    ///
+    /// As FreeType does, this rounds points down to whole font units, and leaves out a final line
+    /// back to the start of each contour, since closing the contour draws it, and contours that
+    /// consist of a single point.
+    pub(crate) fn outline<S>(
+        &self,
+        table: &[u8],
+        glyph_id: u32,
+        sink: &mut S,
+    ) -> Result<(), GlyphLoadingError>
+    where
+        S: OutlineSink,
+    {
+        let mut path = Path::new(sink);
+        self.draw_glyph(table, glyph_id, Vector2F::zero(), &mut path)
+            .ok_or(GlyphLoadingError::NoSuchGlyph)?;
+        path.close();
+        Ok(())
+    }
+
+    /// Returns the name of a glyph from the charset. CID-keyed fonts have no glyph names.
+    pub(crate) fn glyph_name(&self, table: &[u8], glyph_id: u32) -> Option<String> {
+        if let Keying::Cids(..) = self.keying {
+            return None;
+        }
+        let string_id = self.glyph_string_id(table, glyph_id)?;
+        self.string(table, string_id)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+    }
+
+    /// Returns the ID of the glyph with the given name in the charset.
+    pub(crate) fn glyph_by_name(&self, table: &[u8], name: &str) -> Option<u32> {
+        if let Keying::Cids(..) = self.keying {
+            return None;
+        }
+        (0..self.glyph_count()).find(|&glyph_id| {
+            self.glyph_string_id(table, glyph_id)
+                .and_then(|string_id| self.string(table, string_id))
+                == Some(name.as_bytes())
+        })
+    }
+
+    fn string<'a>(&self, table: &'a [u8], string_id: u16) -> Option<&'a [u8]> {
+        match STANDARD_STRINGS.get(string_id as usize) {
+            Some(string) => Some(string.as_bytes()),
+            None => self
+                .strings
+                .get(table, string_id as usize - STANDARD_STRINGS.len()),
+        }
+    }
+
+    // Looks up the string ID that the charset assigns to a glyph.
+    fn glyph_string_id(&self, table: &[u8], glyph_id: u32) -> Option<u16> {
+        if glyph_id >= self.glyph_count() {
+            return None;
+        }
+        if glyph_id == 0 {
+            return Some(0);
+        }
+        let offset = match self.charset {
+            // The ISO Adobe charset maps glyphs to the standard strings in order.
+            Charset::Predefined(ISO_ADOBE_CHARSET) if glyph_id <= 228 => {
+                return Some(glyph_id as u16)
+            }
+            Charset::Predefined(_) => return None,
+            Charset::Custom(offset) => offset,
+        };
+
+        let mut reader = table.get(offset..)?;
+        let format = reader.read_u8().ok()?;
+        let mut remaining = glyph_id - 1;
+        match format {
+            0 => {
+                let mut string_ids = reader.get((remaining as usize * 2)..)?;
+                string_ids.read_u16::<BigEndian>().ok()
+            }
+            1 | 2 => loop {
+                let first = reader.read_u16::<BigEndian>().ok()?;
+                let left = if format == 1 {
+                    reader.read_u8().ok()? as u32
+                } else {
+                    reader.read_u16::<BigEndian>().ok()? as u32
+                };
+                if remaining <= left {
+                    return Some(first + remaining as u16);
+                }
+                remaining -= left + 1;
+            },
+            _ => None,
+        }
+    }
+
+    // Finds the glyph that the charset assigns the given string ID, for `seac`.
+    fn glyph_for_string_id(&self, table: &[u8], string_id: u16) -> Option<u32> {
+        (0..self.glyph_count())
+            .find(|&glyph_id| self.glyph_string_id(table, glyph_id) == Some(string_id))
+    }
+
+    fn local_subrs(&self, table: &[u8], glyph_id: u32) -> Option<Index> {
+        match self.keying {
+            Keying::Names(local_subrs) => local_subrs,
+            Keying::Cids(fd_select, ref local_subrs) => {
+                let fd_index = read_fd_select(table, fd_select, glyph_id)?;
+                local_subrs.get(fd_index as usize).copied().flatten()
+            }
+        }
+    }
+
+    fn draw_glyph<S>(
+        &self,
+        table: &[u8],
+        glyph_id: u32,
+        offset: Vector2F,
+        path: &mut Path<S>,
+    ) -> Option<()>
+    where
+        S: OutlineSink,
+    {
+        let char_string = self.char_strings.get(table, glyph_id as usize)?;
+        let mut interpreter = Interpreter {
+            cff: self,
+            table,
+            local_subrs: self.local_subrs(table, glyph_id),
+            path,
+            offset,
+            stack: Vec::with_capacity(MAX_ARGUMENTS),
+            point: Vector2F::zero(),
+            stem_count: 0,
+            seen_width: false,
+            finished: false,
+        };
+        interpreter.run(char_string, 0)
+    }
+}
+
+fn read_local_subrs(table: &[u8], dict: &[(u16, Vec<f64>)]) -> Option<Index> {
+    let private_size = dict_operand(dict, TOP_DICT_PRIVATE, 0)? as usize;
+    let private_offset = dict_operand(dict, TOP_DICT_PRIVATE, 1)? as usize;
+    let private_dict = read_dict(table.get(private_offset..(private_offset + private_size))?)?;
+    let subrs_offset = dict_operand(&private_dict, PRIVATE_DICT_SUBRS, 0)? as usize;
+    Index::read(table, private_offset + subrs_offset)
+}
+
+// Returns the index of the font DICT that a glyph of a CID-keyed font uses.
+fn read_fd_select(table: &[u8], offset: usize, glyph_id: u32) -> Option<u8> {
+    let mut reader = table.get(offset..)?;
+    match reader.read_u8().ok()? {
+        0 => reader.get(glyph_id as usize).copied(),
+        3 => {
+            let range_count = reader.read_u16::<BigEndian>().ok()?;
+            let mut first = reader.read_u16::<BigEndian>().ok()? as u32;
+            for _ in 0..range_count {
+                let fd_index = reader.read_u8().ok()?;
+                let next = reader.read_u16::<BigEndian>().ok()? as u32;
+                if (first..next).contains(&glyph_id) {
+                    return Some(fd_index);
+                }
+                first = next;
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Reads the operators of a DICT along with their operands. Two-byte operators are returned as
+/// `0x0c00 | second_byte`.
+fn read_dict(mut data: &[u8]) -> Option<Vec<(u16, Vec<f64>)>> {
+    let mut entries = vec![];
+    let mut operands = vec![];
+    while let Some((&byte, rest)) = data.split_first() {
+        data = rest;
+        match byte {
+            0..=11 | 13..=21 => entries.push((byte as u16, std::mem::take(&mut operands))),
+            12 => {
+                let (&second, rest) = data.split_first()?;
+                data = rest;
+                entries.push((0x0c00 | second as u16, std::mem::take(&mut operands)));
+            }
+            28 => operands.push(data.read_i16::<BigEndian>().ok()? as f64),
+            29 => operands.push(data.read_i32::<BigEndian>().ok()? as f64),
+            30 => operands.push(read_real(&mut data)?),
+            32..=246 => operands.push(byte as f64 - 139.0),
+            247..=250 => {
+                let next = data.read_u8().ok()? as f64;
+                operands.push((byte as f64 - 247.0) * 256.0 + next + 108.0)
+            }
+            251..=254 => {
+                let next = data.read_u8().ok()? as f64;
+                operands.push(-(byte as f64 - 251.0) * 256.0 - next - 108.0)
+            }
+            _ => return None,
+        }
+    }
+    Some(entries)
+}
+
+// Reads a real number operand, which is stored as a string of decimal nibbles.
+fn read_real(data: &mut &[u8]) -> Option<f64> {
+    let mut string = String::new();
+    'bytes: loop {
+        let byte = data.read_u8().ok()?;
+        for nibble in [byte >> 4, byte & 0xf] {
+            match nibble {
+                0..=9 => string.push((b'0' + nibble) as char),
+                0xa => string.push('.'),
+                0xb => string.push('E'),
+                0xc => string.push_str("E-"),
+                0xe => string.push('-'),
+                0xf => break 'bytes,
+                _ => return None,
+            }
+        }
+    }
+    string.parse().ok()
+}
+
+fn dict_operand(dict: &[(u16, Vec<f64>)], operator: u16, index: usize) -> Option<f64> {
+    dict.iter()
+        .find(|(entry_operator, _)| *entry_operator == operator)
+        .and_then(|(_, operands)| operands.get(index).copied())
+}
+
+/// Forwards path commands to a sink, starting contours lazily so that contours without segments
+/// are left out and dropping lines that return to the start of a contour as it closes.
+struct Path<'a, S>
+where
+    S: OutlineSink,
+{
+    sink: &'a mut S,
+    start: Vector2F,
+    // Whether the contour has been started in the sink.
+    started: bool,
+    // Whether any segment has been added since the last move.
+    open: bool,
+    pending_line: Option<Vector2F>,
+}
+
+impl<'a, S> Path<'a, S>
+where
+    S: OutlineSink,
+{
+    fn new(sink: &'a mut S) -> Path<'a, S> {
+        Path {
+            sink,
+            start: Vector2F::zero(),
+            started: false,
+            open: false,
+            pending_line: None,
+        }
+    }
+
+    fn move_to(&mut self, to: Vector2F) {
+        self.close();
+        self.start = snap_to_font_units(to);
+    }
+
+    fn line_to(&mut self, to: Vector2F) {
+        self.flush();
+        self.open = true;
+        self.pending_line = Some(snap_to_font_units(to));
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        self.flush();
+        self.open = true;
+        self.start_contour();
+        let ctrl = LineSegment2F::new(
+            snap_to_font_units(ctrl.from()),
+            snap_to_font_units(ctrl.to()),
+        );
+        self.sink.cubic_curve_to(ctrl, snap_to_font_units(to));
+    }
+
+    fn close(&mut self) {
+        if !self.open {
+            return;
+        }
+        if self.pending_line == Some(self.start) {
+            self.pending_line = None;
+        }
+        self.flush();
+        if self.started {
+            self.sink.close();
+        }
+        self.started = false;
+        self.open = false;
+    }
+
+    fn start_contour(&mut self) {
+        if !self.started {
+            self.sink.move_to(self.start);
+            self.started = true;
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(to) = self.pending_line.take() {
+            self.start_contour();
+            self.sink.line_to(to);
+        }
+    }
+}
+
+/// Executes Type 2 charstrings.
+struct Interpreter<'a, 'b, 'c, S>
+where
+    S: OutlineSink,
+{
+    cff: &'a Cff,
+    table: &'a [u8],
+    local_subrs: Option<Index>,
+    path: &'b mut Path<'c, S>,
+    // Added to every point, for the accent of `seac` characters.
+    offset: Vector2F,
+    stack: Vec<f32>,
+    point: Vector2F,
+    stem_count: usize,
+    // Whether the first stack-clearing operator, which may be preceded by the advance width, has
+    // been seen.
+    seen_width: bool,
+    finished: bool,
+}
+
+impl<'a, 'b, 'c, S> Interpreter<'a, 'b, 'c, S>
+where
+    S: OutlineSink,
+{
+    fn run(&mut self, mut code: &[u8], depth: u8) -> Option<()> {
+        if depth > MAX_SUBROUTINE_DEPTH {
+            return None;
+        }
+        while let Some((&byte, rest)) = code.split_first() {
+            code = rest;
+            match byte {
+                // hstem, vstem, hstemhm, vstemhm
+                1 | 3 | 18 | 23 => {
+                    self.take_width(self.stack.len() % 2 == 1);
+                    self.stem_count += self.stack.len() / 2;
+                    self.stack.clear();
+                }
+                // hintmask, cntrmask
+                19 | 20 => {
+                    // Arguments before a mask are an implicit vstem.
+                    self.take_width(self.stack.len() % 2 == 1);
+                    self.stem_count += self.stack.len() / 2;
+                    self.stack.clear();
+                    code = code.get(self.stem_count.div_ceil(8)..)?;
+                }
+                // rmoveto
+                21 => {
+                    self.take_width(self.stack.len() > 2);
+                    let delta = Vector2F::new(self.arg(0)?, self.arg(1)?);
+                    self.move_by(delta);
+                }
+                // hmoveto
+                22 => {
+                    self.take_width(self.stack.len() > 1);
+                    let delta = Vector2F::new(self.arg(0)?, 0.0);
+                    self.move_by(delta);
+                }
+                // vmoveto
+                4 => {
+                    self.take_width(self.stack.len() > 1);
+                    let delta = Vector2F::new(0.0, self.arg(0)?);
+                    self.move_by(delta);
+                }
+                // rlineto
+                5 => {
+                    for pair in self.stack.chunks_exact(2) {
+                        self.point += Vector2F::new(pair[0], pair[1]);
+                        self.path.line_to(self.point + self.offset);
+                    }
+                    self.stack.clear();
+                }
+                // hlineto, vlineto
+                6 | 7 => {
+                    let mut horizontal = byte == 6;
+                    for &delta in &self.stack {
+                        self.point += if horizontal {
+                            Vector2F::new(delta, 0.0)
+                        } else {
+                            Vector2F::new(0.0, delta)
+                        };
+                        self.path.line_to(self.point + self.offset);
+                        horizontal = !horizontal;
+                    }
+                    self.stack.clear();
+                }
+                // rrcurveto
+                8 => {
+                    let stack = std::mem::take(&mut self.stack);
+                    for args in stack.chunks_exact(6) {
+                        self.curve_by(args[0], args[1], args[2], args[3], args[4], args[5]);
+                    }
+                }
+                // rcurveline
+                24 => {
+                    let stack = std::mem::take(&mut self.stack);
+                    let curve_count = stack.len().checked_sub(2)? / 6;
+                    for args in stack.chunks_exact(6).take(curve_count) {
+                        self.curve_by(args[0], args[1], args[2], args[3], args[4], args[5]);
+                    }
+                    let line = &stack[(curve_count * 6)..];
+                    self.point += Vector2F::new(*line.first()?, *line.get(1)?);
+                    self.path.line_to(self.point + self.offset);
+                }
+                // rlinecurve
+                25 => {
+                    let stack = std::mem::take(&mut self.stack);
+                    let line_count = stack.len().checked_sub(6)? / 2;
+                    for pair in stack.chunks_exact(2).take(line_count) {
+                        self.point += Vector2F::new(pair[0], pair[1]);
+                        self.path.line_to(self.point + self.offset);
+                    }
+                    let args = stack.get((line_count * 2)..(line_count * 2 + 6))?;
+                    self.curve_by(args[0], args[1], args[2], args[3], args[4], args[5]);
+                }
+                // vvcurveto, hhcurveto
+                26 | 27 => {
+                    let stack = std::mem::take(&mut self.stack);
+                    let (mut first, args) = if stack.len() % 4 == 1 {
+                        (stack[0], &stack[1..])
+                    } else {
+                        (0.0, &stack[..])
+                    };
+                    for args in args.chunks_exact(4) {
+                        if byte == 26 {
+                            self.curve_by(first, args[0], args[1], args[2], 0.0, args[3]);
+                        } else {
+                            self.curve_by(args[0], first, args[1], args[2], args[3], 0.0);
+                        }
+                        first = 0.0;
+                    }
+                }
+                // vhcurveto, hvcurveto
+                30 | 31 => {
+                    let stack = std::mem::take(&mut self.stack);
+                    let curve_count = stack.len() / 4;
+                    let mut horizontal = byte == 31;
+                    for (index, args) in stack.chunks_exact(4).enumerate() {
+                        // The last curve may have an extra argument for its other coordinate.
+                        let last = if index + 1 == curve_count && stack.len() % 4 == 1 {
+                            stack[stack.len() - 1]
+                        } else {
+                            0.0
+                        };
+                        if horizontal {
+                            self.curve_by(args[0], 0.0, args[1], args[2], last, args[3]);
+                        } else {
+                            self.curve_by(0.0, args[0], args[1], args[2], args[3], last);
+                        }
+                        horizontal = !horizontal;
+                    }
+                }
+                // callsubr
+                10 => {
+                    let subrs = self.local_subrs?;
+                    self.call_subroutine(subrs, depth)?;
+                    if self.finished {
+                        return Some(());
+                    }
+                }
+                // return
+                11 => return Some(()),
+                12 => {
+                    let (&operator, rest) = code.split_first()?;
+                    code = rest;
+                    self.run_escaped_operator(operator)?;
+                }
+                // endchar
+                14 => {
+                    self.take_width(self.stack.len() == 1 || self.stack.len() == 5);
+                    if self.stack.len() == 4 {
+                        self.draw_accented_character()?;
+                    }
+                    self.path.close();
+                    self.finished = true;
+                    return Some(());
+                }
+                28 => {
+                    let value = code.read_i16::<BigEndian>().ok()?;
+                    self.push(value as f32)?;
+                }
+                // callgsubr
+                29 => {
+                    let subrs = self.cff.global_subrs;
+                    self.call_subroutine(subrs, depth)?;
+                    if self.finished {
+                        return Some(());
+                    }
+                }
+                32..=246 => self.push(byte as f32 - 139.0)?,
+                247..=250 => {
+                    let next = code.read_u8().ok()? as f32;
+                    self.push((byte as f32 - 247.0) * 256.0 + next + 108.0)?;
+                }
+                251..=254 => {
+                    let next = code.read_u8().ok()? as f32;
+                    self.push(-(byte as f32 - 251.0) * 256.0 - next - 108.0)?;
+                }
+                255 => {
+                    let value = code.read_i32::<BigEndian>().ok()?;
+                    self.push(value as f32 / 65536.0)?;
+                }
+                _ => return None,
+            }
+        }
+        Some(())
+    }
+
+    fn run_escaped_operator(&mut self, operator: u8) -> Option<()> {
+        match operator {
+            // dotsection, a deprecated hint.
+            0 => self.stack.clear(),
+            // hflex
+            34 => {
+                let args = std::mem::take(&mut self.stack);
+                let args = args.get(..7)?;
+                let y = self.point.y();
+                self.curve_by(args[0], 0.0, args[1], args[2], args[3], 0.0);
+                self.curve_by(args[4], 0.0, args[5], y - self.point.y(), args[6], 0.0);
+            }
+            // flex
+            35 => {
+                let args = std::mem::take(&mut self.stack);
+                let args = args.get(..12)?;
+                self.curve_by(args[0], args[1], args[2], args[3], args[4], args[5]);
+                self.curve_by(args[6], args[7], args[8], args[9], args[10], args[11]);
+            }
+            // hflex1
+            36 => {
+                let args = std::mem::take(&mut self.stack);
+                let args = args.get(..9)?;
+                let y = self.point.y();
+                self.curve_by(args[0], args[1], args[2], args[3], args[4], 0.0);
+                let dy = y - (self.point.y() + args[7]);
+                self.curve_by(args[5], 0.0, args[6], args[7], args[8], dy);
+            }
+            // flex1
+            37 => {
+                let args = std::mem::take(&mut self.stack);
+                let args = args.get(..11)?;
+                let start = self.point;
+                let dx: f32 = args[0] + args[2] + args[4] + args[6] + args[8];
+                let dy: f32 = args[1] + args[3] + args[5] + args[7] + args[9];
+                self.curve_by(args[0], args[1], args[2], args[3], args[4], args[5]);
+                let (last_dx, last_dy) = if dx.abs() > dy.abs() {
+                    (args[10], start.y() - (self.point.y() + args[7] + args[9]))
+                } else {
+                    (start.x() - (self.point.x() + args[6] + args[8]), args[10])
+                };
+                self.curve_by(args[6], args[7], args[8], args[9], last_dx, last_dy);
+            }
+            // The arithmetic operators, which are deprecated but may still appear.
+            // abs, neg, sqrt
+            9 | 14 | 26 => {
+                let value = self.stack.pop()?;
+                self.stack.push(match operator {
+                    9 => value.abs(),
+                    14 => -value,
+                    _ => value.sqrt(),
+                });
+            }
+            // add, sub, div, mul
+            10 | 11 | 12 | 24 => {
+                let b = self.stack.pop()?;
+                let a = self.stack.pop()?;
+                self.stack.push(match operator {
+                    10 => a + b,
+                    11 => a - b,
+                    12 => a / b,
+                    _ => a * b,
+                });
+            }
+            // drop
+            18 => {
+                self.stack.pop()?;
+            }
+            // exch
+            28 => {
+                let length = self.stack.len();
+                if length < 2 {
+                    return None;
+                }
+                self.stack.swap(length - 1, length - 2);
+            }
+            // dup
+            27 => {
+                let value = *self.stack.last()?;
+                self.push(value)?;
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn push(&mut self, value: f32) -> Option<()> {
+        if self.stack.len() >= MAX_ARGUMENTS {
+            return None;
+        }
+        self.stack.push(value);
+        Some(())
+    }
+
+    #[inline]
+    fn arg(&self, index: usize) -> Option<f32> {
+        self.stack.get(index).copied()
+    }
+
+    // Removes the advance width from the bottom of the stack if it's there. It's only allowed
+    // before the first stack-clearing operator.
+    fn take_width(&mut self, has_width: bool) {
+        if !self.seen_width && has_width {
+            self.stack.remove(0);
+        }
+        self.seen_width = true;
+    }
+
+    fn move_by(&mut self, delta: Vector2F) {
+        self.point += delta;
+        self.path.move_to(self.point + self.offset);
+        self.stack.clear();
+    }
+
+    fn curve_by(&mut self, dx0: f32, dy0: f32, dx1: f32, dy1: f32, dx2: f32, dy2: f32) {
+        let ctrl0 = self.point + Vector2F::new(dx0, dy0);
+        let ctrl1 = ctrl0 + Vector2F::new(dx1, dy1);
+        self.point = ctrl1 + Vector2F::new(dx2, dy2);
+        self.path.cubic_curve_to(
+            LineSegment2F::new(ctrl0, ctrl1) + self.offset,
+            self.point + self.offset,
+        );
+    }
+
+    fn call_subroutine(&mut self, subrs: Index, depth: u8) -> Option<()> {
+        let number = self.stack.pop()? as i32 + subrs.subroutine_bias();
+        let subroutine = subrs.get(self.table, usize::try_from(number).ok()?)?;
+        self.run(subroutine, depth + 1)
+    }
+
+    // Draws an accented character from the `endchar` arguments `adx ady bchar achar`, which name
+    // the base and accent glyphs by their codes in the Standard Encoding.
+    fn draw_accented_character(&mut self) -> Option<()> {
+        let (adx, ady) = (self.stack[0], self.stack[1]);
+        let base_code = self.stack[2] as u8;
+        let accent_code = self.stack[3] as u8;
+        let base = self
+            .cff
+            .glyph_for_string_id(self.table, standard_encoding_string_id(base_code)?)?;
+        let accent = self
+            .cff
+            .glyph_for_string_id(self.table, standard_encoding_string_id(accent_code)?)?;
+        self.path.close();
+        self.cff
+            .draw_glyph(self.table, base, self.offset, self.path)?;
+        self.path.close();
+        let accent_offset = self.offset + Vector2F::new(adx, ady);
+        self.cff
+            .draw_glyph(self.table, accent, accent_offset, self.path)?;
+        self.stack.clear();
+        Some(())
+    }
+}
+
+// FreeType computes in 16.16 fixed point, so this allows for the rounding error of `f32` when
+// points that land on whole units are rounded down.
+fn snap_to_font_units(point: Vector2F) -> Vector2F {
+    (point + Vector2F::splat(1.0 / 1024.0)).floor()
+}
+
+fn standard_encoding_string_id(code: u8) -> Option<u16> {
+    STANDARD_ENCODING_RUNS
+        .iter()
+        .find(|&&(first_code, _, count)| code >= first_code && code - first_code < count)
+        .map(|&(first_code, first_string_id, _)| first_string_id + (code - first_code) as u16)
+}
@@ -0,0 +1,257 @@
+// font-kit/src/cmap.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The characters that a font maps to glyphs.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use std::ops::RangeInclusive;
+
+pub(crate) const CMAP_TABLE_TAG: u32 = 0x636d6170;
+
+// The Unicode subtables of the `cmap` table, in order of preference: full repertoire subtables
+// first, then BMP-only ones.
+const UNICODE_ENCODINGS: [(u16, u16); 8] = [
+    (3, 10),
+    (0, 6),
+    (0, 4),
+    (0, 3),
+    (3, 1),
+    (0, 2),
+    (0, 1),
+    (0, 0),
+];
+
+/// The set of characters that a font maps to glyphs, stored as sorted, non-overlapping ranges.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CharacterCoverage {
+    ranges: Vec<RangeInclusive<char>>,
+}
+
+impl CharacterCoverage {
+    /// Builds the coverage of the given characters, which must be sorted.
+    pub(crate) fn from_sorted_chars<I>(characters: I) -> CharacterCoverage
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut ranges: Vec<RangeInclusive<char>> = vec![];
+        for character in characters {
+            if let Some(last) = ranges.last_mut() {
+                if *last.end() as u32 + 1 >= character as u32 {
+                    *last = *last.start()..=character.max(*last.end());
+                    continue;
+                }
+            }
+            ranges.push(character..=character);
+        }
+        CharacterCoverage { ranges }
+    }
+
+    /// Returns the covered ranges of characters, in ascending order.
+    #[inline]
+    pub fn ranges(&self) -> &[RangeInclusive<char>] {
+        &self.ranges
+    }
+
+    /// Returns true if the font maps the given character to a glyph.
+    pub fn contains(&self, character: char) -> bool {
+        self.ranges
+            .binary_search_by(|range| {
+                if *range.end() < character {
+                    std::cmp::Ordering::Less
+                } else if *range.start() > character {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    /// Returns true if the font maps every character of the given string to a glyph.
+    pub fn contains_all(&self, string: &str) -> bool {
+        string.chars().all(|character| self.contains(character))
+    }
+
+    /// Returns the number of covered characters.
+    pub fn len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|range| *range.end() as usize - *range.start() as usize + 1)
+            .sum()
+    }
+
+    /// Returns true if the font maps no characters at all.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+/// Reads every character-to-glyph mapping from the preferred Unicode subtable of a `cmap` table,
+/// sorted by character. Characters mapped to glyph 0, or to glyphs at or past `glyph_count`, are
+/// left out.
+pub(crate) fn read_character_map(cmap: &[u8], glyph_count: u32) -> Vec<(char, u32)> {
+    let mut mappings = UNICODE_ENCODINGS
+        .iter()
+        .find_map(|&(platform_id, encoding_id)| {
+            let subtable = find_subtable(cmap, platform_id, encoding_id)?;
+            read_subtable(subtable, glyph_count)
+        })
+        .unwrap_or_default();
+    mappings.sort_unstable_by_key(|&(character, _)| character);
+    mappings.dedup_by_key(|&mut (character, _)| character);
+    mappings
+}
+
+fn find_subtable(cmap: &[u8], wanted_platform_id: u16, wanted_encoding_id: u16) -> Option<&[u8]> {
+    let mut reader = cmap.get(2..)?;
+    let table_count = reader.read_u16::<BigEndian>().ok()?;
+    for _ in 0..table_count {
+        let platform_id = reader.read_u16::<BigEndian>().ok()?;
+        let encoding_id = reader.read_u16::<BigEndian>().ok()?;
+        let offset = reader.read_u32::<BigEndian>().ok()? as usize;
+        if platform_id == wanted_platform_id && encoding_id == wanted_encoding_id {
+            return cmap.get(offset..);
+        }
+    }
+    None
+}
+
+/// Reads the mappings of a subtable, or returns `None` if its format isn't supported.
+fn read_subtable(subtable: &[u8], glyph_count: u32) -> Option<Vec<(char, u32)>> {
+    let mut mappings = vec![];
+    let mut push = |code_point: u32, glyph_id: u32| {
+        if glyph_id != 0 && glyph_id < glyph_count {
+            if let Some(character) = char::from_u32(code_point) {
+                mappings.push((character, glyph_id));
+            }
+        }
+    };
+
+    let mut reader = subtable;
+    match reader.read_u16::<BigEndian>().ok()? {
+        0 => {
+            let glyph_ids = subtable.get(6..(6 + 256))?;
+            for (code_point, &glyph_id) in glyph_ids.iter().enumerate() {
+                push(code_point as u32, glyph_id as u32);
+            }
+        }
+        4 => {
+            let segment_count = subtable.get(6..)?.read_u16::<BigEndian>().ok()? as usize / 2;
+            let end_codes_offset = 14;
+            let start_codes_offset = end_codes_offset + segment_count * 2 + 2;
+            let id_deltas_offset = start_codes_offset + segment_count * 2;
+            let id_range_offsets_offset = id_deltas_offset + segment_count * 2;
+            let read_u16_at = |offset: usize| -> Option<u16> {
+                subtable.get(offset..)?.read_u16::<BigEndian>().ok()
+            };
+
+            for segment in 0..segment_count {
+                let end_code = read_u16_at(end_codes_offset + segment * 2)? as u32;
+                let start_code = read_u16_at(start_codes_offset + segment * 2)? as u32;
+                let id_delta = read_u16_at(id_deltas_offset + segment * 2)? as u32;
+                let id_range_offset_position = id_range_offsets_offset + segment * 2;
+                let id_range_offset = read_u16_at(id_range_offset_position)? as usize;
+                if start_code > end_code {
+                    continue;
+                }
+
+                for code_point in start_code..=end_code.min(0xfffe) {
+                    let glyph_id = if id_range_offset == 0 {
+                        (code_point + id_delta) & 0xffff
+                    } else {
+                        let glyph_id_offset = id_range_offset_position
+                            + id_range_offset
+                            + (code_point - start_code) as usize * 2;
+                        match read_u16_at(glyph_id_offset)? as u32 {
+                            0 => 0,
+                            glyph_id => (glyph_id + id_delta) & 0xffff,
+                        }
+                    };
+                    push(code_point, glyph_id);
+                }
+            }
+        }
+        6 => {
+            let mut reader = subtable.get(6..)?;
+            let first_code = reader.read_u16::<BigEndian>().ok()? as u32;
+            let entry_count = reader.read_u16::<BigEndian>().ok()? as u32;
+            for code_point in first_code..(first_code + entry_count) {
+                push(code_point, reader.read_u16::<BigEndian>().ok()? as u32);
+            }
+        }
+        12 => {
+            let mut reader = subtable.get(12..)?;
+            let group_count = reader.read_u32::<BigEndian>().ok()?;
+            // Groups must be sorted and must not overlap. Skipping the characters of each group
+            // that an earlier group already reached keeps malformed tables from mapping any
+            // character more than once.
+            let mut next_code = 0;
+            for _ in 0..group_count {
+                let start_code = reader.read_u32::<BigEndian>().ok()?;
+                let end_code = reader.read_u32::<BigEndian>().ok()?.min(char::MAX as u32);
+                let start_glyph_id = reader.read_u32::<BigEndian>().ok()?;
+                if start_code.max(next_code) > end_code {
+                    continue;
+                }
+                for code_point in start_code.max(next_code)..=end_code {
+                    match start_glyph_id.checked_add(code_point - start_code) {
+                        Some(glyph_id) if glyph_id < glyph_count => push(code_point, glyph_id),
+                        _ => break,
+                    }
+                }
+                next_code = end_code + 1;
+            }
+        }
+        _ => return None,
+    }
+    Some(mappings)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cmap::read_character_map;
+
+    // Builds a `cmap` table with a single Windows full repertoire subtable in format 12.
+    fn format_12_cmap(groups: &[(u32, u32, u32)]) -> Vec<u8> {
+        let mut cmap = vec![0, 0, 0, 1, 0, 3, 0, 10, 0, 0, 0, 12];
+        cmap.extend_from_slice(&[0, 12, 0, 0]);
+        cmap.extend_from_slice(&(16 + groups.len() as u32 * 12).to_be_bytes());
+        cmap.extend_from_slice(&[0; 4]);
+        cmap.extend_from_slice(&(groups.len() as u32).to_be_bytes());
+        for &(start_code, end_code, start_glyph_id) in groups {
+            cmap.extend_from_slice(&start_code.to_be_bytes());
+            cmap.extend_from_slice(&end_code.to_be_bytes());
+            cmap.extend_from_slice(&start_glyph_id.to_be_bytes());
+        }
+        cmap
+    }
+
+    #[test]
+    fn format_12_drops_glyphs_past_glyph_count() {
+        let cmap = format_12_cmap(&[(0x41, 0x44, 1), (0x61, 0x62, 9), (0x70, 0x71, u32::MAX)]);
+        assert_eq!(
+            read_character_map(&cmap, 4),
+            vec![('A', 1), ('B', 2), ('C', 3)]
+        );
+    }
+
+    #[test]
+    fn format_12_skips_overlapping_groups() {
+        // Each group spans every Unicode character; only the first one may map them.
+        let cmap = format_12_cmap(&[(0, 0x10ffff, 1); 64]);
+        assert_eq!(read_character_map(&cmap, 3), vec![('\0', 1), ('\u{1}', 2)]);
+        let cmap = format_12_cmap(&[(0x41, 0x42, 1), (0x42, 0x43, 5)]);
+        assert_eq!(
+            read_character_map(&cmap, 8),
+            vec![('A', 1), ('B', 2), ('C', 6)]
+        );
+    }
+}
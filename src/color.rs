@@ -0,0 +1,276 @@
+// font-kit/src/color.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Color glyphs described by the `COLR` and `CPAL` tables.
+//!
+//! A version 0 `COLR` glyph is a stack of ordinary glyph outlines ("layers"), each of which is
+//! filled with a color from a `CPAL` palette or with the foreground (text) color. A version 1
+//! glyph is a graph of paints that adds gradients, transforms, compositing, and clip boxes.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::Vector2I;
+
+use crate::bitmap;
+use crate::canvas::{Canvas, Format, RasterizationOptions};
+use crate::error::GlyphLoadingError;
+use crate::hinting::HintingOptions;
+use crate::loader::Loader;
+use crate::paint::{self, ColrV1};
+
+pub(crate) const COLR_TABLE_TAG: u32 = 0x434f4c52;
+pub(crate) const CPAL_TABLE_TAG: u32 = 0x4350414c;
+
+const FOREGROUND_PALETTE_INDEX: u16 = 0xffff;
+
+/// An sRGB color with 8 bits per channel and straight (not premultiplied) alpha.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RgbaColor {
+    /// The red channel.
+    pub r: u8,
+    /// The green channel.
+    pub g: u8,
+    /// The blue channel.
+    pub b: u8,
+    /// The alpha channel.
+    pub a: u8,
+}
+
+impl RgbaColor {
+    /// Opaque black.
+    pub const BLACK: RgbaColor = RgbaColor::new(0, 0, 0, 255);
+
+    /// Creates a new color from its red, green, blue, and alpha channels.
+    #[inline]
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> RgbaColor {
+        RgbaColor { r, g, b, a }
+    }
+}
+
+/// A set of colors from the `CPAL` table, indexed by `COLR` layers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColorPalette {
+    /// The colors in this palette, in palette entry order.
+    pub colors: Vec<RgbaColor>,
+}
+
+/// The color that a layer of a color glyph is filled with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayerColor {
+    /// The palette entry with the given index in the selected palette.
+    Palette(u16),
+    /// The foreground (text) color chosen by the caller.
+    Foreground,
+}
+
+/// A single layer of a version 0 `COLR` color glyph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColorLayer {
+    /// The ID of the glyph whose outline is filled to draw this layer.
+    pub glyph_id: u32,
+    /// The color to fill the outline with.
+    pub color: LayerColor,
+}
+
+/// Parses all palettes in a `CPAL` table.
+pub(crate) fn read_palettes(cpal: &[u8]) -> Option<Vec<ColorPalette>> {
+    let mut reader = cpal;
+    let _version = reader.read_u16::<BigEndian>().ok()?;
+    let palette_entry_count = reader.read_u16::<BigEndian>().ok()? as usize;
+    let palette_count = reader.read_u16::<BigEndian>().ok()?;
+    let _color_record_count = reader.read_u16::<BigEndian>().ok()?;
+    let color_records_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+
+    let mut palettes = Vec::with_capacity(palette_count as usize);
+    for _ in 0..palette_count {
+        let first_color_record_index = reader.read_u16::<BigEndian>().ok()? as usize;
+        let start = color_records_offset + first_color_record_index * 4;
+        let mut records = cpal.get(start..(start + palette_entry_count * 4))?;
+        let mut colors = Vec::with_capacity(palette_entry_count);
+        for _ in 0..palette_entry_count {
+            // Color records are stored in BGRA order.
+            let b = records.read_u8().ok()?;
+            let g = records.read_u8().ok()?;
+            let r = records.read_u8().ok()?;
+            let a = records.read_u8().ok()?;
+            colors.push(RgbaColor::new(r, g, b, a));
+        }
+        palettes.push(ColorPalette { colors });
+    }
+    Some(palettes)
+}
+
+/// Returns true if the given glyph has a paint graph in the version 1 part of a `COLR` table.
+pub(crate) fn has_paint(colr: &[u8], glyph_id: u32) -> bool {
+    ColrV1::new(colr)
+        .and_then(|colr| colr.base_glyph_paint(glyph_id))
+        .is_some()
+}
+
+/// Looks up the layers of the given base glyph in the version 0 part of a `COLR` table.
+pub(crate) fn read_layers(colr: &[u8], glyph_id: u32) -> Option<Vec<ColorLayer>> {
+    let mut reader = colr;
+    let _version = reader.read_u16::<BigEndian>().ok()?;
+    let base_glyph_count = reader.read_u16::<BigEndian>().ok()? as usize;
+    let base_glyphs_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+    let layers_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+    let layer_count = reader.read_u16::<BigEndian>().ok()? as usize;
+
+    // Base glyph records are sorted by glyph ID, so we can binary search them.
+    let base_glyphs = colr.get(base_glyphs_offset..(base_glyphs_offset + base_glyph_count * 6))?;
+    let (mut low, mut high) = (0, base_glyph_count);
+    while low < high {
+        let mid = (low + high) / 2;
+        let mut record = &base_glyphs[(mid * 6)..];
+        let base_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+        if base_glyph_id < glyph_id {
+            low = mid + 1;
+        } else if base_glyph_id > glyph_id {
+            high = mid;
+        } else {
+            let first_layer_index = record.read_u16::<BigEndian>().ok()? as usize;
+            let layer_record_count = record.read_u16::<BigEndian>().ok()? as usize;
+            if first_layer_index + layer_record_count > layer_count {
+                return None;
+            }
+            let start = layers_offset + first_layer_index * 4;
+            let mut records = colr.get(start..(start + layer_record_count * 4))?;
+            let mut layers = Vec::with_capacity(layer_record_count);
+            for _ in 0..layer_record_count {
+                let glyph_id = records.read_u16::<BigEndian>().ok()? as u32;
+                let color = match records.read_u16::<BigEndian>().ok()? {
+                    FOREGROUND_PALETTE_INDEX => LayerColor::Foreground,
+                    palette_index => LayerColor::Palette(palette_index),
+                };
+                layers.push(ColorLayer { glyph_id, color });
+            }
+            return Some(layers);
+        }
+    }
+    None
+}
+
+/// Rasterizes a color glyph, compositing it over an RGBA canvas.
+///
+/// Glyphs are composited with premultiplied alpha. Other RGBA canvases get the glyph drawn on its
+/// own first, then converted and composited over them.
+///
+/// Version 1 paint graphs take precedence over version 0 layers. The layers of a version 0 glyph
+/// are rasterized one after another. Glyphs without `COLR` data are drawn from their embedded
+/// bitmap image if they have one, and are otherwise filled with the foreground color.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn rasterize_color_glyph<L>(
+    loader: &L,
+    canvas: &mut Canvas,
+    glyph_id: u32,
+    point_size: f32,
+    transform: Transform2F,
+    hinting_options: HintingOptions,
+    rasterization_options: RasterizationOptions,
+    palette_index: usize,
+    foreground_color: RgbaColor,
+) -> Result<(), GlyphLoadingError>
+where
+    L: Loader,
+{
+    assert!(
+        canvas.format.is_rgba(),
+        "Color glyphs can only be rasterized to RGBA canvases."
+    );
+    if canvas.format != Format::Rgba32 {
+        let mut glyph_canvas = Canvas::new(canvas.size, Format::Rgba32);
+        rasterize_color_glyph(
+            loader,
+            &mut glyph_canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+            palette_index,
+            foreground_color,
+        )?;
+        canvas.composite_from(Vector2I::zero(), &glyph_canvas);
+        return Ok(());
+    }
+
+    // An out-of-range palette index selects the first palette, as the `CPAL` specification
+    // requires.
+    let palettes = loader.color_palettes();
+    let palette = palettes.get(palette_index).or_else(|| palettes.first());
+
+    // Glyphs are filled with flat colors and gradients, so subpixel coverage is meaningless here.
+    let rasterization_options = match rasterization_options {
+        RasterizationOptions::SubpixelAa => RasterizationOptions::GrayscaleAa,
+        rasterization_options => rasterization_options,
+    };
+
+    let colr = loader.load_font_table(COLR_TABLE_TAG);
+    if let Some(colr_v1) = colr.as_deref().and_then(ColrV1::new) {
+        if colr_v1.base_glyph_paint(glyph_id).is_some() {
+            return paint::rasterize_paint(
+                loader,
+                &colr_v1,
+                canvas,
+                glyph_id,
+                point_size,
+                transform,
+                hinting_options,
+                rasterization_options,
+                palette,
+                foreground_color,
+            );
+        }
+    }
+
+    let layers = match colr.as_deref().and_then(|colr| read_layers(colr, glyph_id)) {
+        Some(layers) => layers,
+        None => {
+            if let Some(image) = loader.embedded_image(glyph_id, point_size) {
+                if bitmap::rasterize_embedded_image(
+                    canvas,
+                    &image,
+                    point_size,
+                    transform,
+                    foreground_color,
+                ) {
+                    return Ok(());
+                }
+            }
+            vec![ColorLayer {
+                glyph_id,
+                color: LayerColor::Foreground,
+            }]
+        }
+    };
+
+    let mut mask = Canvas::new(canvas.size, Format::A8);
+    for layer in layers {
+        let color = match layer.color {
+            LayerColor::Palette(entry_index) => palette
+                .and_then(|palette| palette.colors.get(entry_index as usize))
+                .cloned()
+                .unwrap_or(foreground_color),
+            LayerColor::Foreground => foreground_color,
+        };
+
+        mask.pixels.iter_mut().for_each(|pixel| *pixel = 0);
+        loader.rasterize_glyph(
+            &mut mask,
+            layer.glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+        )?;
+        canvas.fill_with_mask(&mask, color);
+    }
+    Ok(())
+}
@@ -110,3 +110,56 @@ impl_display! { SelectionError, {
         CannotAccessSource { reason: ref maybe_cow } => maybe_cow.as_deref().unwrap_or("failed to access source")
     }
 }
+
+/// Reasons why a glyph atlas might fail to provide a glyph.
+#[cfg(feature = "atlas")]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AtlasError {
+    /// The glyph key referred to a font that wasn't added to the atlas.
+    NoSuchFont,
+    /// The glyph, with its padding, is bigger than a page.
+    GlyphTooLarge,
+    /// Every page is full of glyphs used during the current frame.
+    Full,
+    /// The glyph couldn't be loaded or rasterized.
+    GlyphLoading(GlyphLoadingError),
+}
+
+#[cfg(feature = "atlas")]
+impl Error for AtlasError {}
+
+#[cfg(feature = "atlas")]
+impl_display! { AtlasError, {
+        NoSuchFont => "no such font in the atlas",
+        GlyphTooLarge => "glyph too large for an atlas page",
+        Full => "atlas is full",
+        GlyphLoading(e) => format!("glyph loading error: {}", e),
+    }
+}
+
+#[cfg(feature = "atlas")]
+impl From<GlyphLoadingError> for AtlasError {
+    fn from(error: GlyphLoadingError) -> AtlasError {
+        AtlasError::GlyphLoading(error)
+    }
+}
+
+/// Reasons why SVG path data might fail to parse.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SvgPathError {
+    /// Something other than a command, a number, or a separator was found at this byte offset.
+    UnexpectedCharacter(usize),
+    /// The command at this byte offset was missing some of its arguments.
+    MissingArguments(usize),
+    /// The path data drew something before its first move command.
+    MissingMoveTo,
+}
+
+impl Error for SvgPathError {}
+
+impl_display! { SvgPathError, {
+        UnexpectedCharacter(offset) => format!("unexpected character at byte {}", offset),
+        MissingArguments(offset) => format!("missing arguments for the command at byte {}", offset),
+        MissingMoveTo => "path data does not start with a move",
+    }
+}
@@ -0,0 +1,208 @@
+// font-kit/src/gamma.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Gamma and contrast correction for glyph coverage.
+//!
+//! Rasterizers produce linear coverage: a pixel that a glyph half covers gets half coverage. Most
+//! renderers blend that coverage with gamma-encoded colors, which makes text that is lighter than
+//! its background look thin, and text that is darker look heavy. The tables here "preblend"
+//! coverage, so that a plain blend of the corrected coverage looks like a blend in linear space,
+//! with some contrast added to keep small text legible. This is how Skia and WebRender correct
+//! text.
+//!
+//! The correction depends on the luminance of the text, as the background is assumed to be the
+//! opposite of the text. There is a table for each of eight steps of luminance.
+
+use crate::canvas::{Canvas, Format};
+use crate::color::RgbaColor;
+
+// The number of bits of luminance that choose a table.
+const LUMINANCE_BITS: u32 = 3;
+
+/// Tables that correct glyph coverage for gamma and contrast, for text of each luminance.
+#[derive(Clone, Debug)]
+pub struct GammaLut {
+    tables: Vec<[u8; 256]>,
+    paint_curve: TransferFunction,
+}
+
+impl GammaLut {
+    /// Builds the tables.
+    ///
+    /// `contrast` is how much to raise partial coverage, from 0.0 for none to 1.0. It tapers off
+    /// as the text gets lighter, as light text on a dark background needs less help.
+    ///
+    /// `paint_gamma` is the gamma that text colors are encoded with, and `device_gamma` the gamma
+    /// of the display. A gamma of 0.0 stands for the sRGB transfer function. With a contrast of
+    /// 0.0 and both gammas 1.0, the tables leave coverage unchanged.
+    pub fn new(contrast: f32, paint_gamma: f32, device_gamma: f32) -> GammaLut {
+        let paint_curve = TransferFunction::new(paint_gamma);
+        let device_curve = TransferFunction::new(device_gamma);
+        let tables = (0..(1 << LUMINANCE_BITS))
+            .map(|index| {
+                let luminance = expand_luminance(index as u8);
+                build_table(luminance, contrast, paint_curve, device_curve)
+            })
+            .collect();
+        GammaLut {
+            tables,
+            paint_curve,
+        }
+    }
+
+    /// Returns the table for text of the given luminance, which maps coverage to corrected
+    /// coverage.
+    #[inline]
+    pub fn table(&self, luminance: u8) -> &[u8; 256] {
+        &self.tables[(luminance >> (8 - LUMINANCE_BITS)) as usize]
+    }
+
+    /// Corrects the coverage in `canvas` for text drawn in `color`.
+    ///
+    /// `A8` coverage is corrected for the luminance of `color`. Each channel of `Rgb24` subpixel
+    /// coverage is corrected for the same channel of `color`, as it only lights that channel.
+    /// The RGBA formats are treated as `Rgb24`, with their alpha corrected for the luminance. As
+    /// `Rgba32` is premultiplied, its color channels are then limited to the corrected alpha.
+    /// `A1` coverage is left alone, as none of it is partial.
+    ///
+    /// Only coverage should be corrected this way, not color glyphs.
+    pub fn preblend(&self, canvas: &mut Canvas, color: RgbaColor) {
+        let luminance_table = self.table(self.luminance(color));
+        let (red_table, green_table, blue_table) = (
+            self.table(color.r),
+            self.table(color.g),
+            self.table(color.b),
+        );
+        // The table for each channel of a pixel, in order.
+        let channel_tables = match canvas.format {
+            Format::A1 => return,
+            Format::A8 => vec![luminance_table],
+            Format::Bgra32 => vec![blue_table, green_table, red_table, luminance_table],
+            Format::Rgb24 | Format::Rgba32 | Format::Rgba32Straight => {
+                vec![red_table, green_table, blue_table, luminance_table]
+            }
+        };
+
+        let bytes_per_pixel = canvas.format.bytes_per_pixel() as usize;
+        let row_length = canvas.format.stride_for_width(canvas.size.x() as usize);
+        for y in 0..(canvas.size.y() as usize) {
+            let row_start = y * canvas.stride;
+            let row = &mut canvas.pixels[row_start..(row_start + row_length)];
+            for pixel in row.chunks_mut(bytes_per_pixel) {
+                for (value, table) in pixel.iter_mut().zip(channel_tables.iter()) {
+                    *value = table[*value as usize];
+                }
+                if canvas.format == Format::Rgba32 {
+                    let alpha = pixel[3];
+                    pixel[..3]
+                        .iter_mut()
+                        .for_each(|value| *value = (*value).min(alpha));
+                }
+            }
+        }
+    }
+
+    // Returns the luminance of `color`, weighting its channels in linear space and encoding the
+    // result with the paint gamma again.
+    fn luminance(&self, color: RgbaColor) -> u8 {
+        let curve = self.paint_curve;
+        let linear = 0.2126 * curve.decode(color.r as f32 / 255.0)
+            + 0.7152 * curve.decode(color.g as f32 / 255.0)
+            + 0.0722 * curve.decode(color.b as f32 / 255.0);
+        (curve.encode(linear) * 255.0).round() as u8
+    }
+}
+
+// A curve that encodes linear light for a gamma.
+#[derive(Clone, Copy, Debug)]
+enum TransferFunction {
+    Linear,
+    Srgb,
+    Gamma(f32),
+}
+
+impl TransferFunction {
+    fn new(gamma: f32) -> TransferFunction {
+        if gamma == 0.0 {
+            TransferFunction::Srgb
+        } else if gamma == 1.0 {
+            TransferFunction::Linear
+        } else {
+            TransferFunction::Gamma(gamma)
+        }
+    }
+
+    fn decode(self, value: f32) -> f32 {
+        match self {
+            TransferFunction::Linear => value,
+            TransferFunction::Srgb if value <= 0.04045 => value / 12.92,
+            TransferFunction::Srgb => ((value + 0.055) / 1.055).powf(2.4),
+            TransferFunction::Gamma(gamma) => value.powf(gamma),
+        }
+    }
+
+    fn encode(self, value: f32) -> f32 {
+        match self {
+            TransferFunction::Linear => value,
+            TransferFunction::Srgb if value <= 0.0031308 => value * 12.92,
+            TransferFunction::Srgb => 1.055 * value.powf(1.0 / 2.4) - 0.055,
+            TransferFunction::Gamma(gamma) => value.powf(1.0 / gamma),
+        }
+    }
+}
+
+// Spreads the bits of a table index over a byte, so that the first table is for black and the
+// last for white.
+fn expand_luminance(index: u8) -> u8 {
+    let mut luminance = 0;
+    let mut shift = 8 - LUMINANCE_BITS as i32;
+    while shift > -(LUMINANCE_BITS as i32) {
+        luminance |= if shift >= 0 {
+            index << shift
+        } else {
+            index >> -shift
+        };
+        shift -= LUMINANCE_BITS as i32;
+    }
+    luminance
+}
+
+// Builds the table for text of `luminance`, assuming the background is the opposite luminance.
+fn build_table(
+    luminance: u8,
+    contrast: f32,
+    paint_curve: TransferFunction,
+    device_curve: TransferFunction,
+) -> [u8; 256] {
+    let src = luminance as f32 / 255.0;
+    let linear_src = paint_curve.decode(src);
+    // Guessing the opposite background keeps the change between neighboring tables small.
+    let dst = 1.0 - src;
+    let linear_dst = device_curve.decode(dst);
+    let contrast = contrast * linear_dst;
+
+    let mut table = [0; 256];
+    for (coverage, entry) in table.iter_mut().enumerate() {
+        let coverage = coverage as f32 / 255.0;
+        let coverage = coverage + (1.0 - coverage) * contrast * coverage;
+        // When the text and background are this close, there is nothing to correct for but
+        // contrast, and the division below is unstable.
+        let corrected = if (src - dst).abs() < 1.0 / 256.0 {
+            coverage
+        } else {
+            // Blend in linear space, then find the coverage that gives the same result when
+            // blended in device space.
+            let linear_out = linear_src * coverage + linear_dst * (1.0 - coverage);
+            (device_curve.encode(linear_out) - dst) / (src - dst)
+        };
+        *entry = (corrected.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+    table
+}
@@ -0,0 +1,723 @@
+// font-kit/src/glyf.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Glyph outlines from the `glyf` table of fonts with TrueType outlines, along with their
+//! variations from the `gvar` table.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use pathfinder_geometry::rect::RectF;
+use pathfinder_geometry::transform2d::Matrix2x2F;
+use pathfinder_geometry::vector::Vector2F;
+
+use crate::metrics;
+use crate::outline::OutlineSink;
+
+pub(crate) const GLYF_TABLE_TAG: u32 = 0x676c7966;
+pub(crate) const LOCA_TABLE_TAG: u32 = 0x6c6f6361;
+pub(crate) const GVAR_TABLE_TAG: u32 = 0x67766172;
+
+const MAX_COMPONENT_DEPTH: u8 = 8;
+
+// The flags of each point in a simple glyph.
+const ON_CURVE_POINT: u8 = 0x01;
+const X_SHORT_VECTOR: u8 = 0x02;
+const Y_SHORT_VECTOR: u8 = 0x04;
+const REPEAT_FLAG: u8 = 0x08;
+const X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR: u8 = 0x10;
+const Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR: u8 = 0x20;
+
+// The flags of each component of a composite glyph.
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const ARGS_ARE_XY_VALUES: u16 = 0x0002;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+const USE_MY_METRICS: u16 = 0x0200;
+const SCALED_COMPONENT_OFFSET: u16 = 0x0800;
+const UNSCALED_COMPONENT_OFFSET: u16 = 0x1000;
+
+const GVAR_LONG_OFFSETS: u16 = 0x0001;
+const SHARED_POINT_NUMBERS: u16 = 0x8000;
+const TUPLE_VARIATION_COUNT_MASK: u16 = 0x0fff;
+const EMBEDDED_PEAK_TUPLE: u16 = 0x8000;
+const INTERMEDIATE_REGION: u16 = 0x4000;
+const PRIVATE_POINT_NUMBERS: u16 = 0x2000;
+const TUPLE_INDEX_MASK: u16 = 0x0fff;
+const POINTS_ARE_WORDS: u8 = 0x80;
+const POINTS_ARE_LONG_COUNT: u8 = 0x80;
+const POINT_RUN_COUNT_MASK: u8 = 0x7f;
+const DELTAS_ARE_ZERO: u8 = 0x80;
+const DELTAS_ARE_WORDS: u8 = 0x40;
+const DELTA_RUN_COUNT_MASK: u8 = 0x3f;
+
+// Variations apply to four extra points after the outline: the horizontal origin, the advance
+// width, and their vertical counterparts.
+const PHANTOM_POINT_COUNT: usize = 4;
+
+/// The tables needed to load glyphs from a font with TrueType outlines.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GlyphTables<'a> {
+    pub(crate) glyf: &'a [u8],
+    pub(crate) loca: &'a [u8],
+    pub(crate) long_loca_offsets: bool,
+    pub(crate) hhea: &'a [u8],
+    pub(crate) hmtx: &'a [u8],
+    pub(crate) gvar: Option<&'a [u8]>,
+}
+
+/// A glyph with its points in font units.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Glyph {
+    points: Vec<Vector2F>,
+    on_curve: Vec<bool>,
+    contour_ends: Vec<usize>,
+    phantom_points: [Vector2F; PHANTOM_POINT_COUNT],
+}
+
+impl Glyph {
+    /// Returns the advance width, including any variations.
+    #[inline]
+    pub(crate) fn advance(&self) -> f32 {
+        self.phantom_points[1].x() - self.phantom_points[0].x()
+    }
+
+    /// Returns the box that encloses all points of the glyph, including off-curve ones.
+    pub(crate) fn control_box(&self) -> RectF {
+        let mut points = self.points.iter();
+        let first = match points.next() {
+            Some(&first) => first,
+            None => return RectF::default(),
+        };
+        points.fold(RectF::new(first, Vector2F::zero()), |rect, &point| {
+            rect.union_point(point)
+        })
+    }
+
+    /// Sends the outline to a sink, converting runs of off-curve points to quadratic curves
+    /// through their implied on-curve midpoints.
+    pub(crate) fn outline<S>(&self, sink: &mut S)
+    where
+        S: OutlineSink,
+    {
+        let mut start = 0;
+        for &end in &self.contour_ends {
+            let points = &self.points[start..=end];
+            let on_curve = &self.on_curve[start..=end];
+            start = end + 1;
+
+            let last = points.len() - 1;
+            let (first_point, mut index) = if on_curve[0] {
+                (points[0], 1)
+            } else if on_curve[last] {
+                // Start from the last point, and treat the first as a control point.
+                (points[last], 0)
+            } else {
+                (points[last].lerp(points[0], 0.5), 0)
+            };
+            sink.move_to(first_point);
+
+            while index < points.len() {
+                let mut point0 = points[index];
+                index += 1;
+                if on_curve[index - 1] {
+                    sink.line_to(point0);
+                    continue;
+                }
+
+                loop {
+                    if index == points.len() {
+                        sink.quadratic_curve_to(point0, first_point);
+                        break;
+                    }
+                    let point1 = points[index];
+                    index += 1;
+                    if on_curve[index - 1] {
+                        sink.quadratic_curve_to(point0, point1);
+                        break;
+                    }
+                    let point_half = point0.lerp(point1, 0.5);
+                    sink.quadratic_curve_to(point0, point_half);
+                    point0 = point1;
+                }
+            }
+            sink.close();
+        }
+    }
+
+    fn translate(&mut self, vector: Vector2F) {
+        for point in self.points.iter_mut().chain(self.phantom_points.iter_mut()) {
+            *point += vector;
+        }
+    }
+}
+
+/// A reference from a composite glyph to one of its components.
+struct Component {
+    glyph_id: u32,
+    flags: u16,
+    arguments: (i32, i32),
+    matrix: Matrix2x2F,
+}
+
+impl<'a> GlyphTables<'a> {
+    /// Loads a glyph at the given normalized variation coordinates, with its origin at (0, 0).
+    pub(crate) fn load(&self, glyph_id: u32, coordinates: &[f32]) -> Option<Glyph> {
+        let mut glyph = self.load_glyph(glyph_id, coordinates, 0)?;
+        // FreeType moves the outline so that the origin phantom point is at zero, which only
+        // makes a difference if the left side bearing disagrees with `xMin`.
+        let origin = glyph.phantom_points[0];
+        glyph.translate(Vector2F::new(-origin.x(), 0.0));
+        Some(glyph)
+    }
+
+    fn glyph_data(&self, glyph_id: u32) -> Option<&'a [u8]> {
+        let index = glyph_id as usize;
+        let (start, end) = if self.long_loca_offsets {
+            let mut reader = self.loca.get((index * 4)..)?;
+            let start = reader.read_u32::<BigEndian>().ok()? as usize;
+            (start, reader.read_u32::<BigEndian>().ok()? as usize)
+        } else {
+            let mut reader = self.loca.get((index * 2)..)?;
+            let start = reader.read_u16::<BigEndian>().ok()? as usize * 2;
+            (start, reader.read_u16::<BigEndian>().ok()? as usize * 2)
+        };
+        if start >= end {
+            return Some(&[]);
+        }
+        self.glyf.get(start..end)
+    }
+
+    fn load_glyph(&self, glyph_id: u32, coordinates: &[f32], depth: u8) -> Option<Glyph> {
+        if depth > MAX_COMPONENT_DEPTH {
+            return None;
+        }
+        let data = self.glyph_data(glyph_id)?;
+        let (advance, left_side_bearing) =
+            metrics::read_horizontal_metrics(self.hhea, self.hmtx, glyph_id).unwrap_or((0, 0));
+
+        let mut glyph = Glyph::default();
+        let (contour_count, x_min) = if data.is_empty() {
+            (0, 0)
+        } else {
+            let mut header = data;
+            (
+                header.read_i16::<BigEndian>().ok()?,
+                header.read_i16::<BigEndian>().ok()?,
+            )
+        };
+        let origin_x = x_min as f32 - left_side_bearing as f32;
+        glyph.phantom_points[0] = Vector2F::new(origin_x, 0.0);
+        glyph.phantom_points[1] = Vector2F::new(origin_x + advance as f32, 0.0);
+
+        let variations = self
+            .gvar
+            .filter(|_| coordinates.iter().any(|&coordinate| coordinate != 0.0));
+
+        if contour_count >= 0 {
+            if !data.is_empty() {
+                read_simple_glyph(&mut glyph, data.get(10..)?, contour_count as usize)?;
+            }
+            if let Some(gvar) = variations {
+                let mut points = glyph.points.clone();
+                points.extend_from_slice(&glyph.phantom_points);
+                apply_variations(
+                    gvar,
+                    glyph_id,
+                    coordinates,
+                    &mut points,
+                    Some(&glyph.contour_ends),
+                );
+                let phantom_start = glyph.points.len();
+                glyph
+                    .phantom_points
+                    .copy_from_slice(&points[phantom_start..]);
+                points.truncate(phantom_start);
+                glyph.points = points;
+            }
+            return Some(glyph);
+        }
+
+        let components = read_components(data.get(10..)?)?;
+        let mut offsets: Vec<Vector2F> = components
+            .iter()
+            .map(|component| {
+                if component.flags & ARGS_ARE_XY_VALUES != 0 {
+                    Vector2F::new(component.arguments.0 as f32, component.arguments.1 as f32)
+                } else {
+                    Vector2F::zero()
+                }
+            })
+            .collect();
+        if let Some(gvar) = variations {
+            offsets.extend_from_slice(&glyph.phantom_points);
+            apply_variations(gvar, glyph_id, coordinates, &mut offsets, None);
+            glyph
+                .phantom_points
+                .copy_from_slice(&offsets[components.len()..]);
+            offsets.truncate(components.len());
+        }
+
+        for (component, &offset) in components.iter().zip(offsets.iter()) {
+            let mut child = self.load_glyph(component.glyph_id, coordinates, depth + 1)?;
+            for point in &mut child.points {
+                *point = component.matrix * *point;
+            }
+
+            let offset = if component.flags & ARGS_ARE_XY_VALUES != 0 {
+                if component.flags & SCALED_COMPONENT_OFFSET != 0
+                    && component.flags & UNSCALED_COMPONENT_OFFSET == 0
+                {
+                    component.matrix * offset
+                } else {
+                    offset
+                }
+            } else {
+                // The arguments are the indices of a point in the glyph so far and of one in the
+                // component that are to be matched up.
+                let parent_point = *glyph.points.get(component.arguments.0 as usize)?;
+                let child_point = *child.points.get(component.arguments.1 as usize)?;
+                parent_point - child_point
+            };
+
+            if component.flags & USE_MY_METRICS != 0 {
+                glyph.phantom_points = child.phantom_points;
+            }
+            let point_offset = glyph.points.len();
+            glyph
+                .points
+                .extend(child.points.iter().map(|&point| point + offset));
+            glyph.on_curve.extend_from_slice(&child.on_curve);
+            glyph
+                .contour_ends
+                .extend(child.contour_ends.iter().map(|&end| end + point_offset));
+        }
+        Some(glyph)
+    }
+}
+
+fn read_simple_glyph(glyph: &mut Glyph, mut reader: &[u8], contour_count: usize) -> Option<()> {
+    for _ in 0..contour_count {
+        let end = reader.read_u16::<BigEndian>().ok()? as usize;
+        if glyph
+            .contour_ends
+            .last()
+            .is_some_and(|&last_end| end <= last_end)
+        {
+            return None;
+        }
+        glyph.contour_ends.push(end);
+    }
+    let point_count = glyph.contour_ends.last().map_or(0, |&end| end + 1);
+
+    let instruction_length = reader.read_u16::<BigEndian>().ok()? as usize;
+    reader = reader.get(instruction_length..)?;
+
+    let mut flags = Vec::with_capacity(point_count);
+    while flags.len() < point_count {
+        let flag = reader.read_u8().ok()?;
+        flags.push(flag);
+        if flag & REPEAT_FLAG != 0 {
+            let repeat_count = reader.read_u8().ok()?;
+            flags.extend((0..repeat_count).map(|_| flag));
+        }
+    }
+    flags.truncate(point_count);
+
+    let mut x_coordinates = Vec::with_capacity(point_count);
+    let mut x = 0;
+    for &flag in &flags {
+        x += read_coordinate_delta(
+            &mut reader,
+            flag,
+            X_SHORT_VECTOR,
+            X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR,
+        )?;
+        x_coordinates.push(x);
+    }
+    let mut y = 0;
+    for (&flag, &x) in flags.iter().zip(x_coordinates.iter()) {
+        y += read_coordinate_delta(
+            &mut reader,
+            flag,
+            Y_SHORT_VECTOR,
+            Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR,
+        )?;
+        glyph.points.push(Vector2F::new(x as f32, y as f32));
+        glyph.on_curve.push(flag & ON_CURVE_POINT != 0);
+    }
+    Some(())
+}
+
+fn read_coordinate_delta(
+    reader: &mut &[u8],
+    flag: u8,
+    short_flag: u8,
+    same_or_positive_flag: u8,
+) -> Option<i32> {
+    if flag & short_flag != 0 {
+        let delta = reader.read_u8().ok()? as i32;
+        Some(if flag & same_or_positive_flag != 0 {
+            delta
+        } else {
+            -delta
+        })
+    } else if flag & same_or_positive_flag != 0 {
+        Some(0)
+    } else {
+        Some(reader.read_i16::<BigEndian>().ok()? as i32)
+    }
+}
+
+fn read_components(mut reader: &[u8]) -> Option<Vec<Component>> {
+    let mut components = vec![];
+    loop {
+        let flags = reader.read_u16::<BigEndian>().ok()?;
+        let glyph_id = reader.read_u16::<BigEndian>().ok()? as u32;
+        let signed = flags & ARGS_ARE_XY_VALUES != 0;
+        let arguments = match (flags & ARG_1_AND_2_ARE_WORDS != 0, signed) {
+            (true, true) => (
+                reader.read_i16::<BigEndian>().ok()? as i32,
+                reader.read_i16::<BigEndian>().ok()? as i32,
+            ),
+            (true, false) => (
+                reader.read_u16::<BigEndian>().ok()? as i32,
+                reader.read_u16::<BigEndian>().ok()? as i32,
+            ),
+            (false, true) => (reader.read_i8().ok()? as i32, reader.read_i8().ok()? as i32),
+            (false, false) => (reader.read_u8().ok()? as i32, reader.read_u8().ok()? as i32),
+        };
+
+        let matrix = if flags & WE_HAVE_A_SCALE != 0 {
+            Matrix2x2F::from_scale(read_f2dot14(&mut reader)?)
+        } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+            let x_scale = read_f2dot14(&mut reader)?;
+            let y_scale = read_f2dot14(&mut reader)?;
+            Matrix2x2F::from_scale(Vector2F::new(x_scale, y_scale))
+        } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+            let x_scale = read_f2dot14(&mut reader)?;
+            let scale_01 = read_f2dot14(&mut reader)?;
+            let scale_10 = read_f2dot14(&mut reader)?;
+            let y_scale = read_f2dot14(&mut reader)?;
+            Matrix2x2F::row_major(x_scale, scale_10, scale_01, y_scale)
+        } else {
+            Matrix2x2F::from_scale(1.0)
+        };
+
+        components.push(Component {
+            glyph_id,
+            flags,
+            arguments,
+            matrix,
+        });
+        if flags & MORE_COMPONENTS == 0 {
+            return Some(components);
+        }
+    }
+}
+
+#[inline]
+fn read_f2dot14(reader: &mut &[u8]) -> Option<f32> {
+    Some(reader.read_i16::<BigEndian>().ok()? as f32 / 16384.0)
+}
+
+/// Adds the deltas that `gvar` gives the glyph at the given coordinates to its points, which are
+/// followed by the phantom points.
+///
+/// Points that a variation leaves out get deltas interpolated from their neighbors in the same
+/// contour if `contour_ends` is given, and no delta otherwise.
+fn apply_variations(
+    gvar: &[u8],
+    glyph_id: u32,
+    coordinates: &[f32],
+    points: &mut [Vector2F],
+    contour_ends: Option<&[usize]>,
+) {
+    if let Some(deltas) = read_variation_deltas(gvar, glyph_id, coordinates, points, contour_ends) {
+        for (point, delta) in points.iter_mut().zip(deltas) {
+            // FreeType rounds the summed deltas to whole font units.
+            *point += Vector2F::new((delta.x() + 0.5).floor(), (delta.y() + 0.5).floor());
+        }
+    }
+}
+
+fn read_variation_deltas(
+    gvar: &[u8],
+    glyph_id: u32,
+    coordinates: &[f32],
+    points: &[Vector2F],
+    contour_ends: Option<&[usize]>,
+) -> Option<Vec<Vector2F>> {
+    let mut header = gvar.get(4..)?;
+    let axis_count = header.read_u16::<BigEndian>().ok()? as usize;
+    let shared_tuple_count = header.read_u16::<BigEndian>().ok()? as usize;
+    let shared_tuples_offset = header.read_u32::<BigEndian>().ok()? as usize;
+    let glyph_count = header.read_u16::<BigEndian>().ok()? as u32;
+    let flags = header.read_u16::<BigEndian>().ok()?;
+    let data_offset = header.read_u32::<BigEndian>().ok()? as usize;
+    if glyph_id >= glyph_count || axis_count != coordinates.len() {
+        return None;
+    }
+
+    let index = glyph_id as usize;
+    let (start, end) = if flags & GVAR_LONG_OFFSETS != 0 {
+        let mut offsets = header.get((index * 4)..)?;
+        let start = offsets.read_u32::<BigEndian>().ok()? as usize;
+        (start, offsets.read_u32::<BigEndian>().ok()? as usize)
+    } else {
+        let mut offsets = header.get((index * 2)..)?;
+        let start = offsets.read_u16::<BigEndian>().ok()? as usize * 2;
+        (start, offsets.read_u16::<BigEndian>().ok()? as usize * 2)
+    };
+    if start >= end {
+        return None;
+    }
+    let data = gvar.get((data_offset + start)..(data_offset + end))?;
+
+    let mut reader = data;
+    let tuple_variation_count = reader.read_u16::<BigEndian>().ok()?;
+    let serialized_data_offset = reader.read_u16::<BigEndian>().ok()? as usize;
+    let mut serialized_data = data.get(serialized_data_offset..)?;
+    let shared_point_numbers = if tuple_variation_count & SHARED_POINT_NUMBERS != 0 {
+        read_packed_point_numbers(&mut serialized_data)?
+    } else {
+        None
+    };
+
+    let mut deltas = vec![Vector2F::zero(); points.len()];
+    let mut peak = vec![0.0; axis_count];
+    let mut intermediate_start = vec![0.0; axis_count];
+    let mut intermediate_end = vec![0.0; axis_count];
+    for _ in 0..(tuple_variation_count & TUPLE_VARIATION_COUNT_MASK) {
+        let variation_data_size = reader.read_u16::<BigEndian>().ok()? as usize;
+        let tuple_index = reader.read_u16::<BigEndian>().ok()?;
+        if tuple_index & EMBEDDED_PEAK_TUPLE != 0 {
+            read_tuple(&mut reader, &mut peak)?;
+        } else {
+            let shared_tuple_index = (tuple_index & TUPLE_INDEX_MASK) as usize;
+            if shared_tuple_index >= shared_tuple_count {
+                return None;
+            }
+            let mut shared_tuple =
+                gvar.get((shared_tuples_offset + shared_tuple_index * axis_count * 2)..)?;
+            read_tuple(&mut shared_tuple, &mut peak)?;
+        }
+        let intermediate = tuple_index & INTERMEDIATE_REGION != 0;
+        if intermediate {
+            read_tuple(&mut reader, &mut intermediate_start)?;
+            read_tuple(&mut reader, &mut intermediate_end)?;
+        }
+
+        let mut tuple_data = serialized_data.get(..variation_data_size)?;
+        serialized_data = &serialized_data[variation_data_size..];
+
+        let region = if intermediate {
+            Some((&intermediate_start[..], &intermediate_end[..]))
+        } else {
+            None
+        };
+        let scalar = tuple_scalar(coordinates, &peak, region);
+        if scalar == 0.0 {
+            continue;
+        }
+
+        let private_point_numbers;
+        let point_numbers = if tuple_index & PRIVATE_POINT_NUMBERS != 0 {
+            private_point_numbers = read_packed_point_numbers(&mut tuple_data)?;
+            &private_point_numbers
+        } else {
+            &shared_point_numbers
+        };
+        let delta_count = point_numbers
+            .as_ref()
+            .map_or(points.len(), |point_numbers| point_numbers.len());
+        let x_deltas = read_packed_deltas(&mut tuple_data, delta_count)?;
+        let y_deltas = read_packed_deltas(&mut tuple_data, delta_count)?;
+
+        match *point_numbers {
+            None => {
+                for (delta, (&x, &y)) in deltas.iter_mut().zip(x_deltas.iter().zip(&y_deltas)) {
+                    *delta += Vector2F::new(x, y) * scalar;
+                }
+            }
+            Some(ref point_numbers) => {
+                let mut tuple_deltas = vec![Vector2F::zero(); points.len()];
+                let mut touched = vec![false; points.len()];
+                for (&point_number, (&x, &y)) in
+                    point_numbers.iter().zip(x_deltas.iter().zip(&y_deltas))
+                {
+                    if let Some(tuple_delta) = tuple_deltas.get_mut(point_number as usize) {
+                        *tuple_delta = Vector2F::new(x, y);
+                        touched[point_number as usize] = true;
+                    }
+                }
+                if let Some(contour_ends) = contour_ends {
+                    interpolate_untouched_points(points, &mut tuple_deltas, &touched, contour_ends);
+                }
+                for (delta, &tuple_delta) in deltas.iter_mut().zip(&tuple_deltas) {
+                    *delta += tuple_delta * scalar;
+                }
+            }
+        }
+    }
+    Some(deltas)
+}
+
+fn read_tuple(reader: &mut &[u8], tuple: &mut [f32]) -> Option<()> {
+    for value in tuple {
+        *value = read_f2dot14(reader)?;
+    }
+    Some(())
+}
+
+/// Returns how much a variation with the given peak, and optionally intermediate region, applies
+/// at the given normalized coordinates.
+fn tuple_scalar(coordinates: &[f32], peak: &[f32], region: Option<(&[f32], &[f32])>) -> f32 {
+    let mut scalar = 1.0;
+    for (axis, (&coordinate, &peak)) in coordinates.iter().zip(peak).enumerate() {
+        if peak == 0.0 || coordinate == peak {
+            continue;
+        }
+        if coordinate == 0.0 {
+            return 0.0;
+        }
+        match region {
+            Some((start, end)) => {
+                let (start, end) = (start[axis], end[axis]);
+                // Invalid regions are ignored.
+                if start > peak || peak > end || (start < 0.0 && end > 0.0) {
+                    continue;
+                }
+                if coordinate < start || coordinate > end {
+                    return 0.0;
+                }
+                scalar *= if coordinate < peak {
+                    (coordinate - start) / (peak - start)
+                } else {
+                    (end - coordinate) / (end - peak)
+                };
+            }
+            None => {
+                if coordinate < peak.min(0.0) || coordinate > peak.max(0.0) {
+                    return 0.0;
+                }
+                scalar *= coordinate / peak;
+            }
+        }
+    }
+    scalar
+}
+
+/// Reads a set of packed point numbers. `None` means that the variation applies to all points.
+fn read_packed_point_numbers(reader: &mut &[u8]) -> Option<Option<Vec<u16>>> {
+    let first = reader.read_u8().ok()?;
+    let count = if first & POINTS_ARE_LONG_COUNT != 0 {
+        ((first & POINT_RUN_COUNT_MASK) as usize) << 8 | reader.read_u8().ok()? as usize
+    } else {
+        first as usize
+    };
+    if count == 0 {
+        return Some(None);
+    }
+
+    let mut point_numbers = Vec::with_capacity(count);
+    let mut point_number = 0u16;
+    while point_numbers.len() < count {
+        let control = reader.read_u8().ok()?;
+        let run_count = (control & POINT_RUN_COUNT_MASK) as usize + 1;
+        for _ in 0..run_count {
+            let delta = if control & POINTS_ARE_WORDS != 0 {
+                reader.read_u16::<BigEndian>().ok()?
+            } else {
+                reader.read_u8().ok()? as u16
+            };
+            point_number = point_number.wrapping_add(delta);
+            point_numbers.push(point_number);
+        }
+    }
+    point_numbers.truncate(count);
+    Some(Some(point_numbers))
+}
+
+fn read_packed_deltas(reader: &mut &[u8], count: usize) -> Option<Vec<f32>> {
+    let mut deltas = Vec::with_capacity(count);
+    while deltas.len() < count {
+        let control = reader.read_u8().ok()?;
+        let run_count = (control & DELTA_RUN_COUNT_MASK) as usize + 1;
+        for _ in 0..run_count {
+            let delta = if control & DELTAS_ARE_ZERO != 0 {
+                0
+            } else if control & DELTAS_ARE_WORDS != 0 {
+                reader.read_i16::<BigEndian>().ok()?
+            } else {
+                reader.read_i8().ok()? as i16
+            };
+            deltas.push(delta as f32);
+        }
+    }
+    deltas.truncate(count);
+    Some(deltas)
+}
+
+/// Infers the deltas of points that a variation leaves out from the nearest points before and
+/// after them in the same contour that it includes, as the OpenType specification describes.
+fn interpolate_untouched_points(
+    points: &[Vector2F],
+    deltas: &mut [Vector2F],
+    touched: &[bool],
+    contour_ends: &[usize],
+) {
+    let mut start = 0;
+    for &end in contour_ends {
+        let touched_points: Vec<usize> = (start..=end).filter(|&index| touched[index]).collect();
+        let next = |index: usize| if index == end { start } else { index + 1 };
+        for (position, &reference_0) in touched_points.iter().enumerate() {
+            let reference_1 = touched_points[(position + 1) % touched_points.len()];
+            let mut index = next(reference_0);
+            while index != reference_1 {
+                let (point_0, point_1) = (points[reference_0], points[reference_1]);
+                let (delta_0, delta_1) = (deltas[reference_0], deltas[reference_1]);
+                deltas[index] = Vector2F::new(
+                    interpolate_delta(
+                        points[index].x(),
+                        point_0.x(),
+                        point_1.x(),
+                        delta_0.x(),
+                        delta_1.x(),
+                    ),
+                    interpolate_delta(
+                        points[index].y(),
+                        point_0.y(),
+                        point_1.y(),
+                        delta_0.y(),
+                        delta_1.y(),
+                    ),
+                );
+                index = next(index);
+            }
+        }
+        start = end + 1;
+    }
+}
+
+fn interpolate_delta(position: f32, from_0: f32, from_1: f32, delta_0: f32, delta_1: f32) -> f32 {
+    let (from_0, from_1, delta_0, delta_1) = if from_0 <= from_1 {
+        (from_0, from_1, delta_0, delta_1)
+    } else {
+        (from_1, from_0, delta_1, delta_0)
+    };
+    if position <= from_0 {
+        delta_0
+    } else if position >= from_1 {
+        delta_1
+    } else {
+        delta_0 + (position - from_0) * (delta_1 - delta_0) / (from_1 - from_0)
+    }
+}
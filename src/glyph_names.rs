@@ -0,0 +1,377 @@
+// font-kit/src/glyph_names.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Glyph names from the `post` table, and names synthesized from the `cmap` table.
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+pub(crate) const POST_TABLE_TAG: u32 = 0x706f7374;
+
+// The standard Macintosh glyph order, which `post` table formats 1 and 2 refer to by index.
+const MAC_GLYPH_NAMES: [&str; 258] = [
+    ".notdef",
+    ".null",
+    "nonmarkingreturn",
+    "space",
+    "exclam",
+    "quotedbl",
+    "numbersign",
+    "dollar",
+    "percent",
+    "ampersand",
+    "quotesingle",
+    "parenleft",
+    "parenright",
+    "asterisk",
+    "plus",
+    "comma",
+    "hyphen",
+    "period",
+    "slash",
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "colon",
+    "semicolon",
+    "less",
+    "equal",
+    "greater",
+    "question",
+    "at",
+    "A",
+    "B",
+    "C",
+    "D",
+    "E",
+    "F",
+    "G",
+    "H",
+    "I",
+    "J",
+    "K",
+    "L",
+    "M",
+    "N",
+    "O",
+    "P",
+    "Q",
+    "R",
+    "S",
+    "T",
+    "U",
+    "V",
+    "W",
+    "X",
+    "Y",
+    "Z",
+    "bracketleft",
+    "backslash",
+    "bracketright",
+    "asciicircum",
+    "underscore",
+    "grave",
+    "a",
+    "b",
+    "c",
+    "d",
+    "e",
+    "f",
+    "g",
+    "h",
+    "i",
+    "j",
+    "k",
+    "l",
+    "m",
+    "n",
+    "o",
+    "p",
+    "q",
+    "r",
+    "s",
+    "t",
+    "u",
+    "v",
+    "w",
+    "x",
+    "y",
+    "z",
+    "braceleft",
+    "bar",
+    "braceright",
+    "asciitilde",
+    "Adieresis",
+    "Aring",
+    "Ccedilla",
+    "Eacute",
+    "Ntilde",
+    "Odieresis",
+    "Udieresis",
+    "aacute",
+    "agrave",
+    "acircumflex",
+    "adieresis",
+    "atilde",
+    "aring",
+    "ccedilla",
+    "eacute",
+    "egrave",
+    "ecircumflex",
+    "edieresis",
+    "iacute",
+    "igrave",
+    "icircumflex",
+    "idieresis",
+    "ntilde",
+    "oacute",
+    "ograve",
+    "ocircumflex",
+    "odieresis",
+    "otilde",
+    "uacute",
+    "ugrave",
+    "ucircumflex",
+    "udieresis",
+    "dagger",
+    "degree",
+    "cent",
+    "sterling",
+    "section",
+    "bullet",
+    "paragraph",
+    "germandbls",
+    "registered",
+    "copyright",
+    "trademark",
+    "acute",
+    "dieresis",
+    "notequal",
+    "AE",
+    "Oslash",
+    "infinity",
+    "plusminus",
+    "lessequal",
+    "greaterequal",
+    "yen",
+    "mu",
+    "partialdiff",
+    "summation",
+    "product",
+    "pi",
+    "integral",
+    "ordfeminine",
+    "ordmasculine",
+    "Omega",
+    "ae",
+    "oslash",
+    "questiondown",
+    "exclamdown",
+    "logicalnot",
+    "radical",
+    "florin",
+    "approxequal",
+    "Delta",
+    "guillemotleft",
+    "guillemotright",
+    "ellipsis",
+    "nonbreakingspace",
+    "Agrave",
+    "Atilde",
+    "Otilde",
+    "OE",
+    "oe",
+    "endash",
+    "emdash",
+    "quotedblleft",
+    "quotedblright",
+    "quoteleft",
+    "quoteright",
+    "divide",
+    "lozenge",
+    "ydieresis",
+    "Ydieresis",
+    "fraction",
+    "currency",
+    "guilsinglleft",
+    "guilsinglright",
+    "fi",
+    "fl",
+    "daggerdbl",
+    "periodcentered",
+    "quotesinglbase",
+    "quotedblbase",
+    "perthousand",
+    "Acircumflex",
+    "Ecircumflex",
+    "Aacute",
+    "Edieresis",
+    "Egrave",
+    "Iacute",
+    "Icircumflex",
+    "Idieresis",
+    "Igrave",
+    "Oacute",
+    "Ocircumflex",
+    "apple",
+    "Ograve",
+    "Uacute",
+    "Ucircumflex",
+    "Ugrave",
+    "dotlessi",
+    "circumflex",
+    "tilde",
+    "macron",
+    "breve",
+    "dotaccent",
+    "ring",
+    "cedilla",
+    "hungarumlaut",
+    "ogonek",
+    "caron",
+    "Lslash",
+    "lslash",
+    "Scaron",
+    "scaron",
+    "Zcaron",
+    "zcaron",
+    "brokenbar",
+    "Eth",
+    "eth",
+    "Yacute",
+    "yacute",
+    "Thorn",
+    "thorn",
+    "minus",
+    "multiply",
+    "onesuperior",
+    "twosuperior",
+    "threesuperior",
+    "onehalf",
+    "onequarter",
+    "threequarters",
+    "franc",
+    "Gbreve",
+    "gbreve",
+    "Idotaccent",
+    "Scedilla",
+    "scedilla",
+    "Cacute",
+    "cacute",
+    "Ccaron",
+    "ccaron",
+    "dcroat",
+];
+
+/// Reads the name of a glyph from a `post` table of format 1 or 2.
+///
+/// Returns `None` for other formats, which carry no glyph names, and for empty names.
+pub(crate) fn read_post_glyph_name(post: &[u8], glyph_id: u32) -> Option<String> {
+    let version = post.get(0..)?.read_u32::<BigEndian>().ok()?;
+    let name = match version {
+        0x00010000 => MAC_GLYPH_NAMES.get(glyph_id as usize)?.to_string(),
+        0x00020000 => {
+            let mut reader = post.get(32..)?;
+            let glyph_count = reader.read_u16::<BigEndian>().ok()? as u32;
+            if glyph_id >= glyph_count {
+                return None;
+            }
+            let index_offset = 34 + glyph_id as usize * 2;
+            let name_index = post.get(index_offset..)?.read_u16::<BigEndian>().ok()? as usize;
+            if name_index < MAC_GLYPH_NAMES.len() {
+                MAC_GLYPH_NAMES[name_index].to_string()
+            } else {
+                // The custom names are Pascal strings stored one after another after the index
+                // array.
+                let mut names = post.get((34 + glyph_count as usize * 2)..)?;
+                for _ in 0..(name_index - MAC_GLYPH_NAMES.len()) {
+                    let length = *names.first()? as usize;
+                    names = names.get((1 + length)..)?;
+                }
+                let length = *names.first()? as usize;
+                String::from_utf8_lossy(names.get(1..(1 + length))?).into_owned()
+            }
+        }
+        _ => return None,
+    };
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// Finds the glyph with the given name in a `post` table of format 1 or 2.
+#[cfg(feature = "loader-opentype")]
+pub(crate) fn find_post_glyph(post: &[u8], name: &str) -> Option<u32> {
+    let version = post.get(0..)?.read_u32::<BigEndian>().ok()?;
+    match version {
+        0x00010000 => MAC_GLYPH_NAMES
+            .iter()
+            .position(|&mac_name| mac_name == name)
+            .map(|glyph_id| glyph_id as u32),
+        0x00020000 => {
+            let mut reader = post.get(32..)?;
+            let glyph_count = reader.read_u16::<BigEndian>().ok()? as usize;
+            let name_indices = post.get(34..(34 + glyph_count * 2))?;
+
+            // Gather the custom names up front so that each glyph can be checked in turn.
+            let mut custom_names = vec![];
+            let mut names = post.get((34 + glyph_count * 2)..)?;
+            while let Some((&length, rest)) = names.split_first() {
+                let custom_name = match rest.get(..(length as usize)) {
+                    Some(custom_name) => custom_name,
+                    None => break,
+                };
+                custom_names.push(custom_name);
+                names = &rest[(length as usize)..];
+            }
+
+            name_indices
+                .chunks_exact(2)
+                .position(|mut name_index| {
+                    let name_index = name_index.read_u16::<BigEndian>().unwrap() as usize;
+                    let glyph_name = match name_index.checked_sub(MAC_GLYPH_NAMES.len()) {
+                        None => MAC_GLYPH_NAMES[name_index].as_bytes(),
+                        Some(custom_index) => match custom_names.get(custom_index) {
+                            Some(custom_name) => custom_name,
+                            None => return false,
+                        },
+                    };
+                    glyph_name == name.as_bytes()
+                })
+                .map(|glyph_id| glyph_id as u32)
+        }
+        _ => None,
+    }
+}
+
+/// Synthesizes a glyph name from the characters that map to the glyph, following the Adobe Glyph
+/// List conventions: `uniXXXX` for characters in the Basic Multilingual Plane and `uXXXXX`
+/// otherwise.
+///
+/// Glyph 0 is always `.notdef`.
+pub(crate) fn synthesize_glyph_name(glyph_id: u32, characters: &[char]) -> Option<String> {
+    if glyph_id == 0 {
+        return Some(".notdef".to_owned());
+    }
+    let character = *characters.first()? as u32;
+    if character <= 0xffff {
+        Some(format!("uni{:04X}", character))
+    } else {
+        Some(format!("u{:X}", character))
+    }
+}
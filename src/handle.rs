@@ -33,7 +33,8 @@ pub enum Handle {
         path: PathBuf,
         /// The index of the font, if the path refers to a collection.
         ///
-        /// If the path refers to a single font, this value will be 0.
+        /// If the path refers to a single font, this value will be 0. The upper 16 bits select
+        /// a named instance of a variable font; see `Loader::named_instance_count()`.
         font_index: u32,
     },
     /// A font in memory.
@@ -42,7 +43,8 @@ pub enum Handle {
         bytes: Arc<Vec<u8>>,
         /// The index of the font, if the memory consists of a collection.
         ///
-        /// If the memory consists of a single font, this value will be 0.
+        /// If the memory consists of a single font, this value will be 0. The upper 16 bits
+        /// select a named instance of a variable font; see `Loader::named_instance_count()`.
         font_index: u32,
     },
 }
@@ -0,0 +1,457 @@
+// font-kit/src/kerning.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Pair kerning from the OpenType `GPOS` and legacy `kern` tables.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use pathfinder_geometry::vector::Vector2F;
+
+use crate::loader::Loader;
+
+pub(crate) const GPOS_TABLE_TAG: u32 = 0x47504f53;
+pub(crate) const KERN_TABLE_TAG: u32 = 0x6b65726e;
+
+const KERN_FEATURE_TAG: u32 = 0x6b65726e;
+
+// The scripts whose default language systems we take the `kern` feature from, in order of
+// preference. Many fonts only register their features under `latn`.
+const DEFAULT_SCRIPT_TAGS: [u32; 3] = [0x44464c54, 0x64666c74, 0x6c61746e];
+
+const NO_REQUIRED_FEATURE: u16 = 0xffff;
+
+const PAIR_ADJUSTMENT_LOOKUP_TYPE: u16 = 2;
+const EXTENSION_LOOKUP_TYPE: u16 = 9;
+
+const VALUE_FORMAT_X_ADVANCE: u16 = 0x0004;
+const VALUE_FORMAT_Y_ADVANCE: u16 = 0x0008;
+
+const KERN_COVERAGE_HORIZONTAL: u16 = 0x0001;
+const KERN_COVERAGE_MINIMUM: u16 = 0x0002;
+const KERN_COVERAGE_CROSS_STREAM: u16 = 0x0004;
+const KERN_COVERAGE_OVERRIDE: u16 = 0x0008;
+const APPLE_KERN_COVERAGE_VERTICAL: u16 = 0x8000;
+const APPLE_KERN_COVERAGE_CROSS_STREAM: u16 = 0x4000;
+const APPLE_KERN_COVERAGE_VARIATION: u16 = 0x2000;
+
+/// The pair kerning tables of a font, loaded once so that many pairs can be looked up.
+pub(crate) struct PairKerning {
+    gpos: Option<Box<[u8]>>,
+    // The offsets within `gpos` of the pair adjustment subtables of each `kern` lookup, in lookup
+    // list order.
+    gpos_lookups: Vec<Vec<usize>>,
+    kern: Option<Box<[u8]>>,
+}
+
+impl PairKerning {
+    /// Loads the kerning tables of the given font.
+    ///
+    /// The `kern` table is only used if the `GPOS` table has no `kern` feature for the default
+    /// script.
+    pub(crate) fn new<L>(loader: &L) -> PairKerning
+    where
+        L: Loader,
+    {
+        let gpos = loader.load_font_table(GPOS_TABLE_TAG);
+        let gpos_lookups = gpos
+            .as_deref()
+            .and_then(read_kern_lookups)
+            .unwrap_or_default();
+        let kern = if gpos_lookups.is_empty() {
+            loader.load_font_table(KERN_TABLE_TAG)
+        } else {
+            None
+        };
+        PairKerning {
+            gpos,
+            gpos_lookups,
+            kern,
+        }
+    }
+
+    /// Returns the adjustment to the advance of the left glyph when it is followed by the right
+    /// glyph, in font units.
+    pub(crate) fn adjustment(&self, left_glyph_id: u32, right_glyph_id: u32) -> Vector2F {
+        // OpenType glyph IDs are 16-bit.
+        if left_glyph_id > 0xffff || right_glyph_id > 0xffff {
+            return Vector2F::default();
+        }
+        if let Some(ref gpos) = self.gpos {
+            if !self.gpos_lookups.is_empty() {
+                return self
+                    .gpos_lookups
+                    .iter()
+                    .filter_map(|subtables| {
+                        // The first subtable that applies to the pair is the only one used.
+                        subtables.iter().find_map(|&offset| {
+                            let subtable = gpos.get(offset..)?;
+                            read_pair_adjustment(subtable, left_glyph_id, right_glyph_id)
+                        })
+                    })
+                    .fold(Vector2F::default(), |sum, adjustment| sum + adjustment);
+            }
+        }
+        match self.kern {
+            Some(ref kern) => Vector2F::new(
+                read_kern_pair(kern, left_glyph_id, right_glyph_id).unwrap_or(0.0),
+                0.0,
+            ),
+            None => Vector2F::default(),
+        }
+    }
+}
+
+/// Finds the pair adjustment subtables of the lookups that the `kern` feature of the default
+/// script and language uses.
+fn read_kern_lookups(gpos: &[u8]) -> Option<Vec<Vec<usize>>> {
+    let mut reader = gpos.get(4..)?;
+    let script_list_offset = reader.read_u16::<BigEndian>().ok()? as usize;
+    let feature_list_offset = reader.read_u16::<BigEndian>().ok()? as usize;
+    let lookup_list_offset = reader.read_u16::<BigEndian>().ok()? as usize;
+
+    let script_list = gpos.get(script_list_offset..)?;
+    let lang_sys = DEFAULT_SCRIPT_TAGS
+        .iter()
+        .find_map(|&script_tag| find_default_lang_sys(script_list, script_tag))?;
+
+    let mut reader = lang_sys.get(2..)?;
+    let required_feature_index = reader.read_u16::<BigEndian>().ok()?;
+    let feature_index_count = reader.read_u16::<BigEndian>().ok()?;
+    let mut feature_indices = vec![];
+    if required_feature_index != NO_REQUIRED_FEATURE {
+        feature_indices.push(required_feature_index);
+    }
+    for _ in 0..feature_index_count {
+        feature_indices.push(reader.read_u16::<BigEndian>().ok()?);
+    }
+
+    let feature_list = gpos.get(feature_list_offset..)?;
+    let mut lookup_indices = vec![];
+    for feature_index in feature_indices {
+        let mut record = feature_list.get((2 + feature_index as usize * 6)..)?;
+        if record.read_u32::<BigEndian>().ok()? != KERN_FEATURE_TAG {
+            continue;
+        }
+        let feature_offset = record.read_u16::<BigEndian>().ok()? as usize;
+        let mut feature = feature_list.get((feature_offset + 2)..)?;
+        let lookup_index_count = feature.read_u16::<BigEndian>().ok()?;
+        for _ in 0..lookup_index_count {
+            lookup_indices.push(feature.read_u16::<BigEndian>().ok()?);
+        }
+    }
+
+    // Lookups are applied in lookup list order, each at most once.
+    lookup_indices.sort_unstable();
+    lookup_indices.dedup();
+
+    let lookup_list = gpos.get(lookup_list_offset..)?;
+    let mut lookups = vec![];
+    for lookup_index in lookup_indices {
+        let lookup_offset = lookup_list
+            .get((2 + lookup_index as usize * 2)..)?
+            .read_u16::<BigEndian>()
+            .ok()? as usize;
+        let lookup_start = lookup_list_offset + lookup_offset;
+        let mut reader = gpos.get(lookup_start..)?;
+        let lookup_type = reader.read_u16::<BigEndian>().ok()?;
+        let _lookup_flag = reader.read_u16::<BigEndian>().ok()?;
+        let subtable_count = reader.read_u16::<BigEndian>().ok()?;
+
+        let mut subtables = vec![];
+        for _ in 0..subtable_count {
+            let subtable_start = lookup_start + reader.read_u16::<BigEndian>().ok()? as usize;
+            match lookup_type {
+                PAIR_ADJUSTMENT_LOOKUP_TYPE => subtables.push(subtable_start),
+                EXTENSION_LOOKUP_TYPE => {
+                    let mut extension = gpos.get((subtable_start + 2)..)?;
+                    let extension_lookup_type = extension.read_u16::<BigEndian>().ok()?;
+                    let extension_offset = extension.read_u32::<BigEndian>().ok()? as usize;
+                    if extension_lookup_type == PAIR_ADJUSTMENT_LOOKUP_TYPE {
+                        subtables.push(subtable_start + extension_offset);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !subtables.is_empty() {
+            lookups.push(subtables);
+        }
+    }
+    Some(lookups)
+}
+
+/// Returns the default language system table of the given script, if the script is present.
+fn find_default_lang_sys(script_list: &[u8], script_tag: u32) -> Option<&[u8]> {
+    let mut reader = script_list;
+    let script_count = reader.read_u16::<BigEndian>().ok()?;
+    for _ in 0..script_count {
+        let tag = reader.read_u32::<BigEndian>().ok()?;
+        let script_offset = reader.read_u16::<BigEndian>().ok()? as usize;
+        if tag != script_tag {
+            continue;
+        }
+        let script = script_list.get(script_offset..)?;
+        let mut script_reader = script;
+        let default_lang_sys_offset = script_reader.read_u16::<BigEndian>().ok()? as usize;
+        if default_lang_sys_offset == 0 {
+            return None;
+        }
+        return script.get(default_lang_sys_offset..);
+    }
+    None
+}
+
+/// Looks up a pair in a pair adjustment subtable, returning the adjustment to the advance of the
+/// left glyph if the subtable applies to the pair.
+fn read_pair_adjustment(
+    subtable: &[u8],
+    left_glyph_id: u32,
+    right_glyph_id: u32,
+) -> Option<Vector2F> {
+    let mut reader = subtable;
+    let format = reader.read_u16::<BigEndian>().ok()?;
+    let coverage_offset = reader.read_u16::<BigEndian>().ok()? as usize;
+    let value_format_1 = reader.read_u16::<BigEndian>().ok()?;
+    let value_format_2 = reader.read_u16::<BigEndian>().ok()?;
+    let coverage_index = read_coverage_index(subtable.get(coverage_offset..)?, left_glyph_id)?;
+    let value_record_size_1 = value_format_1.count_ones() as usize * 2;
+    let value_record_size_2 = value_format_2.count_ones() as usize * 2;
+
+    match format {
+        1 => {
+            let pair_set_count = reader.read_u16::<BigEndian>().ok()? as usize;
+            if coverage_index >= pair_set_count {
+                return None;
+            }
+            let pair_set_offset = subtable
+                .get((10 + coverage_index * 2)..)?
+                .read_u16::<BigEndian>()
+                .ok()? as usize;
+            let pair_set = subtable.get(pair_set_offset..)?;
+            let mut pair_set_reader = pair_set;
+            let pair_value_count = pair_set_reader.read_u16::<BigEndian>().ok()? as usize;
+            let record_size = 2 + value_record_size_1 + value_record_size_2;
+
+            // Pair value records are sorted by the ID of the second glyph.
+            let (mut low, mut high) = (0, pair_value_count);
+            while low < high {
+                let mid = (low + high) / 2;
+                let mut record = pair_set.get((2 + mid * record_size)..)?;
+                let second_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+                if second_glyph_id == right_glyph_id {
+                    return read_value_record_advance(record, value_format_1);
+                }
+                if second_glyph_id < right_glyph_id {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            None
+        }
+        2 => {
+            let class_def_1_offset = reader.read_u16::<BigEndian>().ok()? as usize;
+            let class_def_2_offset = reader.read_u16::<BigEndian>().ok()? as usize;
+            let class_1_count = reader.read_u16::<BigEndian>().ok()? as usize;
+            let class_2_count = reader.read_u16::<BigEndian>().ok()? as usize;
+            let class_1 = read_glyph_class(subtable.get(class_def_1_offset..)?, left_glyph_id);
+            let class_2 = read_glyph_class(subtable.get(class_def_2_offset..)?, right_glyph_id);
+            if class_1 >= class_1_count || class_2 >= class_2_count {
+                return None;
+            }
+            let record_index = class_1 * class_2_count + class_2;
+            let record_offset = 16 + record_index * (value_record_size_1 + value_record_size_2);
+            read_value_record_advance(subtable.get(record_offset..)?, value_format_1)
+        }
+        _ => None,
+    }
+}
+
+/// Reads the advance adjustment from a value record with the given format.
+fn read_value_record_advance(mut record: &[u8], value_format: u16) -> Option<Vector2F> {
+    let mut advance = Vector2F::default();
+    for bit in 0..4 {
+        let flag = 1 << bit;
+        if value_format & flag == 0 {
+            continue;
+        }
+        let value = record.read_i16::<BigEndian>().ok()? as f32;
+        match flag {
+            VALUE_FORMAT_X_ADVANCE => advance.set_x(value),
+            VALUE_FORMAT_Y_ADVANCE => advance.set_y(value),
+            _ => {}
+        }
+    }
+    Some(advance)
+}
+
+/// Returns the index of the given glyph in a coverage table, if it's covered.
+fn read_coverage_index(coverage: &[u8], glyph_id: u32) -> Option<usize> {
+    let mut reader = coverage;
+    let format = reader.read_u16::<BigEndian>().ok()?;
+    let count = reader.read_u16::<BigEndian>().ok()? as usize;
+    let (mut low, mut high) = (0, count);
+    match format {
+        1 => {
+            while low < high {
+                let mid = (low + high) / 2;
+                let covered_glyph_id =
+                    reader.get((mid * 2)..)?.read_u16::<BigEndian>().ok()? as u32;
+                if covered_glyph_id == glyph_id {
+                    return Some(mid);
+                }
+                if covered_glyph_id < glyph_id {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            None
+        }
+        2 => {
+            while low < high {
+                let mid = (low + high) / 2;
+                let mut record = reader.get((mid * 6)..)?;
+                let start_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+                let end_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+                if end_glyph_id < glyph_id {
+                    low = mid + 1;
+                } else if start_glyph_id > glyph_id {
+                    high = mid;
+                } else {
+                    let start_coverage_index = record.read_u16::<BigEndian>().ok()? as u32;
+                    return Some((start_coverage_index + glyph_id - start_glyph_id) as usize);
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Returns the class of the given glyph in a class definition table. Glyphs that the table
+/// doesn't list are in class 0.
+fn read_glyph_class(class_def: &[u8], glyph_id: u32) -> usize {
+    read_listed_glyph_class(class_def, glyph_id).unwrap_or(0) as usize
+}
+
+fn read_listed_glyph_class(class_def: &[u8], glyph_id: u32) -> Option<u16> {
+    let mut reader = class_def;
+    match reader.read_u16::<BigEndian>().ok()? {
+        1 => {
+            let start_glyph_id = reader.read_u16::<BigEndian>().ok()? as u32;
+            let glyph_count = reader.read_u16::<BigEndian>().ok()? as u32;
+            if glyph_id < start_glyph_id || glyph_id >= start_glyph_id + glyph_count {
+                return None;
+            }
+            reader
+                .get(((glyph_id - start_glyph_id) as usize * 2)..)?
+                .read_u16::<BigEndian>()
+                .ok()
+        }
+        2 => {
+            let range_count = reader.read_u16::<BigEndian>().ok()? as usize;
+            let (mut low, mut high) = (0, range_count);
+            while low < high {
+                let mid = (low + high) / 2;
+                let mut record = reader.get((mid * 6)..)?;
+                let start_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+                let end_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+                if end_glyph_id < glyph_id {
+                    low = mid + 1;
+                } else if start_glyph_id > glyph_id {
+                    high = mid;
+                } else {
+                    return record.read_u16::<BigEndian>().ok();
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Looks up a pair in the format 0 subtables of a `kern` table, in either the Microsoft or the
+/// Apple layout, and returns the horizontal adjustment.
+fn read_kern_pair(kern: &[u8], left_glyph_id: u32, right_glyph_id: u32) -> Option<f32> {
+    let mut reader = kern;
+    let (apple, subtable_count) = match reader.read_u16::<BigEndian>().ok()? {
+        0 => (false, reader.read_u16::<BigEndian>().ok()? as u32),
+        1 => {
+            let _minor_version = reader.read_u16::<BigEndian>().ok()?;
+            (true, reader.read_u32::<BigEndian>().ok()?)
+        }
+        _ => return None,
+    };
+
+    let key = (left_glyph_id << 16) | right_glyph_id;
+    let mut adjustment = None;
+    for _ in 0..subtable_count {
+        let subtable = reader;
+        let (length, format, applies, overrides, header_length) = if apple {
+            let length = reader.read_u32::<BigEndian>().ok()? as usize;
+            let coverage = reader.read_u16::<BigEndian>().ok()?;
+            let applies = coverage
+                & (APPLE_KERN_COVERAGE_VERTICAL
+                    | APPLE_KERN_COVERAGE_CROSS_STREAM
+                    | APPLE_KERN_COVERAGE_VARIATION)
+                == 0;
+            (length, coverage & 0xff, applies, false, 8)
+        } else {
+            let _version = reader.read_u16::<BigEndian>().ok()?;
+            let length = reader.read_u16::<BigEndian>().ok()? as usize;
+            let coverage = reader.read_u16::<BigEndian>().ok()?;
+            let applies = coverage
+                & (KERN_COVERAGE_HORIZONTAL | KERN_COVERAGE_MINIMUM | KERN_COVERAGE_CROSS_STREAM)
+                == KERN_COVERAGE_HORIZONTAL;
+            let overrides = coverage & KERN_COVERAGE_OVERRIDE != 0;
+            (length, coverage >> 8, applies, overrides, 6)
+        };
+
+        if applies && format == 0 {
+            let body = subtable.get(header_length..)?;
+            if let Some(value) = read_kern_format_0_pair(body, key) {
+                adjustment = Some(if overrides {
+                    value
+                } else {
+                    adjustment.unwrap_or(0.0) + value
+                });
+            }
+        }
+
+        reader = match subtable.get(length..) {
+            Some(rest) if length >= header_length => rest,
+            _ => break,
+        };
+    }
+    adjustment
+}
+
+fn read_kern_format_0_pair(body: &[u8], key: u32) -> Option<f32> {
+    let mut reader = body;
+    let pair_count = reader.read_u16::<BigEndian>().ok()? as usize;
+    let pairs = body.get(8..)?;
+
+    // Pairs are sorted by the left glyph ID, then the right glyph ID.
+    let (mut low, mut high) = (0, pair_count);
+    while low < high {
+        let mid = (low + high) / 2;
+        let mut pair = pairs.get((mid * 6)..)?;
+        let pair_key = pair.read_u32::<BigEndian>().ok()?;
+        if pair_key == key {
+            return Some(pair.read_i16::<BigEndian>().ok()? as f32);
+        }
+        if pair_key < key {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    None
+}
@@ -0,0 +1,304 @@
+// font-kit/src/layout.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Simple text layout, for placing the glyphs of a string in lines.
+//!
+//! This maps each character to a glyph through the font's character map and places the glyphs
+//! one after another along their advances. It does no shaping: there are no ligatures, no
+//! contextual forms, and no bidirectional reordering. For those, use a shaper such as HarfBuzz.
+//!
+//! Positions are in pixels, with the origin at the top left of the first line and Y pointing
+//! down, as on a `Canvas`.
+
+use pathfinder_geometry::rect::RectF;
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::Vector2F;
+use std::ops::Range;
+
+use crate::canvas::{Canvas, RasterizationOptions};
+use crate::error::GlyphLoadingError;
+use crate::hinting::HintingOptions;
+use crate::loader::Loader;
+use crate::outline::{Outline, OutlineBuilder, TransformingSink};
+
+/// Options that control how text is laid out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayoutOptions {
+    /// Whether to adjust the space between pairs of glyphs by the font's kerning.
+    pub kerning: bool,
+    /// Extra space, in pixels, added between each pair of adjacent glyphs on a line. This may be
+    /// negative to tighten the text.
+    pub letter_spacing: f32,
+    /// The distance between the baselines of successive lines, in pixels.
+    ///
+    /// If this is `None`, the font's ascent, descent, and line gap are added together.
+    pub line_height: Option<f32>,
+}
+
+impl Default for LayoutOptions {
+    #[inline]
+    fn default() -> LayoutOptions {
+        LayoutOptions {
+            kerning: true,
+            letter_spacing: 0.0,
+            line_height: None,
+        }
+    }
+}
+
+/// A glyph placed by a layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PositionedGlyph {
+    /// The ID of the glyph in the font.
+    ///
+    /// Characters that the font doesn't cover get glyph 0, which is the `.notdef` glyph.
+    pub glyph_id: u32,
+    /// The byte offset in the text of the character that this glyph was chosen for.
+    pub text_index: usize,
+    /// The index of the line that this glyph is on.
+    pub line: usize,
+    /// The glyph origin, on the baseline, in pixels.
+    pub position: Vector2F,
+    /// The distance from this glyph to the next one on the line, in pixels. This includes kerning
+    /// and letter spacing.
+    pub advance: f32,
+}
+
+/// A line of a layout.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutLine {
+    /// The range of `Layout::glyphs` on this line.
+    pub glyphs: Range<usize>,
+    /// The range of the text on this line, without the line break.
+    pub text: Range<usize>,
+    /// The vertical position of the baseline, in pixels.
+    pub baseline: f32,
+    /// The distance from the start of the line to the end of its last glyph's advance, in pixels.
+    pub width: f32,
+}
+
+/// Glyphs positioned in lines, ready to be rasterized or turned into outlines.
+///
+/// A layout doesn't keep a reference to its font. Pass the same font that laid it out to the
+/// methods that need one.
+#[derive(Clone, Debug)]
+pub struct Layout {
+    /// The glyphs, in text order.
+    pub glyphs: Vec<PositionedGlyph>,
+    /// The lines, from top to bottom.
+    pub lines: Vec<LayoutLine>,
+    /// The size that the text was laid out at, in pixels per em.
+    pub point_size: f32,
+    /// The distance between baselines, in pixels.
+    pub line_height: f32,
+    /// The box that the lines take up, from the ascent of the first line to the descent of the
+    /// last, and from the start of the lines to the end of the widest.
+    ///
+    /// Glyphs may draw outside of this box. Use `ink_bounds` for the box around what is drawn.
+    pub bounds: RectF,
+}
+
+impl Layout {
+    /// Lays out `text` in `loader` at `point_size`, in pixels per em.
+    ///
+    /// Lines break at `\n`, `\r\n`, and `\r`, and nowhere else.
+    pub fn new<L>(
+        loader: &L,
+        text: &str,
+        point_size: f32,
+        options: &LayoutOptions,
+    ) -> Result<Layout, GlyphLoadingError>
+    where
+        L: Loader,
+    {
+        let metrics = loader.metrics();
+        let scale = point_size / metrics.units_per_em as f32;
+        let ascent = metrics.ascent * scale;
+        let descent = metrics.descent * scale;
+        let line_height = options
+            .line_height
+            .unwrap_or((metrics.ascent - metrics.descent + metrics.line_gap) * scale);
+
+        let mut glyphs = vec![];
+        let mut lines = vec![];
+        let mut width: f32 = 0.0;
+        for (line_index, text_range) in line_ranges(text).enumerate() {
+            let baseline = ascent + line_index as f32 * line_height;
+            let first_glyph = glyphs.len();
+            let glyph_ids: Vec<_> = text[text_range.clone()]
+                .chars()
+                .map(|character| loader.glyph_for_char(character).unwrap_or(0))
+                .collect();
+            let kerning = if options.kerning {
+                loader.kerning_for_glyphs(&glyph_ids)
+            } else {
+                vec![Vector2F::zero(); glyph_ids.len()]
+            };
+
+            let mut x = 0.0;
+            let characters = text[text_range.clone()].char_indices();
+            for (index, (&glyph_id, (offset, _))) in glyph_ids.iter().zip(characters).enumerate() {
+                let mut advance = (loader.advance(glyph_id)?.x() + kerning[index].x()) * scale;
+                if index + 1 < glyph_ids.len() {
+                    advance += options.letter_spacing;
+                }
+                glyphs.push(PositionedGlyph {
+                    glyph_id,
+                    text_index: text_range.start + offset,
+                    line: line_index,
+                    position: Vector2F::new(x, baseline),
+                    advance,
+                });
+                x += advance;
+            }
+
+            width = width.max(x);
+            lines.push(LayoutLine {
+                glyphs: first_glyph..glyphs.len(),
+                text: text_range,
+                baseline,
+                width: x,
+            });
+        }
+
+        let bottom = lines.last().map_or(0.0, |line| line.baseline) - descent;
+        Ok(Layout {
+            glyphs,
+            lines,
+            point_size,
+            line_height,
+            bounds: RectF::from_points(Vector2F::zero(), Vector2F::new(width, bottom)),
+        })
+    }
+
+    /// Returns the smallest box that contains the typographic bounds of every glyph, in pixels.
+    ///
+    /// This is an empty rectangle at the origin if no glyph draws anything.
+    pub fn ink_bounds<L>(&self, loader: &L) -> Result<RectF, GlyphLoadingError>
+    where
+        L: Loader,
+    {
+        let scale = self.point_size / loader.metrics().units_per_em as f32;
+        let mut ink_bounds: Option<RectF> = None;
+        for glyph in &self.glyphs {
+            let bounds = loader.typographic_bounds(glyph.glyph_id)?;
+            if bounds.width() == 0.0 && bounds.height() == 0.0 {
+                continue;
+            }
+            let bounds = glyph_transform(glyph, scale) * bounds;
+            ink_bounds =
+                Some(ink_bounds.map_or(bounds, |ink_bounds| ink_bounds.union_rect(bounds)));
+        }
+        Ok(ink_bounds.unwrap_or_default())
+    }
+
+    /// Rasterizes every glyph into `canvas`, with `transform` mapping layout coordinates to canvas
+    /// pixels.
+    ///
+    /// Unlike `Loader::rasterize_glyph`, which replaces the pixels under each glyph, this keeps
+    /// what is already drawn, so that glyphs that overlap don't erase each other. Coverage formats
+    /// keep the larger coverage of the two, and color glyphs in `Rgba32` canvases are composited
+    /// over what is already there.
+    pub fn rasterize<L>(
+        &self,
+        loader: &L,
+        canvas: &mut Canvas,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<(), GlyphLoadingError>
+    where
+        L: Loader,
+    {
+        for glyph in &self.glyphs {
+            let glyph_transform = transform * Transform2F::from_translation(glyph.position);
+            let bounds = loader.raster_bounds(
+                glyph.glyph_id,
+                self.point_size,
+                glyph_transform,
+                hinting_options,
+                rasterization_options,
+            )?;
+            if bounds.width() <= 0 || bounds.height() <= 0 {
+                continue;
+            }
+
+            let mut glyph_canvas = Canvas::new(bounds.size(), canvas.format);
+            loader.rasterize_glyph(
+                &mut glyph_canvas,
+                glyph.glyph_id,
+                self.point_size,
+                Transform2F::from_translation(-bounds.origin().to_f32()) * glyph_transform,
+                hinting_options,
+                rasterization_options,
+            )?;
+            canvas.composite_from(bounds.origin(), &glyph_canvas);
+        }
+        Ok(())
+    }
+
+    /// Returns the outlines of every glyph combined into one, in layout coordinates.
+    ///
+    /// As layout coordinates point down, the contours wind the opposite way to those in font
+    /// units.
+    pub fn outline<L>(
+        &self,
+        loader: &L,
+        hinting_options: HintingOptions,
+    ) -> Result<Outline, GlyphLoadingError>
+    where
+        L: Loader,
+    {
+        let scale = self.point_size / loader.metrics().units_per_em as f32;
+        let mut builder = OutlineBuilder::new();
+        for glyph in &self.glyphs {
+            let mut glyph_builder = OutlineBuilder::new();
+            loader.outline(glyph.glyph_id, hinting_options, &mut glyph_builder)?;
+            glyph_builder
+                .into_outline()
+                .copy_to(&mut TransformingSink::new(
+                    &mut builder,
+                    glyph_transform(glyph, scale),
+                ));
+        }
+        Ok(builder.into_outline())
+    }
+}
+
+// Returns the transform from the font units of a glyph to layout coordinates.
+fn glyph_transform(glyph: &PositionedGlyph, scale: f32) -> Transform2F {
+    Transform2F::from_translation(glyph.position)
+        * Transform2F::from_scale(Vector2F::new(scale, -scale))
+}
+
+// Returns the range of each line of `text`, without its line break.
+fn line_ranges(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut start = Some(0);
+    std::iter::from_fn(move || {
+        let line_start = start?;
+        let rest = &text[line_start..];
+        match rest.find(['\n', '\r']) {
+            Some(offset) => {
+                let line_end = line_start + offset;
+                let break_length = if rest[offset..].starts_with("\r\n") {
+                    2
+                } else {
+                    1
+                };
+                start = Some(line_end + break_length);
+                Some(line_start..line_end)
+            }
+            None => {
+                start = None;
+                Some(line_start..text.len())
+            }
+        }
+    })
+}
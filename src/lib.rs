@@ -59,6 +59,9 @@
 //!
 //! * FreeType (cross-platform): A full-featured font rendering framework.
 //!
+//! * OpenType (cross-platform): A loader written in pure Rust that parses TrueType and CFF
+//!   outlines itself and rasterizes them in software. Does no hinting.
+//!
 //! Available sources:
 //!
 //! * Core Text (macOS): The system font database on macOS.
@@ -81,6 +84,14 @@
 //! `source-fontconfig-default` Cargo features respectively. Beware that
 //! `source-fontconfig-default` is rarely what you want on those two platforms!
 //!
+//! The OpenType loader is not built by default on any platform. To build it, use the
+//! `loader-opentype` Cargo feature, or `loader-opentype-default` to make it the default loader
+//! everywhere.
+//!
+//! FreeType isn't available on `wasm32`, so builds for it need `loader-opentype-default`, and they
+//! have no C dependencies. On the other platforms that use FreeType and Fontconfig, those libraries
+//! are still linked when the OpenType loader is the default.
+//!
 //! ## Features
 //!
 //! `font-kit` is capable of doing the following:
@@ -98,13 +109,55 @@
 //!
 //! * Reading unhinted or hinted vector outlines from glyphs.
 //!
+//! * Measuring, transforming, flattening, and reorienting outlines, and testing points against
+//!   them.
+//!
+//! * Writing outlines as SVG path data, and reading SVG path data back into outlines.
+//!
 //! * Calculating glyph and font metrics.
 //!
-//! * Looking up glyph advances and origins.
+//! * Inspecting the axes of variable fonts and instantiating them at arbitrary coordinates.
+//!
+//! * Looking up glyph advances and origins, including vertical metrics for vertical text.
+//!
+//! * Enumerating the characters that a font covers, and mapping glyphs back to characters.
+//!
+//! * Looking up glyph names.
+//!
+//! * Looking up pair kerning from the `GPOS` and `kern` tables.
+//!
+//! * Reading localized names in any of the platforms and encodings of the `name` table.
+//!
+//! * Laying out simple text: placing the glyphs of a string in lines with kerning and letter
+//!   spacing, then rasterizing them or combining their outlines.
 //!
 //! * Rasterizing glyphs using the native rasterizer, optionally using hinting. (Custom
 //!   rasterizers, such as Pathfinder, can be used in conjunction with the outline API.)
 //!
+//! * Correcting antialiased glyph coverage for gamma and contrast, for the luminance of the text.
+//!
+//! * Stroking glyph outlines, either as new outlines or while rasterizing, for outlined and haloed
+//!   text.
+//!
+//! * Packing rasterized glyphs into texture atlas pages with LRU eviction and dirty region
+//!   tracking, with the `atlas` Cargo feature.
+//!
+//! * Computing single- and multi-channel signed distance fields of glyphs from their outlines,
+//!   for drawing text at any scale.
+//!
+//! * Synthesizing bold and oblique styles when a family lacks them, as CSS `font-synthesis` does.
+//!
+//! * Rendering `COLR`/`CPAL` color glyphs, including version 1 gradients and compositing, with a
+//!   choice of palette and foreground color.
+//!
+//! * Rendering embedded color bitmap glyphs from `CBDT`/`CBLC` and `sbix` tables, scaled from the
+//!   best matching strike.
+//!
+//! * Enumerating the strikes of bitmap fonts (BDF, PCF, FNT) and rasterizing them at the nearest
+//!   strike.
+//!
+//! * Extracting the SVG documents of OpenType-SVG glyphs for use with an SVG renderer.
+//!
 //! * Looking up all fonts on the system.
 //!
 //! * Searching for specific fonts by family or PostScript name.
@@ -124,25 +177,55 @@
 #[macro_use]
 extern crate bitflags;
 
+// There is no FreeType on wasm32, so the OpenType loader is the only one.
+#[cfg(all(
+    target_arch = "wasm32",
+    not(any(feature = "loader-freetype", feature = "loader-opentype-default"))
+))]
+compile_error!("font-kit needs the `loader-opentype-default` feature on wasm32.");
+
+#[cfg(feature = "atlas")]
+pub mod atlas;
+pub mod bitmap;
 pub mod canvas;
+pub mod cmap;
+pub mod color;
 pub mod error;
 pub mod family;
 pub mod family_handle;
 pub mod family_name;
 pub mod file_type;
 pub mod font;
+pub mod gamma;
 pub mod handle;
 pub mod hinting;
+pub mod layout;
 pub mod loader;
 pub mod loaders;
 pub mod metrics;
+pub mod names;
 pub mod outline;
 pub mod properties;
+pub mod sdf;
+pub mod stroke;
+pub mod svg;
+pub mod svg_path;
+pub mod synthesis;
+pub mod variations;
 
 #[cfg(feature = "source")]
 pub mod source;
 #[cfg(feature = "source")]
 pub mod sources;
 
+#[cfg(feature = "loader-opentype")]
+mod cff;
+#[cfg(feature = "loader-opentype")]
+mod glyf;
+mod glyph_names;
+mod kerning;
+#[cfg(feature = "source")]
 mod matching;
+mod paint;
+mod rasterizer;
 mod utils;
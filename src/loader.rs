@@ -14,17 +14,28 @@
 use log::warn;
 use pathfinder_geometry::rect::{RectF, RectI};
 use pathfinder_geometry::transform2d::Transform2F;
-use pathfinder_geometry::vector::Vector2F;
+use pathfinder_geometry::vector::{Vector2F, Vector2I};
 use std::sync::Arc;
 
-use crate::canvas::{Canvas, RasterizationOptions};
+use crate::bitmap::{self, EmbeddedImage, FixedSize};
+use crate::canvas::{Canvas, Format, RasterizationOptions};
+use crate::cmap::{self, CharacterCoverage};
+use crate::color::{self, ColorLayer, ColorPalette, RgbaColor};
 use crate::error::{FontLoadingError, GlyphLoadingError};
 use crate::file_type::FileType;
+use crate::gamma::GammaLut;
+use crate::glyph_names::{self, POST_TABLE_TAG};
 use crate::handle::Handle;
 use crate::hinting::HintingOptions;
-use crate::metrics::Metrics;
-use crate::outline::OutlineSink;
+use crate::kerning::PairKerning;
+use crate::metrics::{self, Metrics, VerticalMetrics};
+use crate::names::{self, name_id, NameRecord};
+use crate::outline::{Outline, OutlineBuilder, OutlineSink};
 use crate::properties::Properties;
+use crate::rasterizer;
+use crate::sdf::{self, DistanceField};
+use crate::stroke::{self, StrokeStyle};
+use crate::svg::{self, SvgGlyphDocument};
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::fs::File;
@@ -63,6 +74,10 @@ pub trait Loader: Clone + Sized {
     }
 
     /// Creates a font from a native API handle.
+    ///
+    /// # Safety
+    ///
+    /// `native_font` must be a valid handle for the underlying platform API.
     unsafe fn from_native_font(native_font: Self::NativeFont) -> Self;
 
     /// Loads the font pointed to by a handle.
@@ -112,12 +127,102 @@ pub trait Loader: Clone + Sized {
     /// Returns the name of the font family.
     fn family_name(&self) -> String;
 
+    /// Returns the name of the font style within its family, such as "Bold Italic".
+    ///
+    /// This is the subfamily name (name ID 2) of the `name` table, which is limited to the four
+    /// styles Regular, Italic, Bold, and Bold Italic in fonts with typographic names.
+    fn style_name(&self) -> String {
+        self.localized_subfamily_name("en-US").unwrap_or_default()
+    }
+
+    /// Returns the typographic family name (name ID 16), which groups faces beyond the four basic
+    /// styles, or the family name if the font has none.
+    fn typographic_family_name(&self) -> String {
+        self.localized_name(name_id::TYPOGRAPHIC_FAMILY, "en-US")
+            .unwrap_or_else(|| self.family_name())
+    }
+
+    /// Returns the typographic subfamily name (name ID 17), such as "Semibold Italic", or the
+    /// style name if the font has none.
+    fn typographic_subfamily_name(&self) -> String {
+        self.localized_name(name_id::TYPOGRAPHIC_SUBFAMILY, "en-US")
+            .unwrap_or_else(|| self.style_name())
+    }
+
+    /// Returns the WWS family name (name ID 21), which groups faces that differ only in weight,
+    /// width, and slope, or the typographic family name if the font has none.
+    fn wws_family_name(&self) -> String {
+        self.localized_name(name_id::WWS_FAMILY, "en-US")
+            .unwrap_or_else(|| self.typographic_family_name())
+    }
+
+    /// Returns the WWS subfamily name (name ID 22), or the typographic subfamily name if the font
+    /// has none.
+    fn wws_subfamily_name(&self) -> String {
+        self.localized_name(name_id::WWS_SUBFAMILY, "en-US")
+            .unwrap_or_else(|| self.typographic_subfamily_name())
+    }
+
+    /// Returns all the records of the `name` table with the given name ID, such as
+    /// `name_id::FAMILY`, decoded from every platform and encoding that `font-kit` understands.
+    fn name_records(&self, name_id: u16) -> Vec<NameRecord> {
+        match self.load_font_table(names::NAME_TABLE_TAG) {
+            Some(name) => names::read_name_records(&name, name_id),
+            None => vec![],
+        }
+    }
+
+    /// Returns the name with the given name ID in the language that best matches the given BCP 47
+    /// tag, such as `ja` or `zh-Hant`.
+    ///
+    /// If the font has no name in that language, this returns the US English name if there is one
+    /// and any name otherwise.
+    fn localized_name(&self, name_id: u16, language: &str) -> Option<String> {
+        names::find_localized_name(&self.name_records(name_id), language)
+    }
+
+    /// Returns the family name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    fn localized_family_name(&self, language: &str) -> Option<String> {
+        self.localized_name(name_id::FAMILY, language)
+    }
+
+    /// Returns the subfamily (style) name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    fn localized_subfamily_name(&self, language: &str) -> Option<String> {
+        self.localized_name(name_id::SUBFAMILY, language)
+    }
+
+    /// Returns the typographic family name in the language that best matches the given BCP 47
+    /// tag, falling back to the family name for fonts that don't have one.
+    fn localized_typographic_family_name(&self, language: &str) -> Option<String> {
+        self.localized_name(name_id::TYPOGRAPHIC_FAMILY, language)
+            .or_else(|| self.localized_family_name(language))
+    }
+
+    /// Returns the full name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    fn localized_full_name(&self, language: &str) -> Option<String> {
+        self.localized_name(name_id::FULL_NAME, language)
+    }
+
     /// Returns true if and only if the font is monospace (fixed-width).
     fn is_monospace(&self) -> bool;
 
     /// Returns the values of various font properties, corresponding to those defined in CSS.
     fn properties(&self) -> Properties;
 
+    /// Returns the number of named instances (such as "Bold Condensed") that this variable font
+    /// defines in its `fvar` table.
+    ///
+    /// Named instance *n* (counting from 1) can be opened as a face of its own by passing
+    /// `n << 16 | font_index` as the font index, following the convention of FreeType and
+    /// Fontconfig. Loaders that can't open named instances return 0.
+    #[inline]
+    fn named_instance_count(&self) -> u32 {
+        0
+    }
+
     /// Returns the number of glyphs in the font.
     ///
     /// Glyph IDs range from 0 inclusive to this value exclusive.
@@ -130,6 +235,38 @@ pub trait Loader: Clone + Sized {
     /// use cases like "what does character X look like on its own".
     fn glyph_for_char(&self, character: char) -> Option<u32>;
 
+    /// Returns every character that the font maps to a glyph, along with that glyph, sorted by
+    /// character.
+    ///
+    /// This reads the preferred Unicode subtable of the `cmap` table, as `glyph_for_char` does.
+    fn character_map(&self) -> Vec<(char, u32)> {
+        match self.load_font_table(cmap::CMAP_TABLE_TAG) {
+            Some(cmap) => cmap::read_character_map(&cmap, self.glyph_count()),
+            None => vec![],
+        }
+    }
+
+    /// Returns the set of characters that the font maps to glyphs.
+    fn character_coverage(&self) -> CharacterCoverage {
+        CharacterCoverage::from_sorted_chars(
+            self.character_map()
+                .into_iter()
+                .map(|(character, _)| character),
+        )
+    }
+
+    /// Returns the characters that map to the given glyph, in ascending order.
+    ///
+    /// This is the reverse of `glyph_for_char`. To map many glyphs back to characters, as when
+    /// building a PDF `ToUnicode` map, use `character_map` once instead.
+    fn chars_for_glyph(&self, glyph_id: u32) -> Vec<char> {
+        self.character_map()
+            .into_iter()
+            .filter(|&(_, mapped_glyph_id)| mapped_glyph_id == glyph_id)
+            .map(|(character, _)| character)
+            .collect()
+    }
+
     /// Returns the glyph ID for the specified glyph name.
     #[inline]
     fn glyph_by_name(&self, _name: &str) -> Option<u32> {
@@ -137,6 +274,24 @@ pub trait Loader: Clone + Sized {
         None
     }
 
+    /// Returns the name of a glyph, or `None` if the glyph ID is out of range.
+    ///
+    /// Names come from the font where it has them. Otherwise, a name of the form `uniXXXX` is
+    /// derived from the first character that maps to the glyph, as the Adobe Glyph List
+    /// specifies. Glyphs with neither have no name.
+    fn glyph_name(&self, glyph_id: u32) -> Option<String> {
+        if glyph_id >= self.glyph_count() {
+            return None;
+        }
+        if let Some(name) = self
+            .load_font_table(POST_TABLE_TAG)
+            .and_then(|post| glyph_names::read_post_glyph_name(&post, glyph_id))
+        {
+            return Some(name);
+        }
+        glyph_names::synthesize_glyph_name(glyph_id, &self.chars_for_glyph(glyph_id))
+    }
+
     /// Sends the vector path for a glyph to a sink.
     ///
     /// If `hinting_mode` is not None, this function performs grid-fitting as requested before
@@ -163,6 +318,101 @@ pub trait Loader: Clone + Sized {
     /// Returns the amount that the given glyph should be displaced from the origin.
     fn origin(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError>;
 
+    /// Returns the distance from the origin of the glyph with the given ID to the next when text
+    /// is set vertically, in font units.
+    ///
+    /// This comes from the `vmtx` table. Fonts without one use `ascent - descent` from the font
+    /// metrics.
+    fn vertical_advance(&self, glyph_id: u32) -> Result<f32, GlyphLoadingError> {
+        if glyph_id >= self.glyph_count() {
+            return Err(GlyphLoadingError::NoSuchGlyph);
+        }
+        if let (Some(vhea), Some(vmtx)) = (
+            self.load_font_table(metrics::VHEA_TABLE_TAG),
+            self.load_font_table(metrics::VMTX_TABLE_TAG),
+        ) {
+            if let Some((advance_height, _)) =
+                metrics::read_vertical_metrics(&vhea, &vmtx, glyph_id)
+            {
+                return Ok(advance_height as f32);
+            }
+        }
+        let metrics = self.metrics();
+        Ok(metrics.ascent - metrics.descent)
+    }
+
+    /// Returns the position of the origin used when the glyph with the given ID is set
+    /// vertically, in font units relative to its horizontal origin.
+    ///
+    /// The vertical origin is horizontally centered on the advance. Its height comes from the
+    /// `VORG` table if there is one, or else from the top side bearing in the `vmtx` table added
+    /// to the top of the glyph bounds. Fonts with neither use the ascent.
+    fn vertical_origin(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
+        let x = self.advance(glyph_id)?.x() * 0.5;
+        if let Some(vorg) = self.load_font_table(metrics::VORG_TABLE_TAG) {
+            if let Some(y) = metrics::read_vertical_origin_y(&vorg, glyph_id) {
+                return Ok(Vector2F::new(x, y as f32));
+            }
+        }
+        if let (Some(vhea), Some(vmtx)) = (
+            self.load_font_table(metrics::VHEA_TABLE_TAG),
+            self.load_font_table(metrics::VMTX_TABLE_TAG),
+        ) {
+            if let Some((_, top_side_bearing)) =
+                metrics::read_vertical_metrics(&vhea, &vmtx, glyph_id)
+            {
+                let top = self.typographic_bounds(glyph_id)?.max_y();
+                return Ok(Vector2F::new(x, top + top_side_bearing as f32));
+            }
+        }
+        Ok(Vector2F::new(x, self.metrics().ascent))
+    }
+
+    /// Retrieves the metrics that apply to the entire font when text is set vertically.
+    ///
+    /// These come from the `vhea` table. Fonts without one get an ascent of half of
+    /// `ascent - descent` from the font metrics, and the negation of that as the descent.
+    fn vertical_metrics(&self) -> VerticalMetrics {
+        if let Some(vhea) = self.load_font_table(metrics::VHEA_TABLE_TAG) {
+            if let Some(vertical_metrics) = metrics::read_vertical_ascent_and_descent(&vhea) {
+                return vertical_metrics;
+            }
+        }
+        let metrics = self.metrics();
+        let half_height = (metrics.ascent - metrics.descent) * 0.5;
+        VerticalMetrics {
+            ascent: half_height,
+            descent: -half_height,
+        }
+    }
+
+    /// Returns the adjustment to the advance of the left glyph when it is followed by the right
+    /// glyph, in font units.
+    ///
+    /// This comes from the pair adjustment lookups of the `kern` feature in the `GPOS` table, for
+    /// the default script and language. Fonts without that feature fall back to the format 0
+    /// subtables of the legacy `kern` table.
+    fn kerning(&self, left_glyph_id: u32, right_glyph_id: u32) -> Vector2F {
+        PairKerning::new(self).adjustment(left_glyph_id, right_glyph_id)
+    }
+
+    /// Returns the kerning adjustment for each glyph of a run, in font units.
+    ///
+    /// The result has one entry per glyph: the adjustment to its advance given the glyph that
+    /// follows it, which is zero for the last glyph. This loads the kerning tables only once, so
+    /// it is faster than calling `kerning` for every pair.
+    fn kerning_for_glyphs(&self, glyph_ids: &[u32]) -> Vec<Vector2F> {
+        let kerning = PairKerning::new(self);
+        let mut adjustments: Vec<_> = glyph_ids
+            .windows(2)
+            .map(|pair| kerning.adjustment(pair[0], pair[1]))
+            .collect();
+        if !glyph_ids.is_empty() {
+            adjustments.push(Vector2F::default());
+        }
+        adjustments
+    }
+
     /// Retrieves various metrics that apply to the entire font.
     fn metrics(&self) -> Metrics;
 
@@ -236,6 +486,237 @@ pub trait Loader: Clone + Sized {
         rasterization_options: RasterizationOptions,
     ) -> Result<(), GlyphLoadingError>;
 
+    /// Rasterizes a glyph like `rasterize_glyph()`, with its coverage corrected by `gamma_lut` for
+    /// text drawn in `color`. See `GammaLut::preblend()` for how each format is corrected.
+    ///
+    /// Unlike `rasterize_glyph()`, this keeps what is already drawn in `canvas`, like
+    /// `Layout::rasterize()` does, so that the pixels around the glyph aren't corrected again.
+    #[allow(clippy::too_many_arguments)]
+    fn rasterize_glyph_with_gamma(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+        gamma_lut: &GammaLut,
+        color: RgbaColor,
+    ) -> Result<(), GlyphLoadingError> {
+        let mut glyph_canvas = Canvas::new(canvas.size, canvas.format);
+        self.rasterize_glyph(
+            &mut glyph_canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+        )?;
+        gamma_lut.preblend(&mut glyph_canvas, color);
+        canvas.composite_from(Vector2I::zero(), &glyph_canvas);
+        Ok(())
+    }
+
+    /// Returns the border of a stroke along the outline of a glyph, as a new outline in font
+    /// units.
+    ///
+    /// The width of the stroke is in font units. See `stroke::stroke_outline` for details.
+    fn stroked_outline(
+        &self,
+        glyph_id: u32,
+        hinting_mode: HintingOptions,
+        stroke_style: &StrokeStyle,
+    ) -> Result<Outline, GlyphLoadingError> {
+        let mut builder = OutlineBuilder::new();
+        self.outline(glyph_id, hinting_mode, &mut builder)?;
+        Ok(stroke::stroke_outline(
+            &builder.into_outline(),
+            stroke_style,
+        ))
+    }
+
+    /// Returns the pixel boundaries that the stroke of the glyph will take up when rendered with
+    /// `rasterize_glyph_stroke()`.
+    ///
+    /// The width of the stroke is in pixels.
+    fn stroke_raster_bounds(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        stroke_style: &StrokeStyle,
+        hinting_options: HintingOptions,
+        _: RasterizationOptions,
+    ) -> Result<RectI, GlyphLoadingError> {
+        let outline = stroke::glyph_stroke_in_pixels(
+            self,
+            glyph_id,
+            point_size,
+            transform,
+            stroke_style,
+            hinting_options,
+        )?;
+        Ok(rasterizer::outline_raster_bounds(&outline, Transform2F::default()).unwrap_or_default())
+    }
+
+    /// Rasterizes the stroke of a glyph's outline, rather than filling it, to a canvas with the
+    /// given size and transform.
+    ///
+    /// The width of the stroke is in pixels. The outline is grid-fitted as `hinting_options`
+    /// requests before it is stroked, so strokes line up with glyphs filled with the same options.
+    /// Strokes are drawn by font-kit's own rasterizer, which has no subpixel antialiasing, so
+    /// `RasterizationOptions::SubpixelAa` draws them with grayscale coverage in all three channels.
+    #[allow(clippy::too_many_arguments)]
+    fn rasterize_glyph_stroke(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        stroke_style: &StrokeStyle,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<(), GlyphLoadingError> {
+        let outline = stroke::glyph_stroke_in_pixels(
+            self,
+            glyph_id,
+            point_size,
+            transform,
+            stroke_style,
+            hinting_options,
+        )?;
+        rasterizer::rasterize_outline(
+            canvas,
+            &outline,
+            Transform2F::default(),
+            rasterization_options,
+        );
+        Ok(())
+    }
+
+    /// Computes a signed distance field for a glyph from its outline, in a new `Format::A8`
+    /// canvas.
+    ///
+    /// `spread` is the distance in pixels, on either side of the edge, that the field covers. The
+    /// canvas is padded by that much around the glyph. Glyphs without outlines produce an empty
+    /// field.
+    fn rasterize_glyph_sdf(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        spread: f32,
+    ) -> Result<DistanceField, GlyphLoadingError> {
+        sdf::glyph_distance_field(self, glyph_id, point_size, spread, Format::A8)
+    }
+
+    /// Computes a multi-channel signed distance field for a glyph from its outline, in a new
+    /// `Format::Rgb24` canvas.
+    ///
+    /// The median of the three channels is the distance to the outline, with corners kept sharp.
+    /// `spread` is as in `rasterize_glyph_sdf()`.
+    fn rasterize_glyph_msdf(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        spread: f32,
+    ) -> Result<DistanceField, GlyphLoadingError> {
+        sdf::glyph_distance_field(self, glyph_id, point_size, spread, Format::Rgb24)
+    }
+
+    /// Returns the color palettes defined in the font's `CPAL` table.
+    ///
+    /// The result is empty if the font has no `CPAL` table.
+    fn color_palettes(&self) -> Vec<ColorPalette> {
+        self.load_font_table(color::CPAL_TABLE_TAG)
+            .and_then(|cpal| color::read_palettes(&cpal))
+            .unwrap_or_default()
+    }
+
+    /// Returns the layers that make up the given glyph, if it is a version 0 `COLR` color glyph.
+    fn color_glyph_layers(&self, glyph_id: u32) -> Option<Vec<ColorLayer>> {
+        self.load_font_table(color::COLR_TABLE_TAG)
+            .and_then(|colr| color::read_layers(&colr, glyph_id))
+    }
+
+    /// Returns true if the given glyph is drawn with a version 1 `COLR` paint graph.
+    fn has_color_glyph_paint(&self, glyph_id: u32) -> bool {
+        self.load_font_table(color::COLR_TABLE_TAG)
+            .is_some_and(|colr| color::has_paint(&colr, glyph_id))
+    }
+
+    /// Returns the sizes at which the font provides bitmap glyphs.
+    ///
+    /// The result is empty for fonts that only contain outlines.
+    fn fixed_sizes(&self) -> Vec<FixedSize> {
+        bitmap::read_fixed_sizes(self)
+    }
+
+    /// Returns the embedded bitmap image of the given glyph from the `CBDT`/`CBLC` or `sbix`
+    /// table, taken from the strike that best matches `point_size` pixels per em.
+    ///
+    /// The strike nearest to `point_size` is preferred, and the larger of two equally near
+    /// strikes. Strikes without an image for the glyph are skipped.
+    fn embedded_image(&self, glyph_id: u32, point_size: f32) -> Option<EmbeddedImage> {
+        bitmap::find_embedded_image(self, glyph_id, point_size)
+    }
+
+    /// Rasterizes a color glyph to an RGBA canvas with the given size and transform.
+    ///
+    /// Glyphs with a version 1 `COLR` paint graph are rendered in software, with glyph outlines
+    /// rasterized by `rasterize_glyph()` used as masks. Otherwise, each version 0 `COLR` layer is
+    /// rasterized with `rasterize_glyph()` and composited over the canvas. Colors come from palette
+    /// `palette_index`, or are `foreground_color` where the font asks for the foreground color. If
+    /// `palette_index` is out of range, the first palette is used. Glyphs without `COLR` data are
+    /// drawn from their embedded bitmap image, scaled from the best strike as described in
+    /// `embedded_image()`, if they have one in PNG or uncompressed format. Any other glyphs are
+    /// filled with `foreground_color`.
+    ///
+    /// Subpixel antialiasing is not supported for color glyphs; `RasterizationOptions::SubpixelAa`
+    /// is treated as grayscale antialiasing.
+    ///
+    /// Panics if the canvas format is not `Format::Rgba32`, `Format::Rgba32Straight`, or
+    /// `Format::Bgra32`.
+    #[allow(clippy::too_many_arguments)]
+    fn rasterize_color_glyph(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+        palette_index: usize,
+        foreground_color: RgbaColor,
+    ) -> Result<(), GlyphLoadingError> {
+        color::rasterize_color_glyph(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+            palette_index,
+            foreground_color,
+        )
+    }
+
+    /// Returns true if the given glyph is described by a document in the font's `SVG ` table.
+    fn has_svg_glyph(&self, glyph_id: u32) -> bool {
+        self.load_font_table(svg::SVG_TABLE_TAG)
+            .is_some_and(|svg| svg::has_document(&svg, glyph_id))
+    }
+
+    /// Returns the SVG document from the font's `SVG ` table that describes the given glyph,
+    /// decompressing it if necessary, along with the range of glyphs that the document covers.
+    ///
+    /// Returns `None` if the glyph has no SVG document, or if the document couldn't be
+    /// decompressed or is longer than `svg::MAX_DOCUMENT_LENGTH` bytes once decompressed.
+    fn svg_glyph_document(&self, glyph_id: u32) -> Option<SvgGlyphDocument> {
+        self.load_font_table(svg::SVG_TABLE_TAG)
+            .and_then(|svg| svg::read_document(&svg, glyph_id))
+    }
+
     /// Get font fallback results for the given text and locale.
     ///
     /// The `locale` argument is a language tag such as `"en-US"` or `"zh-Hans-CN"`.
@@ -37,15 +37,23 @@ use std::ops::Deref;
 use std::path::Path;
 use std::sync::Arc;
 
+use crate::bitmap::{EmbeddedImage, FixedSize};
 use crate::canvas::{Canvas, Format, RasterizationOptions};
+use crate::cmap::CharacterCoverage;
+use crate::color::{ColorLayer, ColorPalette, RgbaColor};
 use crate::error::{FontLoadingError, GlyphLoadingError};
 use crate::file_type::FileType;
+use crate::gamma::GammaLut;
 use crate::handle::Handle;
 use crate::hinting::HintingOptions;
 use crate::loader::{FallbackResult, Loader};
-use crate::metrics::Metrics;
-use crate::outline::OutlineSink;
+use crate::metrics::{Metrics, VerticalMetrics};
+use crate::names::NameRecord;
+use crate::outline::{Outline, OutlineSink};
 use crate::properties::{Properties, Stretch, Style, Weight};
+use crate::sdf::DistanceField;
+use crate::stroke::StrokeStyle;
+use crate::svg::SvgGlyphDocument;
 use crate::utils;
 
 const TTC_TAG: [u8; 4] = [b't', b't', b'c', b'f'];
@@ -219,14 +227,78 @@ impl Font {
         self.core_text_font.family_name()
     }
 
-    /// Returns the name of the font style, according to Core Text.
-    ///
-    /// NB: This function is only available on the Core Text backend.
+    /// Returns all the records of the `name` table with the given name ID, decoded from every
+    /// platform and encoding that `font-kit` understands.
+    #[inline]
+    pub fn name_records(&self, name_id: u16) -> Vec<NameRecord> {
+        <Self as Loader>::name_records(self, name_id)
+    }
+
+    /// Returns the name with the given name ID in the language that best matches the given BCP 47
+    /// tag.
+    #[inline]
+    pub fn localized_name(&self, name_id: u16, language: &str) -> Option<String> {
+        <Self as Loader>::localized_name(self, name_id, language)
+    }
+
+    /// Returns the family name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    pub fn localized_family_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_family_name(self, language)
+    }
+
+    /// Returns the subfamily (style) name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    pub fn localized_subfamily_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_subfamily_name(self, language)
+    }
+
+    /// Returns the typographic family name in the language that best matches the given BCP 47
+    /// tag, falling back to the family name for fonts that don't have one.
+    #[inline]
+    pub fn localized_typographic_family_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_typographic_family_name(self, language)
+    }
+
+    /// Returns the full name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    pub fn localized_full_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_full_name(self, language)
+    }
+
+    /// Returns the name of the font style within its family, according to Core Text.
     #[inline]
     pub fn style_name(&self) -> String {
         self.core_text_font.style_name()
     }
 
+    /// Returns the typographic family name (name ID 16), or the family name if the font has none.
+    #[inline]
+    pub fn typographic_family_name(&self) -> String {
+        <Self as Loader>::typographic_family_name(self)
+    }
+
+    /// Returns the typographic subfamily name (name ID 17), or the style name if the font has
+    /// none.
+    #[inline]
+    pub fn typographic_subfamily_name(&self) -> String {
+        <Self as Loader>::typographic_subfamily_name(self)
+    }
+
+    /// Returns the WWS family name (name ID 21), or the typographic family name if the font has
+    /// none.
+    #[inline]
+    pub fn wws_family_name(&self) -> String {
+        <Self as Loader>::wws_family_name(self)
+    }
+
+    /// Returns the WWS subfamily name (name ID 22), or the typographic subfamily name if the font
+    /// has none.
+    #[inline]
+    pub fn wws_subfamily_name(&self) -> String {
+        <Self as Loader>::wws_subfamily_name(self)
+    }
+
     /// Returns true if and only if the font is monospace (fixed-width).
     #[inline]
     pub fn is_monospace(&self) -> bool {
@@ -256,6 +328,15 @@ impl Font {
         }
     }
 
+    /// Returns the name of a glyph, or `None` if the glyph ID is out of range.
+    ///
+    /// Names come from the `post` table where it has them. Otherwise, a name of the form `uniXXXX`
+    /// is derived from the first character that maps to the glyph.
+    #[inline]
+    pub fn glyph_name(&self, glyph_id: u32) -> Option<String> {
+        <Self as Loader>::glyph_name(self, glyph_id)
+    }
+
     /// Returns the number of glyphs in the font.
     ///
     /// Glyph IDs range from 0 inclusive to this value exclusive.
@@ -284,6 +365,25 @@ impl Font {
         }
     }
 
+    /// Returns every character that the font maps to a glyph, along with that glyph, sorted by
+    /// character.
+    #[inline]
+    pub fn character_map(&self) -> Vec<(char, u32)> {
+        <Self as Loader>::character_map(self)
+    }
+
+    /// Returns the set of characters that the font maps to glyphs.
+    #[inline]
+    pub fn character_coverage(&self) -> CharacterCoverage {
+        <Self as Loader>::character_coverage(self)
+    }
+
+    /// Returns the characters that map to the given glyph, in ascending order.
+    #[inline]
+    pub fn chars_for_glyph(&self, glyph_id: u32) -> Vec<char> {
+        <Self as Loader>::chars_for_glyph(self, glyph_id)
+    }
+
     /// Returns the glyph ID for the specified glyph name.
     #[inline]
     pub fn glyph_by_name(&self, name: &str) -> Option<u32> {
@@ -390,6 +490,39 @@ impl Font {
         }
     }
 
+    /// Returns the distance from the origin of the glyph with the given ID to the next when text
+    /// is set vertically, in font units.
+    #[inline]
+    pub fn vertical_advance(&self, glyph_id: u32) -> Result<f32, GlyphLoadingError> {
+        <Self as Loader>::vertical_advance(self, glyph_id)
+    }
+
+    /// Returns the position of the origin used when the glyph with the given ID is set
+    /// vertically, in font units relative to its horizontal origin.
+    #[inline]
+    pub fn vertical_origin(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
+        <Self as Loader>::vertical_origin(self, glyph_id)
+    }
+
+    /// Retrieves the metrics that apply to the entire font when text is set vertically.
+    #[inline]
+    pub fn vertical_metrics(&self) -> VerticalMetrics {
+        <Self as Loader>::vertical_metrics(self)
+    }
+
+    /// Returns the adjustment to the advance of the left glyph when it is followed by the right
+    /// glyph, in font units.
+    #[inline]
+    pub fn kerning(&self, left_glyph_id: u32, right_glyph_id: u32) -> Vector2F {
+        <Self as Loader>::kerning(self, left_glyph_id, right_glyph_id)
+    }
+
+    /// Returns the kerning adjustment for each glyph of a run, in font units.
+    #[inline]
+    pub fn kerning_for_glyphs(&self, glyph_ids: &[u32]) -> Vec<Vector2F> {
+        <Self as Loader>::kerning_for_glyphs(self, glyph_ids)
+    }
+
     /// Retrieves various metrics that apply to the entire font.
     pub fn metrics(&self) -> Metrics {
         let units_per_em = self.core_text_font.units_per_em();
@@ -518,10 +651,8 @@ impl Font {
         );
 
         match canvas.format {
-            Format::Rgba32 | Format::Rgb24 => {
-                core_graphics_context.set_rgb_fill_color(0.0, 0.0, 0.0, 0.0);
-            }
             Format::A8 => core_graphics_context.set_gray_fill_color(0.0, 0.0),
+            _ => core_graphics_context.set_rgb_fill_color(0.0, 0.0, 0.0, 0.0),
         }
 
         let core_graphics_size = CGSize::new(canvas.size.x() as f64, canvas.size.y() as f64);
@@ -542,10 +673,8 @@ impl Font {
         }
 
         match canvas.format {
-            Format::Rgba32 | Format::Rgb24 => {
-                core_graphics_context.set_rgb_fill_color(1.0, 1.0, 1.0, 1.0);
-            }
             Format::A8 => core_graphics_context.set_gray_fill_color(1.0, 1.0),
+            _ => core_graphics_context.set_rgb_fill_color(1.0, 1.0, 1.0, 1.0),
         }
 
         // CoreGraphics origin is in the bottom left. This makes behavior consistent.
@@ -568,6 +697,199 @@ impl Font {
         Ok(())
     }
 
+    /// Rasterizes a glyph like `rasterize_glyph()`, with its coverage corrected by `gamma_lut` for
+    /// text drawn in `color`. Unlike `rasterize_glyph()`, this keeps what is already drawn.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn rasterize_glyph_with_gamma(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+        gamma_lut: &GammaLut,
+        color: RgbaColor,
+    ) -> Result<(), GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_with_gamma(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+            gamma_lut,
+            color,
+        )
+    }
+
+    /// Returns the border of a stroke along the outline of a glyph, as a new outline in font
+    /// units. The width of the stroke is in font units.
+    #[inline]
+    pub fn stroked_outline(
+        &self,
+        glyph_id: u32,
+        hinting_mode: HintingOptions,
+        stroke_style: &StrokeStyle,
+    ) -> Result<Outline, GlyphLoadingError> {
+        <Self as Loader>::stroked_outline(self, glyph_id, hinting_mode, stroke_style)
+    }
+
+    /// Returns the pixel boundaries that the stroke of the glyph will take up when rendered with
+    /// `rasterize_glyph_stroke()`. The width of the stroke is in pixels.
+    #[inline]
+    pub fn stroke_raster_bounds(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        stroke_style: &StrokeStyle,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<RectI, GlyphLoadingError> {
+        <Self as Loader>::stroke_raster_bounds(
+            self,
+            glyph_id,
+            point_size,
+            transform,
+            stroke_style,
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    /// Rasterizes the stroke of a glyph's outline, rather than filling it, to a canvas with the
+    /// given size and transform. The width of the stroke is in pixels.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn rasterize_glyph_stroke(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        stroke_style: &StrokeStyle,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<(), GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_stroke(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            stroke_style,
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    /// Computes a signed distance field for a glyph from its outline, in a new `Format::A8`
+    /// canvas. `spread` is the distance in pixels that the field covers on either side of the
+    /// edge.
+    #[inline]
+    pub fn rasterize_glyph_sdf(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        spread: f32,
+    ) -> Result<DistanceField, GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_sdf(self, glyph_id, point_size, spread)
+    }
+
+    /// Computes a multi-channel signed distance field for a glyph from its outline, in a new
+    /// `Format::Rgb24` canvas. The median of the three channels keeps corners sharp.
+    #[inline]
+    pub fn rasterize_glyph_msdf(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        spread: f32,
+    ) -> Result<DistanceField, GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_msdf(self, glyph_id, point_size, spread)
+    }
+
+    /// Returns the color palettes defined in the font's `CPAL` table.
+    #[inline]
+    pub fn color_palettes(&self) -> Vec<ColorPalette> {
+        <Self as Loader>::color_palettes(self)
+    }
+
+    /// Returns the layers that make up the given glyph, if it is a version 0 `COLR` color glyph.
+    #[inline]
+    pub fn color_glyph_layers(&self, glyph_id: u32) -> Option<Vec<ColorLayer>> {
+        <Self as Loader>::color_glyph_layers(self, glyph_id)
+    }
+
+    /// Returns true if the given glyph is drawn with a version 1 `COLR` paint graph.
+    #[inline]
+    pub fn has_color_glyph_paint(&self, glyph_id: u32) -> bool {
+        <Self as Loader>::has_color_glyph_paint(self, glyph_id)
+    }
+
+    /// Returns the sizes at which the font provides bitmap glyphs.
+    #[inline]
+    pub fn fixed_sizes(&self) -> Vec<FixedSize> {
+        <Self as Loader>::fixed_sizes(self)
+    }
+
+    /// Returns the embedded bitmap image of the given glyph from the `CBDT`/`CBLC` or `sbix`
+    /// table, taken from the strike that best matches `point_size` pixels per em.
+    #[inline]
+    pub fn embedded_image(&self, glyph_id: u32, point_size: f32) -> Option<EmbeddedImage> {
+        <Self as Loader>::embedded_image(self, glyph_id, point_size)
+    }
+
+    /// Rasterizes a color glyph to an RGBA canvas with the given size and transform.
+    ///
+    /// Version 1 `COLR` paint graphs and version 0 `COLR` layers are composited over the canvas,
+    /// with colors from palette `palette_index` or `foreground_color`. Other glyphs are drawn from
+    /// their embedded bitmap image if they have one, and are otherwise filled with
+    /// `foreground_color`.
+    ///
+    /// Panics if the canvas format is not `Format::Rgba32`, `Format::Rgba32Straight`, or
+    /// `Format::Bgra32`.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn rasterize_color_glyph(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+        palette_index: usize,
+        foreground_color: RgbaColor,
+    ) -> Result<(), GlyphLoadingError> {
+        <Self as Loader>::rasterize_color_glyph(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+            palette_index,
+            foreground_color,
+        )
+    }
+
+    /// Returns true if the given glyph is described by a document in the font's `SVG ` table.
+    #[inline]
+    pub fn has_svg_glyph(&self, glyph_id: u32) -> bool {
+        <Self as Loader>::has_svg_glyph(self, glyph_id)
+    }
+
+    /// Returns the SVG document from the font's `SVG ` table that describes the given glyph,
+    /// decompressing it if necessary, along with the range of glyphs that the document covers.
+    #[inline]
+    pub fn svg_glyph_document(&self, glyph_id: u32) -> Option<SvgGlyphDocument> {
+        <Self as Loader>::svg_glyph_document(self, glyph_id)
+    }
+
     /// Returns true if and only if the font loader can perform hinting in the requested way.
     ///
     /// Some APIs support only rasterizing glyphs with hinting, not retrieving hinted outlines. If
@@ -662,6 +984,11 @@ impl Loader for Font {
         self.family_name()
     }
 
+    #[inline]
+    fn style_name(&self) -> String {
+        self.style_name()
+    }
+
     #[inline]
     fn is_monospace(&self) -> bool {
         self.is_monospace()
@@ -853,8 +1180,8 @@ fn unpack_otc_font(data: &mut [u8], font_index: u32) -> Result<(), FontLoadingEr
 // NB: This assumes little-endian, but that's true for all extant Apple hardware.
 fn format_to_cg_color_space_and_image_format(format: Format) -> Option<(CGColorSpace, u32)> {
     match format {
-        Format::Rgb24 => {
-            // Unsupported by Core Graphics.
+        Format::Rgb24 | Format::Rgba32Straight | Format::Bgra32 | Format::A1 => {
+            // Unsupported by Core Graphics, which only draws into premultiplied bitmaps.
             None
         }
         Format::Rgba32 => Some((
@@ -13,17 +13,22 @@
 //! On macOS and Windows, the Cargo feature `loader-freetype-default` can be used to opt into this
 //! loader by default.
 
-use byteorder::{BigEndian, ReadBytesExt};
 use freetype_sys::{
-    ft_sfnt_os2, FT_Byte, FT_Done_Face, FT_Done_FreeType, FT_Error, FT_Face, FT_Fixed,
-    FT_Get_Char_Index, FT_Get_Name_Index, FT_Get_Postscript_Name, FT_Get_Sfnt_Name,
-    FT_Get_Sfnt_Name_Count, FT_Get_Sfnt_Table, FT_Init_FreeType, FT_Library,
-    FT_Library_SetLcdFilter, FT_Load_Glyph, FT_Long, FT_Matrix, FT_New_Memory_Face, FT_Pos,
-    FT_Reference_Face, FT_Set_Char_Size, FT_Set_Transform, FT_UInt, FT_ULong, FT_Vector,
-    FT_FACE_FLAG_FIXED_WIDTH, FT_LCD_FILTER_DEFAULT, FT_LOAD_DEFAULT, FT_LOAD_MONOCHROME,
-    FT_LOAD_NO_HINTING, FT_LOAD_RENDER, FT_LOAD_TARGET_LCD, FT_LOAD_TARGET_LIGHT,
-    FT_LOAD_TARGET_MONO, FT_LOAD_TARGET_NORMAL, FT_PIXEL_MODE_GRAY, FT_PIXEL_MODE_LCD,
-    FT_PIXEL_MODE_LCD_V, FT_PIXEL_MODE_MONO, FT_STYLE_FLAG_ITALIC, TT_OS2,
+    ft_sfnt_os2, FT_Bool, FT_Byte, FT_Done_Face, FT_Done_FreeType, FT_Done_MM_Var,
+    FT_Err_Missing_Module, FT_Error, FT_Face, FT_Fixed, FT_Get_Char_Index, FT_Get_First_Char,
+    FT_Get_Glyph_Name, FT_Get_MM_Var, FT_Get_Name_Index, FT_Get_Next_Char, FT_Get_Postscript_Name,
+    FT_Get_Sfnt_Table, FT_Get_Var_Design_Coordinates, FT_Init_FreeType, FT_Int, FT_LcdFilter,
+    FT_Library, FT_Library_SetLcdFilter, FT_Load_Glyph, FT_Long, FT_MM_Var, FT_Matrix,
+    FT_New_Memory_Face, FT_Pointer, FT_Pos, FT_Property_Set, FT_Reference_Face, FT_Select_Size,
+    FT_Set_Char_Size, FT_Set_Transform, FT_Set_Var_Design_Coordinates, FT_UInt, FT_ULong,
+    FT_Var_Axis, FT_Vector, FT_FACE_FLAG_FIXED_WIDTH, FT_FACE_FLAG_MULTIPLE_MASTERS,
+    FT_FACE_FLAG_SCALABLE, FT_FACE_FLAG_SFNT, FT_HAS_GLYPH_NAMES, FT_LCD_FILTER_DEFAULT,
+    FT_LCD_FILTER_LEGACY, FT_LCD_FILTER_NONE, FT_LOAD_DEFAULT, FT_LOAD_FORCE_AUTOHINT,
+    FT_LOAD_MONOCHROME, FT_LOAD_NO_AUTOHINT, FT_LOAD_NO_BITMAP, FT_LOAD_NO_HINTING, FT_LOAD_RENDER,
+    FT_LOAD_TARGET_LCD, FT_LOAD_TARGET_LCD_V, FT_LOAD_TARGET_LIGHT, FT_LOAD_TARGET_MONO,
+    FT_LOAD_TARGET_NORMAL, FT_PIXEL_MODE_BGRA, FT_PIXEL_MODE_GRAY, FT_PIXEL_MODE_GRAY2,
+    FT_PIXEL_MODE_GRAY4, FT_PIXEL_MODE_LCD, FT_PIXEL_MODE_LCD_V, FT_PIXEL_MODE_MONO,
+    FT_STYLE_FLAG_ITALIC, TT_OS2,
 };
 use log::warn;
 use pathfinder_geometry::line_segment::LineSegment2F;
@@ -31,27 +36,40 @@ use pathfinder_geometry::rect::{RectF, RectI};
 use pathfinder_geometry::transform2d::Transform2F;
 use pathfinder_geometry::vector::{Vector2F, Vector2I};
 use pathfinder_simd::default::F32x4;
+use std::cell::Cell;
+use std::convert::TryFrom;
 use std::f32;
 use std::ffi::{CStr, CString};
 use std::fmt::{self, Debug, Formatter};
 use std::io::{Seek, SeekFrom};
 use std::iter;
 use std::mem;
-use std::os::raw::{c_char, c_void};
+use std::os::raw::{c_char, c_uchar, c_void};
 use std::ptr;
 use std::slice;
 use std::sync::Arc;
 
+use crate::bitmap::{self, EmbeddedImage, FixedSize};
 use crate::canvas::{Canvas, Format, RasterizationOptions};
+use crate::cmap::CharacterCoverage;
+use crate::color::{ColorLayer, ColorPalette, RgbaColor};
 use crate::error::{FontLoadingError, GlyphLoadingError};
 use crate::file_type::FileType;
+use crate::gamma::GammaLut;
+use crate::glyph_names;
 use crate::handle::Handle;
 use crate::hinting::HintingOptions;
 use crate::loader::{FallbackResult, Loader};
-use crate::metrics::Metrics;
-use crate::outline::OutlineSink;
+use crate::metrics::{Metrics, VerticalMetrics};
+use crate::names::{self, name_id, NameRecord};
+use crate::outline::{Outline, OutlineSink};
 use crate::properties::{Properties, Stretch, Style, Weight};
+use crate::sdf::DistanceField;
+use crate::stroke::StrokeStyle;
+use crate::svg::SvgGlyphDocument;
 use crate::utils;
+use crate::variations::{Variation, VariationAxis};
+use crate::variations::{ITALIC_AXIS, SLANT_AXIS, WEIGHT_AXIS, WIDTH_AXIS};
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::fs::File;
@@ -61,21 +79,20 @@ use std::path::Path;
 const PS_DICT_FULL_NAME: u32 = 38;
 const TT_NAME_ID_FULL_NAME: u16 = 4;
 
-const TT_PLATFORM_APPLE_UNICODE: u16 = 0;
-
 const FT_POINT_TAG_ON_CURVE: c_char = 0x01;
 const FT_POINT_TAG_CUBIC_CONTROL: c_char = 0x02;
 
 const OS2_FS_SELECTION_OBLIQUE: u16 = 1 << 9;
 
+// Our FreeType bindings give this the value of `FT_LCD_FILTER_LEGACY1`, so we define it ourselves.
+const FT_LCD_FILTER_LIGHT: FT_LcdFilter = 2;
+
 // Not in our FreeType bindings, so we define these ourselves.
 #[allow(dead_code)]
 const BDF_PROPERTY_TYPE_NONE: BDF_PropertyType = 0;
 #[allow(dead_code)]
 const BDF_PROPERTY_TYPE_ATOM: BDF_PropertyType = 1;
-#[allow(dead_code)]
 const BDF_PROPERTY_TYPE_INTEGER: BDF_PropertyType = 2;
-#[allow(dead_code)]
 const BDF_PROPERTY_TYPE_CARDINAL: BDF_PropertyType = 3;
 
 thread_local! {
@@ -87,6 +104,18 @@ thread_local! {
             FtLibrary(library)
         }
     };
+
+    // The properties of `FREETYPE_LIBRARY` that fonts have changed.
+    static LIBRARY_PROPERTIES: Cell<LibraryProperties> = Cell::new(LibraryProperties::default());
+}
+
+// A property is `None` until a font on this thread changes it from the value that FreeType
+// started with, which may have come from the `FREETYPE_PROPERTIES` environment variable.
+#[derive(Clone, Copy, Default)]
+struct LibraryProperties {
+    interpreter_version: Option<InterpreterVersion>,
+    stem_darkening: Option<bool>,
+    lcd_filter: Option<LcdFilter>,
 }
 
 #[repr(transparent)]
@@ -102,6 +131,34 @@ impl Drop for FtLibrary {
     }
 }
 
+// Owns the variation information that `FT_Get_MM_Var` allocates for a face.
+struct FtMmVar(*mut FT_MM_Var);
+
+impl FtMmVar {
+    unsafe fn new(face: FT_Face) -> Option<FtMmVar> {
+        if (*face).face_flags & FT_FACE_FLAG_MULTIPLE_MASTERS == 0 {
+            return None;
+        }
+        let mut mm_var = ptr::null_mut();
+        if FT_Get_MM_Var(face, &mut mm_var) != 0 || mm_var.is_null() {
+            return None;
+        }
+        Some(FtMmVar(mm_var))
+    }
+
+    unsafe fn axes(&self) -> &[FT_Var_Axis] {
+        slice::from_raw_parts((*self.0).axis, (*self.0).num_axis as usize)
+    }
+}
+
+impl Drop for FtMmVar {
+    fn drop(&mut self) {
+        let _ = FREETYPE_LIBRARY.try_with(|freetype_library| unsafe {
+            FT_Done_MM_Var(freetype_library.0, self.0);
+        });
+    }
+}
+
 /// The handle that the FreeType API natively uses to represent a font.
 pub type NativeFont = FT_Face;
 
@@ -113,7 +170,125 @@ type BDF_PropertyType = i32;
 #[repr(C)]
 struct BDF_PropertyRec {
     property_type: BDF_PropertyType,
-    value: *const c_char,
+    value: BDF_PropertyValue,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+union BDF_PropertyValue {
+    atom: *const c_char,
+    integer: i32,
+    cardinal: u32,
+}
+
+/// Chooses how FreeType hints glyphs, where `HintingOptions` chooses how much.
+///
+/// These are set per font with `Font::set_hinting_engine_options()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HintingEngineOptions {
+    /// Whether glyphs are hinted by FreeType's autohinter or by the font's own hints.
+    pub autohinter: AutohinterMode,
+    /// The version of the interpreter that runs the instructions in TrueType fonts.
+    pub interpreter_version: InterpreterVersion,
+    /// Whether to embolden the thin stems of CFF glyphs at small sizes, as Adobe's rasterizer
+    /// does. This makes text look heavier, and closer to macOS, when gamma is applied correctly.
+    pub stem_darkening: bool,
+    /// Whether to draw glyphs with the bitmaps embedded in the font, at the sizes that have them.
+    ///
+    /// If this is false, glyphs are drawn from their outlines. Fonts that only contain bitmaps
+    /// are drawn from their bitmaps either way.
+    pub embedded_bitmaps: bool,
+}
+
+impl Default for HintingEngineOptions {
+    #[inline]
+    fn default() -> HintingEngineOptions {
+        HintingEngineOptions {
+            autohinter: AutohinterMode::Default,
+            interpreter_version: InterpreterVersion::V40,
+            stem_darkening: false,
+            embedded_bitmaps: true,
+        }
+    }
+}
+
+/// When FreeType's autohinter is used in place of the hints in the font.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AutohinterMode {
+    /// FreeType decides, based on the font format and the hinting mode. Fonts without hints are
+    /// always autohinted.
+    Default,
+    /// Glyphs are always autohinted, ignoring the hints in the font.
+    Force,
+    /// Glyphs are never autohinted. Fonts without hints are drawn unhinted.
+    Never,
+}
+
+/// A version of FreeType's TrueType bytecode interpreter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InterpreterVersion {
+    /// The original interpreter, which runs the instructions in both directions, as Windows did
+    /// before ClearType. Glyphs are sharp but may be distorted.
+    V35,
+    /// The minimal interpreter, which ignores the instructions that move points horizontally, as
+    /// DirectWrite does. This is FreeType's default.
+    V40,
+}
+
+/// Chooses how FreeType draws glyphs with `RasterizationOptions::SubpixelAa`.
+///
+/// These are set per font with `Font::set_lcd_options()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LcdOptions {
+    /// The filter that spreads the coverage of each subpixel to its neighbors, trading sharpness
+    /// for less color fringing.
+    pub filter: LcdFilter,
+    /// The order of the subpixels of the screen that the glyphs will be shown on.
+    pub subpixel_order: SubpixelOrder,
+}
+
+impl Default for LcdOptions {
+    #[inline]
+    fn default() -> LcdOptions {
+        LcdOptions {
+            filter: LcdFilter::Default,
+            subpixel_order: SubpixelOrder::Rgb,
+        }
+    }
+}
+
+/// A filter that FreeType applies to subpixel coverage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LcdFilter {
+    /// No filtering. Glyphs are sharpest, but have strong color fringes.
+    None,
+    /// FreeType's default filter, which removes most color fringes.
+    Default,
+    /// A lighter filter than the default, which is sharper but leaves more color fringes.
+    Light,
+    /// The filter of old versions of FreeType, which filters each pixel on its own. Glyphs look
+    /// thin and blurry, so this is only useful for matching old rendering.
+    Legacy,
+    /// A five-tap filter with the given weights, from the farthest subpixel before the one that
+    /// is filtered to the farthest after it. Each weight is out of 256; if they add up to more
+    /// or less than 256, glyphs get darker or lighter.
+    Custom([u8; 5]),
+}
+
+/// The order of the subpixels of a screen.
+///
+/// Canvases always store red, green, and blue in that order. The order of the subpixels decides
+/// which part of each pixel is drawn into which channel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SubpixelOrder {
+    /// Red, green, and blue from left to right. Most screens are like this.
+    Rgb,
+    /// Blue, green, and red from left to right.
+    Bgr,
+    /// Red, green, and blue from top to bottom, as on screens turned on their side.
+    Vrgb,
+    /// Blue, green, and red from top to bottom.
+    Vbgr,
 }
 
 /// A cross-platform loader that uses the FreeType library to load and rasterize fonts.
@@ -124,6 +299,8 @@ struct BDF_PropertyRec {
 pub struct Font {
     freetype_face: FT_Face,
     font_data: Arc<Vec<u8>>,
+    hinting_engine_options: HintingEngineOptions,
+    lcd_options: LcdOptions,
 }
 
 impl Font {
@@ -150,6 +327,8 @@ impl Font {
             Ok(Font {
                 freetype_face,
                 font_data,
+                hinting_engine_options: HintingEngineOptions::default(),
+                lcd_options: LcdOptions::default(),
             })
         })
     }
@@ -180,6 +359,10 @@ impl Font {
     }
 
     /// Creates a font from a native API handle.
+    ///
+    /// # Safety
+    ///
+    /// `freetype_face` must be a valid FreeType face.
     pub unsafe fn from_native_font(freetype_face: NativeFont) -> Font {
         // We make an in-memory copy of the underlying font data. This is because the native font
         // does not necessarily hold a strong reference to the memory backing it.
@@ -199,7 +382,19 @@ impl Font {
             }
         }
 
-        Font::from_bytes(Arc::new(font_data), (*freetype_face).face_index as u32).unwrap()
+        let font =
+            Font::from_bytes(Arc::new(font_data), (*freetype_face).face_index as u32).unwrap();
+
+        // Carry over the variation coordinates, which aren't part of the font data.
+        let coords = get_var_design_coordinates(freetype_face);
+        if !coords.is_empty() {
+            FT_Set_Var_Design_Coordinates(
+                font.freetype_face,
+                coords.len() as FT_UInt,
+                coords.as_ptr(),
+            );
+        }
+        font
     }
 
     /// Loads the font pointed to by a handle.
@@ -309,7 +504,7 @@ impl Font {
             if property.property_type != BDF_PROPERTY_TYPE_ATOM {
                 return None;
             }
-            let dec_device_fontnames = CStr::from_ptr(property.value).to_str().unwrap();
+            let dec_device_fontnames = CStr::from_ptr(property.value.atom).to_str().unwrap();
             if !dec_device_fontnames.starts_with("PS=") {
                 return None;
             }
@@ -319,6 +514,12 @@ impl Font {
 
     /// Returns the full name of the font (also known as "display name" on macOS).
     pub fn full_name(&self) -> String {
+        // Named instances share the `name` table of the default instance, so we build their full
+        // names from the family name and the instance's subfamily name instead.
+        if let Some(style_name) = self.named_instance_style_name() {
+            return format!("{} {}", self.family_name(), style_name);
+        }
+
         self.get_type_1_or_sfnt_name(PS_DICT_FULL_NAME, TT_NAME_ID_FULL_NAME)
             .unwrap_or_else(|| self.family_name())
     }
@@ -336,6 +537,91 @@ impl Font {
         }
     }
 
+    /// Returns the name of the font style within its family, such as "Bold Italic".
+    ///
+    /// This is the subfamily name (name ID 2) of the `name` table. Named instances of variable
+    /// fonts and fonts without a `name` table use the style name that FreeType reports.
+    pub fn style_name(&self) -> String {
+        if let Some(style_name) = self.named_instance_style_name() {
+            return style_name;
+        }
+        self.localized_subfamily_name("en-US")
+            .or_else(|| self.freetype_style_name())
+            .unwrap_or_default()
+    }
+
+    /// Returns the typographic family name (name ID 16), or the family name if the font has none.
+    #[inline]
+    pub fn typographic_family_name(&self) -> String {
+        <Self as Loader>::typographic_family_name(self)
+    }
+
+    /// Returns the typographic subfamily name (name ID 17), or the style name if the font has
+    /// none.
+    ///
+    /// Named instances of variable fonts use the name of the instance.
+    pub fn typographic_subfamily_name(&self) -> String {
+        self.named_instance_style_name()
+            .or_else(|| self.localized_name(name_id::TYPOGRAPHIC_SUBFAMILY, "en-US"))
+            .unwrap_or_else(|| self.style_name())
+    }
+
+    /// Returns the WWS family name (name ID 21), or the typographic family name if the font has
+    /// none.
+    #[inline]
+    pub fn wws_family_name(&self) -> String {
+        <Self as Loader>::wws_family_name(self)
+    }
+
+    /// Returns the WWS subfamily name (name ID 22), or the typographic subfamily name if the font
+    /// has none.
+    ///
+    /// Named instances of variable fonts use the name of the instance.
+    pub fn wws_subfamily_name(&self) -> String {
+        self.named_instance_style_name()
+            .or_else(|| self.localized_name(name_id::WWS_SUBFAMILY, "en-US"))
+            .unwrap_or_else(|| self.typographic_subfamily_name())
+    }
+
+    /// Returns all the records of the `name` table with the given name ID, decoded from every
+    /// platform and encoding that `font-kit` understands.
+    #[inline]
+    pub fn name_records(&self, name_id: u16) -> Vec<NameRecord> {
+        <Self as Loader>::name_records(self, name_id)
+    }
+
+    /// Returns the name with the given name ID in the language that best matches the given BCP 47
+    /// tag.
+    #[inline]
+    pub fn localized_name(&self, name_id: u16, language: &str) -> Option<String> {
+        <Self as Loader>::localized_name(self, name_id, language)
+    }
+
+    /// Returns the family name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    pub fn localized_family_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_family_name(self, language)
+    }
+
+    /// Returns the subfamily (style) name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    pub fn localized_subfamily_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_subfamily_name(self, language)
+    }
+
+    /// Returns the typographic family name in the language that best matches the given BCP 47
+    /// tag, falling back to the family name for fonts that don't have one.
+    #[inline]
+    pub fn localized_typographic_family_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_typographic_family_name(self, language)
+    }
+
+    /// Returns the full name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    pub fn localized_full_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_full_name(self, language)
+    }
+
     /// Returns true if and only if the font is monospace (fixed-width).
     pub fn is_monospace(&self) -> bool {
         unsafe { (*self.freetype_face).face_flags & (FT_FACE_FLAG_FIXED_WIDTH as FT_Long) != 0 }
@@ -366,14 +652,50 @@ impl Font {
                 None => Weight::NORMAL,
                 Some(os2_table) => Weight((*os2_table).usWeightClass as f32),
             };
-            Properties {
+            let mut properties = Properties {
                 style,
                 stretch,
                 weight,
+            };
+
+            // The `OS/2` table only describes the default instance of a variable font, so the
+            // current coordinates take precedence.
+            let variations = self.variations();
+            for variation in &variations {
+                match variation.tag {
+                    WEIGHT_AXIS => {
+                        properties.weight = Weight(utils::clamp(variation.value, 1.0, 1000.0))
+                    }
+                    WIDTH_AXIS => {
+                        let stretch = utils::clamp(variation.value / 100.0, 0.5, 2.0);
+                        properties.stretch = Stretch(stretch)
+                    }
+                    ITALIC_AXIS if variation.value >= 0.5 => properties.style = Style::Italic,
+                    ITALIC_AXIS => properties.style = Style::Normal,
+                    _ => {}
+                }
             }
+            let slanted = variations
+                .iter()
+                .any(|variation| variation.tag == SLANT_AXIS && variation.value != 0.0);
+            if slanted && properties.style == Style::Normal {
+                properties.style = Style::Oblique
+            }
+
+            properties
         }
     }
 
+    /// Returns the number of named instances (such as "Bold Condensed") that this variable font
+    /// defines in its `fvar` table.
+    ///
+    /// Named instance *n* (counting from 1) can be opened as a face of its own by passing
+    /// `n << 16 | font_index` as the font index.
+    #[inline]
+    pub fn named_instance_count(&self) -> u32 {
+        unsafe { (((*self.freetype_face).style_flags >> 16) & 0x7fff) as u32 }
+    }
+
     /// Returns the usual glyph ID for a Unicode character.
     ///
     /// Be careful with this function; typographically correct character-to-glyph mapping must be
@@ -390,6 +712,37 @@ impl Font {
         }
     }
 
+    /// Returns every character that the font maps to a glyph, along with that glyph, sorted by
+    /// character.
+    ///
+    /// This walks the charmap that FreeType selected, as `glyph_for_char` does.
+    pub fn character_map(&self) -> Vec<(char, u32)> {
+        let mut mappings = vec![];
+        unsafe {
+            let mut glyph_id = 0;
+            let mut code_point = FT_Get_First_Char(self.freetype_face, &mut glyph_id);
+            while glyph_id != 0 {
+                if let Some(character) = char::from_u32(code_point as u32) {
+                    mappings.push((character, glyph_id));
+                }
+                code_point = FT_Get_Next_Char(self.freetype_face, code_point, &mut glyph_id);
+            }
+        }
+        mappings
+    }
+
+    /// Returns the set of characters that the font maps to glyphs.
+    #[inline]
+    pub fn character_coverage(&self) -> CharacterCoverage {
+        <Self as Loader>::character_coverage(self)
+    }
+
+    /// Returns the characters that map to the given glyph, in ascending order.
+    #[inline]
+    pub fn chars_for_glyph(&self, glyph_id: u32) -> Vec<char> {
+        <Self as Loader>::chars_for_glyph(self, glyph_id)
+    }
+
     /// Returns the glyph ID for the specified glyph name.
     #[inline]
     pub fn glyph_by_name(&self, name: &str) -> Option<u32> {
@@ -404,6 +757,32 @@ impl Font {
         None
     }
 
+    /// Returns the name of a glyph, or `None` if the glyph ID is out of range.
+    ///
+    /// Names come from the `post` table or the CFF charset where the font has them. Otherwise, a
+    /// name of the form `uniXXXX` is derived from the first character that maps to the glyph.
+    pub fn glyph_name(&self, glyph_id: u32) -> Option<String> {
+        if glyph_id >= self.glyph_count() {
+            return None;
+        }
+        unsafe {
+            if FT_HAS_GLYPH_NAMES(self.freetype_face) {
+                let mut buffer = [0u8; 256];
+                let error = FT_Get_Glyph_Name(
+                    self.freetype_face,
+                    glyph_id,
+                    buffer.as_mut_ptr() as FT_Pointer,
+                    buffer.len() as FT_UInt,
+                );
+                if error == 0 && buffer[0] != 0 {
+                    let name = CStr::from_ptr(buffer.as_ptr() as *const c_char);
+                    return Some(name.to_string_lossy().into_owned());
+                }
+            }
+        }
+        glyph_names::synthesize_glyph_name(glyph_id, &self.chars_for_glyph(glyph_id))
+    }
+
     /// Returns the number of glyphs in the font.
     ///
     /// Glyph IDs range from 0 inclusive to this value exclusive.
@@ -441,9 +820,7 @@ impl Font {
                 );
             }
 
-            if FT_Load_Glyph(self.freetype_face, glyph_id, load_flags) != 0 {
-                return Err(GlyphLoadingError::NoSuchGlyph);
-            }
+            self.load_glyph(glyph_id, load_flags)?;
 
             let outline = &(*(*self.freetype_face).glyph).outline;
             if outline.n_contours == 0 {
@@ -587,14 +964,7 @@ impl Font {
     /// Returns the boundaries of a glyph in font units.
     pub fn typographic_bounds(&self, glyph_id: u32) -> Result<RectF, GlyphLoadingError> {
         unsafe {
-            if FT_Load_Glyph(
-                self.freetype_face,
-                glyph_id,
-                FT_LOAD_DEFAULT | FT_LOAD_NO_HINTING,
-            ) != 0
-            {
-                return Err(GlyphLoadingError::NoSuchGlyph);
-            }
+            self.load_glyph(glyph_id, FT_LOAD_DEFAULT | FT_LOAD_NO_HINTING)?;
 
             let metrics = &(*(*self.freetype_face).glyph).metrics;
             let rect = RectI::new(
@@ -612,14 +982,7 @@ impl Font {
     /// units.
     pub fn advance(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
         unsafe {
-            if FT_Load_Glyph(
-                self.freetype_face,
-                glyph_id,
-                FT_LOAD_DEFAULT | FT_LOAD_NO_HINTING,
-            ) != 0
-            {
-                return Err(GlyphLoadingError::NoSuchGlyph);
-            }
+            self.load_glyph(glyph_id, FT_LOAD_DEFAULT | FT_LOAD_NO_HINTING)?;
 
             let advance = (*(*self.freetype_face).glyph).advance;
             Ok(Vector2I::new(advance.x as i32, advance.y as i32).ft_fixed_26_6_to_f32())
@@ -628,14 +991,51 @@ impl Font {
 
     /// Returns the amount that the given glyph should be displaced from the origin.
     ///
-    /// FIXME(pcwalton): This always returns zero on FreeType.
-    pub fn origin(&self, _: u32) -> Result<Vector2F, GlyphLoadingError> {
-        warn!("unimplemented");
-        Ok(Vector2F::default())
+    /// As with Core Text, this is the translation from the vertical origin of the glyph to its
+    /// horizontal origin, for use when setting text vertically.
+    pub fn origin(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
+        Ok(-self.vertical_origin(glyph_id)?)
+    }
+
+    /// Returns the distance from the origin of the glyph with the given ID to the next when text
+    /// is set vertically, in font units.
+    #[inline]
+    pub fn vertical_advance(&self, glyph_id: u32) -> Result<f32, GlyphLoadingError> {
+        <Self as Loader>::vertical_advance(self, glyph_id)
+    }
+
+    /// Returns the position of the origin used when the glyph with the given ID is set
+    /// vertically, in font units relative to its horizontal origin.
+    #[inline]
+    pub fn vertical_origin(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
+        <Self as Loader>::vertical_origin(self, glyph_id)
+    }
+
+    /// Retrieves the metrics that apply to the entire font when text is set vertically.
+    #[inline]
+    pub fn vertical_metrics(&self) -> VerticalMetrics {
+        <Self as Loader>::vertical_metrics(self)
+    }
+
+    /// Returns the adjustment to the advance of the left glyph when it is followed by the right
+    /// glyph, in font units.
+    #[inline]
+    pub fn kerning(&self, left_glyph_id: u32, right_glyph_id: u32) -> Vector2F {
+        <Self as Loader>::kerning(self, left_glyph_id, right_glyph_id)
+    }
+
+    /// Returns the kerning adjustment for each glyph of a run, in font units.
+    #[inline]
+    pub fn kerning_for_glyphs(&self, glyph_ids: &[u32]) -> Vec<Vector2F> {
+        <Self as Loader>::kerning_for_glyphs(self, glyph_ids)
     }
 
     /// Retrieves various metrics that apply to the entire font.
     pub fn metrics(&self) -> Metrics {
+        if self.is_bitmap_only_font() {
+            return self.bitmap_only_metrics();
+        }
+
         let os2_table = self.get_os2_table();
         unsafe {
             let ascender = (*self.freetype_face).ascender;
@@ -666,6 +1066,167 @@ impl Font {
         }
     }
 
+    /// Returns true if this is a font format like BDF, PCF, or Windows FNT that only contains
+    /// bitmap strikes and has no design units.
+    fn is_bitmap_only_font(&self) -> bool {
+        unsafe {
+            let face_flags = (*self.freetype_face).face_flags;
+            face_flags & (FT_FACE_FLAG_SCALABLE as FT_Long) == 0
+                && face_flags & (FT_FACE_FLAG_SFNT as FT_Long) == 0
+        }
+    }
+
+    /// Returns the metrics of a bitmap-only font. Font units are pixels of the current strike,
+    /// which is the first strike unless a glyph is being rasterized.
+    fn bitmap_only_metrics(&self) -> Metrics {
+        unsafe {
+            let size_metrics = &(*(*self.freetype_face).size).metrics;
+            let ascent = size_metrics.ascender as f32 / 64.0;
+            let descent = size_metrics.descender as f32 / 64.0;
+            let height = size_metrics.height as f32 / 64.0;
+            let max_advance = size_metrics.max_advance as f32 / 64.0;
+
+            // BDF and PCF fonts may specify these as properties. XLFD underline positions are
+            // positive below the baseline. Without them, use a one-pixel underline halfway
+            // through the descent.
+            let property = |name: &str| {
+                self.get_bdf_integer_property(name)
+                    .map(|value| value as f32)
+            };
+            let underline_thickness = property("UNDERLINE_THICKNESS\0").unwrap_or(1.0);
+            let underline_position = property("UNDERLINE_POSITION\0")
+                .map(|position| -position)
+                .unwrap_or((descent / 2.0).round());
+
+            Metrics {
+                units_per_em: size_metrics.y_ppem as u32,
+                ascent,
+                descent,
+                line_gap: height + descent - ascent,
+                underline_position,
+                underline_thickness,
+                cap_height: property("CAP_HEIGHT\0").unwrap_or(0.0),
+                x_height: property("X_HEIGHT\0").unwrap_or(0.0),
+                bounding_box: RectF::from_points(
+                    Vector2F::new(0.0, descent),
+                    Vector2F::new(max_advance, ascent),
+                ),
+            }
+        }
+    }
+
+    /// Returns the sizes at which the font provides bitmap glyphs.
+    ///
+    /// For bitmap-only fonts such as BDF, PCF, and Windows FNT fonts, the first of these sizes
+    /// defines the font units: `metrics().units_per_em` is its number of pixels per em, and
+    /// metrics, advances, and bounds are in its pixels.
+    pub fn fixed_sizes(&self) -> Vec<FixedSize> {
+        unsafe {
+            let face = self.freetype_face;
+            if (*face).available_sizes.is_null() {
+                return vec![];
+            }
+            slice::from_raw_parts((*face).available_sizes, (*face).num_fixed_sizes as usize)
+                .iter()
+                .map(|size| FixedSize {
+                    pixels_per_em: Vector2F::new(size.x_ppem as f32, size.y_ppem as f32)
+                        * (1.0 / 64.0),
+                    height: size.height as i32,
+                })
+                .collect()
+        }
+    }
+
+    /// Returns the axes of variation of this font, as described by its `fvar` table.
+    ///
+    /// If this font is not a variable font, the returned list is empty.
+    pub fn variation_axes(&self) -> Vec<VariationAxis> {
+        unsafe {
+            let mm_var = match FtMmVar::new(self.freetype_face) {
+                Some(mm_var) => mm_var,
+                None => return vec![],
+            };
+            mm_var
+                .axes()
+                .iter()
+                .map(|axis| VariationAxis {
+                    tag: axis.tag as u32,
+                    min_value: ft_fixed_16_16_to_f32(axis.minimum),
+                    default_value: ft_fixed_16_16_to_f32(axis.def),
+                    max_value: ft_fixed_16_16_to_f32(axis.maximum),
+                    name_id: axis.strid as u16,
+                })
+                .collect()
+        }
+    }
+
+    /// Returns the current position of this font along each of its axes of variation, in the
+    /// same order as `variation_axes()`.
+    ///
+    /// If this font is not a variable font, the returned list is empty.
+    pub fn variations(&self) -> Vec<Variation> {
+        let axes = self.variation_axes();
+        let coords = unsafe { get_var_design_coordinates(self.freetype_face) };
+        axes.iter()
+            .enumerate()
+            .map(|(axis_index, axis)| Variation {
+                tag: axis.tag,
+                value: coords
+                    .get(axis_index)
+                    .map(|&coord| ft_fixed_16_16_to_f32(coord))
+                    .unwrap_or(axis.default_value),
+            })
+            .collect()
+    }
+
+    /// Returns a copy of this font positioned at the given coordinates in its design space.
+    ///
+    /// Axes not mentioned in `variations` keep their current values, and values outside the range
+    /// of an axis are clamped to it. Tags that don't name an axis of this font are ignored. The
+    /// outlines, advances, bounds, metrics, and rasterized glyphs of the returned font all reflect
+    /// the new coordinates; this font is left unchanged.
+    pub fn clone_with_variations(
+        &self,
+        variations: &[Variation],
+    ) -> Result<Font, FontLoadingError> {
+        let axes = self.variation_axes();
+        let mut coords: Vec<FT_Fixed> = self
+            .variations()
+            .iter()
+            .map(|variation| f32_to_ft_fixed_16_16(variation.value))
+            .collect();
+        for variation in variations {
+            match axes.iter().position(|axis| axis.tag == variation.tag) {
+                Some(axis_index) => {
+                    let axis = &axes[axis_index];
+                    let value = utils::clamp(variation.value, axis.min_value, axis.max_value);
+                    coords[axis_index] = f32_to_ft_fixed_16_16(value);
+                }
+                None => warn!(
+                    "clone_with_variations(): font has no axis with tag {:#010x}",
+                    variation.tag
+                ),
+            }
+        }
+
+        unsafe {
+            let font_index = (*self.freetype_face).face_index as u32;
+            let mut font = Font::from_bytes(self.font_data.clone(), font_index)?;
+            font.hinting_engine_options = self.hinting_engine_options;
+            font.lcd_options = self.lcd_options;
+            if !coords.is_empty()
+                && FT_Set_Var_Design_Coordinates(
+                    font.freetype_face,
+                    coords.len() as FT_UInt,
+                    coords.as_ptr(),
+                ) != 0
+            {
+                return Err(FontLoadingError::Parse);
+            }
+            Ok(font)
+        }
+    }
+
     /// Returns true if and only if the font loader can perform hinting in the requested way.
     ///
     /// Some APIs support only rasterizing glyphs with hinting, not retrieving hinted outlines. If
@@ -689,6 +1250,31 @@ impl Font {
         }
     }
 
+    /// Returns the style name that FreeType reports for the face.
+    fn freetype_style_name(&self) -> Option<String> {
+        unsafe {
+            let style_name = (*self.freetype_face).style_name;
+            if style_name.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(style_name).to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    /// Returns the subfamily name of the named instance that this face was opened as, if any.
+    ///
+    /// Named instances share the `name` table of the default instance, but FreeType looks up
+    /// their subfamily names from the `fvar` table.
+    fn named_instance_style_name(&self) -> Option<String> {
+        unsafe {
+            if (*self.freetype_face).face_index >> 16 == 0 {
+                return None;
+            }
+        }
+        self.freetype_style_name()
+    }
+
     fn get_type_1_or_sfnt_name(&self, type_1_id: u32, sfnt_id: u16) -> Option<String> {
         unsafe {
             let ps_value_size =
@@ -706,42 +1292,11 @@ impl Font {
                     return String::from_utf8(buffer).ok();
                 }
             }
-
-            let sfnt_name_count = FT_Get_Sfnt_Name_Count(self.freetype_face);
-            let mut sfnt_name = mem::zeroed();
-            for sfnt_name_index in 0..sfnt_name_count {
-                assert_eq!(
-                    FT_Get_Sfnt_Name(self.freetype_face, sfnt_name_index, &mut sfnt_name),
-                    0
-                );
-                if sfnt_name.name_id != sfnt_id {
-                    continue;
-                }
-
-                match (sfnt_name.platform_id, sfnt_name.encoding_id) {
-                    (TT_PLATFORM_APPLE_UNICODE, _) => {
-                        let mut sfnt_name_bytes =
-                            slice::from_raw_parts(sfnt_name.string, sfnt_name.string_len as usize);
-                        let mut sfnt_name_string = Vec::with_capacity(sfnt_name_bytes.len() / 2);
-                        while !sfnt_name_bytes.is_empty() {
-                            sfnt_name_string.push(sfnt_name_bytes.read_u16::<BigEndian>().unwrap())
-                        }
-                        if let Ok(result) = String::from_utf16(&sfnt_name_string) {
-                            return Some(result);
-                        }
-                    }
-                    (platform_id, _) => {
-                        warn!(
-                            "get_type_1_or_sfnt_name(): found invalid platform ID {}",
-                            platform_id
-                        );
-                        // TODO(pcwalton)
-                    }
-                }
-            }
-
-            None
         }
+
+        // Prefer the US English name, as FreeType does for the family and style names.
+        let name_table = self.load_font_table(names::NAME_TABLE_TAG)?;
+        names::find_localized_name(&names::read_name_records(&name_table, sfnt_id), "en-US")
     }
 
     fn get_os2_table(&self) -> Option<*const TT_OS2> {
@@ -755,6 +1310,26 @@ impl Font {
         }
     }
 
+    /// Looks up an integer property of a BDF or PCF font. `name` must be NUL-terminated.
+    fn get_bdf_integer_property(&self, name: &str) -> Option<i32> {
+        unsafe {
+            let mut property: BDF_PropertyRec = mem::zeroed();
+            if FT_Get_BDF_Property(
+                self.freetype_face,
+                name.as_ptr() as *const c_char,
+                &mut property,
+            ) != 0
+            {
+                return None;
+            }
+            match property.property_type {
+                BDF_PROPERTY_TYPE_INTEGER => Some(property.value.integer),
+                BDF_PROPERTY_TYPE_CARDINAL => i32::try_from(property.value.cardinal).ok(),
+                _ => None,
+            }
+        }
+    }
+
     /// Returns the pixel boundaries that the glyph will take up when rendered using this loader's
     /// rasterizer at the given size and origin.
     #[inline]
@@ -785,6 +1360,11 @@ impl Font {
     /// loader.
     ///
     /// If `hinting_options` is not None, the requested grid fitting is performed.
+    ///
+    /// Fonts that only contain bitmaps are drawn from the strike whose size is nearest to
+    /// `point_size`, preferring the larger of two equally near strikes. The strike is then scaled
+    /// by the remaining factor and transformed, taking the nearest bitmap pixel for each canvas
+    /// pixel, so glyphs stay sharp and are drawn unchanged at the sizes of their strikes.
     pub fn rasterize_glyph(
         &self,
         canvas: &mut Canvas,
@@ -797,6 +1377,13 @@ impl Font {
         // TODO(pcwalton): This is woefully incomplete. See WebRender's code for a more complete
         // implementation.
         unsafe {
+            if (*self.freetype_face).face_flags & (FT_FACE_FLAG_SCALABLE as FT_Long) == 0
+                && (*self.freetype_face).num_fixed_sizes > 0
+            {
+                return self.rasterize_fixed_size_glyph(canvas, glyph_id, point_size, transform);
+            }
+
+            // The matrix is stored in column-major order, and FreeType's Y axis points up.
             let matrix = transform.matrix.0 * F32x4::new(65536.0, -65536.0, -65536.0, 65536.0);
             let matrix = matrix.to_i32x4();
             let vector = transform.vector.f32_to_ft_fixed_26_6();
@@ -807,31 +1394,32 @@ impl Font {
             };
             let mut ft_shape = FT_Matrix {
                 xx: matrix.x() as FT_Fixed,
-                xy: matrix.y() as FT_Fixed,
-                yx: matrix.z() as FT_Fixed,
+                xy: matrix.z() as FT_Fixed,
+                yx: matrix.y() as FT_Fixed,
                 yy: matrix.w() as FT_Fixed,
             };
             FT_Set_Transform(self.freetype_face, &mut ft_shape, &mut delta);
 
-            assert_eq!(
-                FT_Set_Char_Size(
-                    self.freetype_face,
-                    point_size.f32_to_ft_fixed_26_6(),
-                    0,
-                    0,
-                    0
-                ),
-                0
-            );
+            // Fonts that only contain bitmaps, such as `sbix` color emoji fonts, can only be set to
+            // the sizes of their strikes.
+            if FT_Set_Char_Size(
+                self.freetype_face,
+                point_size.f32_to_ft_fixed_26_6(),
+                0,
+                0,
+                0,
+            ) != 0
+            {
+                FT_Set_Transform(self.freetype_face, ptr::null_mut(), ptr::null_mut());
+                return Err(GlyphLoadingError::PlatformError);
+            }
 
             let mut load_flags = FT_LOAD_DEFAULT | FT_LOAD_RENDER;
             load_flags |= self.hinting_and_rasterization_options_to_load_flags(
                 hinting_options,
                 rasterization_options,
             );
-            if FT_Load_Glyph(self.freetype_face, glyph_id, load_flags) != 0 {
-                return Err(GlyphLoadingError::NoSuchGlyph);
-            }
+            self.load_glyph(glyph_id, load_flags)?;
 
             // TODO(pcwalton): Use the FreeType "direct" API to save a copy here. Note that we will
             // need to keep this around for bilevel rendering, as the direct API doesn't work with
@@ -850,11 +1438,17 @@ impl Font {
                 );
             } else {
                 let buffer = slice::from_raw_parts(bitmap_buffer, bitmap_length);
-                let dst_point = Vector2I::new(
+                let mut dst_point = Vector2I::new(
                     (*(*self.freetype_face).glyph).bitmap_left,
                     -(*(*self.freetype_face).glyph).bitmap_top,
                 );
 
+                // FreeType doesn't transform embedded bitmaps, which are the only nonempty
+                // bitmaps that come without an outline, so at least honor the translation.
+                if (*(*self.freetype_face).glyph).outline.n_points == 0 {
+                    dst_point += transform.vector.round().to_i32();
+                }
+
                 // FIXME(pcwalton): This function should return a Result instead.
                 match bitmap.pixel_mode as u32 {
                     FT_PIXEL_MODE_GRAY => {
@@ -862,19 +1456,39 @@ impl Font {
                         canvas.blit_from(dst_point, buffer, bitmap_size, bitmap_stride, Format::A8);
                     }
                     FT_PIXEL_MODE_LCD | FT_PIXEL_MODE_LCD_V => {
-                        // Three bytes per pixel for Rgb24 format
-                        let bitmap_size = Vector2I::new(bitmap_width / 3, bitmap_height);
+                        let (bitmap_size, pixels) = lcd_bitmap_to_rgb24(
+                            buffer,
+                            Vector2I::new(bitmap_width, bitmap_height),
+                            bitmap_stride,
+                            bitmap.pixel_mode as u32 == FT_PIXEL_MODE_LCD_V,
+                            self.lcd_options.subpixel_order,
+                        );
                         canvas.blit_from(
                             dst_point,
-                            buffer,
+                            &pixels,
                             bitmap_size,
-                            bitmap_stride,
+                            bitmap_size.x() as usize * 3,
                             Format::Rgb24,
                         );
                     }
                     FT_PIXEL_MODE_MONO => {
                         let bitmap_size = Vector2I::new(bitmap_width, bitmap_height);
-                        canvas.blit_from_bitmap_1bpp(dst_point, buffer, bitmap_size, bitmap_stride);
+                        canvas.blit_from(dst_point, buffer, bitmap_size, bitmap_stride, Format::A1);
+                    }
+                    FT_PIXEL_MODE_BGRA => {
+                        // Color bitmaps are premultiplied BGRA; swizzle them to premultiplied RGBA.
+                        let bitmap_size = Vector2I::new(bitmap_width, bitmap_height);
+                        let mut rgba_buffer = buffer.to_vec();
+                        for pixel in rgba_buffer.chunks_mut(4) {
+                            pixel.swap(0, 2);
+                        }
+                        canvas.blit_from(
+                            dst_point,
+                            &rgba_buffer,
+                            bitmap_size,
+                            bitmap_stride,
+                            Format::Rgba32,
+                        );
                     }
                     _ => panic!("Unexpected FreeType pixel mode!"),
                 }
@@ -886,6 +1500,221 @@ impl Font {
         }
     }
 
+    /// Rasterizes a glyph like `rasterize_glyph()`, with its coverage corrected by `gamma_lut` for
+    /// text drawn in `color`. Unlike `rasterize_glyph()`, this keeps what is already drawn.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn rasterize_glyph_with_gamma(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+        gamma_lut: &GammaLut,
+        color: RgbaColor,
+    ) -> Result<(), GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_with_gamma(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+            gamma_lut,
+            color,
+        )
+    }
+
+    /// Returns the border of a stroke along the outline of a glyph, as a new outline in font
+    /// units. The width of the stroke is in font units.
+    #[inline]
+    pub fn stroked_outline(
+        &self,
+        glyph_id: u32,
+        hinting_mode: HintingOptions,
+        stroke_style: &StrokeStyle,
+    ) -> Result<Outline, GlyphLoadingError> {
+        <Self as Loader>::stroked_outline(self, glyph_id, hinting_mode, stroke_style)
+    }
+
+    /// Returns the pixel boundaries that the stroke of the glyph will take up when rendered with
+    /// `rasterize_glyph_stroke()`. The width of the stroke is in pixels.
+    #[inline]
+    pub fn stroke_raster_bounds(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        stroke_style: &StrokeStyle,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<RectI, GlyphLoadingError> {
+        <Self as Loader>::stroke_raster_bounds(
+            self,
+            glyph_id,
+            point_size,
+            transform,
+            stroke_style,
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    /// Rasterizes the stroke of a glyph's outline, rather than filling it, to a canvas with the
+    /// given size and transform. The width of the stroke is in pixels.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn rasterize_glyph_stroke(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        stroke_style: &StrokeStyle,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<(), GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_stroke(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            stroke_style,
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    /// Computes a signed distance field for a glyph from its outline, in a new `Format::A8`
+    /// canvas. `spread` is the distance in pixels that the field covers on either side of the
+    /// edge.
+    #[inline]
+    pub fn rasterize_glyph_sdf(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        spread: f32,
+    ) -> Result<DistanceField, GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_sdf(self, glyph_id, point_size, spread)
+    }
+
+    /// Computes a multi-channel signed distance field for a glyph from its outline, in a new
+    /// `Format::Rgb24` canvas. The median of the three channels keeps corners sharp.
+    #[inline]
+    pub fn rasterize_glyph_msdf(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        spread: f32,
+    ) -> Result<DistanceField, GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_msdf(self, glyph_id, point_size, spread)
+    }
+
+    unsafe fn rasterize_fixed_size_glyph(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+    ) -> Result<(), GlyphLoadingError> {
+        let face = self.freetype_face;
+        let sizes =
+            slice::from_raw_parts((*face).available_sizes, (*face).num_fixed_sizes as usize);
+        let strike_sizes: Vec<f32> = sizes.iter().map(|size| size.y_ppem as f32 / 64.0).collect();
+        let strike_index = bitmap::strike_preference(&strike_sizes, point_size)[0];
+        let strike_pixels_per_em = strike_sizes[strike_index];
+
+        if FT_Select_Size(face, strike_index as FT_Int) != 0 {
+            return Err(GlyphLoadingError::PlatformError);
+        }
+        let coverage = load_glyph_coverage(self, glyph_id);
+        reset_freetype_face_char_size(face);
+        let (coverage, size, origin) = coverage?;
+
+        let bitmap_to_device = transform
+            * Transform2F::from_scale(point_size / strike_pixels_per_em)
+            * Transform2F::from_translation(origin.to_f32());
+        canvas.blit_from_transformed(&coverage, size, size.x() as usize, bitmap_to_device);
+        Ok(())
+    }
+
+    /// Returns the color palettes defined in the font's `CPAL` table.
+    #[inline]
+    pub fn color_palettes(&self) -> Vec<ColorPalette> {
+        <Self as Loader>::color_palettes(self)
+    }
+
+    /// Returns the layers that make up the given glyph, if it is a version 0 `COLR` color glyph.
+    #[inline]
+    pub fn color_glyph_layers(&self, glyph_id: u32) -> Option<Vec<ColorLayer>> {
+        <Self as Loader>::color_glyph_layers(self, glyph_id)
+    }
+
+    /// Returns true if the given glyph is drawn with a version 1 `COLR` paint graph.
+    #[inline]
+    pub fn has_color_glyph_paint(&self, glyph_id: u32) -> bool {
+        <Self as Loader>::has_color_glyph_paint(self, glyph_id)
+    }
+
+    /// Returns the embedded bitmap image of the given glyph from the `CBDT`/`CBLC` or `sbix`
+    /// table, taken from the strike that best matches `point_size` pixels per em.
+    #[inline]
+    pub fn embedded_image(&self, glyph_id: u32, point_size: f32) -> Option<EmbeddedImage> {
+        <Self as Loader>::embedded_image(self, glyph_id, point_size)
+    }
+
+    /// Rasterizes a color glyph to an RGBA canvas with the given size and transform.
+    ///
+    /// Version 1 `COLR` paint graphs and version 0 `COLR` layers are composited over the canvas,
+    /// with colors from palette `palette_index` or `foreground_color`. Other glyphs are drawn from
+    /// their embedded bitmap image if they have one, and are otherwise filled with
+    /// `foreground_color`.
+    ///
+    /// Panics if the canvas format is not `Format::Rgba32`, `Format::Rgba32Straight`, or
+    /// `Format::Bgra32`.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn rasterize_color_glyph(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+        palette_index: usize,
+        foreground_color: RgbaColor,
+    ) -> Result<(), GlyphLoadingError> {
+        <Self as Loader>::rasterize_color_glyph(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+            palette_index,
+            foreground_color,
+        )
+    }
+
+    /// Returns true if the given glyph is described by a document in the font's `SVG ` table.
+    #[inline]
+    pub fn has_svg_glyph(&self, glyph_id: u32) -> bool {
+        <Self as Loader>::has_svg_glyph(self, glyph_id)
+    }
+
+    /// Returns the SVG document from the font's `SVG ` table that describes the given glyph,
+    /// decompressing it if necessary, along with the range of glyphs that the document covers.
+    #[inline]
+    pub fn svg_glyph_document(&self, glyph_id: u32) -> Option<SvgGlyphDocument> {
+        <Self as Loader>::svg_glyph_document(self, glyph_id)
+    }
+
     fn hinting_and_rasterization_options_to_load_flags(
         &self,
         hinting: HintingOptions,
@@ -893,7 +1722,10 @@ impl Font {
     ) -> i32 {
         let mut options = match (hinting, rasterization) {
             (HintingOptions::VerticalSubpixel(_), _) | (_, RasterizationOptions::SubpixelAa) => {
-                FT_LOAD_TARGET_LCD
+                match self.lcd_options.subpixel_order {
+                    SubpixelOrder::Rgb | SubpixelOrder::Bgr => FT_LOAD_TARGET_LCD,
+                    SubpixelOrder::Vrgb | SubpixelOrder::Vbgr => FT_LOAD_TARGET_LCD_V,
+                }
             }
             (HintingOptions::None, _) => FT_LOAD_TARGET_NORMAL | FT_LOAD_NO_HINTING,
             (HintingOptions::Vertical(_), RasterizationOptions::Bilevel)
@@ -904,15 +1736,147 @@ impl Font {
         if rasterization == RasterizationOptions::Bilevel {
             options |= FT_LOAD_MONOCHROME
         }
+        match self.hinting_engine_options.autohinter {
+            AutohinterMode::Default => {}
+            AutohinterMode::Force => options |= FT_LOAD_FORCE_AUTOHINT,
+            AutohinterMode::Never => options |= FT_LOAD_NO_AUTOHINT,
+        }
+        if !self.hinting_engine_options.embedded_bitmaps {
+            options |= FT_LOAD_NO_BITMAP
+        }
         options
     }
 
+    // Loads a glyph into the glyph slot of the face, with the hinting engine and LCD options of
+    // this font.
+    //
+    // The interpreter version, stem darkening, and LCD filter are properties of the FreeType
+    // library, which every font on this thread shares. Each one is only set when this font needs a
+    // different value from the last one set, so fonts with the default options leave the
+    // library's own defaults alone.
+    unsafe fn load_glyph(&self, glyph_id: u32, load_flags: i32) -> Result<(), GlyphLoadingError> {
+        FREETYPE_LIBRARY.with(|freetype_library| {
+            LIBRARY_PROPERTIES.with(|library_properties| {
+                let library = freetype_library.0;
+                let mut properties = library_properties.get();
+                let result = self.set_library_properties(library, &mut properties);
+                library_properties.set(properties);
+                result
+            })
+        })?;
+        if FT_Load_Glyph(self.freetype_face, glyph_id, load_flags) != 0 {
+            return Err(GlyphLoadingError::NoSuchGlyph);
+        }
+        Ok(())
+    }
+
+    unsafe fn set_library_properties(
+        &self,
+        library: FT_Library,
+        properties: &mut LibraryProperties,
+    ) -> Result<(), GlyphLoadingError> {
+        let (hinting_defaults, lcd_defaults) =
+            (HintingEngineOptions::default(), LcdOptions::default());
+        update_library_property(
+            &mut properties.interpreter_version,
+            self.hinting_engine_options.interpreter_version,
+            hinting_defaults.interpreter_version,
+            |interpreter_version| {
+                let interpreter_version: FT_UInt = match interpreter_version {
+                    InterpreterVersion::V35 => 35,
+                    InterpreterVersion::V40 => 40,
+                };
+                set_module_property(
+                    library,
+                    b"truetype\0",
+                    b"interpreter-version\0",
+                    &interpreter_version as *const FT_UInt as *const c_void,
+                )
+            },
+        )?;
+        update_library_property(
+            &mut properties.stem_darkening,
+            self.hinting_engine_options.stem_darkening,
+            hinting_defaults.stem_darkening,
+            |stem_darkening| {
+                let no_stem_darkening = !stem_darkening as FT_Bool;
+                for module_name in [&b"cff\0"[..], b"type1\0", b"t1cid\0"] {
+                    let error = set_module_property(
+                        library,
+                        module_name,
+                        b"no-stem-darkening\0",
+                        &no_stem_darkening as *const FT_Bool as *const c_void,
+                    );
+                    if error != 0 {
+                        return error;
+                    }
+                }
+                0
+            },
+        )?;
+        update_library_property(
+            &mut properties.lcd_filter,
+            self.lcd_options.filter,
+            lcd_defaults.filter,
+            |filter| match filter {
+                LcdFilter::None => FT_Library_SetLcdFilter(library, FT_LCD_FILTER_NONE),
+                LcdFilter::Default => FT_Library_SetLcdFilter(library, FT_LCD_FILTER_DEFAULT),
+                LcdFilter::Light => FT_Library_SetLcdFilter(library, FT_LCD_FILTER_LIGHT),
+                LcdFilter::Legacy => FT_Library_SetLcdFilter(library, FT_LCD_FILTER_LEGACY),
+                LcdFilter::Custom(mut weights) => {
+                    FT_Library_SetLcdFilterWeights(library, weights.as_mut_ptr())
+                }
+            },
+        )
+    }
+
+    /// Returns the options that choose how FreeType hints the glyphs of this font.
+    #[inline]
+    pub fn hinting_engine_options(&self) -> HintingEngineOptions {
+        self.hinting_engine_options
+    }
+
+    /// Sets the options that choose how FreeType hints the glyphs of this font.
+    ///
+    /// These apply to this font only, and not to its clones made before the change. If FreeType
+    /// was built without support for an option, such as the chosen interpreter version, loading
+    /// glyphs fails with `GlyphLoadingError::PlatformError`.
+    #[inline]
+    pub fn set_hinting_engine_options(&mut self, options: HintingEngineOptions) {
+        self.hinting_engine_options = options;
+    }
+
+    /// Returns the options that choose how FreeType draws the glyphs of this font with subpixel
+    /// antialiasing.
+    #[inline]
+    pub fn lcd_options(&self) -> LcdOptions {
+        self.lcd_options
+    }
+
+    /// Sets the options that choose how FreeType draws the glyphs of this font with subpixel
+    /// antialiasing.
+    ///
+    /// These apply to this font only, and not to its clones made before the change. If FreeType
+    /// was built without support for the filter, loading glyphs fails with
+    /// `GlyphLoadingError::PlatformError`.
+    #[inline]
+    pub fn set_lcd_options(&mut self, options: LcdOptions) {
+        self.lcd_options = options;
+    }
+
     /// Returns a handle to this font, if possible.
     ///
     /// This is useful if you want to open the font with a different loader.
+    ///
+    /// The handle refers to the same member of a collection and the same named instance of a
+    /// variable font as this font does, but coordinates set with `clone_with_variations()` are
+    /// not preserved.
     #[inline]
     pub fn handle(&self) -> Option<Handle> {
-        <Self as Loader>::handle(self)
+        unsafe {
+            let font_index = (*self.freetype_face).face_index as u32;
+            Some(Handle::from_memory(self.font_data.clone(), font_index))
+        }
     }
 
     /// Attempts to return the raw font data (contents of the font file).
@@ -978,6 +1942,8 @@ impl Clone for Font {
             Font {
                 freetype_face: self.freetype_face,
                 font_data: self.font_data.clone(),
+                hinting_engine_options: self.hinting_engine_options,
+                lcd_options: self.lcd_options,
             }
         }
     }
@@ -1051,6 +2017,21 @@ impl Loader for Font {
         self.family_name()
     }
 
+    #[inline]
+    fn style_name(&self) -> String {
+        self.style_name()
+    }
+
+    #[inline]
+    fn typographic_subfamily_name(&self) -> String {
+        self.typographic_subfamily_name()
+    }
+
+    #[inline]
+    fn wws_subfamily_name(&self) -> String {
+        self.wws_subfamily_name()
+    }
+
     #[inline]
     fn is_monospace(&self) -> bool {
         self.is_monospace()
@@ -1061,16 +2042,31 @@ impl Loader for Font {
         self.properties()
     }
 
+    #[inline]
+    fn named_instance_count(&self) -> u32 {
+        self.named_instance_count()
+    }
+
     #[inline]
     fn glyph_for_char(&self, character: char) -> Option<u32> {
         self.glyph_for_char(character)
     }
 
+    #[inline]
+    fn character_map(&self) -> Vec<(char, u32)> {
+        self.character_map()
+    }
+
     #[inline]
     fn glyph_by_name(&self, name: &str) -> Option<u32> {
         self.glyph_by_name(name)
     }
 
+    #[inline]
+    fn glyph_name(&self, glyph_id: u32) -> Option<String> {
+        self.glyph_name(glyph_id)
+    }
+
     #[inline]
     fn glyph_count(&self) -> u32 {
         self.glyph_count()
@@ -1109,6 +2105,11 @@ impl Loader for Font {
         self.metrics()
     }
 
+    #[inline]
+    fn handle(&self) -> Option<Handle> {
+        self.handle()
+    }
+
     #[inline]
     fn copy_font_data(&self) -> Option<Arc<Vec<u8>>> {
         self.copy_font_data()
@@ -1143,6 +2144,11 @@ impl Loader for Font {
         )
     }
 
+    #[inline]
+    fn fixed_sizes(&self) -> Vec<FixedSize> {
+        self.fixed_sizes()
+    }
+
     #[inline]
     fn get_fallbacks(&self, text: &str, locale: &str) -> FallbackResult<Self> {
         self.get_fallbacks(text, locale)
@@ -1158,17 +2164,160 @@ unsafe fn setup_freetype_face(face: FT_Face) {
     reset_freetype_face_char_size(face);
 }
 
+// Returns the design coordinates of a variable font, or an empty list for other fonts.
+unsafe fn get_var_design_coordinates(face: FT_Face) -> Vec<FT_Fixed> {
+    let axis_count = match FtMmVar::new(face) {
+        Some(mm_var) => (*mm_var.0).num_axis,
+        None => return vec![],
+    };
+    let mut coords = vec![0; axis_count as usize];
+    if FT_Get_Var_Design_Coordinates(face, axis_count, coords.as_mut_ptr()) != 0 {
+        return vec![];
+    }
+    coords
+}
+
 unsafe fn reset_freetype_face_char_size(face: FT_Face) {
-    // Apple Color Emoji has 0 units per em. Whee!
+    // Apple Color Emoji has 0 units per em. Whee! Fonts that only contain bitmaps can't be set to
+    // arbitrary sizes either, so they use their first strike.
     let units_per_em = (*face).units_per_EM as i64;
-    if units_per_em > 0 {
+    if units_per_em > 0 && (*face).face_flags & (FT_FACE_FLAG_SCALABLE as FT_Long) != 0 {
         assert_eq!(
             FT_Set_Char_Size(face, ((*face).units_per_EM as FT_Long) << 6, 0, 0, 0),
             0
         );
+    } else if (*face).num_fixed_sizes > 0 {
+        assert_eq!(FT_Select_Size(face, 0), 0);
     }
 }
 
+// Sets a property of the FreeType library with `set`, if `value` differs from `current`, the last
+// value set. Until a property is first set, it is taken to have the value `default`.
+fn update_library_property<T, F>(
+    current: &mut Option<T>,
+    value: T,
+    default: T,
+    set: F,
+) -> Result<(), GlyphLoadingError>
+where
+    T: Copy + PartialEq,
+    F: FnOnce(T) -> FT_Error,
+{
+    if current.unwrap_or(default) == value {
+        return Ok(());
+    }
+    if set(value) != 0 {
+        return Err(GlyphLoadingError::PlatformError);
+    }
+    *current = Some(value);
+    Ok(())
+}
+
+// Sets a property of a FreeType module. Modules that FreeType was built without can't load any
+// fonts, so their properties don't matter, and are skipped.
+unsafe fn set_module_property(
+    library: FT_Library,
+    module_name: &[u8],
+    property_name: &[u8],
+    value: *const c_void,
+) -> FT_Error {
+    let error = FT_Property_Set(
+        library,
+        module_name.as_ptr() as *const c_char,
+        property_name.as_ptr() as *const c_char,
+        value,
+    );
+    if error == FT_Err_Missing_Module {
+        0
+    } else {
+        error
+    }
+}
+
+/// Loads a glyph from the current strike of a bitmap font, returning its coverage as an 8-bit
+/// bitmap along with the bitmap's size and the position of its top left corner relative to the
+/// glyph origin.
+unsafe fn load_glyph_coverage(
+    font: &Font,
+    glyph_id: u32,
+) -> Result<(Vec<u8>, Vector2I, Vector2I), GlyphLoadingError> {
+    font.load_glyph(glyph_id, FT_LOAD_DEFAULT | FT_LOAD_RENDER)?;
+    let face = font.freetype_face;
+    let glyph = (*face).glyph;
+    let bitmap = &(*glyph).bitmap;
+    let (width, height) = (bitmap.width as usize, bitmap.rows as usize);
+    let origin = Vector2I::new((*glyph).bitmap_left, -(*glyph).bitmap_top);
+    if bitmap.buffer.is_null() || width == 0 || height == 0 {
+        return Ok((vec![], Vector2I::zero(), origin));
+    }
+
+    let stride = bitmap.pitch.unsigned_abs() as usize;
+    let buffer = slice::from_raw_parts(bitmap.buffer as *const u8, stride * height);
+    let mut coverage = Vec::with_capacity(width * height);
+    for row in buffer.chunks(stride) {
+        for x in 0..width {
+            coverage.push(match bitmap.pixel_mode as u32 {
+                FT_PIXEL_MODE_MONO => ((row[x / 8] >> (7 - x % 8)) & 1) * 255,
+                FT_PIXEL_MODE_GRAY2 => ((row[x / 4] >> (6 - x % 4 * 2)) & 3) * 85,
+                FT_PIXEL_MODE_GRAY4 => ((row[x / 2] >> (4 - x % 2 * 4)) & 15) * 17,
+                FT_PIXEL_MODE_GRAY => row[x],
+                FT_PIXEL_MODE_BGRA => row[x * 4 + 3],
+                _ => return Err(GlyphLoadingError::NoSuchGlyph),
+            });
+        }
+    }
+    Ok((coverage, Vector2I::new(width as i32, height as i32), origin))
+}
+
+// Converts a bitmap that FreeType rendered for a screen with `subpixel_order` to `Rgb24` pixels,
+// returning their size and the pixels.
+//
+// `FT_PIXEL_MODE_LCD` bitmaps have three samples across for each pixel, and `FT_PIXEL_MODE_LCD_V`
+// bitmaps three down, each from the first subpixel to the last. `size` is the size of the
+// bitmap in samples.
+fn lcd_bitmap_to_rgb24(
+    buffer: &[u8],
+    size: Vector2I,
+    stride: usize,
+    vertical: bool,
+    subpixel_order: SubpixelOrder,
+) -> (Vector2I, Vec<u8>) {
+    let pixel_size = if vertical {
+        Vector2I::new(size.x(), size.y() / 3)
+    } else {
+        Vector2I::new(size.x() / 3, size.y())
+    };
+    let (width, height) = (pixel_size.x() as usize, pixel_size.y() as usize);
+    let mut pixels = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        for x in 0..width {
+            let mut pixel = [0; 3];
+            for (subpixel, value) in pixel.iter_mut().enumerate() {
+                *value = if vertical {
+                    buffer[(y * 3 + subpixel) * stride + x]
+                } else {
+                    buffer[y * stride + x * 3 + subpixel]
+                };
+            }
+            if let SubpixelOrder::Bgr | SubpixelOrder::Vbgr = subpixel_order {
+                pixel.swap(0, 2);
+            }
+            pixels.extend_from_slice(&pixel);
+        }
+    }
+    (pixel_size, pixels)
+}
+
+#[inline]
+fn ft_fixed_16_16_to_f32(value: FT_Fixed) -> f32 {
+    value as f32 * (1.0 / 65536.0)
+}
+
+#[inline]
+fn f32_to_ft_fixed_16_16(value: f32) -> FT_Fixed {
+    (value * 65536.0).round() as FT_Fixed
+}
+
 trait F32ToFtFixed {
     type Output;
     fn f32_to_ft_fixed_26_6(self) -> Self::Output;
@@ -1213,6 +2362,7 @@ impl FtFixedToF32 for RectI {
 
 extern "C" {
     fn FT_Get_Font_Format(face: FT_Face) -> *const c_char;
+    fn FT_Library_SetLcdFilterWeights(library: FT_Library, weights: *mut c_uchar) -> FT_Error;
     fn FT_Get_BDF_Property(
         face: FT_Face,
         prop_name: *const c_char,
@@ -1236,14 +2386,504 @@ extern "C" {
 
 #[cfg(test)]
 mod test {
-    use crate::loaders::freetype::Font;
+    use crate::bitmap::FixedSize;
+    use crate::canvas::{Canvas, Format, RasterizationOptions};
+    use crate::cmap;
+    use crate::glyph_names::{self, POST_TABLE_TAG};
+    use crate::hinting::HintingOptions;
+    use crate::loaders::freetype::{
+        AutohinterMode, Font, HintingEngineOptions, InterpreterVersion, LcdFilter, LcdOptions,
+        SubpixelOrder, FREETYPE_LIBRARY,
+    };
+    use crate::outline::OutlineBuilder;
+    use crate::variations::{Variation, VariationAxis, WEIGHT_AXIS, WIDTH_AXIS};
+    use freetype_sys::{FT_Property_Get, FT_Property_Set, FT_UInt, FT_LOAD_NO_BITMAP};
+    use pathfinder_geometry::rect::RectF;
+    use pathfinder_geometry::transform2d::Transform2F;
+    use pathfinder_geometry::vector::{Vector2F, Vector2I};
+    use std::os::raw::{c_char, c_void};
 
     static PCF_FONT_PATH: &str = "resources/tests/times-roman-pcf/timR12.pcf";
     static PCF_FONT_POSTSCRIPT_NAME: &str = "Times-Roman";
+    static OTF_FONT_PATH: &str = "resources/tests/eb-garamond/EBGaramond12-Regular.otf";
+    static TTF_FONT_PATH: &str = "resources/tests/eb-garamond/EBGaramond12-Regular.ttf";
+    static VARIABLE_FONT_PATH: &str = "resources/tests/font-kit-variable/FontKitVariable.ttf";
 
     #[test]
     fn get_pcf_postscript_name() {
         let font = Font::from_path(PCF_FONT_PATH, 0).unwrap();
         assert_eq!(font.postscript_name().unwrap(), PCF_FONT_POSTSCRIPT_NAME);
     }
+
+    #[test]
+    fn get_pcf_fixed_sizes_and_metrics() {
+        let font = Font::from_path(PCF_FONT_PATH, 0).unwrap();
+        assert_eq!(
+            font.fixed_sizes(),
+            [FixedSize {
+                pixels_per_em: Vector2F::splat(17.0),
+                height: 17,
+            }]
+        );
+
+        let metrics = font.metrics();
+        assert_eq!(metrics.units_per_em, 17);
+        assert_eq!(metrics.ascent, 13.0);
+        assert_eq!(metrics.descent, -4.0);
+        assert_eq!(metrics.line_gap, 0.0);
+        assert_eq!(metrics.underline_thickness, 1.0);
+        assert_eq!(metrics.cap_height, 11.0);
+        assert_eq!(metrics.x_height, 8.0);
+
+        let glyph_id = font.glyph_for_char('A').unwrap();
+        assert_eq!(font.advance(glyph_id).unwrap(), Vector2F::new(12.0, 0.0));
+        assert_eq!(
+            font.typographic_bounds(glyph_id).unwrap(),
+            RectF::new(Vector2F::new(0.0, 0.0), Vector2F::new(12.0, 11.0))
+        );
+    }
+
+    #[test]
+    fn rasterize_pcf_glyph() {
+        let font = Font::from_path(PCF_FONT_PATH, 0).unwrap();
+        let glyph_id = font.glyph_for_char('A').unwrap();
+        let rasterize = |size: f32| {
+            let mut canvas = Canvas::new(Vector2I::splat(40), Format::A8);
+            font.rasterize_glyph(
+                &mut canvas,
+                glyph_id,
+                size,
+                Transform2F::from_translation(Vector2F::new(0.0, 30.0)),
+                HintingOptions::None,
+                RasterizationOptions::GrayscaleAa,
+            )
+            .unwrap();
+            canvas
+        };
+        let coverage_bounds = |canvas: &Canvas| {
+            let (mut min, mut max) = (Vector2I::splat(i32::MAX), Vector2I::splat(i32::MIN));
+            for (index, &pixel) in canvas.pixels.iter().enumerate() {
+                if pixel > 0 {
+                    let point = Vector2I::new(index as i32 % 40, index as i32 / 40);
+                    min = min.min(point);
+                    max = max.max(point + Vector2I::splat(1));
+                }
+            }
+            (min, max)
+        };
+
+        // The native strike is drawn as-is; other sizes scale the nearest strike.
+        assert_eq!(
+            coverage_bounds(&rasterize(17.0)),
+            (Vector2I::new(0, 19), Vector2I::new(12, 30))
+        );
+        assert_eq!(
+            coverage_bounds(&rasterize(34.0)),
+            (Vector2I::new(0, 8), Vector2I::new(24, 30))
+        );
+    }
+
+    #[test]
+    fn character_map_matches_cmap_table() {
+        // FreeType walks its own charmap; make sure the table parser used by the other loaders
+        // agrees with it.
+        let font = Font::from_path(OTF_FONT_PATH, 0).unwrap();
+        let cmap = font.load_font_table(cmap::CMAP_TABLE_TAG).unwrap();
+        let character_map = font.character_map();
+        assert!(!character_map.is_empty());
+        assert_eq!(
+            cmap::read_character_map(&cmap, font.glyph_count()),
+            character_map
+        );
+    }
+
+    #[test]
+    fn get_pcf_character_coverage() {
+        let font = Font::from_path(PCF_FONT_PATH, 0).unwrap();
+        let coverage = font.character_coverage();
+        assert!(coverage.contains_all("The quick brown fox"));
+        let a = font.glyph_for_char('A').unwrap();
+        assert_eq!(font.chars_for_glyph(a), ['A']);
+    }
+
+    #[test]
+    fn glyph_names_match_post_table() {
+        // FreeType reads the `post` table itself; make sure the parser used by the other loaders
+        // agrees with it, for both standard Macintosh names and custom ones.
+        let font = Font::from_path(TTF_FONT_PATH, 0).unwrap();
+        let post = font.load_font_table(POST_TABLE_TAG).unwrap();
+        for glyph_id in 0..font.glyph_count() {
+            assert_eq!(
+                glyph_names::read_post_glyph_name(&post, glyph_id),
+                font.glyph_name(glyph_id)
+            );
+        }
+    }
+
+    #[test]
+    fn get_cff_and_pcf_glyph_names() {
+        let font = Font::from_path(OTF_FONT_PATH, 0).unwrap();
+        let glyph_id = font.glyph_for_char('a').unwrap();
+        assert_eq!(font.glyph_name(glyph_id).unwrap(), "a");
+
+        // PCF fonts have no glyph names, so they're derived from the character map.
+        let font = Font::from_path(PCF_FONT_PATH, 0).unwrap();
+        let glyph_id = font.glyph_for_char('A').unwrap();
+        assert_eq!(font.glyph_name(glyph_id).unwrap(), "uni0041");
+        assert_eq!(font.glyph_name(font.glyph_count()), None);
+    }
+
+    #[test]
+    fn get_named_instance_style_names() {
+        let font = Font::from_path(VARIABLE_FONT_PATH, 0).unwrap();
+        assert_eq!(font.style_name(), "Regular");
+        assert_eq!(font.typographic_subfamily_name(), "Regular");
+
+        // Named instances take their style names from `fvar`, not the shared `name` table.
+        let font = Font::from_path(VARIABLE_FONT_PATH, 4 << 16).unwrap();
+        assert_eq!(font.style_name(), "Bold Condensed");
+        assert_eq!(font.typographic_subfamily_name(), "Bold Condensed");
+        assert_eq!(font.wws_subfamily_name(), "Bold Condensed");
+        assert_eq!(font.typographic_family_name(), "Font Kit Variable");
+    }
+
+    #[test]
+    fn get_variation_axes() {
+        let font = Font::from_path(VARIABLE_FONT_PATH, 0).unwrap();
+        assert_eq!(
+            font.variation_axes(),
+            [
+                VariationAxis {
+                    tag: WEIGHT_AXIS,
+                    min_value: 100.0,
+                    default_value: 400.0,
+                    max_value: 900.0,
+                    name_id: 256,
+                },
+                VariationAxis {
+                    tag: WIDTH_AXIS,
+                    min_value: 50.0,
+                    default_value: 100.0,
+                    max_value: 100.0,
+                    name_id: 257,
+                },
+            ]
+        );
+        assert_eq!(
+            font.variations(),
+            [
+                Variation::new(WEIGHT_AXIS, 400.0),
+                Variation::new(WIDTH_AXIS, 100.0)
+            ]
+        );
+
+        let font = Font::from_path(OTF_FONT_PATH, 0).unwrap();
+        assert!(font.variation_axes().is_empty());
+        assert!(font.variations().is_empty());
+    }
+
+    #[test]
+    fn clone_with_variations() {
+        let font = Font::from_path(VARIABLE_FONT_PATH, 0).unwrap();
+        let glyph_id = font.glyph_for_char('A').unwrap();
+
+        let bold = font
+            .clone_with_variations(&[Variation::new(WEIGHT_AXIS, 900.0)])
+            .unwrap();
+        assert_eq!(
+            bold.variations(),
+            [
+                Variation::new(WEIGHT_AXIS, 900.0),
+                Variation::new(WIDTH_AXIS, 100.0)
+            ]
+        );
+        assert_eq!(bold.advance(glyph_id).unwrap(), Vector2F::new(800.0, 0.0));
+        assert_eq!(
+            bold.typographic_bounds(glyph_id).unwrap(),
+            RectF::new(Vector2F::new(100.0, 0.0), Vector2F::new(600.0, 700.0))
+        );
+
+        // Out-of-range values are clamped, and other axes keep their values.
+        let condensed = bold
+            .clone_with_variations(&[Variation::new(WIDTH_AXIS, 0.0)])
+            .unwrap();
+        assert_eq!(
+            condensed.variations(),
+            [
+                Variation::new(WEIGHT_AXIS, 900.0),
+                Variation::new(WIDTH_AXIS, 50.0)
+            ]
+        );
+        assert_eq!(
+            condensed.advance(glyph_id).unwrap(),
+            Vector2F::new(600.0, 0.0)
+        );
+
+        // The original font is unaffected.
+        assert_eq!(font.advance(glyph_id).unwrap(), Vector2F::new(600.0, 0.0));
+    }
+
+    #[test]
+    fn choose_hinting_engine() {
+        let rasterize = |font: &Font, hinting_options| {
+            let mut canvas = Canvas::new(Vector2I::splat(24), Format::A8);
+            font.rasterize_glyph(
+                &mut canvas,
+                font.glyph_for_char('a').unwrap(),
+                13.0,
+                Transform2F::from_translation(Vector2F::new(4.0, 18.0)),
+                hinting_options,
+                RasterizationOptions::GrayscaleAa,
+            )
+            .unwrap();
+            canvas.pixels
+        };
+        let with_options = |font: &Font, options| {
+            let mut font = font.clone();
+            font.set_hinting_engine_options(options);
+            font
+        };
+
+        // This font has hints. FreeType follows them for full hinting, and autohints for light
+        // hinting.
+        let font = Font::from_path(TTF_FONT_PATH, 0).unwrap();
+        let full = rasterize(&font, HintingOptions::Full(13.0));
+        let vertical = rasterize(&font, HintingOptions::Vertical(13.0));
+        let autohinted = with_options(
+            &font,
+            HintingEngineOptions {
+                autohinter: AutohinterMode::Force,
+                ..HintingEngineOptions::default()
+            },
+        );
+        assert_ne!(rasterize(&autohinted, HintingOptions::Full(13.0)), full);
+        assert_eq!(
+            rasterize(&autohinted, HintingOptions::Vertical(13.0)),
+            vertical
+        );
+        let native = with_options(
+            &font,
+            HintingEngineOptions {
+                autohinter: AutohinterMode::Never,
+                ..HintingEngineOptions::default()
+            },
+        );
+        assert_ne!(rasterize(&native, HintingOptions::Vertical(13.0)), vertical);
+
+        // Options belong to each font, and survive cloning with variations.
+        assert_eq!(rasterize(&font, HintingOptions::Full(13.0)), full);
+        let clone = autohinted.clone_with_variations(&[]).unwrap();
+        assert_eq!(
+            clone.hinting_engine_options(),
+            autohinted.hinting_engine_options()
+        );
+
+        // Darkening makes stems heavier.
+        let font = Font::from_path(OTF_FONT_PATH, 0).unwrap();
+        let coverage = |pixels: Vec<u8>| pixels.iter().map(|&pixel| pixel as u32).sum::<u32>();
+        let darkened = with_options(
+            &font,
+            HintingEngineOptions {
+                stem_darkening: true,
+                ..HintingEngineOptions::default()
+            },
+        );
+        assert!(
+            coverage(rasterize(&darkened, HintingOptions::Full(13.0)))
+                > coverage(rasterize(&font, HintingOptions::Full(13.0)))
+        );
+    }
+
+    #[test]
+    fn set_interpreter_version_and_embedded_bitmaps() {
+        let get_interpreter_version = || {
+            FREETYPE_LIBRARY.with(|freetype_library| unsafe {
+                let mut interpreter_version: FT_UInt = 0;
+                assert_eq!(
+                    FT_Property_Get(
+                        freetype_library.0,
+                        b"truetype\0".as_ptr() as *const c_char,
+                        b"interpreter-version\0".as_ptr() as *const c_char,
+                        &mut interpreter_version as *mut FT_UInt as *mut c_void,
+                    ),
+                    0
+                );
+                interpreter_version
+            })
+        };
+
+        let mut font = Font::from_path(TTF_FONT_PATH, 0).unwrap();
+        let glyph_id = font.glyph_for_char('a').unwrap();
+        let options = HintingEngineOptions {
+            interpreter_version: InterpreterVersion::V35,
+            embedded_bitmaps: false,
+            ..HintingEngineOptions::default()
+        };
+        font.set_hinting_engine_options(options);
+        let mut builder = OutlineBuilder::new();
+        font.outline(glyph_id, HintingOptions::None, &mut builder)
+            .unwrap();
+        assert_eq!(get_interpreter_version(), 35);
+        let load_flags = font.hinting_and_rasterization_options_to_load_flags(
+            HintingOptions::Full(13.0),
+            RasterizationOptions::GrayscaleAa,
+        );
+        assert_ne!(load_flags & FT_LOAD_NO_BITMAP, 0);
+
+        // Other fonts on the same thread are unaffected.
+        let font = Font::from_path(TTF_FONT_PATH, 0).unwrap();
+        font.outline(glyph_id, HintingOptions::None, &mut builder)
+            .unwrap();
+        assert_eq!(get_interpreter_version(), 40);
+        let load_flags = font.hinting_and_rasterization_options_to_load_flags(
+            HintingOptions::Full(13.0),
+            RasterizationOptions::GrayscaleAa,
+        );
+        assert_eq!(load_flags & FT_LOAD_NO_BITMAP, 0);
+    }
+
+    #[test]
+    fn keep_library_properties_by_default() {
+        let interpreter_version_property = |interpreter_version: Option<FT_UInt>| {
+            FREETYPE_LIBRARY.with(|freetype_library| unsafe {
+                let mut value = interpreter_version.unwrap_or(0);
+                let (module_name, property_name) = (
+                    b"truetype\0".as_ptr() as *const c_char,
+                    b"interpreter-version\0".as_ptr() as *const c_char,
+                );
+                let pointer = &mut value as *mut FT_UInt as *mut c_void;
+                let error = match interpreter_version {
+                    Some(_) => {
+                        FT_Property_Set(freetype_library.0, module_name, property_name, pointer)
+                    }
+                    None => {
+                        FT_Property_Get(freetype_library.0, module_name, property_name, pointer)
+                    }
+                };
+                assert_eq!(error, 0);
+                value
+            })
+        };
+
+        // As the `FREETYPE_PROPERTIES` environment variable would, choose a version that isn't
+        // the default. Fonts with the default options must not change it back.
+        interpreter_version_property(Some(35));
+        let font = Font::from_path(TTF_FONT_PATH, 0).unwrap();
+        let glyph_id = font.glyph_for_char('a').unwrap();
+        font.typographic_bounds(glyph_id).unwrap();
+        font.advance(glyph_id).unwrap();
+        let mut builder = OutlineBuilder::new();
+        font.outline(glyph_id, HintingOptions::Full(13.0), &mut builder)
+            .unwrap();
+        assert_eq!(interpreter_version_property(None), 35);
+    }
+
+    #[test]
+    fn rasterize_with_lcd_options() {
+        let rasterize = |font: &Font, format| {
+            let mut canvas = Canvas::new(Vector2I::splat(24), format);
+            font.rasterize_glyph(
+                &mut canvas,
+                font.glyph_for_char('e').unwrap(),
+                16.0,
+                Transform2F::from_translation(Vector2F::new(4.0, 18.0)),
+                HintingOptions::None,
+                RasterizationOptions::SubpixelAa,
+            )
+            .unwrap();
+            canvas.pixels
+        };
+        let font = Font::from_path(TTF_FONT_PATH, 0).unwrap();
+        let with_options = |filter, subpixel_order| {
+            let mut font = font.clone();
+            font.set_lcd_options(LcdOptions {
+                filter,
+                subpixel_order,
+            });
+            font
+        };
+        let swap_red_and_blue = |pixels: &[u8]| {
+            let mut pixels = pixels.to_vec();
+            for pixel in pixels.chunks_mut(3) {
+                pixel.swap(0, 2);
+            }
+            pixels
+        };
+        // Returns the ranges of the columns and rows that have any coverage.
+        let coverage_bounds = |pixels: &[u8], bytes_per_pixel: usize| {
+            let (mut min, mut max) = (Vector2I::splat(i32::MAX), Vector2I::splat(i32::MIN));
+            for (index, pixel) in pixels.chunks(bytes_per_pixel).enumerate() {
+                if pixel.iter().any(|&value| value > 0) {
+                    let point = Vector2I::new(index as i32 % 24, index as i32 / 24);
+                    min = min.min(point);
+                    max = max.max(point + Vector2I::splat(1));
+                }
+            }
+            (min, max)
+        };
+
+        // Screens with red and blue the other way around get the same pixels, swapped.
+        let rgb = rasterize(&font, Format::Rgb24);
+        let bgr = rasterize(
+            &with_options(LcdFilter::Default, SubpixelOrder::Bgr),
+            Format::Rgb24,
+        );
+        assert_eq!(bgr, swap_red_and_blue(&rgb));
+        let vrgb = rasterize(
+            &with_options(LcdFilter::Default, SubpixelOrder::Vrgb),
+            Format::Rgb24,
+        );
+        let vbgr = rasterize(
+            &with_options(LcdFilter::Default, SubpixelOrder::Vbgr),
+            Format::Rgb24,
+        );
+        assert_eq!(vbgr, swap_red_and_blue(&vrgb));
+
+        // Filtering spreads coverage by a pixel across for horizontal subpixels, and down for
+        // vertical ones. Custom weights that match a built-in filter draw what it draws.
+        let unfiltered = rasterize(
+            &with_options(LcdFilter::None, SubpixelOrder::Rgb),
+            Format::Rgb24,
+        );
+        let (min, max) = coverage_bounds(&unfiltered, 3);
+        assert_eq!(
+            coverage_bounds(&rgb, 3),
+            (min - Vector2I::new(1, 0), max + Vector2I::new(1, 0))
+        );
+        let unfiltered = rasterize(
+            &with_options(LcdFilter::None, SubpixelOrder::Vrgb),
+            Format::Rgb24,
+        );
+        let (min, max) = coverage_bounds(&unfiltered, 3);
+        assert_eq!(
+            coverage_bounds(&vrgb, 3),
+            (min - Vector2I::new(0, 1), max + Vector2I::new(0, 1))
+        );
+        let custom = with_options(
+            LcdFilter::Custom([0x08, 0x4d, 0x56, 0x4d, 0x08]),
+            SubpixelOrder::Rgb,
+        );
+        assert_eq!(rasterize(&custom, Format::Rgb24), rgb);
+        let light = rasterize(
+            &with_options(LcdFilter::Light, SubpixelOrder::Rgb),
+            Format::Rgb24,
+        );
+        assert_ne!(light, rgb);
+        let custom = with_options(
+            LcdFilter::Custom([0x00, 0x55, 0x56, 0x55, 0x00]),
+            SubpixelOrder::Rgb,
+        );
+        assert_eq!(rasterize(&custom, Format::Rgb24), light);
+
+        // `Rgba32` canvases get the same channels.
+        let rgba = rasterize(
+            &with_options(LcdFilter::Default, SubpixelOrder::Bgr),
+            Format::Rgba32,
+        );
+        let rgba_channels: Vec<u8> = rgba
+            .chunks(4)
+            .flat_map(|pixel| pixel[0..3].iter().cloned())
+            .collect();
+        assert_eq!(rgba_channels, bgr);
+    }
 }
@@ -12,19 +12,35 @@
 
 #[cfg(all(
     any(target_os = "macos", target_os = "ios"),
-    not(feature = "loader-freetype-default")
+    not(feature = "loader-freetype-default"),
+    not(feature = "loader-opentype-default")
 ))]
 pub use crate::loaders::core_text as default;
 
-#[cfg(all(target_family = "windows", not(feature = "loader-freetype-default")))]
+#[cfg(all(
+    target_family = "windows",
+    not(feature = "loader-freetype-default"),
+    not(feature = "loader-opentype-default")
+))]
 pub use crate::loaders::directwrite as default;
 
-#[cfg(any(
-    not(any(target_os = "macos", target_os = "ios", target_family = "windows")),
-    feature = "loader-freetype-default"
+#[cfg(all(
+    any(
+        not(any(
+            target_os = "macos",
+            target_os = "ios",
+            target_family = "windows",
+            target_arch = "wasm32"
+        )),
+        feature = "loader-freetype-default"
+    ),
+    not(feature = "loader-opentype-default")
 ))]
 pub use crate::loaders::freetype as default;
 
+#[cfg(feature = "loader-opentype-default")]
+pub use crate::loaders::opentype as default;
+
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub mod core_text;
 
@@ -32,7 +48,15 @@ pub mod core_text;
 pub mod directwrite;
 
 #[cfg(any(
-    not(any(target_os = "macos", target_os = "ios", target_family = "windows")),
+    not(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_family = "windows",
+        target_arch = "wasm32"
+    )),
     feature = "loader-freetype"
 ))]
 pub mod freetype;
+
+#[cfg(feature = "loader-opentype")]
+pub mod opentype;
@@ -0,0 +1,1810 @@
+// font-kit/src/loaders/opentype.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A cross-platform loader written in pure Rust, which parses OpenType fonts itself and
+//! rasterizes them in software.
+//!
+//! Outlines come from the `glyf` table, with variations from `gvar`, or from the `CFF ` table.
+//! Hinting is not supported.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use log::warn;
+use pathfinder_geometry::rect::{RectF, RectI};
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::{Vector2F, Vector2I};
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Range;
+use std::sync::{Arc, OnceLock};
+
+use crate::bitmap::{self, EmbeddedImage, FixedSize};
+use crate::canvas::{Canvas, Format, RasterizationOptions};
+use crate::cff::{Cff, CFF_TABLE_TAG};
+use crate::cmap::{self, CharacterCoverage};
+use crate::color::{ColorLayer, ColorPalette, RgbaColor};
+use crate::error::{FontLoadingError, GlyphLoadingError};
+use crate::file_type::FileType;
+use crate::gamma::GammaLut;
+use crate::glyf::{GlyphTables, GLYF_TABLE_TAG, GVAR_TABLE_TAG, LOCA_TABLE_TAG};
+use crate::glyph_names::{self, POST_TABLE_TAG};
+use crate::handle::Handle;
+use crate::hinting::HintingOptions;
+use crate::loader::{FallbackResult, Loader};
+use crate::metrics::{self, Metrics, VerticalMetrics, HHEA_TABLE_TAG, HMTX_TABLE_TAG};
+use crate::names::{name_id, NameRecord};
+use crate::outline::{Outline, OutlineBuilder, OutlineSink};
+use crate::properties::{Properties, Stretch, Style, Weight};
+use crate::rasterizer;
+use crate::sdf::DistanceField;
+use crate::stroke::StrokeStyle;
+use crate::svg::SvgGlyphDocument;
+use crate::utils;
+use crate::variations::{Variation, VariationAxis};
+use crate::variations::{ITALIC_AXIS, SLANT_AXIS, WEIGHT_AXIS, WIDTH_AXIS};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs::File;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{Seek, SeekFrom};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+const TTC_TAG: u32 = 0x74746366;
+const HEAD_TABLE_TAG: u32 = 0x68656164;
+const MAXP_TABLE_TAG: u32 = 0x6d617870;
+const OS2_TABLE_TAG: u32 = 0x4f532f32;
+const FVAR_TABLE_TAG: u32 = 0x66766172;
+const AVAR_TABLE_TAG: u32 = 0x61766172;
+
+const TABLE_RECORD_LENGTH: usize = 16;
+const FVAR_AXIS_RECORD_LENGTH: usize = 20;
+
+const HEAD_UNITS_PER_EM_OFFSET: usize = 18;
+const HEAD_BOUNDING_BOX_OFFSET: usize = 36;
+const HEAD_MAC_STYLE_OFFSET: usize = 44;
+const HEAD_INDEX_TO_LOC_FORMAT_OFFSET: usize = 50;
+const MAXP_NUM_GLYPHS_OFFSET: usize = 4;
+const HHEA_ASCENDER_OFFSET: usize = 4;
+const OS2_WEIGHT_CLASS_OFFSET: usize = 4;
+const OS2_FS_SELECTION_OFFSET: usize = 62;
+const OS2_TYPO_ASCENDER_OFFSET: usize = 68;
+const OS2_X_HEIGHT_OFFSET: usize = 86;
+const POST_UNDERLINE_POSITION_OFFSET: usize = 8;
+const POST_IS_FIXED_PITCH_OFFSET: usize = 12;
+
+const OS2_FS_SELECTION_ITALIC: u16 = 1 << 0;
+const OS2_FS_SELECTION_WWS: u16 = 1 << 8;
+const OS2_FS_SELECTION_OBLIQUE: u16 = 1 << 9;
+const HEAD_MAC_STYLE_ITALIC: u16 = 1 << 1;
+
+/// A loader's native font type. This loader has no separate native type, so it is the font
+/// itself.
+pub type NativeFont = Font;
+
+/// A cross-platform loader that parses fonts and rasterizes glyphs itself, without depending on
+/// any C library.
+///
+/// TrueType (`glyf`) and CFF outlines are supported, along with variable TrueType fonts. Glyphs
+/// are never hinted, and subpixel antialiasing is rendered as grayscale antialiasing.
+///
+/// The Cargo feature `loader-opentype` enables this loader, and `loader-opentype-default` makes
+/// it the default loader on every platform.
+#[derive(Clone)]
+pub struct Font {
+    font_data: Arc<Vec<u8>>,
+    font_index: u32,
+    tables: Arc<Vec<(u32, Range<usize>)>>,
+    cff: Option<Arc<Cff>>,
+    // The current position in the design space, in user coordinates and normalized to -1..1.
+    coordinates: Vec<f32>,
+    normalized_coordinates: Vec<f32>,
+    character_map: Arc<OnceLock<Vec<(char, u32)>>>,
+}
+
+impl Font {
+    /// Loads a font from raw font data (the contents of a `.ttf`/`.otf`/etc. file).
+    ///
+    /// If the data represents a collection (`.ttc`/`.otc`/etc.), `font_index` specifies the index
+    /// of the font to load from it. If the data represents a single font, pass 0 for `font_index`.
+    ///
+    /// As with FreeType, named instance *n* (counting from 1) of a variable font can be selected
+    /// by passing `n << 16 | font_index`.
+    pub fn from_bytes(font_data: Arc<Vec<u8>>, font_index: u32) -> Result<Font, FontLoadingError> {
+        let font_offset = font_offset(&font_data, font_index & 0xffff)?;
+        let tables = read_table_directory(&font_data, font_offset)?;
+
+        let mut font = Font {
+            font_data,
+            font_index,
+            tables: Arc::new(tables),
+            cff: None,
+            coordinates: vec![],
+            normalized_coordinates: vec![],
+            character_map: Arc::new(OnceLock::new()),
+        };
+        if font.table(HEAD_TABLE_TAG).is_none() || font.table(MAXP_TABLE_TAG).is_none() {
+            return Err(FontLoadingError::Parse);
+        }
+        if let Some(cff) = font.table(CFF_TABLE_TAG) {
+            font.cff = Some(Arc::new(Cff::new(cff).ok_or(FontLoadingError::Parse)?));
+        }
+
+        let axes = font.variation_axes();
+        font.coordinates = axes.iter().map(|axis| axis.default_value).collect();
+        let instance_index = font_index >> 16;
+        if instance_index > 0 {
+            let instance = font
+                .table(FVAR_TABLE_TAG)
+                .and_then(|fvar| read_named_instance(fvar, instance_index - 1))
+                .ok_or(FontLoadingError::NoSuchFontInCollection)?;
+            for (coordinate, (value, axis)) in font
+                .coordinates
+                .iter_mut()
+                .zip(instance.coordinates.iter().zip(axes.iter()))
+            {
+                *coordinate = utils::clamp(*value, axis.min_value, axis.max_value);
+            }
+        }
+        font.normalized_coordinates = font.normalize_coordinates(&axes);
+        Ok(font)
+    }
+
+    /// Loads a font from a `.ttf`/`.otf`/etc. file.
+    ///
+    /// If the file is a collection (`.ttc`/`.otc`/etc.), `font_index` specifies the index of the
+    /// font to load from it. If the file represents a single font, pass 0 for `font_index`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_file(file: &mut File, font_index: u32) -> Result<Font, FontLoadingError> {
+        file.seek(SeekFrom::Start(0))?;
+        let font_data = Arc::new(utils::slurp_file(file).map_err(FontLoadingError::Io)?);
+        Font::from_bytes(font_data, font_index)
+    }
+
+    /// Loads a font from the path to a `.ttf`/`.otf`/etc. file.
+    ///
+    /// If the file is a collection (`.ttc`/`.otc`/etc.), `font_index` specifies the index of the
+    /// font to load from it. If the file represents a single font, pass 0 for `font_index`.
+    #[inline]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path<P>(path: P, font_index: u32) -> Result<Font, FontLoadingError>
+    where
+        P: AsRef<Path>,
+    {
+        <Font as Loader>::from_path(path, font_index)
+    }
+
+    /// Creates a font from a native API handle.
+    ///
+    /// # Safety
+    ///
+    /// This loader's native fonts are fonts of its own, so this is always safe.
+    #[inline]
+    pub unsafe fn from_native_font(native_font: NativeFont) -> Font {
+        native_font
+    }
+
+    /// Loads the font pointed to by a handle.
+    #[inline]
+    pub fn from_handle(handle: &Handle) -> Result<Self, FontLoadingError> {
+        <Self as Loader>::from_handle(handle)
+    }
+
+    /// Determines whether a blob of raw font data represents a supported font, and, if so, what
+    /// type of font it is.
+    pub fn analyze_bytes(font_data: Arc<Vec<u8>>) -> Result<FileType, FontLoadingError> {
+        let mut reader = &font_data[..];
+        let tag = reader.read_u32::<BigEndian>()?;
+        if tag == TTC_TAG {
+            let _version = reader.read_u32::<BigEndian>()?;
+            return Ok(FileType::Collection(reader.read_u32::<BigEndian>()?));
+        }
+        if utils::SFNT_VERSIONS
+            .iter()
+            .any(|version| *version == tag.to_be_bytes())
+        {
+            return Ok(FileType::Single);
+        }
+        Err(FontLoadingError::UnknownFormat)
+    }
+
+    /// Determines whether a file represents a supported font, and, if so, what type of font it is.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn analyze_file(file: &mut File) -> Result<FileType, FontLoadingError> {
+        file.seek(SeekFrom::Start(0))?;
+        let font_data = Arc::new(utils::slurp_file(file).map_err(FontLoadingError::Io)?);
+        Font::analyze_bytes(font_data)
+    }
+
+    /// Determines whether a path points to a supported font, and, if so, what type of font it is.
+    #[inline]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn analyze_path<P>(path: P) -> Result<FileType, FontLoadingError>
+    where
+        P: AsRef<Path>,
+    {
+        <Self as Loader>::analyze_path(path)
+    }
+
+    /// Returns the wrapped native font handle, which is a copy of this font.
+    #[inline]
+    pub fn native_font(&self) -> NativeFont {
+        self.clone()
+    }
+
+    /// Returns the PostScript name of the font. This should be globally unique.
+    ///
+    /// Named instances of variable fonts use the PostScript name that `fvar` gives them. Other
+    /// positions in the design space get a name built from their coordinates, as described in
+    /// Adobe Technical Note #5902.
+    pub fn postscript_name(&self) -> Option<String> {
+        if let Some(instance) = self.named_instance()? {
+            if instance.coordinates == self.coordinates {
+                return match instance.postscript_name_id {
+                    Some(postscript_name_id) => self.localized_name(postscript_name_id, "en-US"),
+                    None => {
+                        let subfamily = self.localized_name(instance.subfamily_name_id, "en-US")?;
+                        Some(format!(
+                            "{}-{}",
+                            self.postscript_name_prefix(),
+                            subfamily.replace(|c: char| !c.is_ascii_alphanumeric(), "")
+                        ))
+                    }
+                };
+            }
+        }
+
+        let axes = self.variation_axes();
+        let mut name = self.postscript_name_prefix();
+        let mut at_default_instance = true;
+        for (axis, &value) in axes.iter().zip(self.coordinates.iter()) {
+            if value != axis.default_value {
+                let tag = String::from_utf8_lossy(&axis.tag.to_be_bytes()).into_owned();
+                name.push_str(&format!("_{}{}", format_coordinate(value), tag.trim_end()));
+                at_default_instance = false;
+            }
+        }
+        if at_default_instance {
+            return self.localized_name(name_id::POSTSCRIPT_NAME, "en-US");
+        }
+        Some(name)
+    }
+
+    /// Returns the full name of the font (also known as "display name" on macOS).
+    pub fn full_name(&self) -> String {
+        // Named instances share the `name` table of the default instance, so we build their full
+        // names from the family name and the instance's subfamily name instead.
+        if let Some(style_name) = self.named_instance_style_name() {
+            return format!("{} {}", self.family_name(), style_name);
+        }
+
+        self.localized_name(name_id::FULL_NAME, "en-US")
+            .unwrap_or_else(|| self.family_name())
+    }
+
+    /// Returns the name of the font family.
+    ///
+    /// As FreeType does, this prefers the WWS family name for fonts that set the WWS bit of
+    /// `fsSelection`, and otherwise the typographic family name.
+    pub fn family_name(&self) -> String {
+        let name_ids = if self.fs_selection().unwrap_or(0) & OS2_FS_SELECTION_WWS != 0 {
+            [
+                name_id::WWS_FAMILY,
+                name_id::TYPOGRAPHIC_FAMILY,
+                name_id::FAMILY,
+            ]
+        } else {
+            [
+                name_id::TYPOGRAPHIC_FAMILY,
+                name_id::FAMILY,
+                name_id::FAMILY,
+            ]
+        };
+        name_ids
+            .iter()
+            .find_map(|&name_id| self.localized_name(name_id, "en-US"))
+            .unwrap_or_default()
+    }
+
+    /// Returns the name of the font style within its family, such as "Bold Italic".
+    ///
+    /// This is the subfamily name (name ID 2) of the `name` table. Named instances of variable
+    /// fonts use the subfamily name that `fvar` gives them.
+    pub fn style_name(&self) -> String {
+        if let Some(style_name) = self.named_instance_style_name() {
+            return style_name;
+        }
+        self.localized_subfamily_name("en-US")
+            .or_else(|| {
+                [
+                    name_id::WWS_SUBFAMILY,
+                    name_id::TYPOGRAPHIC_SUBFAMILY,
+                    name_id::SUBFAMILY,
+                ]
+                .iter()
+                .find_map(|&name_id| self.localized_name(name_id, "en-US"))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the typographic family name (name ID 16), or the family name if the font has none.
+    #[inline]
+    pub fn typographic_family_name(&self) -> String {
+        <Self as Loader>::typographic_family_name(self)
+    }
+
+    /// Returns the typographic subfamily name (name ID 17), or the style name if the font has
+    /// none.
+    ///
+    /// Named instances of variable fonts use the name of the instance.
+    pub fn typographic_subfamily_name(&self) -> String {
+        self.named_instance_style_name()
+            .or_else(|| self.localized_name(name_id::TYPOGRAPHIC_SUBFAMILY, "en-US"))
+            .unwrap_or_else(|| self.style_name())
+    }
+
+    /// Returns the WWS family name (name ID 21), or the typographic family name if the font has
+    /// none.
+    #[inline]
+    pub fn wws_family_name(&self) -> String {
+        <Self as Loader>::wws_family_name(self)
+    }
+
+    /// Returns the WWS subfamily name (name ID 22), or the typographic subfamily name if the font
+    /// has none.
+    ///
+    /// Named instances of variable fonts use the name of the instance.
+    pub fn wws_subfamily_name(&self) -> String {
+        self.named_instance_style_name()
+            .or_else(|| self.localized_name(name_id::WWS_SUBFAMILY, "en-US"))
+            .unwrap_or_else(|| self.typographic_subfamily_name())
+    }
+
+    /// Returns all the records of the `name` table with the given name ID, decoded from every
+    /// platform and encoding that `font-kit` understands.
+    #[inline]
+    pub fn name_records(&self, name_id: u16) -> Vec<NameRecord> {
+        <Self as Loader>::name_records(self, name_id)
+    }
+
+    /// Returns the name with the given name ID in the language that best matches the given BCP 47
+    /// tag.
+    #[inline]
+    pub fn localized_name(&self, name_id: u16, language: &str) -> Option<String> {
+        <Self as Loader>::localized_name(self, name_id, language)
+    }
+
+    /// Returns the family name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    pub fn localized_family_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_family_name(self, language)
+    }
+
+    /// Returns the subfamily (style) name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    pub fn localized_subfamily_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_subfamily_name(self, language)
+    }
+
+    /// Returns the typographic family name in the language that best matches the given BCP 47
+    /// tag, falling back to the family name for fonts that don't have one.
+    #[inline]
+    pub fn localized_typographic_family_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_typographic_family_name(self, language)
+    }
+
+    /// Returns the full name in the language that best matches the given BCP 47 tag.
+    #[inline]
+    pub fn localized_full_name(&self, language: &str) -> Option<String> {
+        <Self as Loader>::localized_full_name(self, language)
+    }
+
+    /// Returns true if and only if the font is monospace (fixed-width).
+    pub fn is_monospace(&self) -> bool {
+        self.table(POST_TABLE_TAG)
+            .and_then(|post| post.get(POST_IS_FIXED_PITCH_OFFSET..))
+            .and_then(|mut reader| reader.read_u32::<BigEndian>().ok())
+            .is_some_and(|is_fixed_pitch| is_fixed_pitch != 0)
+    }
+
+    /// Returns the values of various font properties, corresponding to those defined in CSS.
+    pub fn properties(&self) -> Properties {
+        let os2_table = self.table(OS2_TABLE_TAG);
+        let fs_selection = self.fs_selection();
+        let style = match fs_selection {
+            Some(fs_selection) if fs_selection & OS2_FS_SELECTION_OBLIQUE != 0 => Style::Oblique,
+            Some(fs_selection) if fs_selection & OS2_FS_SELECTION_ITALIC != 0 => Style::Italic,
+            Some(_) => Style::Normal,
+            None if self.mac_style() & HEAD_MAC_STYLE_ITALIC != 0 => Style::Italic,
+            None => Style::Normal,
+        };
+        let (weight_class, width_class) = os2_table
+            .and_then(|os2| os2.get(OS2_WEIGHT_CLASS_OFFSET..))
+            .and_then(|mut reader| {
+                Some((
+                    reader.read_u16::<BigEndian>().ok()?,
+                    reader.read_u16::<BigEndian>().ok()?,
+                ))
+            })
+            .unzip();
+        let stretch = match width_class {
+            Some(width_class) if (1..=9).contains(&width_class) => {
+                Stretch(Stretch::MAPPING[(width_class as usize) - 1])
+            }
+            _ => Stretch::NORMAL,
+        };
+        let weight = match weight_class {
+            None => Weight::NORMAL,
+            Some(weight_class) => Weight(weight_class as f32),
+        };
+        let mut properties = Properties {
+            style,
+            stretch,
+            weight,
+        };
+
+        // The `OS/2` table only describes the default instance of a variable font, so the
+        // current coordinates take precedence.
+        let variations = self.variations();
+        for variation in &variations {
+            match variation.tag {
+                WEIGHT_AXIS => {
+                    properties.weight = Weight(utils::clamp(variation.value, 1.0, 1000.0))
+                }
+                WIDTH_AXIS => {
+                    let stretch = utils::clamp(variation.value / 100.0, 0.5, 2.0);
+                    properties.stretch = Stretch(stretch)
+                }
+                ITALIC_AXIS if variation.value >= 0.5 => properties.style = Style::Italic,
+                ITALIC_AXIS => properties.style = Style::Normal,
+                _ => {}
+            }
+        }
+        let slanted = variations
+            .iter()
+            .any(|variation| variation.tag == SLANT_AXIS && variation.value != 0.0);
+        if slanted && properties.style == Style::Normal {
+            properties.style = Style::Oblique
+        }
+
+        properties
+    }
+
+    /// Returns the number of named instances (such as "Bold Condensed") that this variable font
+    /// defines in its `fvar` table.
+    ///
+    /// Named instance *n* (counting from 1) can be opened as a face of its own by passing
+    /// `n << 16 | font_index` as the font index.
+    #[inline]
+    pub fn named_instance_count(&self) -> u32 {
+        self.table(FVAR_TABLE_TAG)
+            .and_then(|fvar| read_fvar_header(fvar).map(|header| header.instance_count as u32))
+            .unwrap_or(0)
+    }
+
+    /// Returns the usual glyph ID for a Unicode character.
+    ///
+    /// Be careful with this function; typographically correct character-to-glyph mapping must be
+    /// done using a *shaper* such as HarfBuzz. This function is only useful for best-effort simple
+    /// use cases like "what does character X look like on its own".
+    #[inline]
+    pub fn glyph_for_char(&self, character: char) -> Option<u32> {
+        let character_map = self.cached_character_map();
+        character_map
+            .binary_search_by_key(&character, |&(character, _)| character)
+            .ok()
+            .map(|index| character_map[index].1)
+    }
+
+    /// Returns every character that the font maps to a glyph, along with that glyph, sorted by
+    /// character.
+    #[inline]
+    pub fn character_map(&self) -> Vec<(char, u32)> {
+        self.cached_character_map().to_vec()
+    }
+
+    /// Returns the set of characters that the font maps to glyphs.
+    #[inline]
+    pub fn character_coverage(&self) -> CharacterCoverage {
+        <Self as Loader>::character_coverage(self)
+    }
+
+    /// Returns the characters that map to the given glyph, in ascending order.
+    #[inline]
+    pub fn chars_for_glyph(&self, glyph_id: u32) -> Vec<char> {
+        <Self as Loader>::chars_for_glyph(self, glyph_id)
+    }
+
+    /// Returns the glyph ID for the specified glyph name.
+    pub fn glyph_by_name(&self, name: &str) -> Option<u32> {
+        if let (Some(cff), Some(table)) = (&self.cff, self.table(CFF_TABLE_TAG)) {
+            if let Some(glyph_id) = cff.glyph_by_name(table, name) {
+                return Some(glyph_id);
+            }
+        }
+        self.table(POST_TABLE_TAG)
+            .and_then(|post| glyph_names::find_post_glyph(post, name))
+            .filter(|&glyph_id| glyph_id < self.glyph_count())
+    }
+
+    /// Returns the name of a glyph, or `None` if the glyph ID is out of range.
+    ///
+    /// Names come from the CFF charset or the `post` table where the font has them. Otherwise, a
+    /// name of the form `uniXXXX` is derived from the first character that maps to the glyph.
+    pub fn glyph_name(&self, glyph_id: u32) -> Option<String> {
+        if glyph_id >= self.glyph_count() {
+            return None;
+        }
+        if let (Some(cff), Some(table)) = (&self.cff, self.table(CFF_TABLE_TAG)) {
+            if let Some(name) = cff.glyph_name(table, glyph_id) {
+                return Some(name);
+            }
+        }
+        self.table(POST_TABLE_TAG)
+            .and_then(|post| glyph_names::read_post_glyph_name(post, glyph_id))
+            .or_else(|| {
+                glyph_names::synthesize_glyph_name(glyph_id, &self.chars_for_glyph(glyph_id))
+            })
+    }
+
+    /// Returns the number of glyphs in the font.
+    ///
+    /// Glyph IDs range from 0 inclusive to this value exclusive.
+    #[inline]
+    pub fn glyph_count(&self) -> u32 {
+        self.table(MAXP_TABLE_TAG)
+            .and_then(|maxp| maxp.get(MAXP_NUM_GLYPHS_OFFSET..))
+            .and_then(|mut reader| reader.read_u16::<BigEndian>().ok())
+            .unwrap_or(0) as u32
+    }
+
+    /// Sends the vector path for a glyph to a path builder, in font units.
+    ///
+    /// This loader doesn't support hinting, so `hinting_mode` is ignored.
+    pub fn outline<S>(
+        &self,
+        glyph_id: u32,
+        _: HintingOptions,
+        sink: &mut S,
+    ) -> Result<(), GlyphLoadingError>
+    where
+        S: OutlineSink,
+    {
+        if glyph_id >= self.glyph_count() {
+            return Err(GlyphLoadingError::NoSuchGlyph);
+        }
+        if let (Some(cff), Some(table)) = (&self.cff, self.table(CFF_TABLE_TAG)) {
+            return cff.outline(table, glyph_id, sink);
+        }
+        match self.glyph_tables() {
+            Some(glyph_tables) => {
+                glyph_tables
+                    .load(glyph_id, &self.normalized_coordinates)
+                    .ok_or(GlyphLoadingError::NoSuchGlyph)?
+                    .outline(sink);
+                Ok(())
+            }
+            // Fonts with only bitmap glyphs have no outlines to send.
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the boundaries of a glyph in font units.
+    ///
+    /// As with FreeType, this is the box that encloses every point of the outline, including
+    /// control points.
+    pub fn typographic_bounds(&self, glyph_id: u32) -> Result<RectF, GlyphLoadingError> {
+        if self.cff.is_none() {
+            if let Some(glyph_tables) = self.glyph_tables() {
+                return glyph_tables
+                    .load(glyph_id, &self.normalized_coordinates)
+                    .map(|glyph| glyph.control_box())
+                    .ok_or(GlyphLoadingError::NoSuchGlyph);
+            }
+        }
+
+        let mut builder = OutlineBuilder::new();
+        self.outline(glyph_id, HintingOptions::None, &mut builder)?;
+        let mut points = builder
+            .into_outline()
+            .contours
+            .into_iter()
+            .flat_map(|contour| contour.positions);
+        Ok(match points.next() {
+            Some(first) => points.fold(RectF::new(first, Vector2F::zero()), |rect, point| {
+                rect.union_point(point)
+            }),
+            None => RectF::default(),
+        })
+    }
+
+    /// Returns the distance from the origin of the glyph with the given ID to the next, in font
+    /// units.
+    pub fn advance(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
+        if glyph_id >= self.glyph_count() {
+            return Err(GlyphLoadingError::NoSuchGlyph);
+        }
+        if self.cff.is_none() {
+            if let Some(glyph_tables) = self.glyph_tables() {
+                return glyph_tables
+                    .load(glyph_id, &self.normalized_coordinates)
+                    .map(|glyph| Vector2F::new(glyph.advance(), 0.0))
+                    .ok_or(GlyphLoadingError::NoSuchGlyph);
+            }
+        }
+
+        let (hhea, hmtx) = (self.table(HHEA_TABLE_TAG), self.table(HMTX_TABLE_TAG));
+        let advance = hhea
+            .zip(hmtx)
+            .and_then(|(hhea, hmtx)| metrics::read_horizontal_metrics(hhea, hmtx, glyph_id))
+            .map_or(0, |(advance, _)| advance);
+        Ok(Vector2F::new(advance as f32, 0.0))
+    }
+
+    /// Returns the amount that the given glyph should be displaced from the origin.
+    ///
+    /// As with Core Text, this is the translation from the vertical origin of the glyph to its
+    /// horizontal origin, for use when setting text vertically.
+    pub fn origin(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
+        Ok(-self.vertical_origin(glyph_id)?)
+    }
+
+    /// Returns the distance from the origin of the glyph with the given ID to the next when text
+    /// is set vertically, in font units.
+    #[inline]
+    pub fn vertical_advance(&self, glyph_id: u32) -> Result<f32, GlyphLoadingError> {
+        <Self as Loader>::vertical_advance(self, glyph_id)
+    }
+
+    /// Returns the position of the origin used when the glyph with the given ID is set
+    /// vertically, in font units relative to its horizontal origin.
+    #[inline]
+    pub fn vertical_origin(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
+        <Self as Loader>::vertical_origin(self, glyph_id)
+    }
+
+    /// Retrieves the metrics that apply to the entire font when text is set vertically.
+    #[inline]
+    pub fn vertical_metrics(&self) -> VerticalMetrics {
+        <Self as Loader>::vertical_metrics(self)
+    }
+
+    /// Returns the adjustment to the advance of the left glyph when it is followed by the right
+    /// glyph, in font units.
+    #[inline]
+    pub fn kerning(&self, left_glyph_id: u32, right_glyph_id: u32) -> Vector2F {
+        <Self as Loader>::kerning(self, left_glyph_id, right_glyph_id)
+    }
+
+    /// Returns the kerning adjustment for each glyph of a run, in font units.
+    #[inline]
+    pub fn kerning_for_glyphs(&self, glyph_ids: &[u32]) -> Vec<Vector2F> {
+        <Self as Loader>::kerning_for_glyphs(self, glyph_ids)
+    }
+
+    /// Retrieves various metrics that apply to the entire font.
+    ///
+    /// These are computed as FreeType computes them: the ascent, descent, and line gap come from
+    /// the `hhea` table unless its ascent and descent are both zero, in which case they come from
+    /// the `OS/2` table.
+    pub fn metrics(&self) -> Metrics {
+        let head = self.table(HEAD_TABLE_TAG).unwrap_or_default();
+        let read_i16s = |table: Option<&[u8]>, offset: usize, count: usize| -> Vec<i16> {
+            let mut reader = table
+                .and_then(|table| table.get(offset..))
+                .unwrap_or_default();
+            (0..count)
+                .map(|_| reader.read_i16::<BigEndian>().unwrap_or(0))
+                .collect()
+        };
+
+        let os2 = self.table(OS2_TABLE_TAG);
+        let hhea = read_i16s(self.table(HHEA_TABLE_TAG), HHEA_ASCENDER_OFFSET, 3);
+        let (mut ascent, mut descent, mut line_gap) = (hhea[0], hhea[1], hhea[2]);
+        if ascent == 0 && descent == 0 && os2.is_some() {
+            let typo = read_i16s(os2, OS2_TYPO_ASCENDER_OFFSET, 5);
+            if typo[0] != 0 || typo[1] != 0 {
+                (ascent, descent, line_gap) = (typo[0], typo[1], typo[2]);
+            } else {
+                // `usWinAscent` and `usWinDescent` are unsigned, and the descent is positive.
+                let (win_ascent, win_descent) = (typo[3] as u16, typo[4] as u16);
+                (ascent, descent, line_gap) = (win_ascent as i16, -(win_descent as i16), 0);
+            }
+        }
+
+        let post = read_i16s(
+            self.table(POST_TABLE_TAG),
+            POST_UNDERLINE_POSITION_OFFSET,
+            2,
+        );
+        let os2_version = os2
+            .and_then(|mut reader| reader.read_u16::<BigEndian>().ok())
+            .unwrap_or(0);
+        let (x_height, cap_height) = if os2_version >= 2 {
+            let heights = read_i16s(os2, OS2_X_HEIGHT_OFFSET, 2);
+            (heights[0], heights[1])
+        } else {
+            (0, 0)
+        };
+
+        let bbox = read_i16s(Some(head), HEAD_BOUNDING_BOX_OFFSET, 4);
+        let bounding_box = RectI::from_points(
+            Vector2I::new(bbox[0] as i32, bbox[1] as i32),
+            Vector2I::new(bbox[2] as i32, bbox[3] as i32),
+        );
+
+        Metrics {
+            units_per_em: self.units_per_em() as u32,
+            ascent: ascent as f32,
+            descent: descent as f32,
+            line_gap: line_gap as f32,
+            underline_position: post[0] as f32,
+            underline_thickness: post[1] as f32,
+            cap_height: cap_height as f32,
+            x_height: x_height as f32,
+            bounding_box: bounding_box.to_f32(),
+        }
+    }
+
+    /// Returns the sizes at which the font provides bitmap glyphs, from its `CBLC`, `EBLC`, or
+    /// `sbix` table.
+    #[inline]
+    pub fn fixed_sizes(&self) -> Vec<FixedSize> {
+        bitmap::read_fixed_sizes(self)
+    }
+
+    /// Returns the axes of variation of this font, as described by its `fvar` table.
+    ///
+    /// If this font is not a variable font, the returned list is empty.
+    pub fn variation_axes(&self) -> Vec<VariationAxis> {
+        self.table(FVAR_TABLE_TAG)
+            .and_then(read_variation_axes)
+            .unwrap_or_default()
+    }
+
+    /// Returns the current position of this font along each of its axes of variation, in the
+    /// same order as `variation_axes()`.
+    ///
+    /// If this font is not a variable font, the returned list is empty.
+    pub fn variations(&self) -> Vec<Variation> {
+        self.variation_axes()
+            .iter()
+            .zip(self.coordinates.iter())
+            .map(|(axis, &value)| Variation::new(axis.tag, value))
+            .collect()
+    }
+
+    /// Returns a copy of this font positioned at the given coordinates in its design space.
+    ///
+    /// Axes not mentioned in `variations` keep their current values, and values outside the range
+    /// of an axis are clamped to it. Tags that don't name an axis of this font are ignored. The
+    /// outlines, advances, bounds, and rasterized glyphs of the returned font all reflect the new
+    /// coordinates; this font is left unchanged.
+    pub fn clone_with_variations(
+        &self,
+        variations: &[Variation],
+    ) -> Result<Font, FontLoadingError> {
+        let axes = self.variation_axes();
+        let mut font = self.clone();
+        for variation in variations {
+            match axes.iter().position(|axis| axis.tag == variation.tag) {
+                Some(axis_index) => {
+                    let axis = &axes[axis_index];
+                    font.coordinates[axis_index] =
+                        utils::clamp(variation.value, axis.min_value, axis.max_value);
+                }
+                None => warn!(
+                    "clone_with_variations(): font has no axis with tag {:#010x}",
+                    variation.tag
+                ),
+            }
+        }
+        font.normalized_coordinates = font.normalize_coordinates(&axes);
+        Ok(font)
+    }
+
+    /// Returns true if and only if the font loader can perform hinting in the requested way.
+    ///
+    /// This loader never hints, so only `HintingOptions::None` is supported.
+    #[inline]
+    pub fn supports_hinting_options(&self, hinting_options: HintingOptions, _: bool) -> bool {
+        hinting_options == HintingOptions::None
+    }
+
+    /// Returns the pixel boundaries that the glyph will take up when rendered using this loader's
+    /// rasterizer at the given size and origin.
+    #[inline]
+    pub fn raster_bounds(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<RectI, GlyphLoadingError> {
+        <Self as Loader>::raster_bounds(
+            self,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    /// Rasterizes a glyph to a canvas with the given size and origin.
+    ///
+    /// The outline is filled with the built-in scanline rasterizer. Subpixel antialiasing is not
+    /// supported, so `RasterizationOptions::SubpixelAa` produces grayscale coverage in all three
+    /// channels of an `Rgb24` canvas.
+    ///
+    /// Glyphs without outlines are drawn from their embedded bitmap image, if they have one. Only
+    /// the coverage of the image is kept unless the canvas has an RGBA format.
+    pub fn rasterize_glyph(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        _: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<(), GlyphLoadingError> {
+        let mut builder = OutlineBuilder::new();
+        self.outline(glyph_id, HintingOptions::None, &mut builder)?;
+        let outline = builder.into_outline();
+
+        if outline.contours.is_empty() {
+            if let Some(image) = self.embedded_image(glyph_id, point_size) {
+                self.rasterize_embedded_image(canvas, &image, point_size, transform);
+                return Ok(());
+            }
+        }
+
+        let scale = point_size / self.units_per_em() as f32;
+        let to_pixels = transform * Transform2F::from_scale(Vector2F::new(scale, -scale));
+        rasterizer::rasterize_outline(canvas, &outline, to_pixels, rasterization_options);
+        Ok(())
+    }
+
+    /// Rasterizes a glyph like `rasterize_glyph()`, with its coverage corrected by `gamma_lut` for
+    /// text drawn in `color`. Unlike `rasterize_glyph()`, this keeps what is already drawn.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn rasterize_glyph_with_gamma(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+        gamma_lut: &GammaLut,
+        color: RgbaColor,
+    ) -> Result<(), GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_with_gamma(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+            gamma_lut,
+            color,
+        )
+    }
+
+    /// Returns the border of a stroke along the outline of a glyph, as a new outline in font
+    /// units. The width of the stroke is in font units.
+    #[inline]
+    pub fn stroked_outline(
+        &self,
+        glyph_id: u32,
+        hinting_mode: HintingOptions,
+        stroke_style: &StrokeStyle,
+    ) -> Result<Outline, GlyphLoadingError> {
+        <Self as Loader>::stroked_outline(self, glyph_id, hinting_mode, stroke_style)
+    }
+
+    /// Returns the pixel boundaries that the stroke of the glyph will take up when rendered with
+    /// `rasterize_glyph_stroke()`. The width of the stroke is in pixels.
+    #[inline]
+    pub fn stroke_raster_bounds(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        stroke_style: &StrokeStyle,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<RectI, GlyphLoadingError> {
+        <Self as Loader>::stroke_raster_bounds(
+            self,
+            glyph_id,
+            point_size,
+            transform,
+            stroke_style,
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    /// Rasterizes the stroke of a glyph's outline, rather than filling it, to a canvas with the
+    /// given size and transform. The width of the stroke is in pixels.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn rasterize_glyph_stroke(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        stroke_style: &StrokeStyle,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<(), GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_stroke(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            stroke_style,
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    /// Computes a signed distance field for a glyph from its outline, in a new `Format::A8`
+    /// canvas. `spread` is the distance in pixels that the field covers on either side of the
+    /// edge.
+    #[inline]
+    pub fn rasterize_glyph_sdf(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        spread: f32,
+    ) -> Result<DistanceField, GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_sdf(self, glyph_id, point_size, spread)
+    }
+
+    /// Computes a multi-channel signed distance field for a glyph from its outline, in a new
+    /// `Format::Rgb24` canvas. The median of the three channels keeps corners sharp.
+    #[inline]
+    pub fn rasterize_glyph_msdf(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        spread: f32,
+    ) -> Result<DistanceField, GlyphLoadingError> {
+        <Self as Loader>::rasterize_glyph_msdf(self, glyph_id, point_size, spread)
+    }
+
+    /// Returns the color palettes defined in the font's `CPAL` table.
+    #[inline]
+    pub fn color_palettes(&self) -> Vec<ColorPalette> {
+        <Self as Loader>::color_palettes(self)
+    }
+
+    /// Returns the layers that make up the given glyph, if it is a version 0 `COLR` color glyph.
+    #[inline]
+    pub fn color_glyph_layers(&self, glyph_id: u32) -> Option<Vec<ColorLayer>> {
+        <Self as Loader>::color_glyph_layers(self, glyph_id)
+    }
+
+    /// Returns true if the given glyph is drawn with a version 1 `COLR` paint graph.
+    #[inline]
+    pub fn has_color_glyph_paint(&self, glyph_id: u32) -> bool {
+        <Self as Loader>::has_color_glyph_paint(self, glyph_id)
+    }
+
+    /// Returns the embedded bitmap image of the given glyph from the `CBDT`/`CBLC` or `sbix`
+    /// table, taken from the strike that best matches `point_size` pixels per em.
+    #[inline]
+    pub fn embedded_image(&self, glyph_id: u32, point_size: f32) -> Option<EmbeddedImage> {
+        <Self as Loader>::embedded_image(self, glyph_id, point_size)
+    }
+
+    /// Rasterizes a color glyph to an RGBA canvas with the given size and transform.
+    ///
+    /// Version 1 `COLR` paint graphs and version 0 `COLR` layers are composited over the canvas,
+    /// with colors from palette `palette_index` or `foreground_color`. Other glyphs are drawn from
+    /// their embedded bitmap image if they have one, and are otherwise filled with
+    /// `foreground_color`.
+    ///
+    /// Panics if the canvas format is not `Format::Rgba32`, `Format::Rgba32Straight`, or
+    /// `Format::Bgra32`.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn rasterize_color_glyph(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+        palette_index: usize,
+        foreground_color: RgbaColor,
+    ) -> Result<(), GlyphLoadingError> {
+        <Self as Loader>::rasterize_color_glyph(
+            self,
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+            palette_index,
+            foreground_color,
+        )
+    }
+
+    /// Returns true if the given glyph is described by a document in the font's `SVG ` table.
+    #[inline]
+    pub fn has_svg_glyph(&self, glyph_id: u32) -> bool {
+        <Self as Loader>::has_svg_glyph(self, glyph_id)
+    }
+
+    /// Returns the SVG document from the font's `SVG ` table that describes the given glyph,
+    /// decompressing it if necessary, along with the range of glyphs that the document covers.
+    #[inline]
+    pub fn svg_glyph_document(&self, glyph_id: u32) -> Option<SvgGlyphDocument> {
+        <Self as Loader>::svg_glyph_document(self, glyph_id)
+    }
+
+    /// Returns a handle to this font, if possible.
+    ///
+    /// This is useful if you want to open the font with a different loader.
+    ///
+    /// The handle refers to the same member of a collection and the same named instance of a
+    /// variable font as this font does, but coordinates set with `clone_with_variations()` are
+    /// not preserved.
+    #[inline]
+    pub fn handle(&self) -> Option<Handle> {
+        Some(Handle::from_memory(self.font_data.clone(), self.font_index))
+    }
+
+    /// Attempts to return the raw font data (contents of the font file).
+    ///
+    /// If this font is a member of a collection, this function returns the data for the entire
+    /// collection.
+    pub fn copy_font_data(&self) -> Option<Arc<Vec<u8>>> {
+        Some(self.font_data.clone())
+    }
+
+    /// Get font fallback results for the given text and locale.
+    ///
+    /// Note: this is currently just a stub implementation. This loader has no access to the fonts
+    /// installed on the system.
+    fn get_fallbacks(&self, text: &str, _locale: &str) -> FallbackResult<Font> {
+        warn!("unsupported");
+        FallbackResult {
+            fonts: Vec::new(),
+            valid_len: text.len(),
+        }
+    }
+
+    /// Returns the raw contents of the OpenType table with the given tag.
+    ///
+    /// Tags are four-character codes. A list of tags can be found in the [OpenType specification].
+    ///
+    /// [OpenType specification]: https://docs.microsoft.com/en-us/typography/opentype/spec/
+    #[inline]
+    pub fn load_font_table(&self, table_tag: u32) -> Option<Box<[u8]>> {
+        self.table(table_tag).map(Box::from)
+    }
+
+    fn table(&self, table_tag: u32) -> Option<&[u8]> {
+        self.tables
+            .iter()
+            .find(|(tag, _)| *tag == table_tag)
+            .map(|(_, range)| &self.font_data[range.clone()])
+    }
+
+    fn glyph_tables(&self) -> Option<GlyphTables<'_>> {
+        let index_to_loc_format = self
+            .table(HEAD_TABLE_TAG)?
+            .get(HEAD_INDEX_TO_LOC_FORMAT_OFFSET..)?
+            .read_i16::<BigEndian>()
+            .ok()?;
+        Some(GlyphTables {
+            glyf: self.table(GLYF_TABLE_TAG)?,
+            loca: self.table(LOCA_TABLE_TAG)?,
+            long_loca_offsets: index_to_loc_format != 0,
+            hhea: self.table(HHEA_TABLE_TAG)?,
+            hmtx: self.table(HMTX_TABLE_TAG)?,
+            gvar: self.table(GVAR_TABLE_TAG),
+        })
+    }
+
+    fn cached_character_map(&self) -> &[(char, u32)] {
+        self.character_map.get_or_init(|| {
+            self.table(cmap::CMAP_TABLE_TAG)
+                .map(|cmap| cmap::read_character_map(cmap, self.glyph_count()))
+                .unwrap_or_default()
+        })
+    }
+
+    fn units_per_em(&self) -> u16 {
+        self.table(HEAD_TABLE_TAG)
+            .and_then(|head| head.get(HEAD_UNITS_PER_EM_OFFSET..))
+            .and_then(|mut reader| reader.read_u16::<BigEndian>().ok())
+            .unwrap_or(0)
+    }
+
+    fn fs_selection(&self) -> Option<u16> {
+        self.table(OS2_TABLE_TAG)?
+            .get(OS2_FS_SELECTION_OFFSET..)?
+            .read_u16::<BigEndian>()
+            .ok()
+    }
+
+    fn mac_style(&self) -> u16 {
+        self.table(HEAD_TABLE_TAG)
+            .and_then(|head| head.get(HEAD_MAC_STYLE_OFFSET..))
+            .and_then(|mut reader| reader.read_u16::<BigEndian>().ok())
+            .unwrap_or(0)
+    }
+
+    /// Returns the named instance that this face was opened as, `Some(None)` if it wasn't opened
+    /// as one, or `None` if the instance can't be read.
+    fn named_instance(&self) -> Option<Option<NamedInstance>> {
+        match self.font_index >> 16 {
+            0 => Some(None),
+            instance_index => {
+                read_named_instance(self.table(FVAR_TABLE_TAG)?, instance_index - 1).map(Some)
+            }
+        }
+    }
+
+    fn postscript_name_prefix(&self) -> String {
+        self.localized_name(name_id::VARIATIONS_POSTSCRIPT_NAME_PREFIX, "en-US")
+            .unwrap_or_else(|| self.typographic_family_name())
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "")
+    }
+
+    /// Returns the subfamily name of the named instance that this face was opened as, if any.
+    ///
+    /// Named instances share the `name` table of the default instance, but `fvar` gives each of
+    /// them a subfamily name of its own.
+    fn named_instance_style_name(&self) -> Option<String> {
+        let instance = self.named_instance()??;
+        self.localized_name(instance.subfamily_name_id, "en-US")
+    }
+
+    // Maps the current coordinates to the range -1..1 and through the `avar` table, as `gvar`
+    // expects.
+    fn normalize_coordinates(&self, axes: &[VariationAxis]) -> Vec<f32> {
+        let segment_maps = self.table(AVAR_TABLE_TAG).and_then(read_avar_segment_maps);
+        axes.iter()
+            .zip(self.coordinates.iter())
+            .enumerate()
+            .map(|(axis_index, (axis, &value))| {
+                let normalized =
+                    if value < axis.default_value && axis.min_value < axis.default_value {
+                        (value - axis.default_value) / (axis.default_value - axis.min_value)
+                    } else if value > axis.default_value && axis.max_value > axis.default_value {
+                        (value - axis.default_value) / (axis.max_value - axis.default_value)
+                    } else {
+                        0.0
+                    };
+                let normalized = utils::clamp(normalized, -1.0, 1.0);
+                match segment_maps
+                    .as_ref()
+                    .and_then(|segment_maps| segment_maps.get(axis_index))
+                {
+                    Some(segment_map) => map_avar_segments(segment_map, normalized),
+                    None => normalized,
+                }
+            })
+            .collect()
+    }
+
+    fn rasterize_embedded_image(
+        &self,
+        canvas: &mut Canvas,
+        image: &EmbeddedImage,
+        point_size: f32,
+        transform: Transform2F,
+    ) {
+        let foreground_color = RgbaColor::new(0, 0, 0, 255);
+        if canvas.format == Format::Rgba32 {
+            bitmap::rasterize_embedded_image(
+                canvas,
+                image,
+                point_size,
+                transform,
+                foreground_color,
+            );
+            return;
+        }
+
+        // Draw the image on its own, then copy the pixels that it covers into the canvas.
+        let mut image_canvas = Canvas::new(canvas.size, Format::Rgba32);
+        if !bitmap::rasterize_embedded_image(
+            &mut image_canvas,
+            image,
+            point_size,
+            transform,
+            foreground_color,
+        ) {
+            return;
+        }
+        let scale = Vector2F::splat(point_size) / image.strike.pixels_per_em.to_f32();
+        let image_rect = RectF::new(image.origin.to_f32() * scale, image.size.to_f32() * scale);
+        let device_rect = match (transform * image_rect)
+            .round_out()
+            .to_i32()
+            .intersection(RectI::new(Vector2I::zero(), canvas.size))
+        {
+            Some(device_rect) => device_rect,
+            None => return,
+        };
+
+        let bytes_per_pixel = Format::Rgba32.bytes_per_pixel() as usize;
+        let (left, width) = (device_rect.min_x() as usize, device_rect.width() as usize);
+        let pixels: Vec<u8> = (device_rect.min_y()..device_rect.max_y())
+            .flat_map(|y| {
+                let start = y as usize * image_canvas.stride + left * bytes_per_pixel;
+                image_canvas.pixels[start..(start + width * bytes_per_pixel)].iter()
+            })
+            .cloned()
+            .collect();
+        canvas.blit_from(
+            device_rect.origin(),
+            &pixels,
+            device_rect.size(),
+            width * bytes_per_pixel,
+            Format::Rgba32,
+        );
+    }
+}
+
+impl Debug for Font {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), fmt::Error> {
+        self.family_name().fmt(fmt)
+    }
+}
+
+impl Loader for Font {
+    type NativeFont = NativeFont;
+
+    #[inline]
+    fn from_bytes(font_data: Arc<Vec<u8>>, font_index: u32) -> Result<Self, FontLoadingError> {
+        Font::from_bytes(font_data, font_index)
+    }
+
+    #[inline]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn from_file(file: &mut File, font_index: u32) -> Result<Font, FontLoadingError> {
+        Font::from_file(file, font_index)
+    }
+
+    #[inline]
+    fn analyze_bytes(font_data: Arc<Vec<u8>>) -> Result<FileType, FontLoadingError> {
+        Font::analyze_bytes(font_data)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn analyze_file(file: &mut File) -> Result<FileType, FontLoadingError> {
+        Font::analyze_file(file)
+    }
+
+    #[inline]
+    fn native_font(&self) -> Self::NativeFont {
+        self.native_font()
+    }
+
+    #[inline]
+    unsafe fn from_native_font(native_font: Self::NativeFont) -> Self {
+        Font::from_native_font(native_font)
+    }
+
+    #[inline]
+    fn postscript_name(&self) -> Option<String> {
+        self.postscript_name()
+    }
+
+    #[inline]
+    fn full_name(&self) -> String {
+        self.full_name()
+    }
+
+    #[inline]
+    fn family_name(&self) -> String {
+        self.family_name()
+    }
+
+    #[inline]
+    fn style_name(&self) -> String {
+        self.style_name()
+    }
+
+    #[inline]
+    fn typographic_subfamily_name(&self) -> String {
+        self.typographic_subfamily_name()
+    }
+
+    #[inline]
+    fn wws_subfamily_name(&self) -> String {
+        self.wws_subfamily_name()
+    }
+
+    #[inline]
+    fn is_monospace(&self) -> bool {
+        self.is_monospace()
+    }
+
+    #[inline]
+    fn properties(&self) -> Properties {
+        self.properties()
+    }
+
+    #[inline]
+    fn named_instance_count(&self) -> u32 {
+        self.named_instance_count()
+    }
+
+    #[inline]
+    fn glyph_for_char(&self, character: char) -> Option<u32> {
+        self.glyph_for_char(character)
+    }
+
+    #[inline]
+    fn character_map(&self) -> Vec<(char, u32)> {
+        self.character_map()
+    }
+
+    #[inline]
+    fn glyph_by_name(&self, name: &str) -> Option<u32> {
+        self.glyph_by_name(name)
+    }
+
+    #[inline]
+    fn glyph_name(&self, glyph_id: u32) -> Option<String> {
+        self.glyph_name(glyph_id)
+    }
+
+    #[inline]
+    fn glyph_count(&self) -> u32 {
+        self.glyph_count()
+    }
+
+    #[inline]
+    fn outline<S>(
+        &self,
+        glyph_id: u32,
+        hinting_mode: HintingOptions,
+        sink: &mut S,
+    ) -> Result<(), GlyphLoadingError>
+    where
+        S: OutlineSink,
+    {
+        self.outline(glyph_id, hinting_mode, sink)
+    }
+
+    #[inline]
+    fn typographic_bounds(&self, glyph_id: u32) -> Result<RectF, GlyphLoadingError> {
+        self.typographic_bounds(glyph_id)
+    }
+
+    #[inline]
+    fn advance(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
+        self.advance(glyph_id)
+    }
+
+    #[inline]
+    fn origin(&self, origin: u32) -> Result<Vector2F, GlyphLoadingError> {
+        self.origin(origin)
+    }
+
+    #[inline]
+    fn metrics(&self) -> Metrics {
+        self.metrics()
+    }
+
+    #[inline]
+    fn handle(&self) -> Option<Handle> {
+        self.handle()
+    }
+
+    #[inline]
+    fn copy_font_data(&self) -> Option<Arc<Vec<u8>>> {
+        self.copy_font_data()
+    }
+
+    #[inline]
+    fn supports_hinting_options(
+        &self,
+        hinting_options: HintingOptions,
+        for_rasterization: bool,
+    ) -> bool {
+        self.supports_hinting_options(hinting_options, for_rasterization)
+    }
+
+    #[inline]
+    fn rasterize_glyph(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<(), GlyphLoadingError> {
+        self.rasterize_glyph(
+            canvas,
+            glyph_id,
+            point_size,
+            transform,
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    #[inline]
+    fn fixed_sizes(&self) -> Vec<FixedSize> {
+        self.fixed_sizes()
+    }
+
+    #[inline]
+    fn get_fallbacks(&self, text: &str, locale: &str) -> FallbackResult<Self> {
+        self.get_fallbacks(text, locale)
+    }
+
+    #[inline]
+    fn load_font_table(&self, table_tag: u32) -> Option<Box<[u8]>> {
+        self.load_font_table(table_tag)
+    }
+}
+
+/// A named instance from the `fvar` table.
+struct NamedInstance {
+    subfamily_name_id: u16,
+    coordinates: Vec<f32>,
+    postscript_name_id: Option<u16>,
+}
+
+struct FvarHeader {
+    axes_offset: usize,
+    axis_count: usize,
+    axis_size: usize,
+    instance_count: usize,
+    instance_size: usize,
+}
+
+// Returns the offset of the table directory of the font with the given index.
+fn font_offset(font_data: &[u8], font_index: u32) -> Result<usize, FontLoadingError> {
+    let mut reader = font_data;
+    if reader.read_u32::<BigEndian>()? != TTC_TAG {
+        return match font_index {
+            0 => Ok(0),
+            _ => Err(FontLoadingError::NoSuchFontInCollection),
+        };
+    }
+    let _version = reader.read_u32::<BigEndian>()?;
+    let font_count = reader.read_u32::<BigEndian>()?;
+    if font_index >= font_count {
+        return Err(FontLoadingError::NoSuchFontInCollection);
+    }
+    let mut reader = reader
+        .get((font_index as usize * 4)..)
+        .ok_or(FontLoadingError::Parse)?;
+    Ok(reader.read_u32::<BigEndian>()? as usize)
+}
+
+fn read_table_directory(
+    font_data: &[u8],
+    offset: usize,
+) -> Result<Vec<(u32, Range<usize>)>, FontLoadingError> {
+    let mut reader = font_data.get(offset..).ok_or(FontLoadingError::Parse)?;
+    let sfnt_version = reader.read_u32::<BigEndian>()?;
+    if !utils::SFNT_VERSIONS
+        .iter()
+        .any(|version| *version == sfnt_version.to_be_bytes())
+    {
+        return Err(FontLoadingError::UnknownFormat);
+    }
+    let table_count = reader.read_u16::<BigEndian>()? as usize;
+    let records = reader
+        .get(6..)
+        .and_then(|records| records.get(..(table_count * TABLE_RECORD_LENGTH)))
+        .ok_or(FontLoadingError::Parse)?;
+
+    let mut tables = Vec::with_capacity(table_count);
+    for mut record in records.chunks(TABLE_RECORD_LENGTH) {
+        let tag = record.read_u32::<BigEndian>()?;
+        let _checksum = record.read_u32::<BigEndian>()?;
+        let start = record.read_u32::<BigEndian>()? as usize;
+        let end = start + record.read_u32::<BigEndian>()? as usize;
+        if end > font_data.len() {
+            return Err(FontLoadingError::Parse);
+        }
+        tables.push((tag, start..end));
+    }
+    Ok(tables)
+}
+
+fn read_fvar_header(fvar: &[u8]) -> Option<FvarHeader> {
+    let mut reader = fvar.get(4..)?;
+    let axes_offset = reader.read_u16::<BigEndian>().ok()? as usize;
+    let _reserved = reader.read_u16::<BigEndian>().ok()?;
+    Some(FvarHeader {
+        axes_offset,
+        axis_count: reader.read_u16::<BigEndian>().ok()? as usize,
+        axis_size: reader.read_u16::<BigEndian>().ok()? as usize,
+        instance_count: reader.read_u16::<BigEndian>().ok()? as usize,
+        instance_size: reader.read_u16::<BigEndian>().ok()? as usize,
+    })
+}
+
+fn read_variation_axes(fvar: &[u8]) -> Option<Vec<VariationAxis>> {
+    let header = read_fvar_header(fvar)?;
+    if header.axis_size < FVAR_AXIS_RECORD_LENGTH {
+        return None;
+    }
+    (0..header.axis_count)
+        .map(|axis_index| {
+            let mut reader = fvar.get((header.axes_offset + axis_index * header.axis_size)..)?;
+            let tag = reader.read_u32::<BigEndian>().ok()?;
+            let min_value = read_fixed(&mut reader)?;
+            let default_value = read_fixed(&mut reader)?;
+            let max_value = read_fixed(&mut reader)?;
+            let _flags = reader.read_u16::<BigEndian>().ok()?;
+            Some(VariationAxis {
+                tag,
+                min_value,
+                default_value,
+                max_value,
+                name_id: reader.read_u16::<BigEndian>().ok()?,
+            })
+        })
+        .collect()
+}
+
+fn read_named_instance(fvar: &[u8], instance_index: u32) -> Option<NamedInstance> {
+    let header = read_fvar_header(fvar)?;
+    if instance_index as usize >= header.instance_count {
+        return None;
+    }
+    let instances_offset = header.axes_offset + header.axis_count * header.axis_size;
+    let mut reader = fvar
+        .get((instances_offset + instance_index as usize * header.instance_size)..)?
+        .get(..header.instance_size)?;
+    let subfamily_name_id = reader.read_u16::<BigEndian>().ok()?;
+    let _flags = reader.read_u16::<BigEndian>().ok()?;
+    let coordinates = (0..header.axis_count)
+        .map(|_| read_fixed(&mut reader))
+        .collect::<Option<Vec<_>>>()?;
+    // The PostScript name ID is optional, and present only if the records are large enough.
+    let postscript_name_id = reader
+        .read_u16::<BigEndian>()
+        .ok()
+        .filter(|&name_id| name_id != 0xffff);
+    Some(NamedInstance {
+        subfamily_name_id,
+        coordinates,
+        postscript_name_id,
+    })
+}
+
+// Reads the segment maps of the `avar` table, one list of `(from, to)` pairs per axis.
+fn read_avar_segment_maps(avar: &[u8]) -> Option<Vec<Vec<(f32, f32)>>> {
+    let mut reader = avar.get(6..)?;
+    let axis_count = reader.read_u16::<BigEndian>().ok()?;
+    (0..axis_count)
+        .map(|_| {
+            let map_count = reader.read_u16::<BigEndian>().ok()?;
+            (0..map_count)
+                .map(|_| Some((read_f2dot14(&mut reader)?, read_f2dot14(&mut reader)?)))
+                .collect()
+        })
+        .collect()
+}
+
+fn map_avar_segments(segment_map: &[(f32, f32)], value: f32) -> f32 {
+    let mut previous = match segment_map.first() {
+        Some(&first) => first,
+        None => return value,
+    };
+    if value <= previous.0 {
+        return previous.1;
+    }
+    for &(from, to) in &segment_map[1..] {
+        if value <= from {
+            if from == previous.0 {
+                return to;
+            }
+            return previous.1 + (to - previous.1) * (value - previous.0) / (from - previous.0);
+        }
+        previous = (from, to);
+    }
+    previous.1
+}
+
+// Formats a design space coordinate for a PostScript name, with at most five decimal places.
+fn format_coordinate(value: f32) -> String {
+    let value = format!("{:.5}", value);
+    value.trim_end_matches('0').trim_end_matches('.').to_owned()
+}
+
+fn read_fixed(reader: &mut &[u8]) -> Option<f32> {
+    Some(reader.read_i32::<BigEndian>().ok()? as f32 / 65536.0)
+}
+
+fn read_f2dot14(reader: &mut &[u8]) -> Option<f32> {
+    Some(reader.read_i16::<BigEndian>().ok()? as f32 / 16384.0)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::canvas::{Canvas, Format, RasterizationOptions};
+    use crate::glyph_names::{self, POST_TABLE_TAG};
+    use crate::hinting::HintingOptions;
+    use crate::loaders::opentype::Font;
+    use crate::outline::OutlineBuilder;
+    use crate::variations::{Variation, VariationAxis, WEIGHT_AXIS, WIDTH_AXIS};
+    use pathfinder_geometry::rect::RectF;
+    use pathfinder_geometry::transform2d::Transform2F;
+    use pathfinder_geometry::vector::{Vector2F, Vector2I};
+
+    static OTF_FONT_PATH: &str = "resources/tests/eb-garamond/EBGaramond12-Regular.otf";
+    static TTF_FONT_PATH: &str = "resources/tests/eb-garamond/EBGaramond12-Regular.ttf";
+    static VARIABLE_FONT_PATH: &str = "resources/tests/font-kit-variable/FontKitVariable.ttf";
+
+    #[test]
+    fn cff_and_truetype_outlines_agree() {
+        // The OTF and TTF versions of EB Garamond have the same design, so their outlines should
+        // cover about the same area even though one uses cubic curves and the other quadratics.
+        let otf = Font::from_path(OTF_FONT_PATH, 0).unwrap();
+        let ttf = Font::from_path(TTF_FONT_PATH, 0).unwrap();
+        let bounds = |font: &Font| {
+            let glyph_id = font.glyph_for_char('g').unwrap();
+            let mut builder = OutlineBuilder::new();
+            font.outline(glyph_id, HintingOptions::None, &mut builder)
+                .unwrap();
+            assert!(!builder.into_outline().contours.is_empty());
+            font.typographic_bounds(glyph_id).unwrap()
+        };
+        let (otf_bounds, ttf_bounds) = (bounds(&otf), bounds(&ttf));
+        assert!((otf_bounds.origin() - ttf_bounds.origin()).length() < 8.0);
+        assert!((otf_bounds.size() - ttf_bounds.size()).length() < 8.0);
+    }
+
+    #[test]
+    fn rasterize_glyph() {
+        let font = Font::from_path(TTF_FONT_PATH, 0).unwrap();
+        let glyph_id = font.glyph_for_char('l').unwrap();
+        let mut canvas = Canvas::new(Vector2I::splat(64), Format::A8);
+        font.rasterize_glyph(
+            &mut canvas,
+            glyph_id,
+            48.0,
+            Transform2F::from_translation(Vector2F::new(16.0, 48.0)),
+            HintingOptions::None,
+            RasterizationOptions::GrayscaleAa,
+        )
+        .unwrap();
+
+        // The stem of the `l` is covered, and nothing reaches far below the baseline.
+        let row = |y: usize| &canvas.pixels[(y * canvas.stride)..((y + 1) * canvas.stride)];
+        assert!(row(32).contains(&0xff));
+        assert!((52..64).all(|y| row(y).iter().all(|&value| value == 0)));
+    }
+
+    #[test]
+    fn glyph_names_match_post_table() {
+        let font = Font::from_path(TTF_FONT_PATH, 0).unwrap();
+        let post = font.load_font_table(POST_TABLE_TAG).unwrap();
+        for glyph_id in 0..font.glyph_count() {
+            let name = glyph_names::read_post_glyph_name(&post, glyph_id);
+            assert_eq!(name, font.glyph_name(glyph_id));
+            assert_eq!(font.glyph_by_name(&name.unwrap()), Some(glyph_id));
+        }
+    }
+
+    #[test]
+    fn get_cff_glyph_names() {
+        let font = Font::from_path(OTF_FONT_PATH, 0).unwrap();
+        let glyph_id = font.glyph_for_char('a').unwrap();
+        assert_eq!(font.glyph_name(glyph_id).unwrap(), "a");
+        assert_eq!(font.glyph_by_name("a"), Some(glyph_id));
+        assert_eq!(font.glyph_name(font.glyph_count()), None);
+    }
+
+    #[test]
+    fn get_named_instance_style_names() {
+        let font = Font::from_path(VARIABLE_FONT_PATH, 0).unwrap();
+        assert_eq!(font.style_name(), "Regular");
+        assert_eq!(font.typographic_subfamily_name(), "Regular");
+
+        // Named instances take their style names from `fvar`, not the shared `name` table.
+        let font = Font::from_path(VARIABLE_FONT_PATH, 4 << 16).unwrap();
+        assert_eq!(font.style_name(), "Bold Condensed");
+        assert_eq!(font.typographic_subfamily_name(), "Bold Condensed");
+        assert_eq!(font.wws_subfamily_name(), "Bold Condensed");
+        assert_eq!(font.typographic_family_name(), "Font Kit Variable");
+        assert_eq!(
+            font.variations(),
+            [
+                Variation::new(WEIGHT_AXIS, 700.0),
+                Variation::new(WIDTH_AXIS, 75.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn get_variation_axes() {
+        let font = Font::from_path(VARIABLE_FONT_PATH, 0).unwrap();
+        assert_eq!(
+            font.variation_axes(),
+            [
+                VariationAxis {
+                    tag: WEIGHT_AXIS,
+                    min_value: 100.0,
+                    default_value: 400.0,
+                    max_value: 900.0,
+                    name_id: 256,
+                },
+                VariationAxis {
+                    tag: WIDTH_AXIS,
+                    min_value: 50.0,
+                    default_value: 100.0,
+                    max_value: 100.0,
+                    name_id: 257,
+                },
+            ]
+        );
+        assert_eq!(
+            font.variations(),
+            [
+                Variation::new(WEIGHT_AXIS, 400.0),
+                Variation::new(WIDTH_AXIS, 100.0)
+            ]
+        );
+
+        let font = Font::from_path(OTF_FONT_PATH, 0).unwrap();
+        assert!(font.variation_axes().is_empty());
+        assert!(font.variations().is_empty());
+    }
+
+    #[test]
+    fn clone_with_variations() {
+        let font = Font::from_path(VARIABLE_FONT_PATH, 0).unwrap();
+        let glyph_id = font.glyph_for_char('A').unwrap();
+
+        let bold = font
+            .clone_with_variations(&[Variation::new(WEIGHT_AXIS, 900.0)])
+            .unwrap();
+        assert_eq!(bold.advance(glyph_id).unwrap(), Vector2F::new(800.0, 0.0));
+        assert_eq!(
+            bold.typographic_bounds(glyph_id).unwrap(),
+            RectF::new(Vector2F::new(100.0, 0.0), Vector2F::new(600.0, 700.0))
+        );
+
+        // Out-of-range values are clamped, and other axes keep their values.
+        let condensed = bold
+            .clone_with_variations(&[Variation::new(WIDTH_AXIS, 0.0)])
+            .unwrap();
+        assert_eq!(
+            condensed.variations(),
+            [
+                Variation::new(WEIGHT_AXIS, 900.0),
+                Variation::new(WIDTH_AXIS, 50.0)
+            ]
+        );
+        assert_eq!(
+            condensed.advance(glyph_id).unwrap(),
+            Vector2F::new(600.0, 0.0)
+        );
+
+        // The original font is unaffected.
+        assert_eq!(font.advance(glyph_id).unwrap(), Vector2F::new(600.0, 0.0));
+    }
+}
@@ -12,8 +12,22 @@
 //!
 //! For OpenType fonts, these mostly come from the `OS/2` table.
 
+use byteorder::{BigEndian, ReadBytesExt};
 use pathfinder_geometry::rect::RectF;
 
+#[cfg(feature = "loader-opentype")]
+pub(crate) const HHEA_TABLE_TAG: u32 = 0x68686561;
+#[cfg(feature = "loader-opentype")]
+pub(crate) const HMTX_TABLE_TAG: u32 = 0x686d7478;
+pub(crate) const VHEA_TABLE_TAG: u32 = 0x76686561;
+pub(crate) const VMTX_TABLE_TAG: u32 = 0x766d7478;
+pub(crate) const VORG_TABLE_TAG: u32 = 0x564f5247;
+
+const VHEA_ASCENT_OFFSET: usize = 4;
+// The `hhea` and `vhea` tables share a layout, so this is the same for both.
+const NUMBER_OF_LONG_METRICS_OFFSET: usize = 34;
+const VORG_METRICS_OFFSET: usize = 8;
+
 /// Various metrics that apply to the entire font.
 ///
 /// For OpenType fonts, these mostly come from the `OS/2` table.
@@ -22,6 +36,9 @@ pub struct Metrics {
     /// The number of font units per em.
     ///
     /// Font sizes are usually expressed in pixels per em; e.g. `12px` means 12 pixels per em.
+    ///
+    /// Bitmap-only fonts (BDF, PCF, and Windows FNT) have no font units of their own; for them,
+    /// this is the pixels-per-em size of the first strike, and all metrics are in its pixels.
     pub units_per_em: u32,
 
     /// The maximum amount the font rises above the baseline, in font units.
@@ -56,3 +73,99 @@ pub struct Metrics {
     /// This corresponds to the `xMin`/`xMax`/`yMin`/`yMax` values in the OpenType `head` table.
     pub bounding_box: RectF,
 }
+
+/// Metrics that apply to the entire font when text is set vertically.
+///
+/// For OpenType fonts, these come from the `vhea` table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VerticalMetrics {
+    /// The distance from the vertical center line to the right edge of the ideographic em box,
+    /// in font units.
+    pub ascent: f32,
+
+    /// The distance from the vertical center line to the left edge of the ideographic em box, in
+    /// font units.
+    ///
+    /// Like `Metrics::descent`, this is typically negative.
+    pub descent: f32,
+}
+
+/// Returns the vertical ascent and descent from the given `vhea` table.
+pub(crate) fn read_vertical_ascent_and_descent(vhea: &[u8]) -> Option<VerticalMetrics> {
+    let mut reader = vhea.get(VHEA_ASCENT_OFFSET..)?;
+    let ascent = reader.read_i16::<BigEndian>().ok()?;
+    let descent = reader.read_i16::<BigEndian>().ok()?;
+    Some(VerticalMetrics {
+        ascent: ascent as f32,
+        descent: descent as f32,
+    })
+}
+
+/// Returns the advance width and left side bearing of the given glyph from the `hhea` and `hmtx`
+/// tables.
+#[cfg(feature = "loader-opentype")]
+pub(crate) fn read_horizontal_metrics(
+    hhea: &[u8],
+    hmtx: &[u8],
+    glyph_id: u32,
+) -> Option<(u16, i16)> {
+    read_long_metrics(hhea, hmtx, glyph_id)
+}
+
+/// Returns the advance height and top side bearing of the given glyph from the `vhea` and `vmtx`
+/// tables.
+pub(crate) fn read_vertical_metrics(vhea: &[u8], vmtx: &[u8], glyph_id: u32) -> Option<(u16, i16)> {
+    read_long_metrics(vhea, vmtx, glyph_id)
+}
+
+fn read_long_metrics(header: &[u8], metrics: &[u8], glyph_id: u32) -> Option<(u16, i16)> {
+    let number_of_long_metrics = header
+        .get(NUMBER_OF_LONG_METRICS_OFFSET..)?
+        .read_u16::<BigEndian>()
+        .ok()? as usize;
+    if number_of_long_metrics == 0 {
+        return None;
+    }
+
+    // Glyphs past the last long metric share its advance and only store their side bearing.
+    let glyph_id = glyph_id as usize;
+    let long_metric_index = glyph_id.min(number_of_long_metrics - 1);
+    let advance = metrics
+        .get(long_metric_index * 4..)?
+        .read_u16::<BigEndian>()
+        .ok()?;
+    let side_bearing_offset = if glyph_id < number_of_long_metrics {
+        glyph_id * 4 + 2
+    } else {
+        number_of_long_metrics * 4 + (glyph_id - number_of_long_metrics) * 2
+    };
+    let side_bearing = metrics
+        .get(side_bearing_offset..)?
+        .read_i16::<BigEndian>()
+        .ok()?;
+    Some((advance, side_bearing))
+}
+
+/// Returns the y coordinate of the vertical origin of the given glyph from the `VORG` table.
+pub(crate) fn read_vertical_origin_y(vorg: &[u8], glyph_id: u32) -> Option<i16> {
+    let mut reader = vorg.get(4..)?;
+    let default_vertical_origin_y = reader.read_i16::<BigEndian>().ok()?;
+    let metric_count = reader.read_u16::<BigEndian>().ok()? as usize;
+
+    // The metrics are sorted by glyph ID.
+    let (mut low, mut high) = (0, metric_count);
+    while low < high {
+        let mid = (low + high) / 2;
+        let mut metric = vorg.get(VORG_METRICS_OFFSET + mid * 4..)?;
+        let metric_glyph_id = metric.read_u16::<BigEndian>().ok()? as u32;
+        if metric_glyph_id == glyph_id {
+            return metric.read_i16::<BigEndian>().ok();
+        }
+        if metric_glyph_id < glyph_id {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    Some(default_vertical_origin_y)
+}
@@ -0,0 +1,438 @@
+// font-kit/src/names.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Localized names from the OpenType `name` table.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use encoding_rs::{Encoding, BIG5, EUC_KR, GBK, MACINTOSH, SHIFT_JIS, X_MAC_CYRILLIC};
+
+pub(crate) const NAME_TABLE_TAG: u32 = 0x6e616d65;
+
+const NAME_RECORD_SIZE: usize = 12;
+const FIRST_LANGUAGE_TAG_ID: u16 = 0x8000;
+
+/// The platform IDs of `name` records.
+pub mod platform_id {
+    /// The Unicode platform.
+    pub const UNICODE: u16 = 0;
+    /// The Macintosh platform.
+    pub const MACINTOSH: u16 = 1;
+    /// The Windows platform.
+    pub const WINDOWS: u16 = 3;
+}
+
+/// Well-known name IDs.
+pub mod name_id {
+    /// Copyright notice.
+    pub const COPYRIGHT_NOTICE: u16 = 0;
+    /// Font family name.
+    pub const FAMILY: u16 = 1;
+    /// Font subfamily (style) name, such as "Bold Italic".
+    pub const SUBFAMILY: u16 = 2;
+    /// Unique font identifier.
+    pub const UNIQUE_ID: u16 = 3;
+    /// Full font name.
+    pub const FULL_NAME: u16 = 4;
+    /// Version string.
+    pub const VERSION: u16 = 5;
+    /// PostScript name.
+    pub const POSTSCRIPT_NAME: u16 = 6;
+    /// Typographic family name, for families with more than the four basic styles.
+    pub const TYPOGRAPHIC_FAMILY: u16 = 16;
+    /// Typographic subfamily name.
+    pub const TYPOGRAPHIC_SUBFAMILY: u16 = 17;
+    /// WWS family name, for families that only vary in weight, width, and slope.
+    pub const WWS_FAMILY: u16 = 21;
+    /// WWS subfamily name.
+    pub const WWS_SUBFAMILY: u16 = 22;
+    /// Prefix for the PostScript names of instances of a variable font.
+    pub const VARIATIONS_POSTSCRIPT_NAME_PREFIX: u16 = 25;
+}
+
+/// A decoded record of the `name` table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NameRecord {
+    /// What the name is, such as `name_id::FAMILY`.
+    pub name_id: u16,
+    /// The platform that the record is for, such as `platform_id::WINDOWS`.
+    pub platform_id: u16,
+    /// The platform-specific encoding ID of the stored string.
+    pub encoding_id: u16,
+    /// The platform-specific language ID.
+    pub language_id: u16,
+    /// The language of the name as a BCP 47 tag, such as `en-US` or `zh-Hant`, if known.
+    ///
+    /// Records of the Unicode platform have no language.
+    pub language: Option<String>,
+    /// The name, decoded from the record's encoding.
+    pub value: String,
+}
+
+/// Decodes all the records of a `name` table that have the given name ID, in table order.
+///
+/// Records in encodings that can't be decoded are skipped.
+pub(crate) fn read_name_records(name: &[u8], name_id: u16) -> Vec<NameRecord> {
+    read_all_name_records(name, name_id).unwrap_or_default()
+}
+
+fn read_all_name_records(name: &[u8], wanted_name_id: u16) -> Option<Vec<NameRecord>> {
+    let mut reader = name;
+    let format = reader.read_u16::<BigEndian>().ok()?;
+    let count = reader.read_u16::<BigEndian>().ok()? as usize;
+    let storage = name.get(reader.read_u16::<BigEndian>().ok()? as usize..)?;
+    let language_tags = if format == 1 {
+        name.get((6 + count * NAME_RECORD_SIZE)..)
+    } else {
+        None
+    };
+
+    let mut records = vec![];
+    for index in 0..count {
+        let mut record = name.get((6 + index * NAME_RECORD_SIZE)..)?;
+        let platform_id = record.read_u16::<BigEndian>().ok()?;
+        let encoding_id = record.read_u16::<BigEndian>().ok()?;
+        let language_id = record.read_u16::<BigEndian>().ok()?;
+        let name_id = record.read_u16::<BigEndian>().ok()?;
+        let length = record.read_u16::<BigEndian>().ok()? as usize;
+        let offset = record.read_u16::<BigEndian>().ok()? as usize;
+        if name_id != wanted_name_id {
+            continue;
+        }
+
+        let value = match storage
+            .get(offset..(offset + length))
+            .and_then(|bytes| decode_name(platform_id, encoding_id, bytes))
+        {
+            Some(value) => value,
+            None => continue,
+        };
+        let language = if language_id >= FIRST_LANGUAGE_TAG_ID {
+            language_tags.and_then(|tags| read_language_tag(tags, storage, language_id))
+        } else {
+            match platform_id {
+                platform_id::MACINTOSH => macintosh_language_tag(language_id),
+                platform_id::WINDOWS => windows_language_tag(language_id),
+                _ => None,
+            }
+            .map(str::to_owned)
+        };
+
+        records.push(NameRecord {
+            name_id,
+            platform_id,
+            encoding_id,
+            language_id,
+            language,
+            value,
+        });
+    }
+    Some(records)
+}
+
+/// Reads a language tag from the language tag records of a format 1 `name` table.
+fn read_language_tag(language_tags: &[u8], storage: &[u8], language_id: u16) -> Option<String> {
+    let mut reader = language_tags;
+    let count = reader.read_u16::<BigEndian>().ok()?;
+    let index = language_id - FIRST_LANGUAGE_TAG_ID;
+    if index >= count {
+        return None;
+    }
+    let mut record = reader.get((index as usize * 4)..)?;
+    let length = record.read_u16::<BigEndian>().ok()? as usize;
+    let offset = record.read_u16::<BigEndian>().ok()? as usize;
+    decode_utf16_be(storage.get(offset..(offset + length))?)
+}
+
+fn decode_name(platform_id: u16, encoding_id: u16, bytes: &[u8]) -> Option<String> {
+    match (platform_id, encoding_id) {
+        (platform_id::UNICODE, _) | (platform_id::WINDOWS, 0 | 1 | 10) => decode_utf16_be(bytes),
+        (platform_id::WINDOWS, encoding_id) => {
+            let encoding = windows_legacy_encoding(encoding_id)?;
+            decode_legacy(encoding, &unpack_windows_legacy_string(bytes))
+        }
+        (platform_id::MACINTOSH, encoding_id) => {
+            decode_legacy(macintosh_encoding(encoding_id)?, bytes)
+        }
+        _ => None,
+    }
+}
+
+fn decode_utf16_be(mut bytes: &[u8]) -> Option<String> {
+    let mut code_units = Vec::with_capacity(bytes.len() / 2);
+    while let Ok(code_unit) = bytes.read_u16::<BigEndian>() {
+        code_units.push(code_unit);
+    }
+    String::from_utf16(&code_units).ok()
+}
+
+fn decode_legacy(encoding: &'static Encoding, bytes: &[u8]) -> Option<String> {
+    encoding
+        .decode_without_bom_handling_and_without_replacement(bytes)
+        .map(|string| string.into_owned())
+}
+
+/// Windows names in legacy CJK encodings store each character as a big-endian 16-bit value, with
+/// single-byte characters zero-extended. This recovers the byte string.
+fn unpack_windows_legacy_string(bytes: &[u8]) -> Vec<u8> {
+    let mut unpacked = Vec::with_capacity(bytes.len());
+    for pair in bytes.chunks(2) {
+        match *pair {
+            [0, low] => unpacked.push(low),
+            _ => unpacked.extend_from_slice(pair),
+        }
+    }
+    unpacked
+}
+
+fn windows_legacy_encoding(encoding_id: u16) -> Option<&'static Encoding> {
+    match encoding_id {
+        2 => Some(SHIFT_JIS),
+        3 => Some(GBK),
+        4 => Some(BIG5),
+        5 => Some(EUC_KR),
+        _ => None,
+    }
+}
+
+fn macintosh_encoding(encoding_id: u16) -> Option<&'static Encoding> {
+    match encoding_id {
+        0 => Some(MACINTOSH),
+        1 => Some(SHIFT_JIS),
+        2 => Some(BIG5),
+        3 => Some(EUC_KR),
+        7 => Some(X_MAC_CYRILLIC),
+        25 => Some(GBK),
+        _ => None,
+    }
+}
+
+/// BCP 47 tags of the Macintosh language IDs, indexed by ID.
+static MACINTOSH_LANGUAGE_TAGS: [&str; 95] = [
+    "en", "fr", "de", "it", "nl", "sv", "es", "da", "pt", "nb", "he", "ja", "ar", "fi", "el", "is",
+    "mt", "tr", "hr", "zh-Hant", "ur", "hi", "th", "ko", "lt", "pl", "hu", "et", "lv", "se", "fo",
+    "fa", "ru", "zh-Hans", "nl-BE", "ga", "sq", "ro", "cs", "sk", "sl", "yi", "sr", "mk", "bg",
+    "uk", "be", "uz", "kk", "az-Cyrl", "az-Arab", "hy", "ka", "ro-MD", "ky", "tg", "tk", "mn-Mong",
+    "mn-Cyrl", "ps", "ku", "ks", "sd", "bo", "ne", "sa", "mr", "bn", "as", "gu", "pa", "or", "ml",
+    "kn", "ta", "te", "si", "my", "km", "lo", "vi", "id", "tl", "ms", "ms-Arab", "am", "ti", "om",
+    "so", "sw", "rw", "rn", "ny", "mg", "eo",
+];
+
+fn macintosh_language_tag(language_id: u16) -> Option<&'static str> {
+    match language_id {
+        128 => Some("cy"),
+        129 => Some("eu"),
+        130 => Some("ca"),
+        131 => Some("la"),
+        132 => Some("qu"),
+        133 => Some("gn"),
+        134 => Some("ay"),
+        135 => Some("tt"),
+        136 => Some("ug"),
+        137 => Some("dz"),
+        138 => Some("jv"),
+        139 => Some("su"),
+        140 => Some("gl"),
+        141 => Some("af"),
+        142 => Some("br"),
+        143 => Some("iu"),
+        144 => Some("gd"),
+        145 => Some("gv"),
+        146 => Some("ga"),
+        147 => Some("to"),
+        148 => Some("el-polyton"),
+        149 => Some("kl"),
+        150 => Some("az"),
+        _ => MACINTOSH_LANGUAGE_TAGS.get(language_id as usize).copied(),
+    }
+}
+
+/// BCP 47 tags of common Windows language IDs, sorted by ID.
+static WINDOWS_LANGUAGE_TAGS: [(u16, &str); 113] = [
+    (0x0401, "ar-SA"),
+    (0x0402, "bg-BG"),
+    (0x0403, "ca-ES"),
+    (0x0404, "zh-TW"),
+    (0x0405, "cs-CZ"),
+    (0x0406, "da-DK"),
+    (0x0407, "de-DE"),
+    (0x0408, "el-GR"),
+    (0x0409, "en-US"),
+    (0x040a, "es-ES"),
+    (0x040b, "fi-FI"),
+    (0x040c, "fr-FR"),
+    (0x040d, "he-IL"),
+    (0x040e, "hu-HU"),
+    (0x040f, "is-IS"),
+    (0x0410, "it-IT"),
+    (0x0411, "ja-JP"),
+    (0x0412, "ko-KR"),
+    (0x0413, "nl-NL"),
+    (0x0414, "nb-NO"),
+    (0x0415, "pl-PL"),
+    (0x0416, "pt-BR"),
+    (0x0417, "rm-CH"),
+    (0x0418, "ro-RO"),
+    (0x0419, "ru-RU"),
+    (0x041a, "hr-HR"),
+    (0x041b, "sk-SK"),
+    (0x041c, "sq-AL"),
+    (0x041d, "sv-SE"),
+    (0x041e, "th-TH"),
+    (0x041f, "tr-TR"),
+    (0x0420, "ur-PK"),
+    (0x0421, "id-ID"),
+    (0x0422, "uk-UA"),
+    (0x0423, "be-BY"),
+    (0x0424, "sl-SI"),
+    (0x0425, "et-EE"),
+    (0x0426, "lv-LV"),
+    (0x0427, "lt-LT"),
+    (0x0428, "tg-Cyrl-TJ"),
+    (0x0429, "fa-IR"),
+    (0x042a, "vi-VN"),
+    (0x042b, "hy-AM"),
+    (0x042c, "az-Latn-AZ"),
+    (0x042d, "eu-ES"),
+    (0x042f, "mk-MK"),
+    (0x0436, "af-ZA"),
+    (0x0437, "ka-GE"),
+    (0x0438, "fo-FO"),
+    (0x0439, "hi-IN"),
+    (0x043a, "mt-MT"),
+    (0x043e, "ms-MY"),
+    (0x043f, "kk-KZ"),
+    (0x0440, "ky-KG"),
+    (0x0441, "sw-KE"),
+    (0x0442, "tk-TM"),
+    (0x0443, "uz-Latn-UZ"),
+    (0x0444, "tt-RU"),
+    (0x0445, "bn-IN"),
+    (0x0446, "pa-IN"),
+    (0x0447, "gu-IN"),
+    (0x0448, "or-IN"),
+    (0x0449, "ta-IN"),
+    (0x044a, "te-IN"),
+    (0x044b, "kn-IN"),
+    (0x044c, "ml-IN"),
+    (0x044d, "as-IN"),
+    (0x044e, "mr-IN"),
+    (0x044f, "sa-IN"),
+    (0x0450, "mn-MN"),
+    (0x0451, "bo-CN"),
+    (0x0452, "cy-GB"),
+    (0x0453, "km-KH"),
+    (0x0454, "lo-LA"),
+    (0x0456, "gl-ES"),
+    (0x045a, "syr-SY"),
+    (0x045b, "si-LK"),
+    (0x045d, "iu-Cans-CA"),
+    (0x045e, "am-ET"),
+    (0x0461, "ne-NP"),
+    (0x0462, "fy-NL"),
+    (0x0463, "ps-AF"),
+    (0x0464, "fil-PH"),
+    (0x0465, "dv-MV"),
+    (0x0801, "ar-IQ"),
+    (0x0804, "zh-CN"),
+    (0x0807, "de-CH"),
+    (0x0809, "en-GB"),
+    (0x080a, "es-MX"),
+    (0x080c, "fr-BE"),
+    (0x0810, "it-CH"),
+    (0x0813, "nl-BE"),
+    (0x0814, "nn-NO"),
+    (0x0816, "pt-PT"),
+    (0x081a, "sr-Latn-CS"),
+    (0x081d, "sv-FI"),
+    (0x0843, "uz-Cyrl-UZ"),
+    (0x0c01, "ar-EG"),
+    (0x0c04, "zh-HK"),
+    (0x0c07, "de-AT"),
+    (0x0c09, "en-AU"),
+    (0x0c0a, "es-ES"),
+    (0x0c0c, "fr-CA"),
+    (0x0c1a, "sr-Cyrl-CS"),
+    (0x1004, "zh-SG"),
+    (0x1007, "de-LU"),
+    (0x1009, "en-CA"),
+    (0x100c, "fr-CH"),
+    (0x1404, "zh-MO"),
+    (0x1409, "en-NZ"),
+    (0x1809, "en-IE"),
+    (0x1c09, "en-ZA"),
+    (0x2009, "en-JM"),
+];
+
+fn windows_language_tag(language_id: u16) -> Option<&'static str> {
+    WINDOWS_LANGUAGE_TAGS
+        .binary_search_by_key(&language_id, |&(id, _)| id)
+        .ok()
+        .map(|index| WINDOWS_LANGUAGE_TAGS[index].1)
+}
+
+/// Chooses the name from the given records that best matches the requested BCP 47 language tag.
+///
+/// The tag is matched with its trailing subtags removed one at a time, so `zh-Hant-HK` falls back
+/// to `zh-Hant` and then to `zh`. Chinese regions imply their script. If no record matches, the
+/// US English name is used, followed by a name without a language, followed by any name.
+pub(crate) fn find_localized_name(records: &[NameRecord], language: &str) -> Option<String> {
+    find_best_match(records, language)
+        .or_else(|| find_best_match(records, "en-US"))
+        .or_else(|| {
+            records
+                .iter()
+                .find(|record| record.language.is_none())
+                .or_else(|| records.first())
+        })
+        .map(|record| record.value.clone())
+}
+
+fn find_best_match<'a>(records: &'a [NameRecord], language: &str) -> Option<&'a NameRecord> {
+    let requested = expand_chinese_script(language);
+    let mut prefix = requested.as_str();
+    loop {
+        let best = records
+            .iter()
+            .filter(|record| match record.language {
+                Some(ref record_language) => {
+                    let record_language = expand_chinese_script(record_language);
+                    record_language == prefix
+                        || (record_language.starts_with(prefix)
+                            && record_language.as_bytes().get(prefix.len()) == Some(&b'-'))
+                }
+                None => false,
+            })
+            .min_by_key(|record| platform_preference(record.platform_id));
+        if best.is_some() {
+            return best;
+        }
+        prefix = &prefix[..prefix.rfind('-')?];
+    }
+}
+
+fn platform_preference(platform_id: u16) -> u8 {
+    match platform_id {
+        platform_id::WINDOWS => 0,
+        platform_id::UNICODE => 1,
+        _ => 2,
+    }
+}
+
+/// Lowercases a language tag and inserts the script implied by a Chinese region, so that, for
+/// example, `zh-TW` becomes `zh-hant-tw`.
+fn expand_chinese_script(language: &str) -> String {
+    let language = language.to_ascii_lowercase();
+    let script = match language.as_str() {
+        "zh-tw" | "zh-hk" | "zh-mo" => "hant",
+        "zh-cn" | "zh-sg" => "hans",
+        _ => return language,
+    };
+    format!("zh-{}-{}", script, &language[3..])
+}
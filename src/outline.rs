@@ -8,12 +8,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-//! Bézier paths.
+//! Bézier paths, and geometry operations on them.
 
 use pathfinder_geometry::line_segment::LineSegment2F;
+use pathfinder_geometry::rect::RectF;
+use pathfinder_geometry::transform2d::Transform2F;
 use pathfinder_geometry::vector::Vector2F;
 use std::mem;
 
+use crate::error::SvgPathError;
+use crate::svg_path::{self, SvgPathOptions, SvgPathSink};
+
 /// Receives Bézier path rendering commands.
 pub trait OutlineSink {
     /// Moves the pen to a point.
@@ -28,6 +33,36 @@ pub trait OutlineSink {
     fn close(&mut self);
 }
 
+impl<S> OutlineSink for &mut S
+where
+    S: OutlineSink + ?Sized,
+{
+    #[inline]
+    fn move_to(&mut self, to: Vector2F) {
+        (**self).move_to(to)
+    }
+
+    #[inline]
+    fn line_to(&mut self, to: Vector2F) {
+        (**self).line_to(to)
+    }
+
+    #[inline]
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        (**self).quadratic_curve_to(ctrl, to)
+    }
+
+    #[inline]
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        (**self).cubic_curve_to(ctrl, to)
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        (**self).close()
+    }
+}
+
 /// A glyph vector outline or path.
 #[derive(Clone, PartialEq, Debug)]
 pub struct Outline {
@@ -93,6 +128,122 @@ impl Outline {
             contour.copy_to(sink);
         }
     }
+
+    /// Returns the smallest rectangle that contains this outline.
+    ///
+    /// This includes the extrema of curves, but not control points that lie outside of them, so it
+    /// is usually tighter than the box around all points. An empty outline has an empty rectangle
+    /// at the origin.
+    pub fn bounds(&self) -> RectF {
+        let mut sink = BoundsSink::new();
+        self.copy_to(&mut sink);
+        sink.bounds()
+    }
+
+    /// Applies `transform` to every point of this outline.
+    ///
+    /// Transforms that mirror the outline, such as flipping the Y axis, reverse its winding.
+    pub fn transform(&mut self, transform: &Transform2F) {
+        for contour in &mut self.contours {
+            contour.transform(transform);
+        }
+    }
+
+    /// Returns a copy of this outline with every curve replaced by lines that stray no further
+    /// than `tolerance` from it.
+    pub fn flatten(&self, tolerance: f32) -> Outline {
+        let mut builder = OutlineBuilder::new();
+        self.copy_to(&mut FlatteningSink::new(&mut builder, tolerance));
+        builder.into_outline()
+    }
+
+    /// Returns a copy of this outline with every quadratic curve converted to the cubic curve that
+    /// traces the same path.
+    pub fn to_cubics(&self) -> Outline {
+        let mut builder = OutlineBuilder::new();
+        self.copy_to(&mut CubicSink {
+            sink: &mut builder,
+            current_point: Vector2F::zero(),
+        });
+        builder.into_outline()
+    }
+
+    /// Reverses the direction of every contour.
+    pub fn reverse(&mut self) {
+        for contour in &mut self.contours {
+            contour.reverse();
+        }
+    }
+
+    /// Orients every contour so that outer contours run clockwise and the holes inside them run
+    /// counterclockwise, with Y pointing up, as TrueType expects.
+    ///
+    /// A contour counts as a hole if its first point lies inside an odd number of other contours,
+    /// so this assumes that contours don't cross each other.
+    pub fn normalize_winding(&mut self) {
+        let mut reversals = vec![];
+        for (index, contour) in self.contours.iter().enumerate() {
+            let area = contour.area();
+            let point = match contour.positions.first() {
+                Some(&point) if area != 0.0 => point,
+                _ => continue,
+            };
+            let depth = self
+                .contours
+                .iter()
+                .enumerate()
+                .filter(|&(other_index, other)| {
+                    other_index != index && other.winding_number(point) != 0
+                })
+                .count();
+            let is_hole = depth % 2 == 1;
+            if (area > 0.0) != is_hole {
+                reversals.push(index);
+            }
+        }
+        for index in reversals {
+            self.contours[index].reverse();
+        }
+    }
+
+    /// Returns the signed area that this outline encloses.
+    ///
+    /// With Y pointing up, counterclockwise contours add to the area and clockwise contours
+    /// subtract from it. Curves count exactly, not as their control polygons.
+    pub fn area(&self) -> f32 {
+        self.contours.iter().map(Contour::area).sum()
+    }
+
+    /// Returns how many times this outline winds around `point`, counting counterclockwise turns
+    /// as positive when Y points up.
+    pub fn winding_number(&self, point: Vector2F) -> i32 {
+        self.contours
+            .iter()
+            .map(|contour| contour.winding_number(point))
+            .sum()
+    }
+
+    /// Returns true if `point` is inside this outline under the nonzero fill rule that glyphs use.
+    #[inline]
+    pub fn contains_point(&self, point: Vector2F) -> bool {
+        self.winding_number(point) != 0
+    }
+
+    /// Returns this outline as SVG path data, suitable for the `d` attribute of a `<path>`.
+    ///
+    /// For a glyph outline in font units, set `flip_y` in `options`, as Y points down in SVG.
+    pub fn to_svg_path_data(&self, options: &SvgPathOptions) -> String {
+        let mut sink = SvgPathSink::new(options);
+        self.copy_to(&mut sink);
+        sink.into_path_data()
+    }
+
+    /// Builds an outline from SVG path data. See `svg_path::read_path_data` for details.
+    pub fn from_svg_path_data(path_data: &str) -> Result<Outline, SvgPathError> {
+        let mut builder = OutlineBuilder::new();
+        svg_path::read_path_data(path_data, &mut builder)?;
+        Ok(builder.into_outline())
+    }
 }
 
 impl Default for Contour {
@@ -118,6 +269,75 @@ impl Contour {
         self.flags.push(flags);
     }
 
+    /// Applies `transform` to every point of this contour.
+    pub fn transform(&mut self, transform: &Transform2F) {
+        for position in &mut self.positions {
+            *position = *transform * *position;
+        }
+    }
+
+    /// Reverses the direction of this contour, keeping its first point where it is.
+    pub fn reverse(&mut self) {
+        if self.positions.len() < 2 {
+            return;
+        }
+
+        // Reverse the contour with the point it closes back to written out, so that a curve that
+        // ends there ends there again.
+        self.positions.push(self.positions[0]);
+        self.flags.push(PointFlags::empty());
+        self.positions.reverse();
+        self.flags.reverse();
+
+        // The control points of cubic curves now come in the opposite order.
+        let mut index = 1;
+        while index < self.flags.len() {
+            if self.flags[index].contains(PointFlags::CONTROL_POINT_1) {
+                self.flags[index] = PointFlags::CONTROL_POINT_0;
+                self.flags[index + 1] = PointFlags::CONTROL_POINT_1;
+                index += 1;
+            }
+            index += 1;
+        }
+
+        // A point that closed the contour explicitly is now a line of no length at its start, and
+        // a final line back to the start can be left implicit.
+        if self.positions[1] == self.positions[0] && self.flags[1].is_empty() {
+            self.positions.remove(1);
+            self.flags.remove(1);
+        }
+        let last = self.positions.len() - 1;
+        if last > 0 && self.flags[last - 1].is_empty() {
+            self.positions.pop();
+            self.flags.pop();
+        }
+    }
+
+    /// Returns the signed area that this contour encloses, positive if it runs counterclockwise
+    /// with Y pointing up.
+    pub fn area(&self) -> f32 {
+        self.segments().iter().map(Segment::signed_area).sum()
+    }
+
+    /// Returns how many times this contour winds around `point`, counting counterclockwise turns
+    /// as positive when Y points up.
+    pub fn winding_number(&self, point: Vector2F) -> i32 {
+        self.segments()
+            .iter()
+            .map(|segment| segment.winding_number(point))
+            .sum()
+    }
+
+    fn segments(&self) -> Vec<Segment> {
+        let mut sink = SegmentSink {
+            segments: vec![],
+            first_point: Vector2F::zero(),
+            current_point: Vector2F::zero(),
+        };
+        self.copy_to(&mut sink);
+        sink.segments
+    }
+
     /// Sends this contour to an `OutlineSink`.
     pub fn copy_to<S>(&self, sink: &mut S)
     where
@@ -178,7 +398,7 @@ impl OutlineBuilder {
     pub fn take_outline(&mut self) -> Outline {
         assert!(self.current_contour.positions.is_empty());
         self.current_contour = Contour::new();
-        mem::replace(&mut self.outline, Outline::new())
+        mem::take(&mut self.outline)
     }
 }
 
@@ -212,6 +432,476 @@ impl OutlineSink for OutlineBuilder {
     fn close(&mut self) {
         self.outline
             .contours
-            .push(mem::replace(&mut self.current_contour, Contour::new()));
+            .push(mem::take(&mut self.current_contour));
+    }
+}
+
+/// Forwards path commands to another sink after transforming their points.
+#[derive(Clone, Debug)]
+pub struct TransformingSink<S> {
+    sink: S,
+    transform: Transform2F,
+}
+
+impl<S> TransformingSink<S>
+where
+    S: OutlineSink,
+{
+    /// Creates a sink that applies `transform` to every point before passing it to `sink`.
+    #[inline]
+    pub fn new(sink: S, transform: Transform2F) -> TransformingSink<S> {
+        TransformingSink { sink, transform }
+    }
+
+    /// Returns the sink that path commands are forwarded to.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+impl<S> OutlineSink for TransformingSink<S>
+where
+    S: OutlineSink,
+{
+    #[inline]
+    fn move_to(&mut self, to: Vector2F) {
+        self.sink.move_to(self.transform * to)
+    }
+
+    #[inline]
+    fn line_to(&mut self, to: Vector2F) {
+        self.sink.line_to(self.transform * to)
+    }
+
+    #[inline]
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        self.sink
+            .quadratic_curve_to(self.transform * ctrl, self.transform * to)
+    }
+
+    #[inline]
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        self.sink
+            .cubic_curve_to(self.transform * ctrl, self.transform * to)
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.sink.close()
+    }
+}
+
+/// Forwards path commands to another sink after replacing every curve with lines.
+#[derive(Clone, Debug)]
+pub struct FlatteningSink<S> {
+    sink: S,
+    tolerance: f32,
+    current_point: Vector2F,
+}
+
+impl<S> FlatteningSink<S>
+where
+    S: OutlineSink,
+{
+    /// Creates a sink that passes curves to `sink` as lines that stray no further than
+    /// `tolerance` from them.
+    #[inline]
+    pub fn new(sink: S, tolerance: f32) -> FlatteningSink<S> {
+        FlatteningSink {
+            sink,
+            tolerance,
+            current_point: Vector2F::zero(),
+        }
+    }
+
+    /// Returns the sink that path commands are forwarded to.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+impl<S> OutlineSink for FlatteningSink<S>
+where
+    S: OutlineSink,
+{
+    #[inline]
+    fn move_to(&mut self, to: Vector2F) {
+        self.sink.move_to(to);
+        self.current_point = to;
+    }
+
+    #[inline]
+    fn line_to(&mut self, to: Vector2F) {
+        self.sink.line_to(to);
+        self.current_point = to;
+    }
+
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        let sink = &mut self.sink;
+        flatten_quadratic(self.current_point, ctrl, to, self.tolerance, |point| {
+            sink.line_to(point)
+        });
+        self.current_point = to;
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        let sink = &mut self.sink;
+        flatten_cubic(self.current_point, ctrl, to, self.tolerance, |point| {
+            sink.line_to(point)
+        });
+        self.current_point = to;
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.sink.close()
+    }
+}
+
+/// Computes the smallest rectangle that contains the paths sent to it, including the extrema of
+/// curves.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BoundsSink {
+    bounds: Option<RectF>,
+    current_point: Vector2F,
+}
+
+impl BoundsSink {
+    /// Creates a sink that hasn't seen any points yet.
+    #[inline]
+    pub fn new() -> BoundsSink {
+        BoundsSink::default()
+    }
+
+    /// Returns the bounds of the paths so far, or an empty rectangle at the origin if there are
+    /// none.
+    #[inline]
+    pub fn bounds(&self) -> RectF {
+        self.bounds.unwrap_or_default()
+    }
+
+    fn add_point(&mut self, point: Vector2F) {
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => bounds.union_point(point),
+            None => RectF::new(point, Vector2F::zero()),
+        });
+    }
+
+    fn add_segment(&mut self, segment: Segment) {
+        for t in segment.extrema().iter().flatten() {
+            self.add_point(segment.sample(*t));
+        }
+        self.add_point(segment.to());
+        self.current_point = segment.to();
+    }
+}
+
+impl OutlineSink for BoundsSink {
+    #[inline]
+    fn move_to(&mut self, to: Vector2F) {
+        self.add_point(to);
+        self.current_point = to;
+    }
+
+    #[inline]
+    fn line_to(&mut self, to: Vector2F) {
+        self.add_point(to);
+        self.current_point = to;
+    }
+
+    #[inline]
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        self.add_segment(Segment::Quadratic(self.current_point, ctrl, to));
+    }
+
+    #[inline]
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        self.add_segment(Segment::Cubic(
+            self.current_point,
+            ctrl.from(),
+            ctrl.to(),
+            to,
+        ));
+    }
+
+    #[inline]
+    fn close(&mut self) {}
+}
+
+/// Approximates a quadratic Bézier curve with line segments that stray no further than
+/// `tolerance` from it, calling `line_to` with the end of each segment.
+pub(crate) fn flatten_quadratic<F>(
+    from: Vector2F,
+    ctrl: Vector2F,
+    to: Vector2F,
+    tolerance: f32,
+    mut line_to: F,
+) where
+    F: FnMut(Vector2F),
+{
+    let deviation = (from - ctrl * 2.0 + to).length();
+    let segments = segment_count(deviation * 0.25, tolerance);
+    for index in 1..=segments {
+        let t = index as f32 / segments as f32;
+        line_to(from.lerp(ctrl, t).lerp(ctrl.lerp(to, t), t));
+    }
+}
+
+/// Approximates a cubic Bézier curve with line segments that stray no further than `tolerance`
+/// from it, calling `line_to` with the end of each segment.
+pub(crate) fn flatten_cubic<F>(
+    from: Vector2F,
+    ctrl: LineSegment2F,
+    to: Vector2F,
+    tolerance: f32,
+    mut line_to: F,
+) where
+    F: FnMut(Vector2F),
+{
+    let deviation = (from - ctrl.from() * 2.0 + ctrl.to())
+        .length()
+        .max((ctrl.from() - ctrl.to() * 2.0 + to).length());
+    let segments = segment_count(deviation * 0.75, tolerance);
+    for index in 1..=segments {
+        let t = index as f32 / segments as f32;
+        let p01 = from.lerp(ctrl.from(), t);
+        let p12 = ctrl.from().lerp(ctrl.to(), t);
+        let p23 = ctrl.to().lerp(to, t);
+        line_to(p01.lerp(p12, t).lerp(p12.lerp(p23, t), t));
+    }
+}
+
+// Returns the number of line segments needed to approximate a curve whose control polygon bends
+// by `deviation`.
+fn segment_count(deviation: f32, tolerance: f32) -> usize {
+    ((deviation / tolerance).sqrt().ceil() as usize).clamp(1, 256)
+}
+
+// Converts quadratic curves to cubic curves on their way to another sink.
+struct CubicSink<S> {
+    sink: S,
+    current_point: Vector2F,
+}
+
+impl<S> OutlineSink for CubicSink<S>
+where
+    S: OutlineSink,
+{
+    fn move_to(&mut self, to: Vector2F) {
+        self.sink.move_to(to);
+        self.current_point = to;
+    }
+
+    fn line_to(&mut self, to: Vector2F) {
+        self.sink.line_to(to);
+        self.current_point = to;
+    }
+
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        let ctrl = LineSegment2F::new(
+            self.current_point.lerp(ctrl, 2.0 / 3.0),
+            to.lerp(ctrl, 2.0 / 3.0),
+        );
+        self.sink.cubic_curve_to(ctrl, to);
+        self.current_point = to;
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        self.sink.cubic_curve_to(ctrl, to);
+        self.current_point = to;
+    }
+
+    fn close(&mut self) {
+        self.sink.close();
+    }
+}
+
+// Collects the segments of a contour, including the line that closes it.
+struct SegmentSink {
+    segments: Vec<Segment>,
+    first_point: Vector2F,
+    current_point: Vector2F,
+}
+
+impl OutlineSink for SegmentSink {
+    fn move_to(&mut self, to: Vector2F) {
+        self.first_point = to;
+        self.current_point = to;
+    }
+
+    fn line_to(&mut self, to: Vector2F) {
+        self.segments.push(Segment::Line(self.current_point, to));
+        self.current_point = to;
+    }
+
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        self.segments
+            .push(Segment::Quadratic(self.current_point, ctrl, to));
+        self.current_point = to;
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        self.segments.push(Segment::Cubic(
+            self.current_point,
+            ctrl.from(),
+            ctrl.to(),
+            to,
+        ));
+        self.current_point = to;
+    }
+
+    fn close(&mut self) {
+        if self.current_point != self.first_point {
+            self.line_to(self.first_point);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Segment {
+    Line(Vector2F, Vector2F),
+    Quadratic(Vector2F, Vector2F, Vector2F),
+    Cubic(Vector2F, Vector2F, Vector2F, Vector2F),
+}
+
+impl Segment {
+    fn to(&self) -> Vector2F {
+        match *self {
+            Segment::Line(_, to) | Segment::Quadratic(_, _, to) | Segment::Cubic(_, _, _, to) => to,
+        }
+    }
+
+    fn sample(&self, t: f32) -> Vector2F {
+        match *self {
+            Segment::Line(from, to) => from.lerp(to, t),
+            Segment::Quadratic(from, ctrl, to) => from.lerp(ctrl, t).lerp(ctrl.lerp(to, t), t),
+            Segment::Cubic(from, ctrl0, ctrl1, to) => {
+                let p12 = ctrl0.lerp(ctrl1, t);
+                from.lerp(ctrl0, t)
+                    .lerp(p12, t)
+                    .lerp(p12.lerp(ctrl1.lerp(to, t), t), t)
+            }
+        }
+    }
+
+    // Returns the parameters strictly between the ends where the curve turns around in X, then
+    // those where it turns around in Y.
+    fn extrema(&self) -> [Option<f32>; 4] {
+        let x = self.axis_extrema(|point| point.x());
+        let y = self.axis_extrema(|point| point.y());
+        [x[0], x[1], y[0], y[1]]
+    }
+
+    fn axis_extrema<F>(&self, axis: F) -> [Option<f32>; 2]
+    where
+        F: Fn(Vector2F) -> f32,
+    {
+        match *self {
+            Segment::Line(..) => [None, None],
+            Segment::Quadratic(from, ctrl, to) => {
+                let (p0, p1, p2) = (axis(from), axis(ctrl), axis(to));
+                let denominator = p0 - 2.0 * p1 + p2;
+                if denominator == 0.0 {
+                    return [None, None];
+                }
+                [unit_interval_root((p0 - p1) / denominator), None]
+            }
+            Segment::Cubic(from, ctrl0, ctrl1, to) => {
+                // The derivative is a quadratic, `a t² + b t + c`, scaled by 3.
+                let (p0, p1, p2, p3) = (axis(from), axis(ctrl0), axis(ctrl1), axis(to));
+                let a = -p0 + 3.0 * (p1 - p2) + p3;
+                let b = 2.0 * (p0 - 2.0 * p1 + p2);
+                let c = p1 - p0;
+                if a.abs() <= f32::EPSILON * (b.abs() + c.abs()) {
+                    if b == 0.0 {
+                        return [None, None];
+                    }
+                    return [unit_interval_root(-c / b), None];
+                }
+                let discriminant = b * b - 4.0 * a * c;
+                if discriminant < 0.0 {
+                    return [None, None];
+                }
+                let root = discriminant.sqrt();
+                [
+                    unit_interval_root((-b - root) / (2.0 * a)),
+                    unit_interval_root((-b + root) / (2.0 * a)),
+                ]
+            }
+        }
+    }
+
+    // Green's theorem gives the area between the segment and the origin exactly; see Raph
+    // Levien's kurbo.
+    fn signed_area(&self) -> f32 {
+        match *self {
+            Segment::Line(p0, p1) => (p0.x() * p1.y() - p1.x() * p0.y()) * 0.5,
+            Segment::Quadratic(p0, p1, p2) => {
+                (p0.x() * (2.0 * p1.y() + p2.y()) + 2.0 * p1.x() * (p2.y() - p0.y())
+                    - p2.x() * (p0.y() + 2.0 * p1.y()))
+                    / 6.0
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                (p0.x() * (6.0 * p1.y() + 3.0 * p2.y() + p3.y())
+                    + 3.0
+                        * (p1.x() * (-2.0 * p0.y() + p2.y() + p3.y())
+                            - p2.x() * (p0.y() + p1.y() - 2.0 * p3.y()))
+                    - p3.x() * (p0.y() + 3.0 * p1.y() + 6.0 * p2.y()))
+                    / 20.0
+            }
+        }
+    }
+
+    // Counts the crossings of the segment with the ray from `point` toward positive X, upward
+    // crossings as +1 and downward ones as -1. Each span of the segment between turns in Y
+    // crosses at most once; ends count only at their lower point, so that crossings at joins
+    // between segments count once.
+    fn winding_number(&self, point: Vector2F) -> i32 {
+        let [first, second] = self.axis_extrema(|point| point.y());
+        let mut splits = [0.0, 1.0, 1.0, 1.0];
+        let mut split_count = 1;
+        for t in [first, second].iter().flatten() {
+            splits[split_count] = *t;
+            split_count += 1;
+        }
+        splits[split_count] = 1.0;
+        splits[..=split_count].sort_by(|a, b| a.total_cmp(b));
+
+        let mut winding = 0;
+        for span in splits[..=split_count].windows(2) {
+            let (mut start, mut end) = (span[0], span[1]);
+            let (start_y, end_y) = (self.sample(start).y(), self.sample(end).y());
+            let direction = if start_y <= point.y() && end_y > point.y() {
+                1
+            } else if end_y <= point.y() && start_y > point.y() {
+                -1
+            } else {
+                continue;
+            };
+
+            // Find where the span crosses the ray by bisection, as it is monotonic in Y.
+            for _ in 0..32 {
+                let middle = (start + end) * 0.5;
+                if (self.sample(middle).y() <= point.y()) == (direction > 0) {
+                    start = middle;
+                } else {
+                    end = middle;
+                }
+            }
+            if self.sample((start + end) * 0.5).x() > point.x() {
+                winding += direction;
+            }
+        }
+        winding
+    }
+}
+
+fn unit_interval_root(t: f32) -> Option<f32> {
+    if t > 0.0 && t < 1.0 {
+        Some(t)
+    } else {
+        None
     }
 }
@@ -0,0 +1,989 @@
+// font-kit/src/paint.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A software renderer for the paint graphs of version 1 `COLR` color glyphs.
+//!
+//! Each paint is rendered into a premultiplied floating-point layer the size of the target canvas,
+//! and layers are combined according to the paint graph. Glyph outlines are rasterized with the
+//! loader's own `rasterize_glyph()` and used as coverage masks.
+//!
+//! Variation deltas of the `PaintVar*` formats are not applied, so variable color fonts are always
+//! drawn at their default instance.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use log::warn;
+use pathfinder_geometry::rect::RectF;
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::{Vector2F, Vector2I};
+use std::f32::consts::PI;
+
+use crate::canvas::{Canvas, Format, RasterizationOptions};
+use crate::color::{ColorPalette, RgbaColor};
+use crate::error::GlyphLoadingError;
+use crate::hinting::HintingOptions;
+use crate::loader::Loader;
+
+// Paint graphs may reference each other, so bound the recursion in case a font contains cycles.
+const MAX_PAINT_DEPTH: u32 = 64;
+
+const FOREGROUND_PALETTE_INDEX: u16 = 0xffff;
+
+/// The version 1 parts of a `COLR` table.
+pub(crate) struct ColrV1<'a> {
+    base_glyph_list: &'a [u8],
+    layer_list: Option<&'a [u8]>,
+    clip_list: Option<&'a [u8]>,
+}
+
+impl<'a> ColrV1<'a> {
+    /// Returns the version 1 parts of the given `COLR` table, or `None` if it's a version 0
+    /// table.
+    pub(crate) fn new(colr: &'a [u8]) -> Option<ColrV1<'a>> {
+        let mut reader = colr;
+        if reader.read_u16::<BigEndian>().ok()? < 1 {
+            return None;
+        }
+        // Skip the version 0 fields.
+        reader = reader.get(12..)?;
+        let base_glyph_list_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+        let layer_list_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+        let clip_list_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+        if base_glyph_list_offset == 0 {
+            return None;
+        }
+        Some(ColrV1 {
+            base_glyph_list: colr.get(base_glyph_list_offset..)?,
+            layer_list: subtable(colr, layer_list_offset),
+            clip_list: subtable(colr, clip_list_offset),
+        })
+    }
+
+    /// Returns the root paint of the given glyph, if it has one.
+    pub(crate) fn base_glyph_paint(&self, glyph_id: u32) -> Option<&'a [u8]> {
+        let mut reader = self.base_glyph_list;
+        let record_count = reader.read_u32::<BigEndian>().ok()? as usize;
+        let records = reader.get(..(record_count * 6))?;
+        let (mut low, mut high) = (0, record_count);
+        while low < high {
+            let mid = (low + high) / 2;
+            let mut record = &records[(mid * 6)..];
+            let base_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+            if base_glyph_id < glyph_id {
+                low = mid + 1;
+            } else if base_glyph_id > glyph_id {
+                high = mid;
+            } else {
+                let paint_offset = record.read_u32::<BigEndian>().ok()? as usize;
+                return self.base_glyph_list.get(paint_offset..);
+            }
+        }
+        None
+    }
+
+    fn layer_count(&self) -> u32 {
+        self.layer_list
+            .and_then(|mut reader| reader.read_u32::<BigEndian>().ok())
+            .unwrap_or(0)
+    }
+
+    fn layer_paint(&self, layer_index: u32) -> Option<&'a [u8]> {
+        if layer_index >= self.layer_count() {
+            return None;
+        }
+        let layer_list = self.layer_list?;
+        let mut reader = layer_list.get((4 + layer_index as usize * 4)..)?;
+        let paint_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+        layer_list.get(paint_offset..)
+    }
+
+    /// Returns the clip box of the given glyph in font units, if it has one.
+    fn clip_box(&self, glyph_id: u32) -> Option<RectF> {
+        let clip_list = self.clip_list?;
+        let mut reader = clip_list;
+        let _format = reader.read_u8().ok()?;
+        let clip_count = reader.read_u32::<BigEndian>().ok()?;
+        for _ in 0..clip_count {
+            let start_glyph_id = reader.read_u16::<BigEndian>().ok()? as u32;
+            let end_glyph_id = reader.read_u16::<BigEndian>().ok()? as u32;
+            let clip_box_offset = reader.read_u24::<BigEndian>().ok()? as usize;
+            if glyph_id < start_glyph_id || glyph_id > end_glyph_id {
+                continue;
+            }
+
+            // Both clip box formats start with the same fields; format 2 only adds variations.
+            let mut clip_box = clip_list.get(clip_box_offset..)?;
+            let _format = clip_box.read_u8().ok()?;
+            let x_min = clip_box.read_i16::<BigEndian>().ok()? as f32;
+            let y_min = clip_box.read_i16::<BigEndian>().ok()? as f32;
+            let x_max = clip_box.read_i16::<BigEndian>().ok()? as f32;
+            let y_max = clip_box.read_i16::<BigEndian>().ok()? as f32;
+            return Some(RectF::from_points(
+                Vector2F::new(x_min, y_min),
+                Vector2F::new(x_max, y_max),
+            ));
+        }
+        None
+    }
+}
+
+fn subtable(table: &[u8], offset: usize) -> Option<&[u8]> {
+    if offset == 0 {
+        None
+    } else {
+        table.get(offset..)
+    }
+}
+
+/// A node of the paint graph.
+enum Paint<'a> {
+    ColrLayers {
+        first_layer_index: u32,
+        layer_count: u32,
+    },
+    Solid {
+        palette_index: u16,
+        alpha: f32,
+    },
+    LinearGradient {
+        color_line: ColorLine,
+        p0: Vector2F,
+        p1: Vector2F,
+        p2: Vector2F,
+    },
+    RadialGradient {
+        color_line: ColorLine,
+        center0: Vector2F,
+        radius0: f32,
+        center1: Vector2F,
+        radius1: f32,
+    },
+    SweepGradient {
+        color_line: ColorLine,
+        center: Vector2F,
+        start_angle: f32,
+        end_angle: f32,
+    },
+    Glyph {
+        glyph_id: u32,
+        paint: &'a [u8],
+    },
+    ColrGlyph {
+        glyph_id: u32,
+    },
+    Transform {
+        transform: Transform2F,
+        paint: &'a [u8],
+    },
+    Composite {
+        source: &'a [u8],
+        mode: u8,
+        backdrop: &'a [u8],
+    },
+}
+
+impl<'a> Paint<'a> {
+    fn parse(data: &'a [u8]) -> Option<Paint<'a>> {
+        let mut reader = data;
+        let format = reader.read_u8().ok()?;
+
+        // Every paint format except `PaintColrLayers` has a variable counterpart with the same
+        // layout plus trailing variation indices, which we ignore.
+        let is_variable = format >= 3 && format % 2 == 1;
+        let child = |reader: &mut &'a [u8]| -> Option<&'a [u8]> {
+            let offset = reader.read_u24::<BigEndian>().ok()? as usize;
+            data.get(offset..)
+        };
+
+        match format {
+            1 => {
+                let layer_count = reader.read_u8().ok()? as u32;
+                let first_layer_index = reader.read_u32::<BigEndian>().ok()?;
+                Some(Paint::ColrLayers {
+                    first_layer_index,
+                    layer_count,
+                })
+            }
+            2 | 3 => {
+                let palette_index = reader.read_u16::<BigEndian>().ok()?;
+                let alpha = read_f2dot14(&mut reader)?;
+                Some(Paint::Solid {
+                    palette_index,
+                    alpha,
+                })
+            }
+            4 | 5 => {
+                let color_line = ColorLine::parse(child(&mut reader)?, is_variable)?;
+                let p0 = read_point(&mut reader)?;
+                let p1 = read_point(&mut reader)?;
+                let p2 = read_point(&mut reader)?;
+                Some(Paint::LinearGradient {
+                    color_line,
+                    p0,
+                    p1,
+                    p2,
+                })
+            }
+            6 | 7 => {
+                let color_line = ColorLine::parse(child(&mut reader)?, is_variable)?;
+                let center0 = read_point(&mut reader)?;
+                let radius0 = reader.read_u16::<BigEndian>().ok()? as f32;
+                let center1 = read_point(&mut reader)?;
+                let radius1 = reader.read_u16::<BigEndian>().ok()? as f32;
+                Some(Paint::RadialGradient {
+                    color_line,
+                    center0,
+                    radius0,
+                    center1,
+                    radius1,
+                })
+            }
+            8 | 9 => {
+                let color_line = ColorLine::parse(child(&mut reader)?, is_variable)?;
+                let center = read_point(&mut reader)?;
+                // Sweep angles are biased by 180°, so that 0° to 360° fits in an F2DOT14.
+                let start_angle = (read_f2dot14(&mut reader)? + 1.0) * PI;
+                let end_angle = (read_f2dot14(&mut reader)? + 1.0) * PI;
+                Some(Paint::SweepGradient {
+                    color_line,
+                    center,
+                    start_angle,
+                    end_angle,
+                })
+            }
+            10 => {
+                let paint = child(&mut reader)?;
+                let glyph_id = reader.read_u16::<BigEndian>().ok()? as u32;
+                Some(Paint::Glyph { glyph_id, paint })
+            }
+            11 => {
+                let glyph_id = reader.read_u16::<BigEndian>().ok()? as u32;
+                Some(Paint::ColrGlyph { glyph_id })
+            }
+            12..=31 => {
+                let paint = child(&mut reader)?;
+                let transform = read_transform(format, &mut reader, child)?;
+                Some(Paint::Transform { transform, paint })
+            }
+            32 => {
+                let source = child(&mut reader)?;
+                let mode = reader.read_u8().ok()?;
+                let backdrop = child(&mut reader)?;
+                Some(Paint::Composite {
+                    source,
+                    mode,
+                    backdrop,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reads the fields of one of the transform paint formats (12 to 31) that follow the child paint
+/// offset, and converts them to a transform in font units.
+fn read_transform<'a, F>(format: u8, reader: &mut &'a [u8], child: F) -> Option<Transform2F>
+where
+    F: Fn(&mut &'a [u8]) -> Option<&'a [u8]>,
+{
+    // The "around center" formats transform relative to a center point instead of the origin.
+    let around_center = |transform: Transform2F, center: Vector2F| {
+        Transform2F::from_translation(center) * transform * Transform2F::from_translation(-center)
+    };
+
+    match format {
+        12 | 13 => {
+            let mut affine = child(reader)?;
+            let xx = read_fixed(&mut affine)?;
+            let yx = read_fixed(&mut affine)?;
+            let xy = read_fixed(&mut affine)?;
+            let yy = read_fixed(&mut affine)?;
+            let dx = read_fixed(&mut affine)?;
+            let dy = read_fixed(&mut affine)?;
+            Some(Transform2F::row_major(xx, xy, yx, yy, dx, dy))
+        }
+        14 | 15 => Some(Transform2F::from_translation(read_point(reader)?)),
+        16 | 17 => {
+            let scale_x = read_f2dot14(reader)?;
+            let scale_y = read_f2dot14(reader)?;
+            Some(Transform2F::from_scale(Vector2F::new(scale_x, scale_y)))
+        }
+        18 | 19 => {
+            let scale_x = read_f2dot14(reader)?;
+            let scale_y = read_f2dot14(reader)?;
+            let center = read_point(reader)?;
+            let scale = Transform2F::from_scale(Vector2F::new(scale_x, scale_y));
+            Some(around_center(scale, center))
+        }
+        20 | 21 => Some(Transform2F::from_scale(read_f2dot14(reader)?)),
+        22 | 23 => {
+            let scale = read_f2dot14(reader)?;
+            let center = read_point(reader)?;
+            Some(around_center(Transform2F::from_scale(scale), center))
+        }
+        24 | 25 => Some(Transform2F::from_rotation(read_f2dot14(reader)? * PI)),
+        26 | 27 => {
+            let angle = read_f2dot14(reader)? * PI;
+            let center = read_point(reader)?;
+            Some(around_center(Transform2F::from_rotation(angle), center))
+        }
+        28..=31 => {
+            let x_skew_angle = read_f2dot14(reader)? * PI;
+            let y_skew_angle = read_f2dot14(reader)? * PI;
+            let center = if format >= 30 {
+                read_point(reader)?
+            } else {
+                Vector2F::zero()
+            };
+            // Positive angles skew counterclockwise.
+            let skew =
+                Transform2F::row_major(1.0, -x_skew_angle.tan(), y_skew_angle.tan(), 1.0, 0.0, 0.0);
+            Some(around_center(skew, center))
+        }
+        _ => None,
+    }
+}
+
+fn read_f2dot14(reader: &mut &[u8]) -> Option<f32> {
+    Some(reader.read_i16::<BigEndian>().ok()? as f32 / 16384.0)
+}
+
+fn read_fixed(reader: &mut &[u8]) -> Option<f32> {
+    Some(reader.read_i32::<BigEndian>().ok()? as f32 / 65536.0)
+}
+
+fn read_point(reader: &mut &[u8]) -> Option<Vector2F> {
+    let x = reader.read_i16::<BigEndian>().ok()? as f32;
+    let y = reader.read_i16::<BigEndian>().ok()? as f32;
+    Some(Vector2F::new(x, y))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Extend {
+    Pad,
+    Repeat,
+    Reflect,
+}
+
+struct ColorStop {
+    offset: f32,
+    palette_index: u16,
+    alpha: f32,
+}
+
+struct ColorLine {
+    extend: Extend,
+    stops: Vec<ColorStop>,
+}
+
+impl ColorLine {
+    fn parse(mut reader: &[u8], is_variable: bool) -> Option<ColorLine> {
+        let extend = match reader.read_u8().ok()? {
+            1 => Extend::Repeat,
+            2 => Extend::Reflect,
+            _ => Extend::Pad,
+        };
+        let stop_count = reader.read_u16::<BigEndian>().ok()?;
+        let mut stops = Vec::with_capacity(stop_count as usize);
+        for _ in 0..stop_count {
+            let offset = read_f2dot14(&mut reader)?;
+            let palette_index = reader.read_u16::<BigEndian>().ok()?;
+            let alpha = read_f2dot14(&mut reader)?;
+            if is_variable {
+                let _var_index_base = reader.read_u32::<BigEndian>().ok()?;
+            }
+            stops.push(ColorStop {
+                offset,
+                palette_index,
+                alpha,
+            });
+        }
+        Some(ColorLine { extend, stops })
+    }
+}
+
+/// A color line with its colors resolved and premultiplied, ready to be sampled.
+struct Gradient {
+    extend: Extend,
+    stops: Vec<(f32, [f32; 4])>,
+}
+
+impl Gradient {
+    fn sample(&self, t: f32) -> [f32; 4] {
+        let (first_offset, first_color) = self.stops[0];
+        let (last_offset, last_color) = self.stops[self.stops.len() - 1];
+
+        let span = last_offset - first_offset;
+        let t = if span <= 0.0 {
+            t
+        } else {
+            match self.extend {
+                Extend::Pad => t,
+                Extend::Repeat => first_offset + (t - first_offset).rem_euclid(span),
+                Extend::Reflect => {
+                    let t = (t - first_offset).rem_euclid(2.0 * span);
+                    first_offset + if t > span { 2.0 * span - t } else { t }
+                }
+            }
+        };
+
+        if t <= first_offset {
+            return first_color;
+        }
+        for window in self.stops.windows(2) {
+            let ((offset0, color0), (offset1, color1)) = (window[0], window[1]);
+            if t <= offset1 {
+                if offset1 <= offset0 {
+                    return color1;
+                }
+                let fraction = (t - offset0) / (offset1 - offset0);
+                let mut color = [0.0; 4];
+                for channel in 0..4 {
+                    color[channel] =
+                        color0[channel] + (color1[channel] - color0[channel]) * fraction;
+                }
+                return color;
+            }
+        }
+        last_color
+    }
+}
+
+/// A premultiplied RGBA image the size of the target canvas.
+struct Layer {
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Layer {
+    fn transparent(size: Vector2I) -> Layer {
+        Layer {
+            pixels: vec![[0.0; 4]; size.x() as usize * size.y() as usize],
+        }
+    }
+
+    fn composite(&mut self, source: &Layer, mode: u8) {
+        for (backdrop, source) in self.pixels.iter_mut().zip(source.pixels.iter()) {
+            *backdrop = composite_pixel(*source, *backdrop, mode);
+        }
+    }
+}
+
+struct Renderer<'a, L>
+where
+    L: Loader,
+{
+    loader: &'a L,
+    colr: &'a ColrV1<'a>,
+    palette: Option<&'a ColorPalette>,
+    foreground_color: RgbaColor,
+    size: Vector2I,
+    point_size: f32,
+    /// Maps font units to pixels, before the caller's transform is applied.
+    units_to_pixels: Transform2F,
+    hinting_options: HintingOptions,
+    rasterization_options: RasterizationOptions,
+    depth: u32,
+}
+
+impl<'a, L> Renderer<'a, L>
+where
+    L: Loader,
+{
+    fn render_base_glyph(
+        &mut self,
+        glyph_id: u32,
+        transform: Transform2F,
+    ) -> Result<Layer, GlyphLoadingError> {
+        let paint = match self.colr.base_glyph_paint(glyph_id) {
+            Some(paint) => paint,
+            None => return Ok(Layer::transparent(self.size)),
+        };
+        let mut layer = self.render_paint(paint, transform)?;
+
+        if let Some(clip_box) = self.colr.clip_box(glyph_id) {
+            let clip_rect = transform * clip_box;
+            let width = self.size.x() as usize;
+            for (index, pixel) in layer.pixels.iter_mut().enumerate() {
+                let center =
+                    Vector2F::new((index % width) as f32 + 0.5, (index / width) as f32 + 0.5);
+                if !clip_rect.contains_point(center) {
+                    *pixel = [0.0; 4];
+                }
+            }
+        }
+        Ok(layer)
+    }
+
+    fn render_paint(
+        &mut self,
+        data: &'a [u8],
+        transform: Transform2F,
+    ) -> Result<Layer, GlyphLoadingError> {
+        if self.depth >= MAX_PAINT_DEPTH {
+            warn!("render_paint(): paint graph is too deep; is there a cycle?");
+            return Ok(Layer::transparent(self.size));
+        }
+        let paint = match Paint::parse(data) {
+            Some(paint) => paint,
+            None => {
+                warn!("render_paint(): malformed or unknown paint");
+                return Ok(Layer::transparent(self.size));
+            }
+        };
+
+        self.depth += 1;
+        let result = self.render_parsed_paint(paint, transform);
+        self.depth -= 1;
+        result
+    }
+
+    fn render_parsed_paint(
+        &mut self,
+        paint: Paint<'a>,
+        transform: Transform2F,
+    ) -> Result<Layer, GlyphLoadingError> {
+        match paint {
+            Paint::ColrLayers {
+                first_layer_index,
+                layer_count,
+            } => {
+                let layer_end = match first_layer_index.checked_add(layer_count) {
+                    Some(layer_end) if layer_end <= self.colr.layer_count() => layer_end,
+                    _ => {
+                        warn!("render_paint(): layers out of range of the layer list");
+                        return Ok(Layer::transparent(self.size));
+                    }
+                };
+                let mut layer = Layer::transparent(self.size);
+                for layer_index in first_layer_index..layer_end {
+                    if let Some(paint) = self.colr.layer_paint(layer_index) {
+                        let source = self.render_paint(paint, transform)?;
+                        layer.composite(&source, COMPOSITE_SRC_OVER);
+                    }
+                }
+                Ok(layer)
+            }
+            Paint::Solid {
+                palette_index,
+                alpha,
+            } => {
+                let color = self.resolve_color(palette_index, alpha);
+                let mut layer = Layer::transparent(self.size);
+                layer.pixels.iter_mut().for_each(|pixel| *pixel = color);
+                Ok(layer)
+            }
+            Paint::LinearGradient {
+                color_line,
+                p0,
+                p1,
+                p2,
+            } => {
+                // The color lines are parallel to p0p2, so project p1 onto the perpendicular of
+                // p0p2 to find the direction of the gradient.
+                let normal = Vector2F::new(-(p2 - p0).y(), (p2 - p0).x());
+                let p1 = if normal.square_length() > 0.0 {
+                    p0 + normal * ((p1 - p0).dot(normal) / normal.square_length())
+                } else {
+                    p1
+                };
+                let direction = p1 - p0;
+                let length_squared = direction.square_length();
+                if length_squared == 0.0 {
+                    return Ok(Layer::transparent(self.size));
+                }
+                Ok(self.fill_gradient(&color_line, transform, |point| {
+                    Some((point - p0).dot(direction) / length_squared)
+                }))
+            }
+            Paint::RadialGradient {
+                color_line,
+                center0,
+                radius0,
+                center1,
+                radius1,
+            } => {
+                // Find the largest t for which the point lies on the circle interpolated between
+                // the two circles, with a nonnegative radius.
+                let center_delta = center1 - center0;
+                let radius_delta = radius1 - radius0;
+                let a = center_delta.square_length() - radius_delta * radius_delta;
+                Ok(self.fill_gradient(&color_line, transform, |point| {
+                    let point_delta = point - center0;
+                    let b = point_delta.dot(center_delta) + radius0 * radius_delta;
+                    let c = point_delta.square_length() - radius0 * radius0;
+                    let radius_is_valid = |t: f32| radius0 + t * radius_delta >= 0.0;
+                    if a.abs() < 1e-6 {
+                        if b == 0.0 {
+                            return None;
+                        }
+                        let t = c / (2.0 * b);
+                        return if radius_is_valid(t) { Some(t) } else { None };
+                    }
+                    let discriminant = b * b - a * c;
+                    if discriminant < 0.0 {
+                        return None;
+                    }
+                    let t0 = (b + discriminant.sqrt()) / a;
+                    let t1 = (b - discriminant.sqrt()) / a;
+                    let (t_max, t_min) = if t0 > t1 { (t0, t1) } else { (t1, t0) };
+                    if radius_is_valid(t_max) {
+                        Some(t_max)
+                    } else if radius_is_valid(t_min) {
+                        Some(t_min)
+                    } else {
+                        None
+                    }
+                }))
+            }
+            Paint::SweepGradient {
+                color_line,
+                center,
+                start_angle,
+                end_angle,
+            } => {
+                if start_angle == end_angle {
+                    return Ok(Layer::transparent(self.size));
+                }
+                Ok(self.fill_gradient(&color_line, transform, |point| {
+                    let delta = point - center;
+                    let angle = delta.y().atan2(delta.x()).rem_euclid(2.0 * PI);
+                    Some((angle - start_angle) / (end_angle - start_angle))
+                }))
+            }
+            Paint::Glyph { glyph_id, paint } => {
+                let mut mask = Canvas::new(self.size, Format::A8);
+                self.loader.rasterize_glyph(
+                    &mut mask,
+                    glyph_id,
+                    self.point_size,
+                    transform * self.units_to_pixels.inverse(),
+                    self.hinting_options,
+                    self.rasterization_options,
+                )?;
+                let mut layer = self.render_paint(paint, transform)?;
+                for (pixel, &coverage) in layer.pixels.iter_mut().zip(mask.pixels.iter()) {
+                    let coverage = coverage as f32 / 255.0;
+                    pixel.iter_mut().for_each(|channel| *channel *= coverage);
+                }
+                Ok(layer)
+            }
+            Paint::ColrGlyph { glyph_id } => self.render_base_glyph(glyph_id, transform),
+            Paint::Transform {
+                transform: paint_transform,
+                paint,
+            } => self.render_paint(paint, transform * paint_transform),
+            Paint::Composite {
+                source,
+                mode,
+                backdrop,
+            } => {
+                let source = self.render_paint(source, transform)?;
+                let mut layer = self.render_paint(backdrop, transform)?;
+                layer.composite(&source, mode);
+                Ok(layer)
+            }
+        }
+    }
+
+    /// Fills a layer with a gradient. `parameter` maps a point in font units to the position
+    /// along the color line, or `None` if the gradient doesn't cover that point.
+    fn fill_gradient<F>(
+        &self,
+        color_line: &ColorLine,
+        transform: Transform2F,
+        parameter: F,
+    ) -> Layer
+    where
+        F: Fn(Vector2F) -> Option<f32>,
+    {
+        let mut layer = Layer::transparent(self.size);
+        if color_line.stops.is_empty() || transform.matrix.det() == 0.0 {
+            return layer;
+        }
+
+        let mut stops: Vec<_> = color_line
+            .stops
+            .iter()
+            .map(|stop| {
+                let color = self.resolve_color(stop.palette_index, stop.alpha);
+                (stop.offset, color)
+            })
+            .collect();
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        let gradient = Gradient {
+            extend: color_line.extend,
+            stops,
+        };
+
+        let pixels_to_units = transform.inverse();
+        let width = self.size.x() as usize;
+        for (index, pixel) in layer.pixels.iter_mut().enumerate() {
+            let center = Vector2F::new((index % width) as f32 + 0.5, (index / width) as f32 + 0.5);
+            if let Some(t) = parameter(pixels_to_units * center) {
+                *pixel = gradient.sample(t);
+            }
+        }
+        layer
+    }
+
+    /// Looks up a palette entry (or the foreground color) and returns it premultiplied.
+    fn resolve_color(&self, palette_index: u16, alpha: f32) -> [f32; 4] {
+        let color = if palette_index == FOREGROUND_PALETTE_INDEX {
+            self.foreground_color
+        } else {
+            self.palette
+                .and_then(|palette| palette.colors.get(palette_index as usize))
+                .cloned()
+                .unwrap_or(self.foreground_color)
+        };
+        let alpha = color.a as f32 / 255.0 * alpha.clamp(0.0, 1.0);
+        [
+            color.r as f32 / 255.0 * alpha,
+            color.g as f32 / 255.0 * alpha,
+            color.b as f32 / 255.0 * alpha,
+            alpha,
+        ]
+    }
+}
+
+/// Renders the paint graph of a version 1 color glyph and composites it over an `Rgba32` canvas.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn rasterize_paint<L>(
+    loader: &L,
+    colr: &ColrV1,
+    canvas: &mut Canvas,
+    glyph_id: u32,
+    point_size: f32,
+    transform: Transform2F,
+    hinting_options: HintingOptions,
+    rasterization_options: RasterizationOptions,
+    palette: Option<&ColorPalette>,
+    foreground_color: RgbaColor,
+) -> Result<(), GlyphLoadingError>
+where
+    L: Loader,
+{
+    let units_per_em = loader.metrics().units_per_em as f32;
+    if point_size == 0.0 || units_per_em == 0.0 {
+        return Ok(());
+    }
+    let scale = point_size / units_per_em;
+    let units_to_pixels = Transform2F::from_scale(Vector2F::new(scale, -scale));
+
+    let mut renderer = Renderer {
+        loader,
+        colr,
+        palette,
+        foreground_color,
+        size: canvas.size,
+        point_size,
+        units_to_pixels,
+        hinting_options,
+        rasterization_options,
+        depth: 0,
+    };
+    let layer = renderer.render_base_glyph(glyph_id, transform * units_to_pixels)?;
+
+    let width = canvas.size.x() as usize;
+    for (y, row) in layer.pixels.chunks(width.max(1)).enumerate() {
+        let dest_row_start = y * canvas.stride;
+        let dest_row = &mut canvas.pixels[dest_row_start..(dest_row_start + width * 4)];
+        for (dest, source) in dest_row.chunks_mut(4).zip(row.iter()) {
+            let backdrop = [
+                dest[0] as f32 / 255.0,
+                dest[1] as f32 / 255.0,
+                dest[2] as f32 / 255.0,
+                dest[3] as f32 / 255.0,
+            ];
+            let result = composite_pixel(*source, backdrop, COMPOSITE_SRC_OVER);
+            for channel in 0..4 {
+                dest[channel] = (result[channel].clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+        }
+    }
+    Ok(())
+}
+
+const COMPOSITE_SRC_OVER: u8 = 3;
+
+/// Composites a premultiplied source pixel over a premultiplied backdrop pixel with one of the
+/// `COLR` composite modes.
+fn composite_pixel(source: [f32; 4], backdrop: [f32; 4], mode: u8) -> [f32; 4] {
+    let (source_alpha, backdrop_alpha) = (source[3], backdrop[3]);
+
+    // Porter-Duff operators, as factors applied to the source and the backdrop.
+    let porter_duff = match mode {
+        0 => Some((0.0, 0.0)),
+        1 => Some((1.0, 0.0)),
+        2 => Some((0.0, 1.0)),
+        3 => Some((1.0, 1.0 - source_alpha)),
+        4 => Some((1.0 - backdrop_alpha, 1.0)),
+        5 => Some((backdrop_alpha, 0.0)),
+        6 => Some((0.0, source_alpha)),
+        7 => Some((1.0 - backdrop_alpha, 0.0)),
+        8 => Some((0.0, 1.0 - source_alpha)),
+        9 => Some((backdrop_alpha, 1.0 - source_alpha)),
+        10 => Some((1.0 - backdrop_alpha, source_alpha)),
+        11 => Some((1.0 - backdrop_alpha, 1.0 - source_alpha)),
+        12 => Some((1.0, 1.0)),
+        13..=27 => None,
+        _ => {
+            warn!("composite_pixel(): unknown composite mode {}", mode);
+            Some((1.0, 1.0 - source_alpha))
+        }
+    };
+    if let Some((source_factor, backdrop_factor)) = porter_duff {
+        let mut result = [0.0; 4];
+        for channel in 0..4 {
+            result[channel] =
+                (source[channel] * source_factor + backdrop[channel] * backdrop_factor).min(1.0);
+        }
+        return result;
+    }
+
+    // Blend modes, following the W3C Compositing and Blending specification.
+    let unpremultiply = |color: [f32; 4]| {
+        if color[3] == 0.0 {
+            [0.0; 3]
+        } else {
+            [
+                color[0] / color[3],
+                color[1] / color[3],
+                color[2] / color[3],
+            ]
+        }
+    };
+    let (source_color, backdrop_color) = (unpremultiply(source), unpremultiply(backdrop));
+    let blended = match mode {
+        24 => set_lum(
+            set_sat(source_color, sat(backdrop_color)),
+            lum(backdrop_color),
+        ),
+        25 => set_lum(
+            set_sat(backdrop_color, sat(source_color)),
+            lum(backdrop_color),
+        ),
+        26 => set_lum(source_color, lum(backdrop_color)),
+        27 => set_lum(backdrop_color, lum(source_color)),
+        _ => {
+            let mut blended = [0.0; 3];
+            for channel in 0..3 {
+                blended[channel] =
+                    blend_separable(mode, source_color[channel], backdrop_color[channel]);
+            }
+            blended
+        }
+    };
+
+    let mut result = [0.0; 4];
+    for channel in 0..3 {
+        result[channel] = source[channel] * (1.0 - backdrop_alpha)
+            + backdrop[channel] * (1.0 - source_alpha)
+            + source_alpha * backdrop_alpha * blended[channel];
+    }
+    result[3] = source_alpha + backdrop_alpha - source_alpha * backdrop_alpha;
+    result
+}
+
+fn blend_separable(mode: u8, source: f32, backdrop: f32) -> f32 {
+    let multiply = |source: f32, backdrop: f32| source * backdrop;
+    let screen = |source: f32, backdrop: f32| source + backdrop - source * backdrop;
+    let hard_light = |source: f32, backdrop: f32| {
+        if source <= 0.5 {
+            multiply(2.0 * source, backdrop)
+        } else {
+            screen(2.0 * source - 1.0, backdrop)
+        }
+    };
+    match mode {
+        13 => screen(source, backdrop),
+        14 => hard_light(backdrop, source),
+        15 => source.min(backdrop),
+        16 => source.max(backdrop),
+        17 => {
+            if backdrop == 0.0 {
+                0.0
+            } else if source >= 1.0 {
+                1.0
+            } else {
+                (backdrop / (1.0 - source)).min(1.0)
+            }
+        }
+        18 => {
+            if backdrop >= 1.0 {
+                1.0
+            } else if source <= 0.0 {
+                0.0
+            } else {
+                1.0 - ((1.0 - backdrop) / source).min(1.0)
+            }
+        }
+        19 => hard_light(source, backdrop),
+        20 => {
+            if source <= 0.5 {
+                backdrop - (1.0 - 2.0 * source) * backdrop * (1.0 - backdrop)
+            } else {
+                let d = if backdrop <= 0.25 {
+                    ((16.0 * backdrop - 12.0) * backdrop + 4.0) * backdrop
+                } else {
+                    backdrop.sqrt()
+                };
+                backdrop + (2.0 * source - 1.0) * (d - backdrop)
+            }
+        }
+        21 => (backdrop - source).abs(),
+        22 => source + backdrop - 2.0 * source * backdrop,
+        _ => multiply(source, backdrop),
+    }
+}
+
+fn lum(color: [f32; 3]) -> f32 {
+    0.3 * color[0] + 0.59 * color[1] + 0.11 * color[2]
+}
+
+fn set_lum(color: [f32; 3], lum_value: f32) -> [f32; 3] {
+    let delta = lum_value - lum(color);
+    let color = [color[0] + delta, color[1] + delta, color[2] + delta];
+
+    // Clip the color back into gamut while preserving its luminosity.
+    let l = lum(color);
+    let min = color[0].min(color[1]).min(color[2]);
+    let max = color[0].max(color[1]).max(color[2]);
+    let mut result = color;
+    for channel in result.iter_mut() {
+        if min < 0.0 && l != min {
+            *channel = l + (*channel - l) * l / (l - min);
+        }
+        if max > 1.0 && max != l {
+            *channel = l + (*channel - l) * (1.0 - l) / (max - l);
+        }
+    }
+    result
+}
+
+fn sat(color: [f32; 3]) -> f32 {
+    color[0].max(color[1]).max(color[2]) - color[0].min(color[1]).min(color[2])
+}
+
+fn set_sat(color: [f32; 3], sat_value: f32) -> [f32; 3] {
+    let mut indices = [0, 1, 2];
+    indices.sort_by(|&a, &b| {
+        color[a]
+            .partial_cmp(&color[b])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let (min, mid, max) = (indices[0], indices[1], indices[2]);
+
+    let mut result = [0.0; 3];
+    if color[max] > color[min] {
+        result[mid] = (color[mid] - color[min]) * sat_value / (color[max] - color[min]);
+        result[max] = sat_value;
+    }
+    result
+}
@@ -0,0 +1,292 @@
+// font-kit/src/rasterizer.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A simple antialiasing scanline rasterizer for outlines that the platform rasterizer can't draw,
+//! such as synthesized ones.
+//!
+//! Coverage is computed by accumulating the signed area that each edge contributes to every pixel
+//! it crosses, then summing along each row.
+
+use pathfinder_geometry::line_segment::LineSegment2F;
+use pathfinder_geometry::rect::{RectF, RectI};
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::{Vector2F, Vector2I};
+
+use crate::canvas::{Canvas, Format, RasterizationOptions};
+use crate::outline::{flatten_cubic, flatten_quadratic, Outline, OutlineSink, TransformingSink};
+
+/// The maximum distance, in pixels, that a flattened curve may stray from the true curve.
+pub(crate) const FLATTENING_TOLERANCE: f32 = 0.1;
+
+/// Accumulates the area covered by a set of closed paths within a pixel grid.
+pub(crate) struct Rasterizer {
+    size: Vector2I,
+    // Each row has two extra cells so that edges on the right boundary don't spill into the next
+    // row.
+    stride: usize,
+    accumulation: Vec<f32>,
+    // Every edge drawn, kept for sampling pixel centers.
+    edges: Vec<LineSegment2F>,
+    first_point: Vector2F,
+    current_point: Vector2F,
+}
+
+impl Rasterizer {
+    /// Creates a rasterizer with an empty grid of the given size, in pixels.
+    pub(crate) fn new(size: Vector2I) -> Rasterizer {
+        let size = size.max(Vector2I::default());
+        let stride = size.x() as usize + 2;
+        Rasterizer {
+            size,
+            stride,
+            accumulation: vec![0.0; stride * size.y() as usize],
+            edges: vec![],
+            first_point: Vector2F::zero(),
+            current_point: Vector2F::zero(),
+        }
+    }
+
+    /// Adds a line from `from` to `to` to the paths being filled.
+    pub(crate) fn draw_line(&mut self, from: Vector2F, to: Vector2F) {
+        if from.y() == to.y() {
+            return;
+        }
+        self.edges.push(LineSegment2F::new(from, to));
+
+        // Split the line where it crosses the left and right edges of the grid, so that the parts
+        // outside can be clamped to vertical lines along the edges. Those still contribute their
+        // winding to the pixels to their right, but nothing else.
+        let width = self.size.x() as f32;
+        let mut splits = [0.0, 1.0, 1.0, 1.0];
+        let mut split_count = 1;
+        if from.x() != to.x() {
+            for edge in [0.0, width] {
+                let t = (edge - from.x()) / (to.x() - from.x());
+                if t > 0.0 && t < 1.0 {
+                    splits[split_count] = t;
+                    split_count += 1;
+                }
+            }
+        }
+        splits[split_count] = 1.0;
+        splits[..=split_count].sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let clamp = |point: Vector2F| Vector2F::new(point.x().max(0.0).min(width), point.y());
+        for index in 0..split_count {
+            let start = from.lerp(to, splits[index]);
+            let end = from.lerp(to, splits[index + 1]);
+            self.draw_clipped_line(clamp(start), clamp(end));
+        }
+    }
+
+    fn draw_clipped_line(&mut self, from: Vector2F, to: Vector2F) {
+        if from.y() == to.y() {
+            return;
+        }
+        let (direction, top, bottom) = if from.y() < to.y() {
+            (1.0, from, to)
+        } else {
+            (-1.0, to, from)
+        };
+        let dxdy = (bottom.x() - top.x()) / (bottom.y() - top.y());
+        let height = self.size.y() as f32;
+
+        let y_start = top.y().max(0.0);
+        let y_end = bottom.y().min(height);
+        if y_start >= y_end {
+            return;
+        }
+        let mut x = top.x() + (y_start - top.y()) * dxdy;
+        let mut y = y_start;
+        while y < y_end {
+            let row = y.floor();
+            let next_y = (row + 1.0).min(y_end);
+            let dy = next_y - y;
+            let next_x = x + dxdy * dy;
+            let area = dy * direction;
+            self.accumulate_span(row as usize, x, next_x, area);
+            x = next_x;
+            y = next_y;
+        }
+    }
+
+    // Distributes the signed area of one row's worth of an edge among the cells it crosses.
+    fn accumulate_span(&mut self, row: usize, x0: f32, x1: f32, area: f32) {
+        let accumulation = &mut self.accumulation[(row * self.stride)..((row + 1) * self.stride)];
+        // Rounding error can take the ends of an edge on the left boundary slightly below zero.
+        let (x0, x1) = (x0.max(0.0), x1.max(0.0));
+        let (x0, x1) = if x0 < x1 { (x0, x1) } else { (x1, x0) };
+        let x0_floor = x0.floor();
+        let x0_cell = x0_floor as usize;
+        let x1_ceil = x1.ceil();
+        let x1_cell = x1_ceil as usize;
+
+        if x1_cell <= x0_cell + 1 {
+            // The edge stays within one cell.
+            let x_mid = 0.5 * (x0 + x1) - x0_floor;
+            accumulation[x0_cell] += area - area * x_mid;
+            accumulation[x0_cell + 1] += area * x_mid;
+            return;
+        }
+
+        let inverse_width = (x1 - x0).recip();
+        let x0_fraction = x0 - x0_floor;
+        let first_area = 0.5 * inverse_width * (1.0 - x0_fraction) * (1.0 - x0_fraction);
+        let x1_fraction = x1 - x1_ceil + 1.0;
+        let last_area = 0.5 * inverse_width * x1_fraction * x1_fraction;
+        accumulation[x0_cell] += area * first_area;
+        if x1_cell == x0_cell + 2 {
+            accumulation[x0_cell + 1] += area * (1.0 - first_area - last_area);
+        } else {
+            let second_area = inverse_width * (1.5 - x0_fraction);
+            accumulation[x0_cell + 1] += area * (second_area - first_area);
+            for cell in &mut accumulation[(x0_cell + 2)..(x1_cell - 1)] {
+                *cell += area * inverse_width;
+            }
+            let area_before_last = second_area + (x1_cell - x0_cell - 3) as f32 * inverse_width;
+            accumulation[x1_cell - 1] += area * (1.0 - area_before_last - last_area);
+        }
+        accumulation[x1_cell] += area * last_area;
+    }
+
+    /// Returns the coverage of each pixel, row by row, from 0 to 255.
+    pub(crate) fn coverage(&self) -> Vec<u8> {
+        let width = self.size.x() as usize;
+        let mut coverage = Vec::with_capacity(width * self.size.y() as usize);
+        for row in self.accumulation.chunks(self.stride) {
+            let mut sum = 0.0;
+            for &area in &row[..width] {
+                sum += area;
+                coverage.push((sum.abs().min(1.0) * 255.0).round() as u8);
+            }
+        }
+        coverage
+    }
+
+    /// Returns, row by row, 255 for each pixel whose center lies inside the paths under the
+    /// nonzero rule and 0 for every other pixel.
+    ///
+    /// Unlike thresholding `coverage()`, this keeps straight stems the same width on every row,
+    /// as the platform rasterizers do for bilevel output.
+    pub(crate) fn center_samples(&self) -> Vec<u8> {
+        let width = self.size.x() as usize;
+        let mut samples = vec![0; width * self.size.y() as usize];
+        let mut crossings = vec![];
+        for (y, row) in samples.chunks_mut(width.max(1)).enumerate() {
+            let center_y = y as f32 + 0.5;
+            crossings.clear();
+            for edge in &self.edges {
+                let (from, to) = (edge.from(), edge.to());
+                let winding = if from.y() <= center_y && to.y() > center_y {
+                    1
+                } else if to.y() <= center_y && from.y() > center_y {
+                    -1
+                } else {
+                    continue;
+                };
+                let t = (center_y - from.y()) / (to.y() - from.y());
+                crossings.push((from.x() + (to.x() - from.x()) * t, winding));
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                if winding == 0 {
+                    continue;
+                }
+                // Fill the pixels whose centers lie between these two crossings.
+                let start = (pair[0].0 - 0.5).ceil().clamp(0.0, width as f32) as usize;
+                let end = (pair[1].0 - 0.5).ceil().clamp(0.0, width as f32) as usize;
+                for value in &mut row[start..end.max(start)] {
+                    *value = 255;
+                }
+            }
+        }
+        samples
+    }
+}
+
+impl OutlineSink for Rasterizer {
+    fn move_to(&mut self, to: Vector2F) {
+        self.first_point = to;
+        self.current_point = to;
+    }
+
+    fn line_to(&mut self, to: Vector2F) {
+        self.draw_line(self.current_point, to);
+        self.current_point = to;
+    }
+
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        let from = self.current_point;
+        flatten_quadratic(from, ctrl, to, FLATTENING_TOLERANCE, |point| {
+            self.line_to(point)
+        });
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        let from = self.current_point;
+        flatten_cubic(from, ctrl, to, FLATTENING_TOLERANCE, |point| {
+            self.line_to(point)
+        });
+    }
+
+    fn close(&mut self) {
+        self.draw_line(self.current_point, self.first_point);
+        self.current_point = self.first_point;
+    }
+}
+
+/// Returns the smallest rectangle of whole pixels that contains every point of an outline,
+/// control points included, after it is transformed.
+pub(crate) fn outline_raster_bounds(outline: &Outline, transform: Transform2F) -> Option<RectI> {
+    let mut points = outline
+        .contours
+        .iter()
+        .flat_map(|contour| contour.positions.iter())
+        .map(|&position| transform * position);
+    let first = points.next()?;
+    let bounds = points.fold(RectF::new(first, Vector2F::zero()), |bounds, point| {
+        bounds.union_point(point)
+    });
+    Some(bounds.round_out().to_i32())
+}
+
+/// Fills an outline into a canvas, with `transform` mapping outline coordinates to pixels.
+///
+/// Like the platform rasterizers, this replaces the pixels within the bounds of the outline.
+pub(crate) fn rasterize_outline(
+    canvas: &mut Canvas,
+    outline: &Outline,
+    transform: Transform2F,
+    rasterization_options: RasterizationOptions,
+) {
+    let bounds = match outline_raster_bounds(outline, transform)
+        .and_then(|bounds| bounds.intersection(RectI::new(Vector2I::default(), canvas.size)))
+    {
+        Some(bounds) => bounds,
+        None => return,
+    };
+
+    let mut rasterizer = Rasterizer::new(bounds.size());
+    let origin = Transform2F::from_translation(-bounds.origin().to_f32());
+    outline.copy_to(&mut TransformingSink::new(
+        &mut rasterizer,
+        origin * transform,
+    ));
+    let coverage = match rasterization_options {
+        RasterizationOptions::Bilevel => rasterizer.center_samples(),
+        _ => rasterizer.coverage(),
+    };
+
+    let width = bounds.width() as usize;
+    canvas.blit_from(bounds.origin(), &coverage, bounds.size(), width, Format::A8);
+}
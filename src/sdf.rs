@@ -0,0 +1,498 @@
+// font-kit/src/sdf.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Signed distance fields of glyphs, for drawing text at any scale.
+//!
+//! Distance fields are computed from glyph outlines rather than by the native rasterizer, so every
+//! loader produces the same result. Each pixel stores the distance from its center to the nearest
+//! edge of the glyph, positive inside and negative outside. Distances are mapped so that 128 is on
+//! the edge, 255 is `spread` pixels inside, and 0 is `spread` pixels outside.
+//!
+//! Multi-channel distance fields store a distance per color channel, computed as in Viktor
+//! Chlumsky's msdfgen. The median of the three channels reproduces sharp corners that a single
+//! channel would round off.
+//!
+//! https://github.com/Chlumsky/msdfgen
+
+use pathfinder_geometry::line_segment::LineSegment2F;
+use pathfinder_geometry::rect::{RectF, RectI};
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::{Vector2F, Vector2I};
+
+use crate::canvas::{Canvas, Format};
+use crate::error::GlyphLoadingError;
+use crate::hinting::HintingOptions;
+use crate::loader::Loader;
+use crate::outline::{self, OutlineBuilder, OutlineSink, TransformingSink};
+use crate::rasterizer;
+
+/// The maximum distance, in pixels, that the flattened curves of an outline may stray from the
+/// true curves when measuring distances.
+const TOLERANCE: f32 = 0.01;
+
+// Two edges meet at a corner if the angle between their directions is larger than about 8°.
+const CORNER_CROSS_THRESHOLD: f32 = 0.141;
+
+// Distances that differ by less than this are treated as equal, and the edge that the point is
+// more perpendicular to wins.
+const DISTANCE_EPSILON: f32 = 1e-4;
+
+const RED: u8 = 1;
+const GREEN: u8 = 2;
+const BLUE: u8 = 4;
+const CYAN: u8 = GREEN | BLUE;
+const MAGENTA: u8 = RED | BLUE;
+const YELLOW: u8 = RED | GREEN;
+const WHITE: u8 = RED | GREEN | BLUE;
+
+/// A distance field of a glyph, along with where to draw it.
+#[derive(Debug)]
+pub struct DistanceField {
+    /// The distances, in `Format::A8` for a single channel or `Format::Rgb24` for multiple
+    /// channels.
+    pub canvas: Canvas,
+    /// The pixels that the canvas covers, relative to the glyph origin, with y pointing down.
+    ///
+    /// This is the raster bounds of the glyph outline grown by the spread on every side.
+    pub bounds: RectI,
+    /// The distance, in pixels, that the range of values stored in the canvas spans on either
+    /// side of the edge.
+    pub spread: f32,
+}
+
+/// Returns the median of three channels of a multi-channel distance field, which is the
+/// distance that a renderer compares against 128 to decide whether a pixel is inside the glyph.
+#[inline]
+pub fn median(red: u8, green: u8, blue: u8) -> u8 {
+    red.max(green).min(red.min(green).max(blue))
+}
+
+pub(crate) fn glyph_distance_field<L>(
+    loader: &L,
+    glyph_id: u32,
+    point_size: f32,
+    spread: f32,
+    format: Format,
+) -> Result<DistanceField, GlyphLoadingError>
+where
+    L: Loader,
+{
+    let mut builder = OutlineBuilder::new();
+    loader.outline(glyph_id, HintingOptions::None, &mut builder)?;
+    let outline = builder.into_outline();
+
+    let scale = point_size / loader.metrics().units_per_em as f32;
+    let to_pixels = Transform2F::from_scale(Vector2F::new(scale, -scale));
+    let spread = spread.max(f32::MIN_POSITIVE);
+    let bounds = match rasterizer::outline_raster_bounds(&outline, to_pixels) {
+        Some(bounds) => {
+            let padding = Vector2I::splat(spread.ceil() as i32);
+            RectI::from_points(bounds.origin() - padding, bounds.lower_right() + padding)
+        }
+        None => {
+            return Ok(DistanceField {
+                canvas: Canvas::new(Vector2I::default(), format),
+                bounds: RectI::default(),
+                spread,
+            })
+        }
+    };
+
+    let mut shape = Shape::new();
+    outline.copy_to(&mut TransformingSink::new(&mut shape, to_pixels));
+    shape.finish_contour();
+    if format == Format::Rgb24 {
+        shape.color_edges();
+    }
+
+    let inside = shape.inside_side();
+    let mut canvas = Canvas::new(bounds.size(), format);
+    let bytes_per_pixel = format.bytes_per_pixel() as usize;
+    for y in 0..bounds.height() {
+        for x in 0..bounds.width() {
+            let point = (bounds.origin() + Vector2I::new(x, y)).to_f32() + Vector2F::splat(0.5);
+            let offset = y as usize * canvas.stride + x as usize * bytes_per_pixel;
+            let pixel = &mut canvas.pixels[offset..offset + bytes_per_pixel];
+            let distance = shape.signed_distance(point);
+            if format == Format::Rgb24 {
+                let distances = shape.multichannel_distance(point, distance, inside);
+                for (value, &distance) in pixel.iter_mut().zip(distances.iter()) {
+                    *value = encode(distance, spread);
+                }
+            } else {
+                pixel[0] = encode(distance, spread);
+            }
+        }
+    }
+
+    Ok(DistanceField {
+        canvas,
+        bounds,
+        spread,
+    })
+}
+
+fn encode(distance: f32, spread: f32) -> u8 {
+    ((0.5 + distance / (2.0 * spread)).clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+// The outline of a glyph in pixels, as contours of edges between on-curve points.
+struct Shape {
+    contours: Vec<Vec<Edge>>,
+    current: Vec<Edge>,
+    first_point: Vector2F,
+    last_point: Vector2F,
+}
+
+// A line or curve of an outline, flattened to a polyline, along with its true tangents at either
+// end and the channels it contributes to.
+struct Edge {
+    points: Vec<Vector2F>,
+    bounds: RectF,
+    start_direction: Vector2F,
+    end_direction: Vector2F,
+    color: u8,
+}
+
+// The distance from a point to an edge. `distance` is signed, positive to the left of the edge;
+// `pseudo_distance` extends the ends of the edge along their tangents, as msdfgen does.
+#[derive(Clone, Copy)]
+struct EdgeDistance {
+    distance: f32,
+    orthogonality: f32,
+    pseudo_distance: f32,
+}
+
+impl EdgeDistance {
+    fn is_closer_than(&self, other: &EdgeDistance) -> bool {
+        let (distance, other_distance) = (self.distance.abs(), other.distance.abs());
+        distance < other_distance - DISTANCE_EPSILON
+            || (distance <= other_distance + DISTANCE_EPSILON
+                && self.orthogonality < other.orthogonality)
+    }
+}
+
+impl Shape {
+    fn new() -> Shape {
+        Shape {
+            contours: vec![],
+            current: vec![],
+            first_point: Vector2F::zero(),
+            last_point: Vector2F::zero(),
+        }
+    }
+
+    fn push_edge(&mut self, points: Vec<Vector2F>, start_control: Vector2F, end_control: Vector2F) {
+        let (from, to) = (points[0], points[points.len() - 1]);
+        let direction = |vector: Vector2F, fallback: Vector2F| {
+            if vector.square_length() > 0.0 {
+                vector.normalize()
+            } else {
+                fallback
+            }
+        };
+        let chord = direction(to - from, Vector2F::zero());
+        let (min, max) = points.iter().fold((from, from), |(min, max), &point| {
+            (min.min(point), max.max(point))
+        });
+        self.current.push(Edge {
+            start_direction: direction(start_control - from, chord),
+            end_direction: direction(to - end_control, chord),
+            bounds: RectF::from_points(min, max),
+            points,
+            color: WHITE,
+        });
+        self.last_point = to;
+    }
+
+    fn finish_contour(&mut self) {
+        if self.last_point != self.first_point {
+            let (from, to) = (self.last_point, self.first_point);
+            self.push_edge(vec![from, to], to, from);
+        }
+        if !self.current.is_empty() {
+            self.contours.push(std::mem::take(&mut self.current));
+        }
+    }
+
+    // Colors the edges so that the two edges at every corner share exactly one channel, using
+    // msdfgen's simple edge coloring.
+    fn color_edges(&mut self) {
+        for contour in &mut self.contours {
+            let count = contour.len();
+            let corners: Vec<usize> = (0..count)
+                .filter(|&index| {
+                    let previous = &contour[(index + count - 1) % count];
+                    is_corner(previous.end_direction, contour[index].start_direction)
+                })
+                .collect();
+
+            match corners.len() {
+                0 => {}
+                1 => {
+                    // A teardrop. Split the contour into three runs of edges.
+                    if count >= 3 {
+                        for offset in 0..count {
+                            let color = [MAGENTA, WHITE, YELLOW][(offset * 3 / count).min(2)];
+                            contour[(corners[0] + offset) % count].color = color;
+                        }
+                    }
+                }
+                corner_count => {
+                    let mut spline = 0;
+                    let mut color = switch_color(WHITE, 0);
+                    let initial_color = color;
+                    for offset in 0..count {
+                        let index = (corners[0] + offset) % count;
+                        if spline + 1 < corner_count && corners[spline + 1] == index {
+                            spline += 1;
+                            let banned = if spline == corner_count - 1 {
+                                initial_color
+                            } else {
+                                0
+                            };
+                            color = switch_color(color, banned);
+                        }
+                        contour[index].color = color;
+                    }
+                }
+            }
+        }
+    }
+
+    // The true distance to the outline, positive inside according to the nonzero fill rule.
+    fn signed_distance(&self, point: Vector2F) -> f32 {
+        let mut distance = f32::INFINITY;
+        for edge in self.edges() {
+            if edge.distance_to_bounds(point) < distance {
+                distance = edge
+                    .points
+                    .windows(2)
+                    .map(|segment| distance_to_segment(point, segment[0], segment[1]))
+                    .fold(distance, f32::min);
+            }
+        }
+        if self.winding_number(point) != 0 {
+            distance
+        } else {
+            -distance
+        }
+    }
+
+    // The pseudo-distance for each channel, where `inside` is the side of the edges that is inside
+    // the outline. Where the median of the channels would put the point on the wrong side of the
+    // outline, the true distance is used for every channel instead.
+    fn multichannel_distance(&self, point: Vector2F, true_distance: f32, inside: f32) -> [f32; 3] {
+        let mut distances = [true_distance; 3];
+        for (channel, distance) in [RED, GREEN, BLUE].iter().zip(distances.iter_mut()) {
+            let mut nearest: Option<EdgeDistance> = None;
+            for edge in self.edges().filter(|edge| edge.color & channel != 0) {
+                if let Some(nearest) = nearest {
+                    let limit = nearest.distance.abs() + DISTANCE_EPSILON;
+                    if edge.distance_to_bounds(point) > limit {
+                        continue;
+                    }
+                }
+                let candidate = edge.distance(point);
+                if nearest.map_or(true, |nearest| candidate.is_closer_than(&nearest)) {
+                    nearest = Some(candidate);
+                }
+            }
+            if let Some(nearest) = nearest {
+                *distance = nearest.pseudo_distance * inside;
+            }
+        }
+
+        let median = distances[0]
+            .max(distances[1])
+            .min(distances[0].min(distances[1]).max(distances[2]));
+        if (median > 0.0) != (true_distance > 0.0) {
+            distances = [true_distance; 3];
+        }
+        distances
+    }
+
+    // Returns 1.0 if the inside of the outline is to the left of its edges and -1.0 if it is to
+    // the right, going by the direction that most of the outline winds in.
+    fn inside_side(&self) -> f32 {
+        let area: f32 = self.edges().map(|edge| signed_area(&edge.points)).sum();
+        if area < 0.0 {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+
+    fn winding_number(&self, point: Vector2F) -> i32 {
+        let mut winding = 0;
+        let edges = self.edges().filter(|edge| {
+            edge.bounds.min_y() <= point.y()
+                && edge.bounds.max_y() > point.y()
+                && edge.bounds.max_x() >= point.x()
+        });
+        for segment in edges.flat_map(|edge| edge.points.windows(2)) {
+            let (from, to) = (segment[0], segment[1]);
+            let side = (to - from).det(point - from);
+            if from.y() <= point.y() && to.y() > point.y() && side > 0.0 {
+                winding += 1;
+            } else if to.y() <= point.y() && from.y() > point.y() && side < 0.0 {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    fn edges(&self) -> impl Iterator<Item = &Edge> {
+        self.contours.iter().flatten()
+    }
+}
+
+impl OutlineSink for Shape {
+    fn move_to(&mut self, to: Vector2F) {
+        self.finish_contour();
+        self.first_point = to;
+        self.last_point = to;
+    }
+
+    fn line_to(&mut self, to: Vector2F) {
+        let from = self.last_point;
+        if to != from {
+            self.push_edge(vec![from, to], to, from);
+        }
+    }
+
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        let from = self.last_point;
+        let mut points = vec![from];
+        outline::flatten_quadratic(from, ctrl, to, TOLERANCE, |point| points.push(point));
+        self.push_edge(points, ctrl, ctrl);
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        let from = self.last_point;
+        let mut points = vec![from];
+        outline::flatten_cubic(from, ctrl, to, TOLERANCE, |point| points.push(point));
+        let start_control = if ctrl.from() != from {
+            ctrl.from()
+        } else {
+            ctrl.to()
+        };
+        let end_control = if ctrl.to() != to {
+            ctrl.to()
+        } else {
+            ctrl.from()
+        };
+        self.push_edge(points, start_control, end_control);
+    }
+
+    fn close(&mut self) {
+        self.finish_contour();
+        self.first_point = self.last_point;
+    }
+}
+
+impl Edge {
+    fn distance_to_bounds(&self, point: Vector2F) -> f32 {
+        let outside = (self.bounds.origin() - point).max(point - self.bounds.lower_right());
+        outside.max(Vector2F::zero()).length()
+    }
+
+    fn distance(&self, point: Vector2F) -> EdgeDistance {
+        let segment_count = self.points.len() - 1;
+        let mut nearest = EdgeDistance {
+            distance: f32::INFINITY,
+            orthogonality: 1.0,
+            pseudo_distance: f32::INFINITY,
+        };
+        let mut nearest_segment = (0, 0.0);
+        for index in 0..segment_count {
+            let (from, to) = (self.points[index], self.points[index + 1]);
+            let vector = to - from;
+            let square_length = vector.square_length();
+            if square_length == 0.0 {
+                continue;
+            }
+            let t = (point - from).dot(vector) / square_length;
+            let offset = point - (from + vector * t.clamp(0.0, 1.0));
+            let length = offset.length();
+            let side = if vector.det(point - from) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            let candidate = EdgeDistance {
+                distance: length * side,
+                orthogonality: if length > 0.0 {
+                    (vector.normalize().dot(offset) / length).abs()
+                } else {
+                    0.0
+                },
+                pseudo_distance: length * side,
+            };
+            if candidate.is_closer_than(&nearest) {
+                nearest = candidate;
+                nearest_segment = (index, t);
+            }
+        }
+
+        // Beyond either end of the edge, measure from the line that continues it instead.
+        let (index, t) = nearest_segment;
+        let (end, direction) = if index == 0 && t < 0.0 {
+            (self.points[0], self.start_direction)
+        } else if index + 1 == segment_count && t > 1.0 {
+            (self.points[segment_count], self.end_direction)
+        } else {
+            return nearest;
+        };
+        let pseudo_distance = direction.det(point - end);
+        if pseudo_distance.abs() <= nearest.distance.abs() {
+            nearest.pseudo_distance = pseudo_distance;
+        }
+        nearest
+    }
+}
+
+fn distance_to_segment(point: Vector2F, from: Vector2F, to: Vector2F) -> f32 {
+    let vector = to - from;
+    let square_length = vector.square_length();
+    let t = if square_length > 0.0 {
+        ((point - from).dot(vector) / square_length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (point - (from + vector * t)).length()
+}
+
+fn signed_area(points: &[Vector2F]) -> f32 {
+    points
+        .windows(2)
+        .map(|segment| segment[0].det(segment[1]))
+        .sum::<f32>()
+        * 0.5
+}
+
+fn is_corner(direction_in: Vector2F, direction_out: Vector2F) -> bool {
+    direction_in.dot(direction_out) <= 0.0
+        || direction_in.det(direction_out).abs() > CORNER_CROSS_THRESHOLD
+}
+
+// Moves to the next of cyan, magenta, and yellow, avoiding `banned` where possible, as msdfgen
+// does with a seed of zero.
+fn switch_color(color: u8, banned: u8) -> u8 {
+    let combined = color & banned;
+    if combined == RED || combined == GREEN || combined == BLUE {
+        return combined ^ WHITE;
+    }
+    if color == 0 || color == WHITE {
+        return CYAN;
+    }
+    let shifted = color << 1;
+    (shifted | shifted >> 3) & WHITE
+}
@@ -18,6 +18,7 @@ use crate::font::Font;
 use crate::handle::Handle;
 use crate::matching;
 use crate::properties::Properties;
+use crate::synthesis::Synthesis;
 use std::any::Any;
 
 #[cfg(all(
@@ -140,17 +141,35 @@ pub trait Source: Any {
 
     /// Performs font matching according to the CSS Fonts Level 3 specification and returns the
     /// handle.
+    ///
+    /// If the family lacks the requested weight or style, this quietly returns the closest face.
+    /// Use `select_best_match_with_synthesis` to find out which styles must be synthesized.
     #[inline]
     fn select_best_match(
         &self,
         family_names: &[FamilyName],
         properties: &Properties,
     ) -> Result<Handle, SelectionError> {
+        self.select_best_match_with_synthesis(family_names, properties)
+            .map(|best_match| best_match.handle)
+    }
+
+    /// Performs font matching according to the CSS Fonts Level 3 specification and returns the
+    /// handle, along with the bold and oblique styles that must be synthesized because the
+    /// selected font lacks them.
+    fn select_best_match_with_synthesis(
+        &self,
+        family_names: &[FamilyName],
+        properties: &Properties,
+    ) -> Result<BestMatch, SelectionError> {
         for family_name in family_names {
             if let Ok(family_handle) = self.select_family_by_generic_name(family_name) {
                 let candidates = self.select_descriptions_in_family(&family_handle)?;
                 if let Ok(index) = matching::find_best_match(&candidates, properties) {
-                    return Ok(family_handle.fonts[index].clone());
+                    return Ok(BestMatch {
+                        handle: family_handle.fonts[index].clone(),
+                        synthesis: Synthesis::for_match(properties, &candidates[index]),
+                    });
                 }
             }
         }
@@ -180,3 +199,12 @@ pub trait Source: Any {
     /// trait object.
     fn as_mut_any(&mut self) -> &mut dyn Any;
 }
+
+/// The font selected by `Source::select_best_match_with_synthesis`.
+#[derive(Clone, Debug)]
+pub struct BestMatch {
+    /// The handle of the closest matching font.
+    pub handle: Handle,
+    /// The styles that must be synthesized because the font lacks them.
+    pub synthesis: Synthesis,
+}
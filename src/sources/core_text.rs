@@ -32,7 +32,7 @@ use crate::font::Font;
 use crate::handle::Handle;
 use crate::loaders::core_text::{self as core_text_loader, FONT_WEIGHT_MAPPING};
 use crate::properties::{Properties, Stretch, Weight};
-use crate::source::Source;
+use crate::source::{BestMatch, Source};
 use crate::utils;
 
 /// A source that contains the installed fonts on macOS.
@@ -109,6 +109,17 @@ impl CoreTextSource {
     ) -> Result<Handle, SelectionError> {
         <Self as Source>::select_best_match(self, family_names, properties)
     }
+
+    /// Performs font matching according to the CSS Fonts Level 3 specification and returns the
+    /// handle, along with the styles that must be synthesized because the font lacks them.
+    #[inline]
+    pub fn select_best_match_with_synthesis(
+        &self,
+        family_names: &[FamilyName],
+        properties: &Properties,
+    ) -> Result<BestMatch, SelectionError> {
+        <Self as Source>::select_best_match_with_synthesis(self, family_names, properties)
+    }
 }
 
 impl Source for CoreTextSource {
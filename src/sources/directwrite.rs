@@ -19,7 +19,7 @@ use crate::family_handle::FamilyHandle;
 use crate::family_name::FamilyName;
 use crate::handle::Handle;
 use crate::properties::Properties;
-use crate::source::Source;
+use crate::source::{BestMatch, Source};
 
 /// A source that contains the installed fonts on Windows.
 #[allow(missing_debug_implementations)]
@@ -100,6 +100,17 @@ impl DirectWriteSource {
         <Self as Source>::select_best_match(self, family_names, properties)
     }
 
+    /// Performs font matching according to the CSS Fonts Level 3 specification and returns the
+    /// handle, along with the styles that must be synthesized because the font lacks them.
+    #[inline]
+    pub fn select_best_match_with_synthesis(
+        &self,
+        family_names: &[FamilyName],
+        properties: &Properties,
+    ) -> Result<BestMatch, SelectionError> {
+        <Self as Source>::select_best_match_with_synthesis(self, family_names, properties)
+    }
+
     fn create_handle_from_dwrite_font(&self, dwrite_font: DWriteFont) -> Handle {
         let dwrite_font_face = dwrite_font.create_font_face();
         let dwrite_font_files = dwrite_font_face.get_files();
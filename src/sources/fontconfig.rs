@@ -20,7 +20,7 @@ use crate::family_handle::FamilyHandle;
 use crate::family_name::FamilyName;
 use crate::handle::Handle;
 use crate::properties::Properties;
-use crate::source::Source;
+use crate::source::{BestMatch, Source};
 use std::any::Any;
 
 /// A source that contains the fonts installed on the system, as reported by the Fontconfig
@@ -212,6 +212,17 @@ impl FontconfigSource {
     ) -> Result<Handle, SelectionError> {
         <Self as Source>::select_best_match(self, family_names, properties)
     }
+
+    /// Performs font matching according to the CSS Fonts Level 3 specification and returns the
+    /// handle, along with the styles that must be synthesized because the font lacks them.
+    #[inline]
+    pub fn select_best_match_with_synthesis(
+        &self,
+        family_names: &[FamilyName],
+        properties: &Properties,
+    ) -> Result<BestMatch, SelectionError> {
+        <Self as Source>::select_best_match_with_synthesis(self, family_names, properties)
+    }
 }
 
 impl Source for FontconfigSource {
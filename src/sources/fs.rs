@@ -37,7 +37,7 @@ use crate::file_type::FileType;
 use crate::font::Font;
 use crate::handle::Handle;
 use crate::properties::Properties;
-use crate::source::Source;
+use crate::source::{BestMatch, Source};
 use crate::sources::mem::MemSource;
 
 /// A source that loads fonts from a directory or directories on disk.
@@ -145,6 +145,17 @@ impl FsSource {
     ) -> Result<Handle, SelectionError> {
         <Self as Source>::select_best_match(self, family_names, properties)
     }
+
+    /// Performs font matching according to the CSS Fonts Level 3 specification and returns the
+    /// handle, along with the styles that must be synthesized because the font lacks them.
+    #[inline]
+    pub fn select_best_match_with_synthesis(
+        &self,
+        family_names: &[FamilyName],
+        properties: &Properties,
+    ) -> Result<BestMatch, SelectionError> {
+        <Self as Source>::select_best_match_with_synthesis(self, family_names, properties)
+    }
 }
 
 impl Source for FsSource {
@@ -16,7 +16,7 @@ use crate::family_name::FamilyName;
 use crate::font::Font;
 use crate::handle::Handle;
 use crate::properties::Properties;
-use crate::source::Source;
+use crate::source::{BestMatch, Source};
 use std::any::Any;
 
 /// A source that keeps fonts in memory.
@@ -33,7 +33,8 @@ impl MemSource {
 
     /// Creates a new memory source that contains the given set of font handles.
     ///
-    /// The fonts referenced by the handles are eagerly loaded into memory.
+    /// The fonts referenced by the handles are eagerly loaded into memory. The named instances of
+    /// variable fonts are added as faces of their own.
     pub fn from_fonts<I>(fonts: I) -> Result<MemSource, FontLoadingError>
     where
         I: Iterator<Item = Handle>,
@@ -48,7 +49,8 @@ impl MemSource {
 
     /// Add an existing font handle to a `MemSource`.
     ///
-    /// Returns the font that was just added.
+    /// Returns the font that was just added. If it is a variable font, its named instances are
+    /// added as faces of their own.
     ///
     /// Note that adding fonts to an existing `MemSource` is slower than creating a new one from a
     /// `Handle` iterator, since this method sorts after every addition, rather than once at the
@@ -153,6 +155,17 @@ impl MemSource {
     ) -> Result<Handle, SelectionError> {
         <Self as Source>::select_best_match(self, family_names, properties)
     }
+
+    /// Performs font matching according to the CSS Fonts Level 3 specification and returns the
+    /// handle, along with the styles that must be synthesized because the font lacks them.
+    #[inline]
+    pub fn select_best_match_with_synthesis(
+        &self,
+        family_names: &[FamilyName],
+        properties: &Properties,
+    ) -> Result<BestMatch, SelectionError> {
+        <Self as Source>::select_best_match_with_synthesis(self, family_names, properties)
+    }
 }
 
 impl Source for MemSource {
@@ -186,18 +199,68 @@ impl Source for MemSource {
 }
 
 /// Adds a font, but doesn't sort. Returns the font that was created to check for validity.
+///
+/// Each named instance of a variable font is added as a face of its own, unless it has the same
+/// PostScript name as the default instance.
 fn add_font(handle: Handle, families: &mut Vec<FamilyEntry>) -> Result<Font, FontLoadingError> {
     let font = Font::from_handle(&handle)?;
+    let first_entry_index = families.len();
     if let Some(postscript_name) = font.postscript_name() {
         families.push(FamilyEntry {
             family_name: font.family_name(),
             postscript_name,
-            font: handle,
+            font: handle.clone(),
         })
     }
+
+    // Only expand handles that don't already select a named instance.
+    if font_index(&handle) >> 16 == 0 {
+        for instance_index in 1..=font.named_instance_count() {
+            let instance_handle = named_instance_handle(&handle, instance_index);
+            let instance = match Font::from_handle(&instance_handle) {
+                Ok(instance) => instance,
+                Err(_) => continue,
+            };
+            let postscript_name = match instance.postscript_name() {
+                Some(postscript_name) => postscript_name,
+                None => continue,
+            };
+            if families[first_entry_index..]
+                .iter()
+                .any(|entry| entry.postscript_name == postscript_name)
+            {
+                continue;
+            }
+            families.push(FamilyEntry {
+                family_name: instance.family_name(),
+                postscript_name,
+                font: instance_handle,
+            })
+        }
+    }
+
     Ok(font)
 }
 
+fn font_index(handle: &Handle) -> u32 {
+    match *handle {
+        Handle::Path { font_index, .. } | Handle::Memory { font_index, .. } => font_index,
+    }
+}
+
+fn named_instance_handle(handle: &Handle, instance_index: u32) -> Handle {
+    match *handle {
+        Handle::Path {
+            ref path,
+            font_index,
+        } => Handle::from_path(path.clone(), instance_index << 16 | font_index),
+        Handle::Memory {
+            ref bytes,
+            font_index,
+        } => Handle::from_memory(bytes.clone(), instance_index << 16 | font_index),
+    }
+}
+
 struct FamilyEntry {
     family_name: String,
     postscript_name: String,
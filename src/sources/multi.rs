@@ -18,7 +18,7 @@ use crate::family_handle::FamilyHandle;
 use crate::family_name::FamilyName;
 use crate::handle::Handle;
 use crate::properties::Properties;
-use crate::source::Source;
+use crate::source::{BestMatch, Source};
 use std::{
     any::Any,
     fmt,
@@ -45,7 +45,7 @@ impl MultiSource {
     pub fn all_fonts(&self) -> Result<Vec<Handle>, SelectionError> {
         let mut handles = vec![];
         for subsource in &self.subsources {
-            handles.extend(subsource.all_fonts()?.into_iter())
+            handles.extend(subsource.all_fonts()?)
         }
         Ok(handles)
     }
@@ -54,7 +54,7 @@ impl MultiSource {
     pub fn all_families(&self) -> Result<Vec<String>, SelectionError> {
         let mut families = vec![];
         for subsource in &self.subsources {
-            families.extend(subsource.all_families()?.into_iter())
+            families.extend(subsource.all_families()?)
         }
         Ok(families)
     }
@@ -97,6 +97,17 @@ impl MultiSource {
         <Self as Source>::select_best_match(self, family_names, properties)
     }
 
+    /// Performs font matching according to the CSS Fonts Level 3 specification and returns the
+    /// handle, along with the styles that must be synthesized because the font lacks them.
+    #[inline]
+    pub fn select_best_match_with_synthesis(
+        &self,
+        family_names: &[FamilyName],
+        properties: &Properties,
+    ) -> Result<BestMatch, SelectionError> {
+        <Self as Source>::select_best_match_with_synthesis(self, family_names, properties)
+    }
+
     /// Returns an iterator over the contained sources.
     #[inline]
     pub fn iter(&self) -> MultiIter<'_> {
@@ -0,0 +1,348 @@
+// font-kit/src/stroke.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Stroking glyph outlines, for outlined and haloed text.
+
+use pathfinder_geometry::line_segment::LineSegment2F;
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::Vector2F;
+use std::f32::consts::PI;
+
+use crate::error::GlyphLoadingError;
+use crate::hinting::HintingOptions;
+use crate::loader::Loader;
+use crate::outline::{self, Contour, Outline, OutlineBuilder, OutlineSink, PointFlags};
+use crate::rasterizer::FLATTENING_TOLERANCE;
+
+/// The maximum distance, in font units, that the flattened curves of a stroked outline may stray
+/// from the true stroke.
+const OUTLINE_TOLERANCE: f32 = 0.1;
+
+/// How two segments of a stroke are joined at a corner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet, unless that point is further away than the
+    /// miter limit allows, in which case the corner is beveled.
+    Miter,
+    /// The corner is rounded off with a circular arc.
+    Round,
+    /// The corner is cut off with a straight line.
+    Bevel,
+}
+
+/// How the ends of a stroke are drawn.
+///
+/// Glyph contours are always closed, so the cap only affects contours that collapse to a single
+/// point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineCap {
+    /// The stroke stops at the end point. A contour that collapses to a point draws nothing.
+    Butt,
+    /// The stroke ends in a semicircle. A contour that collapses to a point draws a dot.
+    Round,
+    /// The stroke ends in a half square. A contour that collapses to a point draws a square.
+    Square,
+}
+
+/// The parameters of a stroke.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StrokeStyle {
+    /// The full width of the stroke, centered on the outline.
+    pub width: f32,
+    /// How segments are joined at corners.
+    pub line_join: LineJoin,
+    /// How the ends of the stroke are drawn.
+    pub line_cap: LineCap,
+    /// The maximum ratio of the length of a miter to the width of the stroke, beyond which
+    /// `LineJoin::Miter` corners are beveled instead.
+    pub miter_limit: f32,
+}
+
+impl StrokeStyle {
+    /// Creates a stroke of the given width with round joins and caps, which suits halos and
+    /// outlined text.
+    #[inline]
+    pub fn new(width: f32) -> StrokeStyle {
+        StrokeStyle {
+            width,
+            line_join: LineJoin::Round,
+            line_cap: LineCap::Round,
+            miter_limit: 4.0,
+        }
+    }
+}
+
+impl Default for StrokeStyle {
+    #[inline]
+    fn default() -> StrokeStyle {
+        StrokeStyle::new(1.0)
+    }
+}
+
+/// Strokes an outline, returning the border of the stroke as a new outline in the same units.
+///
+/// Each contour becomes two contours, one on either side of it, running in opposite directions.
+/// The result is meant to be filled with the nonzero winding rule, as glyph outlines are. Curves
+/// are flattened to line segments along the way.
+pub fn stroke_outline(outline: &Outline, style: &StrokeStyle) -> Outline {
+    stroke_outline_with_tolerance(outline, style, OUTLINE_TOLERANCE)
+}
+
+pub(crate) fn stroke_outline_with_tolerance(
+    outline: &Outline,
+    style: &StrokeStyle,
+    tolerance: f32,
+) -> Outline {
+    let mut result = Outline::new();
+    if style.width <= 0.0 {
+        return result;
+    }
+    let mut flattener = Flattener::new(tolerance);
+    outline.copy_to(&mut flattener);
+    let stroker = Stroker {
+        style,
+        half_width: style.width * 0.5,
+        tolerance,
+    };
+    for polygon in &flattener.polygons {
+        stroker.stroke_polygon(polygon, &mut result);
+    }
+    result
+}
+
+/// Returns the stroked outline of a glyph, in pixels, as `rasterize_glyph_stroke` draws it.
+pub(crate) fn glyph_stroke_in_pixels<L>(
+    loader: &L,
+    glyph_id: u32,
+    point_size: f32,
+    transform: Transform2F,
+    style: &StrokeStyle,
+    hinting_options: HintingOptions,
+) -> Result<Outline, GlyphLoadingError>
+where
+    L: Loader,
+{
+    let mut builder = OutlineBuilder::new();
+    loader.outline(glyph_id, hinting_options, &mut builder)?;
+    let mut outline = builder.into_outline();
+
+    let scale = point_size / loader.metrics().units_per_em as f32;
+    let to_pixels = transform * Transform2F::from_scale(Vector2F::new(scale, -scale));
+    for contour in &mut outline.contours {
+        for position in &mut contour.positions {
+            *position = to_pixels * *position;
+        }
+    }
+    Ok(stroke_outline_with_tolerance(
+        &outline,
+        style,
+        FLATTENING_TOLERANCE,
+    ))
+}
+
+// Collects the contours of an outline as closed polygons with no repeated points.
+struct Flattener {
+    tolerance: f32,
+    polygons: Vec<Vec<Vector2F>>,
+    current: Vec<Vector2F>,
+}
+
+impl Flattener {
+    fn new(tolerance: f32) -> Flattener {
+        Flattener {
+            tolerance,
+            polygons: vec![],
+            current: vec![],
+        }
+    }
+
+    fn push(&mut self, point: Vector2F) {
+        if self.current.last() != Some(&point) {
+            self.current.push(point);
+        }
+    }
+
+    fn finish_polygon(&mut self) {
+        let mut polygon = std::mem::take(&mut self.current);
+        while polygon.len() > 1 && polygon.first() == polygon.last() {
+            polygon.pop();
+        }
+        if !polygon.is_empty() {
+            self.polygons.push(polygon);
+        }
+    }
+}
+
+impl OutlineSink for Flattener {
+    fn move_to(&mut self, to: Vector2F) {
+        self.finish_polygon();
+        self.current.push(to);
+    }
+
+    fn line_to(&mut self, to: Vector2F) {
+        self.push(to);
+    }
+
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        let from = *self.current.last().unwrap_or(&ctrl);
+        let tolerance = self.tolerance;
+        outline::flatten_quadratic(from, ctrl, to, tolerance, |point| self.push(point));
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        let from = *self.current.last().unwrap_or(&ctrl.from());
+        let tolerance = self.tolerance;
+        outline::flatten_cubic(from, ctrl, to, tolerance, |point| self.push(point));
+    }
+
+    fn close(&mut self) {
+        self.finish_polygon();
+    }
+}
+
+struct Stroker<'a> {
+    style: &'a StrokeStyle,
+    half_width: f32,
+    tolerance: f32,
+}
+
+impl<'a> Stroker<'a> {
+    fn stroke_polygon(&self, polygon: &[Vector2F], result: &mut Outline) {
+        if polygon.len() < 2 {
+            self.stroke_point(polygon[0], result);
+            return;
+        }
+
+        let left = self.border(polygon, 1.0);
+        let mut right = self.border(polygon, -1.0);
+        right.reverse();
+        for border in [left, right] {
+            let mut contour = Contour::new();
+            for position in border {
+                contour.push(position, PointFlags::empty());
+            }
+            result.contours.push(contour);
+        }
+    }
+
+    // Draws the cap of a contour that collapses to a single point.
+    fn stroke_point(&self, point: Vector2F, result: &mut Outline) {
+        let mut contour = Contour::new();
+        match self.style.line_cap {
+            LineCap::Butt => return,
+            LineCap::Round => {
+                let radius = Vector2F::new(self.half_width, 0.0);
+                for position in self.arc(point, radius, 2.0 * PI) {
+                    contour.push(position, PointFlags::empty());
+                }
+            }
+            LineCap::Square => {
+                let half_width = self.half_width;
+                for corner in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                    let offset = Vector2F::new(corner.0, corner.1) * half_width;
+                    contour.push(point + offset, PointFlags::empty());
+                }
+            }
+        }
+        result.contours.push(contour);
+    }
+
+    // Offsets a closed polygon by half the stroke width to one side, given by `side`, adding joins
+    // at the corners.
+    fn border(&self, polygon: &[Vector2F], side: f32) -> Vec<Vector2F> {
+        let count = polygon.len();
+        let mut border = vec![];
+        for index in 0..count {
+            let previous = polygon[(index + count - 1) % count];
+            let point = polygon[index];
+            let next = polygon[(index + 1) % count];
+            let direction_in = (point - previous).normalize();
+            let direction_out = (next - point).normalize();
+            let normal_in = perpendicular(direction_in) * side;
+            let normal_out = perpendicular(direction_out) * side;
+            let offset_in = point + normal_in * self.half_width;
+            let offset_out = point + normal_out * self.half_width;
+
+            let cross = direction_in.det(direction_out);
+            let dot = direction_in.dot(direction_out);
+            if cross.abs() < 1e-4 && dot > 0.0 {
+                // No turn at all.
+                border.push(offset_in);
+                continue;
+            }
+
+            // The side that the path turns away from is the outside of the corner. A full
+            // reversal has two outsides.
+            let outer = cross * side < 0.0 || (cross.abs() < 1e-4 && dot < 0.0);
+            if !outer {
+                // Route the inside of the corner through the vertex. The borders overlap there,
+                // which the nonzero fill rule absorbs.
+                border.extend_from_slice(&[offset_in, point, offset_out]);
+                continue;
+            }
+
+            border.push(offset_in);
+            match self.style.line_join {
+                LineJoin::Bevel => {}
+                LineJoin::Miter => {
+                    // The miter length relative to the stroke width is 1 / sin(θ / 2), where θ
+                    // is the angle between the segments.
+                    let cos_half_turn = ((1.0 + normal_in.dot(normal_out)) * 0.5).sqrt();
+                    if cos_half_turn > 0.0 && cos_half_turn.recip() <= self.style.miter_limit {
+                        let miter = (normal_in + normal_out)
+                            * (self.half_width / (1.0 + normal_in.dot(normal_out)));
+                        border.push(point + miter);
+                    }
+                }
+                LineJoin::Round => {
+                    let angle = if cross.abs() < 1e-4 {
+                        // Go around the far side of a reversal, through the point straight ahead.
+                        -side * PI
+                    } else {
+                        normal_in.det(normal_out).atan2(normal_in.dot(normal_out))
+                    };
+                    let arc = self.arc(point, normal_in * self.half_width, angle);
+                    border.extend(arc.into_iter().skip(1));
+                }
+            }
+            border.push(offset_out);
+        }
+        border
+    }
+
+    // Returns points along a circular arc around `center`, starting at `center + radius` and
+    // sweeping counterclockwise by `angle` radians (clockwise if it's negative).
+    fn arc(&self, center: Vector2F, radius: Vector2F, angle: f32) -> Vec<Vector2F> {
+        let length = radius.length();
+        let step = if length > self.tolerance {
+            2.0 * (1.0 - self.tolerance / length).acos()
+        } else {
+            PI * 0.5
+        };
+        let segments = ((angle.abs() / step).ceil() as usize).clamp(1, 256);
+        (0..=segments)
+            .map(|index| {
+                let theta = angle * index as f32 / segments as f32;
+                let (sin, cos) = theta.sin_cos();
+                center
+                    + Vector2F::new(
+                        radius.x() * cos - radius.y() * sin,
+                        radius.x() * sin + radius.y() * cos,
+                    )
+            })
+            .collect()
+    }
+}
+
+// Rotates a vector a quarter turn counterclockwise.
+#[inline]
+fn perpendicular(vector: Vector2F) -> Vector2F {
+    Vector2F::new(-vector.y(), vector.x())
+}
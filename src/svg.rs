@@ -0,0 +1,127 @@
+// font-kit/src/svg.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Glyphs described by SVG documents in the OpenType `SVG ` table.
+//!
+//! `font-kit` does not render these glyphs itself; it only finds and decompresses the documents so
+//! that they can be handed to an SVG renderer.
+
+use byteorder::{BigEndian, ReadBytesExt};
+use flate2::read::GzDecoder;
+use std::io::Read;
+use std::ops::RangeInclusive;
+
+pub(crate) const SVG_TABLE_TAG: u32 = 0x53564720;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// The longest document, in bytes, that a compressed SVG document may decompress to. Longer ones
+/// are treated as malformed, so that a small font can't make font-kit allocate without bound.
+pub const MAX_DOCUMENT_LENGTH: usize = 32 * 1024 * 1024;
+
+/// An SVG document from the `SVG ` table, along with the glyphs that it describes.
+///
+/// Each glyph is the element with the ID `glyph<glyph ID>` in the document, for example `glyph42`.
+/// Unless the document says otherwise, its coordinates are in font units, with the glyph origin at
+/// the origin and the Y axis pointing down.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SvgGlyphDocument {
+    /// The SVG document, decompressed if it was stored compressed with gzip.
+    pub document: Vec<u8>,
+    /// The IDs of the glyphs that the document describes.
+    pub glyph_ids: RangeInclusive<u32>,
+}
+
+/// Finds the record of the document that describes the given glyph, returning the glyph range
+/// along with the stored document data.
+fn find_document(svg: &[u8], glyph_id: u32) -> Option<(RangeInclusive<u32>, &[u8])> {
+    let mut reader = svg;
+    let _version = reader.read_u16::<BigEndian>().ok()?;
+    let document_list_offset = reader.read_u32::<BigEndian>().ok()? as usize;
+
+    let document_list = svg.get(document_list_offset..)?;
+    let mut reader = document_list;
+    let entry_count = reader.read_u16::<BigEndian>().ok()? as usize;
+    let records = reader.get(..(entry_count * 12))?;
+
+    // Document records are sorted by glyph ID and don't overlap, so we can binary search them.
+    let (mut low, mut high) = (0, entry_count);
+    while low < high {
+        let mid = (low + high) / 2;
+        let mut record = &records[(mid * 12)..];
+        let start_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+        let end_glyph_id = record.read_u16::<BigEndian>().ok()? as u32;
+        if end_glyph_id < glyph_id {
+            low = mid + 1;
+        } else if start_glyph_id > glyph_id {
+            high = mid;
+        } else {
+            let document_offset = record.read_u32::<BigEndian>().ok()? as usize;
+            let document_length = record.read_u32::<BigEndian>().ok()? as usize;
+            let document =
+                document_list.get(document_offset..(document_offset + document_length))?;
+            return Some((start_glyph_id..=end_glyph_id, document));
+        }
+    }
+    None
+}
+
+/// Returns true if the given glyph is described by a document in an `SVG ` table.
+pub(crate) fn has_document(svg: &[u8], glyph_id: u32) -> bool {
+    find_document(svg, glyph_id).is_some()
+}
+
+/// Looks up and decompresses the document that describes the given glyph in an `SVG ` table.
+pub(crate) fn read_document(svg: &[u8], glyph_id: u32) -> Option<SvgGlyphDocument> {
+    let (glyph_ids, data) = find_document(svg, glyph_id)?;
+    let document = if data.starts_with(&GZIP_MAGIC) {
+        decompress(data, MAX_DOCUMENT_LENGTH)?
+    } else {
+        data.to_vec()
+    };
+    Some(SvgGlyphDocument {
+        document,
+        glyph_ids,
+    })
+}
+
+// Decompresses a gzip stream, or returns `None` if it is malformed or longer than `max_length`.
+fn decompress(data: &[u8], max_length: usize) -> Option<Vec<u8>> {
+    let mut document = vec![];
+    GzDecoder::new(data)
+        .take(max_length as u64 + 1)
+        .read_to_end(&mut document)
+        .ok()?;
+    if document.len() > max_length {
+        return None;
+    }
+    Some(document)
+}
+
+#[cfg(test)]
+mod test {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    use crate::svg::decompress;
+
+    #[test]
+    fn decompress_stops_at_max_length() {
+        let document = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>".repeat(1000);
+        let mut encoder = GzEncoder::new(vec![], Compression::best());
+        encoder.write_all(&document).unwrap();
+        let data = encoder.finish().unwrap();
+
+        assert_eq!(decompress(&data, document.len()), Some(document.clone()));
+        assert_eq!(decompress(&data, document.len() - 1), None);
+        assert_eq!(decompress(&data[..(data.len() / 2)], document.len()), None);
+    }
+}
@@ -0,0 +1,457 @@
+// font-kit/src/svg_path.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! SVG path data, for moving outlines in and out of SVG documents.
+//!
+//! Path data is the language of the `d` attribute of the SVG `<path>` element, as described in
+//! the SVG 1.1 specification.
+//!
+//! https://www.w3.org/TR/SVG11/paths.html#PathData
+
+use pathfinder_geometry::line_segment::LineSegment2F;
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::Vector2F;
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::fmt::Write;
+
+use crate::error::SvgPathError;
+use crate::outline::OutlineSink;
+
+/// How outline coordinates map to SVG user units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SvgPathOptions {
+    /// The number of SVG user units per outline unit. For glyph outlines in font units, this is
+    /// usually the point size divided by the units per em.
+    pub scale: f32,
+    /// Whether to negate Y coordinates, as Y points up in font units but down in SVG.
+    pub flip_y: bool,
+}
+
+impl Default for SvgPathOptions {
+    #[inline]
+    fn default() -> SvgPathOptions {
+        SvgPathOptions {
+            scale: 1.0,
+            flip_y: false,
+        }
+    }
+}
+
+impl SvgPathOptions {
+    fn transform(&self) -> Transform2F {
+        let y_scale = if self.flip_y { -self.scale } else { self.scale };
+        Transform2F::from_scale(Vector2F::new(self.scale, y_scale))
+    }
+}
+
+/// Writes the paths sent to it as SVG path data.
+///
+/// Each command is written in absolute coordinates, separated from the next by a space.
+#[derive(Clone, Debug)]
+pub struct SvgPathSink {
+    path_data: String,
+    transform: Transform2F,
+}
+
+impl SvgPathSink {
+    /// Creates a sink with no path data yet.
+    #[inline]
+    pub fn new(options: &SvgPathOptions) -> SvgPathSink {
+        SvgPathSink {
+            path_data: String::new(),
+            transform: options.transform(),
+        }
+    }
+
+    /// Returns the path data so far.
+    #[inline]
+    pub fn path_data(&self) -> &str {
+        &self.path_data
+    }
+
+    /// Consumes this sink and returns its path data.
+    #[inline]
+    pub fn into_path_data(self) -> String {
+        self.path_data
+    }
+
+    fn write_command(&mut self, command: char, points: &[Vector2F]) {
+        if !self.path_data.is_empty() {
+            self.path_data.push(' ');
+        }
+        self.path_data.push(command);
+        for (index, &point) in points.iter().enumerate() {
+            if index > 0 {
+                self.path_data.push(' ');
+            }
+            let point = self.transform * point;
+            // Adding zero turns negative zero, which `Display` would write as `-0`, into zero.
+            write!(self.path_data, "{} {}", point.x() + 0.0, point.y() + 0.0).unwrap();
+        }
+    }
+}
+
+impl OutlineSink for SvgPathSink {
+    #[inline]
+    fn move_to(&mut self, to: Vector2F) {
+        self.write_command('M', &[to]);
+    }
+
+    #[inline]
+    fn line_to(&mut self, to: Vector2F) {
+        self.write_command('L', &[to]);
+    }
+
+    #[inline]
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        self.write_command('Q', &[ctrl, to]);
+    }
+
+    #[inline]
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        self.write_command('C', &[ctrl.from(), ctrl.to(), to]);
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.write_command('Z', &[]);
+    }
+}
+
+/// Reads SVG path data and sends the paths that it describes to `sink`.
+///
+/// Every command is supported. Relative commands are made absolute, horizontal and vertical
+/// lines become lines, smooth curves get their reflected control points, and elliptical arcs are
+/// approximated with cubic curves. As glyph contours are always closed, so is every subpath, even
+/// those that don't end with `Z`.
+///
+/// If the path data is invalid, the paths before the error have already been sent to `sink`.
+pub fn read_path_data<S>(path_data: &str, sink: &mut S) -> Result<(), SvgPathError>
+where
+    S: OutlineSink,
+{
+    let mut parser = Parser {
+        data: path_data.as_bytes(),
+        position: 0,
+    };
+    let mut command = None;
+    let mut command_offset = 0;
+    let mut subpath = Subpath::None;
+    let (mut current_point, mut start_point) = (Vector2F::zero(), Vector2F::zero());
+    // The control point that smooth curves reflect, if the last command drew the right kind of
+    // curve.
+    let mut last_cubic_ctrl = None;
+    let mut last_quadratic_ctrl = None;
+
+    loop {
+        parser.skip_separators();
+        let next = match parser.peek() {
+            Some(next) => next,
+            None => break,
+        };
+        if next.is_ascii_alphabetic() {
+            command = Some(next);
+            command_offset = parser.position;
+            parser.position += 1;
+        } else if !parser.at_number() || matches!(command, None | Some(b'Z') | Some(b'z')) {
+            return Err(SvgPathError::UnexpectedCharacter(parser.position));
+        }
+        let upper_command = command.unwrap().to_ascii_uppercase();
+        let relative = command.unwrap().is_ascii_lowercase();
+        let origin = if relative {
+            current_point
+        } else {
+            Vector2F::zero()
+        };
+
+        match upper_command {
+            b'M' | b'Z' => {}
+            b'L' | b'H' | b'V' | b'C' | b'S' | b'Q' | b'T' | b'A' => match subpath {
+                Subpath::Open => {}
+                Subpath::Closed => {
+                    sink.move_to(start_point);
+                    subpath = Subpath::Open;
+                }
+                Subpath::None => return Err(SvgPathError::MissingMoveTo),
+            },
+            _ => return Err(SvgPathError::UnexpectedCharacter(command_offset)),
+        }
+
+        let (mut cubic_ctrl, mut quadratic_ctrl) = (None, None);
+        match upper_command {
+            b'M' => {
+                let to = origin + parser.point(command_offset)?;
+                if subpath == Subpath::Open {
+                    sink.close();
+                }
+                sink.move_to(to);
+                subpath = Subpath::Open;
+                current_point = to;
+                start_point = to;
+                // Further coordinate pairs are lines.
+                command = Some(if relative { b'l' } else { b'L' });
+            }
+            b'Z' => {
+                if subpath == Subpath::Open {
+                    sink.close();
+                    subpath = Subpath::Closed;
+                }
+                current_point = start_point;
+            }
+            b'L' => {
+                current_point = origin + parser.point(command_offset)?;
+                sink.line_to(current_point);
+            }
+            b'H' => {
+                let x = origin.x() + parser.number(command_offset)?;
+                current_point = Vector2F::new(x, current_point.y());
+                sink.line_to(current_point);
+            }
+            b'V' => {
+                let y = origin.y() + parser.number(command_offset)?;
+                current_point = Vector2F::new(current_point.x(), y);
+                sink.line_to(current_point);
+            }
+            b'C' | b'S' => {
+                let ctrl0 = if upper_command == b'C' {
+                    origin + parser.point(command_offset)?
+                } else {
+                    reflect(last_cubic_ctrl, current_point)
+                };
+                let ctrl1 = origin + parser.point(command_offset)?;
+                let to = origin + parser.point(command_offset)?;
+                sink.cubic_curve_to(LineSegment2F::new(ctrl0, ctrl1), to);
+                cubic_ctrl = Some(ctrl1);
+                current_point = to;
+            }
+            b'Q' | b'T' => {
+                let ctrl = if upper_command == b'Q' {
+                    origin + parser.point(command_offset)?
+                } else {
+                    reflect(last_quadratic_ctrl, current_point)
+                };
+                let to = origin + parser.point(command_offset)?;
+                sink.quadratic_curve_to(ctrl, to);
+                quadratic_ctrl = Some(ctrl);
+                current_point = to;
+            }
+            _ => {
+                let radii = parser.point(command_offset)?;
+                let x_axis_rotation = parser.number(command_offset)?;
+                let large_arc = parser.flag(command_offset)?;
+                let sweep = parser.flag(command_offset)?;
+                let to = origin + parser.point(command_offset)?;
+                arc_to(
+                    sink,
+                    current_point,
+                    radii,
+                    x_axis_rotation.to_radians(),
+                    large_arc,
+                    sweep,
+                    to,
+                );
+                current_point = to;
+            }
+        }
+        last_cubic_ctrl = cubic_ctrl;
+        last_quadratic_ctrl = quadratic_ctrl;
+    }
+
+    if subpath == Subpath::Open {
+        sink.close();
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Subpath {
+    // Nothing has been drawn yet.
+    None,
+    // A subpath has been started and not closed.
+    Open,
+    // The last subpath has been closed, and drawing again starts a new one at the same point.
+    Closed,
+}
+
+// Returns the first control point of a smooth curve, which is the reflection of the previous
+// curve's last control point if the previous command drew that kind of curve.
+fn reflect(ctrl: Option<Vector2F>, current_point: Vector2F) -> Vector2F {
+    match ctrl {
+        Some(ctrl) => current_point * 2.0 - ctrl,
+        None => current_point,
+    }
+}
+
+// Approximates an elliptical arc with cubic curves of at most a quarter turn each, converting it
+// to center parameterization as in appendix F.6 of the SVG 1.1 specification.
+#[allow(clippy::too_many_arguments)]
+fn arc_to<S>(
+    sink: &mut S,
+    from: Vector2F,
+    radii: Vector2F,
+    x_axis_rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Vector2F,
+) where
+    S: OutlineSink,
+{
+    if from == to {
+        return;
+    }
+    let mut radii = Vector2F::new(radii.x().abs(), radii.y().abs());
+    if radii.x() == 0.0 || radii.y() == 0.0 {
+        sink.line_to(to);
+        return;
+    }
+
+    let rotation = Transform2F::from_rotation(x_axis_rotation);
+    let point = rotation.inverse() * ((from - to) * 0.5);
+
+    // Scale up radii that are too small to reach the end point.
+    let lambda = (point / radii).square_length();
+    if lambda > 1.0 {
+        radii *= lambda.sqrt();
+    }
+
+    let (rx2, ry2) = (radii.x() * radii.x(), radii.y() * radii.y());
+    let (px2, py2) = (point.x() * point.x(), point.y() * point.y());
+    let numerator = rx2 * ry2 - rx2 * py2 - ry2 * px2;
+    let denominator = rx2 * py2 + ry2 * px2;
+    let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let center = Vector2F::new(
+        radii.x() * point.y() / radii.y(),
+        -radii.y() * point.x() / radii.x(),
+    ) * coefficient;
+
+    let start = (point - center) / radii;
+    let end = (-point - center) / radii;
+    let start_angle = start.y().atan2(start.x());
+    let cross = start.x() * end.y() - start.y() * end.x();
+    let mut sweep_angle = cross.atan2(start.dot(end));
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= 2.0 * PI;
+    } else if sweep && sweep_angle < 0.0 {
+        sweep_angle += 2.0 * PI;
+    }
+
+    // Maps points on the unit circle to the ellipse.
+    let to_ellipse = Transform2F::from_translation((from + to) * 0.5)
+        * rotation
+        * Transform2F::from_translation(center)
+        * Transform2F::from_scale(radii);
+
+    let segment_count = (sweep_angle.abs() / FRAC_PI_2 - 0.001).ceil().max(1.0) as usize;
+    let segment_angle = sweep_angle / segment_count as f32;
+    let handle_length = 4.0 / 3.0 * (segment_angle / 4.0).tan();
+    for index in 0..segment_count {
+        let angle0 = start_angle + segment_angle * index as f32;
+        let angle1 = angle0 + segment_angle;
+        let (unit0, unit1) = (
+            Vector2F::new(angle0.cos(), angle0.sin()),
+            Vector2F::new(angle1.cos(), angle1.sin()),
+        );
+        let ctrl0 = unit0 + Vector2F::new(-unit0.y(), unit0.x()) * handle_length;
+        let ctrl1 = unit1 - Vector2F::new(-unit1.y(), unit1.x()) * handle_length;
+        let end = if index + 1 == segment_count {
+            to
+        } else {
+            to_ellipse * unit1
+        };
+        sink.cubic_curve_to(
+            LineSegment2F::new(to_ellipse * ctrl0, to_ellipse * ctrl1),
+            end,
+        );
+    }
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.position).cloned()
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r' | b'\x0c' | b',') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn at_number(&self) -> bool {
+        matches!(self.peek(), Some(b'0'..=b'9' | b'+' | b'-' | b'.'))
+    }
+
+    fn number(&mut self, command_offset: usize) -> Result<f32, SvgPathError> {
+        self.skip_separators();
+        let start = self.position;
+        if let Some(b'+' | b'-') = self.peek() {
+            self.position += 1;
+        }
+        let mut digit_count = self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            digit_count += self.skip_digits();
+        }
+        if digit_count == 0 {
+            self.position = start;
+            return Err(SvgPathError::MissingArguments(command_offset));
+        }
+
+        // An exponent needs digits, so that `2e` is read as `2` followed by something else.
+        if let Some(b'e' | b'E') = self.peek() {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.position += 1;
+            }
+            if self.skip_digits() == 0 {
+                self.position = mantissa_end;
+            }
+        }
+
+        // The bytes are all ASCII, so they are valid UTF-8.
+        let text = std::str::from_utf8(&self.data[start..self.position]).unwrap();
+        text.parse()
+            .map_err(|_| SvgPathError::MissingArguments(command_offset))
+    }
+
+    fn point(&mut self, command_offset: usize) -> Result<Vector2F, SvgPathError> {
+        let x = self.number(command_offset)?;
+        let y = self.number(command_offset)?;
+        Ok(Vector2F::new(x, y))
+    }
+
+    // Arc flags are single digits, which need no separator after them.
+    fn flag(&mut self, command_offset: usize) -> Result<bool, SvgPathError> {
+        self.skip_separators();
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(SvgPathError::MissingArguments(command_offset)),
+        };
+        self.position += 1;
+        Ok(flag)
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.position;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+        self.position - start
+    }
+}
@@ -0,0 +1,330 @@
+// font-kit/src/synthesis.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Synthetic bold and oblique styles for families that lack real ones, as CSS `font-synthesis`
+//! describes.
+//!
+//! https://drafts.csswg.org/css-fonts-4/#font-synthesis
+
+use pathfinder_geometry::rect::{RectF, RectI};
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::Vector2F;
+
+use crate::canvas::{Canvas, RasterizationOptions};
+use crate::error::GlyphLoadingError;
+use crate::font::Font;
+use crate::hinting::HintingOptions;
+use crate::loader::Loader;
+use crate::outline::{Outline, OutlineBuilder, OutlineSink};
+use crate::properties::{Properties, Style, Weight};
+use crate::rasterizer;
+
+/// The angle, in degrees, by which synthetic obliques are slanted. This is the default angle of
+/// `font-style: oblique` in CSS.
+pub const OBLIQUE_ANGLE: f32 = 14.0;
+
+/// How much synthetic bold widens strokes, as a fraction of the em.
+pub const EMBOLDEN_STRENGTH: f32 = 1.0 / 24.0;
+
+// Requests for weights at least this heavy are bold, and faces lighter than it are not.
+const BOLD_THRESHOLD: f32 = 600.0;
+
+/// The styles that must be synthesized because the selected font lacks them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Synthesis {
+    /// Whether strokes must be thickened to make the font look bold.
+    pub bold: bool,
+    /// Whether glyphs must be slanted to make the font look italic or oblique.
+    pub oblique: bool,
+}
+
+impl Synthesis {
+    /// Determines the synthesis needed when a font with the `matched` properties is used for a
+    /// request for the `requested` properties.
+    ///
+    /// Bold is synthesized when a weight of 600 or more is requested but the font is lighter than
+    /// that. An oblique is synthesized when an italic or oblique style is requested but the font
+    /// is upright.
+    pub fn for_match(requested: &Properties, matched: &Properties) -> Synthesis {
+        Synthesis {
+            bold: requested.weight.0 >= BOLD_THRESHOLD && matched.weight.0 < BOLD_THRESHOLD,
+            oblique: requested.style != Style::Normal && matched.style == Style::Normal,
+        }
+    }
+
+    /// Returns true if nothing needs to be synthesized.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        !self.bold && !self.oblique
+    }
+}
+
+/// A view of a font that applies synthetic bold and oblique styles to its glyphs.
+///
+/// Outlines, bounds, advances, and rasterized glyphs all reflect the synthesized styles. Glyphs
+/// without outlines, such as bitmap glyphs, are slanted but not emboldened.
+///
+/// Synthetic bold glyphs are rasterized from their outlines by font-kit's own rasterizer rather
+/// than the font's loader. The outline is grid-fitted as the hinting options request before it is
+/// emboldened, but the rasterizer has no subpixel antialiasing, so
+/// `RasterizationOptions::SubpixelAa` draws them with grayscale coverage in all three channels.
+#[derive(Clone, Debug)]
+pub struct SyntheticFont<F = Font>
+where
+    F: Loader,
+{
+    font: F,
+    synthesis: Synthesis,
+}
+
+impl<F> SyntheticFont<F>
+where
+    F: Loader,
+{
+    /// Wraps a font so that the given styles are synthesized.
+    #[inline]
+    pub fn new(font: F, synthesis: Synthesis) -> SyntheticFont<F> {
+        SyntheticFont { font, synthesis }
+    }
+
+    /// Returns the underlying font.
+    #[inline]
+    pub fn font(&self) -> &F {
+        &self.font
+    }
+
+    /// Unwraps the underlying font.
+    #[inline]
+    pub fn into_font(self) -> F {
+        self.font
+    }
+
+    /// Returns the styles that are synthesized.
+    #[inline]
+    pub fn synthesis(&self) -> Synthesis {
+        self.synthesis
+    }
+
+    /// Returns the properties of the font as they appear once the styles are synthesized.
+    pub fn properties(&self) -> Properties {
+        let mut properties = self.font.properties();
+        if self.synthesis.bold && properties.weight.0 < Weight::BOLD.0 {
+            properties.weight = Weight::BOLD;
+        }
+        if self.synthesis.oblique && properties.style == Style::Normal {
+            properties.style = Style::Oblique;
+        }
+        properties
+    }
+
+    /// Returns the distance that synthetic bold widens strokes by, in font units.
+    pub fn embolden_strength(&self) -> f32 {
+        if self.synthesis.bold {
+            self.font.metrics().units_per_em as f32 * EMBOLDEN_STRENGTH
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the distance from the origin of the glyph with the given ID to the next, in font
+    /// units. Synthetic bold widens the advance by the embolden strength.
+    pub fn advance(&self, glyph_id: u32) -> Result<Vector2F, GlyphLoadingError> {
+        Ok(self.font.advance(glyph_id)? + Vector2F::new(self.embolden_strength(), 0.0))
+    }
+
+    /// Returns the boundaries of a glyph in font units, once the styles are synthesized.
+    pub fn typographic_bounds(&self, glyph_id: u32) -> Result<RectF, GlyphLoadingError> {
+        let bounds = self.font.typographic_bounds(glyph_id)?;
+        let strength = self.embolden_strength();
+        let bounds = RectF::new(bounds.origin(), bounds.size() + Vector2F::splat(strength));
+        Ok(self.font_unit_transform() * bounds)
+    }
+
+    /// Sends the vector path for a glyph, with the styles synthesized, to a sink.
+    pub fn outline<S>(
+        &self,
+        glyph_id: u32,
+        hinting_mode: HintingOptions,
+        sink: &mut S,
+    ) -> Result<(), GlyphLoadingError>
+    where
+        S: OutlineSink,
+    {
+        self.synthesized_outline(glyph_id, hinting_mode)?
+            .copy_to(sink);
+        Ok(())
+    }
+
+    /// Returns the pixel boundaries that the glyph will take up when rendered with
+    /// `rasterize_glyph()`, once the styles are synthesized.
+    pub fn raster_bounds(
+        &self,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<RectI, GlyphLoadingError> {
+        if self.synthesis.bold {
+            let outline = self.synthesized_outline(glyph_id, hinting_options)?;
+            let to_pixels = transform * self.font_units_to_pixels(point_size);
+            if let Some(bounds) = rasterizer::outline_raster_bounds(&outline, to_pixels) {
+                return Ok(bounds);
+            }
+        }
+        self.font.raster_bounds(
+            glyph_id,
+            point_size,
+            transform * self.pixel_transform(),
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    /// Rasterizes a glyph to a canvas with the given size and transform, once the styles are
+    /// synthesized.
+    ///
+    /// Synthetic obliques are drawn by the platform rasterizer with the slant added to
+    /// `transform`. Synthetic bold outlines are drawn by font-kit itself, in grayscale even when
+    /// subpixel antialiasing is requested.
+    pub fn rasterize_glyph(
+        &self,
+        canvas: &mut Canvas,
+        glyph_id: u32,
+        point_size: f32,
+        transform: Transform2F,
+        hinting_options: HintingOptions,
+        rasterization_options: RasterizationOptions,
+    ) -> Result<(), GlyphLoadingError> {
+        if self.synthesis.bold {
+            let outline = self.synthesized_outline(glyph_id, hinting_options)?;
+            if !outline.contours.is_empty() {
+                let to_pixels = transform * self.font_units_to_pixels(point_size);
+                rasterizer::rasterize_outline(canvas, &outline, to_pixels, rasterization_options);
+                return Ok(());
+            }
+        }
+        self.font.rasterize_glyph(
+            canvas,
+            glyph_id,
+            point_size,
+            transform * self.pixel_transform(),
+            hinting_options,
+            rasterization_options,
+        )
+    }
+
+    fn synthesized_outline(
+        &self,
+        glyph_id: u32,
+        hinting_mode: HintingOptions,
+    ) -> Result<Outline, GlyphLoadingError> {
+        let mut builder = OutlineBuilder::new();
+        self.font.outline(glyph_id, hinting_mode, &mut builder)?;
+        let mut outline = builder.into_outline();
+        if self.synthesis.bold {
+            embolden(&mut outline, self.embolden_strength());
+        }
+        if self.synthesis.oblique {
+            let transform = self.font_unit_transform();
+            for contour in &mut outline.contours {
+                for position in &mut contour.positions {
+                    *position = transform * *position;
+                }
+            }
+        }
+        Ok(outline)
+    }
+
+    // The slant in font units, where y points up.
+    fn font_unit_transform(&self) -> Transform2F {
+        if self.synthesis.oblique {
+            Transform2F::row_major(1.0, oblique_skew(), 0.0, 1.0, 0.0, 0.0)
+        } else {
+            Transform2F::default()
+        }
+    }
+
+    // The slant in pixels, where y points down.
+    fn pixel_transform(&self) -> Transform2F {
+        if self.synthesis.oblique {
+            Transform2F::row_major(1.0, -oblique_skew(), 0.0, 1.0, 0.0, 0.0)
+        } else {
+            Transform2F::default()
+        }
+    }
+
+    fn font_units_to_pixels(&self, point_size: f32) -> Transform2F {
+        let scale = point_size / self.font.metrics().units_per_em as f32;
+        Transform2F::from_scale(Vector2F::new(scale, -scale))
+    }
+}
+
+fn oblique_skew() -> f32 {
+    OBLIQUE_ANGLE.to_radians().tan()
+}
+
+/// Thickens the strokes of an outline by moving every point outward by half of `strength`, then
+/// shifts it so that the left and bottom edges stay where they were, as FreeType does.
+fn embolden(outline: &mut Outline, strength: f32) {
+    let half_strength = strength * 0.5;
+
+    // TrueType outlines wind clockwise and PostScript ones counterclockwise. Find which this one
+    // uses so that points move away from the filled area.
+    let area: f32 = outline
+        .contours
+        .iter()
+        .map(|contour| signed_area(&contour.positions))
+        .sum();
+    let outward = if area < 0.0 { 1.0 } else { -1.0 };
+    let normal = |from: Vector2F, to: Vector2F| {
+        let direction = (to - from).normalize();
+        Vector2F::new(-direction.y(), direction.x()) * outward
+    };
+
+    for contour in &mut outline.contours {
+        let positions = contour.positions.clone();
+        let count = positions.len();
+        for (index, &position) in positions.iter().enumerate() {
+            let neighbors = |step: usize| {
+                (1..count)
+                    .map(|offset| positions[(index + step * offset) % count])
+                    .find(|&neighbor| neighbor != position)
+            };
+            let previous = neighbors(count - 1);
+            let next = neighbors(1);
+            let shift = match (previous, next) {
+                (Some(previous), Some(next)) => {
+                    let (normal_in, normal_out) =
+                        (normal(previous, position), normal(position, next));
+                    // Move along the bisector of the two normals far enough that both adjacent
+                    // edges move by half the strength. Leave very sharp corners alone rather than
+                    // sending them off to infinity.
+                    let denominator = 1.0 + normal_in.dot(normal_out);
+                    if denominator > 1.0 / 16.0 {
+                        (normal_in + normal_out) * (half_strength / denominator)
+                    } else {
+                        Vector2F::zero()
+                    }
+                }
+                _ => Vector2F::zero(),
+            };
+            contour.positions[index] = position + shift + Vector2F::splat(half_strength);
+        }
+    }
+}
+
+fn signed_area(positions: &[Vector2F]) -> f32 {
+    let count = positions.len();
+    (0..count)
+        .map(|index| positions[index].det(positions[(index + 1) % count]))
+        .sum::<f32>()
+        * 0.5
+}
@@ -0,0 +1,58 @@
+// font-kit/src/variations.rs
+//
+// Copyright © 2026 The Pathfinder Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Axes and coordinates of OpenType variable fonts.
+//!
+//! Variable fonts describe their design space in the `fvar` table. Each axis is identified by a
+//! four-character tag such as `wght` (weight) or `wdth` (width), packed big-endian into a `u32`
+//! the same way table tags are.
+
+/// A single axis of variation in a variable font, as described by the `fvar` table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VariationAxis {
+    /// The four-character tag that identifies this axis, e.g. `wght`.
+    pub tag: u32,
+    /// The minimum coordinate value for this axis.
+    pub min_value: f32,
+    /// The default coordinate value for this axis.
+    pub default_value: f32,
+    /// The maximum coordinate value for this axis.
+    pub max_value: f32,
+    /// The ID of the `name` table entry that provides a display name for this axis.
+    pub name_id: u16,
+}
+
+/// A position along a single variation axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Variation {
+    /// The four-character tag that identifies the axis, e.g. `wght`.
+    pub tag: u32,
+    /// The user-space coordinate value along that axis.
+    pub value: f32,
+}
+
+impl Variation {
+    /// Creates a new variation from an axis tag and a coordinate value.
+    #[inline]
+    pub fn new(tag: u32, value: f32) -> Variation {
+        Variation { tag, value }
+    }
+}
+
+/// The tag of the standard weight axis, `wght`.
+pub const WEIGHT_AXIS: u32 = 0x77676874;
+/// The tag of the standard width axis, `wdth`.
+pub const WIDTH_AXIS: u32 = 0x77647468;
+/// The tag of the standard slant axis, `slnt`.
+pub const SLANT_AXIS: u32 = 0x736c6e74;
+/// The tag of the standard italic axis, `ital`.
+pub const ITALIC_AXIS: u32 = 0x6974616c;
+/// The tag of the standard optical size axis, `opsz`.
+pub const OPTICAL_SIZE_AXIS: u32 = 0x6f70737a;
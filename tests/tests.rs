@@ -10,37 +10,80 @@
 
 // General tests.
 
+use font_kit::bitmap::{BitmapStrike, EmbeddedImageFormat, FixedSize};
 use font_kit::canvas::{Canvas, Format, RasterizationOptions};
-use font_kit::family_name::FamilyName;
+use font_kit::color::{ColorLayer, ColorPalette, LayerColor, RgbaColor};
+use font_kit::error::{GlyphLoadingError, SvgPathError};
 use font_kit::file_type::FileType;
 use font_kit::font::Font;
+use font_kit::gamma::GammaLut;
 use font_kit::hinting::HintingOptions;
-use font_kit::outline::{Contour, Outline, OutlineBuilder, PointFlags};
-use font_kit::properties::{Properties, Stretch, Weight};
+use font_kit::layout::{Layout, LayoutOptions};
+use font_kit::names::{name_id, platform_id, NameRecord};
+use font_kit::outline::{
+    BoundsSink, Contour, FlatteningSink, Outline, OutlineBuilder, OutlineSink, PointFlags,
+    TransformingSink,
+};
+use font_kit::properties::{Style, Weight};
+use font_kit::stroke::{LineJoin, StrokeStyle};
+use font_kit::svg::SvgGlyphDocument;
+use font_kit::svg_path::{SvgPathOptions, SvgPathSink};
+use font_kit::synthesis::{Synthesis, SyntheticFont};
+use pathfinder_geometry::line_segment::LineSegment2F;
 use pathfinder_geometry::rect::{RectF, RectI};
-use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::transform2d::{Matrix2x2F, Transform2F};
 use pathfinder_geometry::vector::{Vector2F, Vector2I};
 use std::fs::File;
 use std::io::Read;
 use std::sync::Arc;
 
+#[cfg(feature = "atlas")]
+use font_kit::atlas::{AtlasOptions, DirtyRegion, FontId, GlyphAtlas, GlyphKey};
+#[cfg(feature = "atlas")]
+use font_kit::error::AtlasError;
+#[cfg(feature = "source")]
+use font_kit::family_name::FamilyName;
+#[cfg(feature = "source")]
+use font_kit::handle::Handle;
+#[cfg(feature = "source")]
+use font_kit::properties::{Properties, Stretch};
 #[cfg(feature = "source")]
 use font_kit::source::SystemSource;
+#[cfg(feature = "source")]
+use font_kit::sources::mem::MemSource;
 
 static TEST_FONT_FILE_PATH: &str = "resources/tests/eb-garamond/EBGaramond12-Regular.otf";
 static TEST_FONT_POSTSCRIPT_NAME: &str = "EBGaramond12-Regular";
 static TEST_FONT_COLLECTION_FILE_PATH: &str = "resources/tests/eb-garamond/EBGaramond12.otc";
+#[cfg(feature = "source")]
+static FILE_PATH_EB_GARAMOND_ITALIC_OTF: &str =
+    "resources/tests/eb-garamond/EBGaramond12-Italic.otf";
 static TEST_FONT_COLLECTION_POSTSCRIPT_NAME: [&str; 2] =
     ["EBGaramond12-Regular", "EBGaramond12-Italic"];
 
 static FILE_PATH_EB_GARAMOND_TTF: &str = "resources/tests/eb-garamond/EBGaramond12-Regular.ttf";
 static FILE_PATH_INCONSOLATA_TTF: &str = "resources/tests/inconsolata/Inconsolata-Regular.ttf";
-
-#[cfg(not(target_os = "linux"))]
+#[cfg(feature = "source")]
+static FILE_PATH_VARIABLE_TTF: &str = "resources/tests/font-kit-variable/FontKitVariable.ttf";
+static FILE_PATH_COLOR_TTF: &str = "resources/tests/font-kit-color/FontKitColor.ttf";
+static FILE_PATH_COLOR_V1_TTF: &str = "resources/tests/font-kit-color/FontKitColorV1.ttf";
+static FILE_PATH_CBDT_TTF: &str = "resources/tests/font-kit-bitmap/FontKitCBDT.ttf";
+static FILE_PATH_CBDT_INDEX_TTF: &str = "resources/tests/font-kit-bitmap/FontKitCBDTIndex.ttf";
+static FILE_PATH_SBIX_TTF: &str = "resources/tests/font-kit-bitmap/FontKitSbix.ttf";
+static FILE_PATH_SVG_TTF: &str = "resources/tests/font-kit-svg/FontKitSVG.ttf";
+static FILE_PATH_VERTICAL_TTF: &str = "resources/tests/font-kit-vertical/FontKitVertical.ttf";
+static FILE_PATH_VERTICAL_ORIGIN_TTF: &str =
+    "resources/tests/font-kit-vertical/FontKitVerticalOrigin.ttf";
+static FILE_PATH_KERNING_TTF: &str = "resources/tests/font-kit-kerning/FontKitKerning.ttf";
+static FILE_PATH_KERN_TTF: &str = "resources/tests/font-kit-kerning/FontKitKern.ttf";
+static FILE_PATH_NAMES_TTF: &str = "resources/tests/font-kit-names/FontKitNames.ttf";
+
+#[cfg(all(feature = "source", not(target_os = "linux")))]
 static KNOWN_SYSTEM_FONT_NAME: &'static str = "Arial";
-#[cfg(target_os = "linux")]
+#[cfg(all(feature = "source", target_os = "linux"))]
 static KNOWN_SYSTEM_FONT_NAME: &str = "DejaVu Sans";
 
+#[cfg(feature = "source")]
 static SFNT_VERSIONS: [[u8; 4]; 4] = [
     [0x00, 0x01, 0x00, 0x00],
     [b'O', b'T', b'T', b'O'],
@@ -48,6 +91,7 @@ static SFNT_VERSIONS: [[u8; 4]; 4] = [
     [b't', b'y', b'p', b'1'],
 ];
 
+#[cfg(feature = "source")]
 const OPENTYPE_TABLE_TAG_HEAD: u32 = 0x68656164;
 
 #[cfg(feature = "source")]
@@ -215,6 +259,7 @@ pub fn get_glyph_outline() {
 
 // Right now, only FreeType can do hinting.
 #[cfg(all(
+    not(feature = "loader-opentype-default"),
     not(any(target_os = "macos", target_os = "ios", target_family = "windows")),
     feature = "loader-freetype-default",
     feature = "source"
@@ -260,6 +305,7 @@ pub fn get_vertically_hinted_glyph_outline() {
 }
 
 #[cfg(all(
+    not(feature = "loader-opentype-default"),
     feature = "source",
     not(feature = "loader-freetype-default"),
     not(any(target_os = "macos", target_os = "ios", target_family = "windows"))
@@ -306,6 +352,7 @@ pub fn get_vertically_hinted_glyph_outline() {
 
 // Right now, only FreeType can do hinting.
 #[cfg(all(
+    not(feature = "loader-opentype-default"),
     not(any(target_os = "macos", target_os = "ios", target_family = "windows")),
     feature = "loader-freetype-default",
     feature = "source"
@@ -351,6 +398,7 @@ pub fn get_fully_hinted_glyph_outline() {
 }
 
 #[cfg(all(
+    not(feature = "loader-opentype-default"),
     feature = "source",
     not(feature = "loader-freetype-default"),
     not(any(target_os = "macos", target_os = "ios", target_family = "windows"))
@@ -430,6 +478,23 @@ pub fn get_fully_hinted_glyph_outline() {
     );
 }
 
+// The OpenType loader does no hinting, so the hinting tests above don't apply to it.
+#[cfg(feature = "loader-opentype")]
+#[test]
+pub fn opentype_loader_does_no_hinting() {
+    let font = font_kit::loaders::opentype::Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    assert!(font.supports_hinting_options(HintingOptions::None, false));
+    assert!(font.supports_hinting_options(HintingOptions::None, true));
+    for &hinting_options in &[
+        HintingOptions::Vertical(16.0),
+        HintingOptions::VerticalSubpixel(16.0),
+        HintingOptions::Full(16.0),
+    ] {
+        assert!(!font.supports_hinting_options(hinting_options, false));
+        assert!(!font.supports_hinting_options(hinting_options, true));
+    }
+}
+
 #[test]
 pub fn get_empty_glyph_outline() {
     let mut file = File::open(TEST_FONT_FILE_PATH).unwrap();
@@ -550,7 +615,361 @@ pub fn get_glyph_advance_and_origin() {
         .unwrap();
     let glyph = font.glyph_for_char('a').expect("No glyph for char!");
     assert_eq!(font.advance(glyph), Ok(Vector2F::new(1255.0, 0.0)));
-    assert_eq!(font.origin(glyph), Ok(Vector2F::default()));
+    assert_eq!(font.origin(glyph), Ok(Vector2F::new(-627.5, -1901.0)));
+}
+
+#[test]
+pub fn get_name_records() {
+    let font = Font::from_path(FILE_PATH_NAMES_TTF, 0).unwrap();
+
+    let family_names = font.name_records(name_id::FAMILY);
+    assert_eq!(family_names.len(), 6);
+    assert!(family_names.contains(&NameRecord {
+        name_id: name_id::FAMILY,
+        platform_id: platform_id::MACINTOSH,
+        encoding_id: 1,
+        language_id: 11,
+        language: Some("ja".to_owned()),
+        value: "マックフォント".to_owned(),
+    }));
+
+    // Windows names in legacy CJK encodings.
+    let unique_ids: Vec<_> = font
+        .name_records(name_id::UNIQUE_ID)
+        .into_iter()
+        .map(|record| (record.language.unwrap(), record.value))
+        .collect();
+    assert_eq!(
+        unique_ids,
+        [
+            ("ja-JP".to_owned(), "シフトJIS".to_owned()),
+            ("zh-TW".to_owned(), "大五碼".to_owned()),
+        ]
+    );
+
+    // Mac Roman, and the Unicode platform, which has no language.
+    let versions: Vec<_> = font
+        .name_records(name_id::VERSION)
+        .into_iter()
+        .map(|record| (record.language, record.value))
+        .collect();
+    assert_eq!(
+        versions,
+        [
+            (None, "Version 1.0".to_owned()),
+            (Some("en".to_owned()), "Version 1.0 – Café".to_owned()),
+        ]
+    );
+}
+
+#[test]
+pub fn get_localized_names() {
+    let font = Font::from_path(FILE_PATH_NAMES_TTF, 0).unwrap();
+    assert_eq!(font.full_name(), "Font Kit Names Regular");
+
+    let family_name = |language| font.localized_family_name(language).unwrap();
+    assert_eq!(family_name("en"), "Font Kit Names");
+    assert_eq!(family_name("ja"), "フォントキット名前");
+    assert_eq!(family_name("ja-JP"), "フォントキット名前");
+    assert_eq!(family_name("zh-TW"), "字型工具名稱");
+    assert_eq!(family_name("zh-Hant"), "字型工具名稱");
+    assert_eq!(family_name("zh-Hant-HK"), "字型工具名稱");
+    assert_eq!(family_name("zh-CN"), "字体工具名称");
+    assert_eq!(family_name("zh-Hans"), "字体工具名称");
+    // Languages without a name fall back to US English.
+    assert_eq!(family_name("ko"), "Font Kit Names");
+
+    assert_eq!(font.localized_subfamily_name("ja").unwrap(), "標準");
+    assert_eq!(font.localized_subfamily_name("fr-CA").unwrap(), "Normal");
+    // A language tag from the format 1 `name` table.
+    assert_eq!(
+        font.localized_subfamily_name("de-CH").unwrap(),
+        "Gewöhnlich"
+    );
+    assert_eq!(
+        font.localized_full_name("ja").unwrap(),
+        "フォントキット名前 標準"
+    );
+    assert_eq!(
+        font.localized_typographic_family_name("ja").unwrap(),
+        "Font Kit"
+    );
+
+    // The typographic family name falls back to the family name.
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    assert_eq!(
+        font.localized_typographic_family_name("en").unwrap(),
+        "Font Kit Kerning"
+    );
+    assert_eq!(font.localized_name(name_id::WWS_FAMILY, "en"), None);
+}
+
+#[test]
+pub fn get_style_and_typographic_names() {
+    let font = Font::from_path(FILE_PATH_NAMES_TTF, 0).unwrap();
+    assert_eq!(font.style_name(), "Regular");
+    assert_eq!(font.typographic_family_name(), "Font Kit");
+    assert_eq!(font.typographic_subfamily_name(), "Names");
+    // Without WWS names, the typographic names are used.
+    assert_eq!(font.wws_family_name(), "Font Kit");
+    assert_eq!(font.wws_subfamily_name(), "Names");
+
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    assert_eq!(font.style_name(), "Regular");
+    assert_eq!(font.typographic_family_name(), "EB Garamond");
+    assert_eq!(font.typographic_subfamily_name(), "12 Regular");
+}
+
+#[test]
+pub fn get_gpos_kerning() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    let glyph = |character| font.glyph_for_char(character).unwrap();
+    let (a, v, t, o) = (glyph('A'), glyph('V'), glyph('T'), glyph('o'));
+
+    // Format 1 pair adjustment. The `latn` script has a different `kern` feature that must not be
+    // used.
+    assert_eq!(font.kerning(a, v), Vector2F::new(-80.0, 0.0));
+    assert_eq!(font.kerning(v, a), Vector2F::new(-60.0, 0.0));
+    // Format 2 class pair adjustment, inside an extension lookup.
+    assert_eq!(font.kerning(t, o), Vector2F::new(-120.0, 0.0));
+    assert_eq!(font.kerning(v, o), Vector2F::new(-120.0, 0.0));
+    assert_eq!(font.kerning(t, t), Vector2F::default());
+    assert_eq!(font.kerning(o, t), Vector2F::default());
+
+    assert_eq!(
+        font.kerning_for_glyphs(&[a, v, a, t, o]),
+        [
+            Vector2F::new(-80.0, 0.0),
+            Vector2F::new(-60.0, 0.0),
+            Vector2F::default(),
+            Vector2F::new(-120.0, 0.0),
+            Vector2F::default(),
+        ]
+    );
+    assert_eq!(font.kerning_for_glyphs(&[]), []);
+}
+
+#[test]
+pub fn get_kern_table_kerning() {
+    let font = Font::from_path(FILE_PATH_KERN_TTF, 0).unwrap();
+    let glyph = |character| font.glyph_for_char(character).unwrap();
+    let (a, v, t, o) = (glyph('A'), glyph('V'), glyph('T'), glyph('o'));
+
+    // The cross-stream subtable is ignored.
+    assert_eq!(font.kerning(a, v), Vector2F::new(-70.0, 0.0));
+    assert_eq!(font.kerning(t, o), Vector2F::new(-90.0, 0.0));
+    assert_eq!(font.kerning(v, a), Vector2F::default());
+    assert_eq!(
+        font.kerning_for_glyphs(&[t, o]),
+        [Vector2F::new(-90.0, 0.0), Vector2F::default()]
+    );
+
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    assert_eq!(font.kerning(0, 0), Vector2F::default());
+}
+
+#[test]
+pub fn lay_out_text_in_lines() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    let layout = Layout::new(&font, "AV\nTo", 500.0, &LayoutOptions::default()).unwrap();
+    let positions: Vec<_> = layout.glyphs.iter().map(|glyph| glyph.position).collect();
+    assert_eq!(
+        positions,
+        [
+            Vector2F::new(0.0, 400.0),
+            Vector2F::new(260.0, 400.0),
+            Vector2F::new(0.0, 900.0),
+            Vector2F::new(240.0, 900.0),
+        ]
+    );
+    assert_eq!(layout.glyphs[3].glyph_id, font.glyph_for_char('o').unwrap());
+    assert_eq!(layout.glyphs[3].text_index, 4);
+    assert_eq!(layout.glyphs[3].line, 1);
+    assert_eq!(layout.line_height, 500.0);
+    assert_eq!(layout.lines[0].width, 560.0);
+    assert_eq!(layout.lines[1].width, 490.0);
+    assert_eq!(layout.lines[1].glyphs, 2..4);
+    assert_eq!(
+        layout.bounds,
+        RectF::new(Vector2F::zero(), Vector2F::new(560.0, 1000.0))
+    );
+    assert_eq!(
+        layout.ink_bounds(&font).unwrap(),
+        RectF::from_points(Vector2F::new(0.0, 50.0), Vector2F::new(560.0, 900.0))
+    );
+
+    // Every kind of line break counts, and missing characters get `.notdef`.
+    let layout = Layout::new(&font, "A\r\nV\r!\n", 500.0, &LayoutOptions::default()).unwrap();
+    let lines: Vec<_> = layout.lines.iter().map(|line| line.text.clone()).collect();
+    assert_eq!(lines, [0..1, 3..4, 5..6, 7..7]);
+    assert_eq!(layout.glyphs[2].glyph_id, 0);
+    assert_eq!(layout.bounds.height(), 2000.0);
+}
+
+#[test]
+pub fn lay_out_text_with_options() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    let options = LayoutOptions {
+        kerning: false,
+        letter_spacing: 10.0,
+        line_height: Some(600.0),
+    };
+    let layout = Layout::new(&font, "AV\nTo", 500.0, &options).unwrap();
+    assert_eq!(layout.glyphs[1].position, Vector2F::new(310.0, 400.0));
+    assert_eq!(layout.glyphs[3].position, Vector2F::new(310.0, 1000.0));
+    // Letter spacing only goes between glyphs.
+    assert_eq!(layout.lines[0].width, 610.0);
+    assert_eq!(layout.bounds.height(), 1100.0);
+}
+
+#[test]
+pub fn rasterize_layout() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    let rasterize = |text| {
+        let layout = Layout::new(&font, text, 20.0, &LayoutOptions::default()).unwrap();
+        let mut canvas = Canvas::new(Vector2I::new(32, 24), Format::A8);
+        layout
+            .rasterize(
+                &font,
+                &mut canvas,
+                Transform2F::default(),
+                HintingOptions::None,
+                RasterizationOptions::GrayscaleAa,
+            )
+            .unwrap();
+        canvas
+    };
+
+    // The kerned 'V' overlaps the 'A' without erasing any of it.
+    let (a, a_v, v) = (rasterize("A"), rasterize("AV"), rasterize(" V"));
+    assert!(a.pixels.iter().any(|&value| value != 0));
+    assert!(a
+        .pixels
+        .iter()
+        .zip(a_v.pixels.iter())
+        .all(|(&a, &a_v)| a <= a_v));
+    assert_ne!(a_v.pixels, v.pixels);
+}
+
+#[test]
+pub fn get_layout_outline() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    let layout = Layout::new(&font, "To", 500.0, &LayoutOptions::default()).unwrap();
+    let outline = layout.outline(&font, HintingOptions::None).unwrap();
+
+    let mut o = OutlineBuilder::new();
+    font.outline(
+        font.glyph_for_char('o').unwrap(),
+        HintingOptions::None,
+        &mut o,
+    )
+    .unwrap();
+    let o = o.into_outline();
+    let contours = &outline.contours[(outline.contours.len() - o.contours.len())..];
+    for (contour, o_contour) in contours.iter().zip(o.contours.iter()) {
+        let positions: Vec<_> = o_contour
+            .positions
+            .iter()
+            .map(|&position| Vector2F::new(240.0, 400.0) + position * Vector2F::new(0.5, -0.5))
+            .collect();
+        assert_eq!(contour.positions, positions);
+        assert_eq!(contour.flags, o_contour.flags);
+    }
+}
+
+#[test]
+pub fn get_glyph_names() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    assert_eq!(font.glyph_name(0).unwrap(), ".notdef");
+    assert_eq!(font.glyph_name(1).unwrap(), "space");
+    assert_eq!(font.glyph_name(3).unwrap(), "V");
+    assert_eq!(font.glyph_name(font.glyph_count()), None);
+
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    for glyph_id in 1..font.glyph_count() {
+        if let Some(name) = font.glyph_name(glyph_id) {
+            assert_eq!(font.glyph_by_name(&name), Some(glyph_id));
+        }
+    }
+}
+
+#[test]
+pub fn get_character_map_and_coverage() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    assert_eq!(
+        font.character_map(),
+        [(' ', 1), ('A', 2), ('T', 4), ('V', 3), ('o', 5)]
+    );
+    assert_eq!(font.chars_for_glyph(3), ['V']);
+    assert_eq!(font.chars_for_glyph(0), Vec::<char>::new());
+
+    let coverage = font.character_coverage();
+    assert_eq!(
+        coverage.ranges(),
+        [' '..=' ', 'A'..='A', 'T'..='T', 'V'..='V', 'o'..='o']
+    );
+    assert_eq!(coverage.len(), 5);
+    assert!(coverage.contains_all("VAT To"));
+    assert!(!coverage.contains('B'));
+
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    let character_map = font.character_map();
+    for &(character, glyph_id) in &character_map {
+        assert_eq!(font.glyph_for_char(character), Some(glyph_id));
+    }
+    let coverage = font.character_coverage();
+    assert_eq!(coverage.len(), character_map.len());
+    assert!(coverage
+        .ranges()
+        .iter()
+        .any(|range| range.contains(&'a') && range.contains(&'z')));
+    assert!(coverage.contains_all("Hello, world!"));
+    assert!(!coverage.contains('\u{4e00}'));
+    let a = font.glyph_for_char('a').unwrap();
+    assert!(font.chars_for_glyph(a).contains(&'a'));
+}
+
+#[test]
+pub fn get_vertical_metrics() {
+    let font = Font::from_path(FILE_PATH_VERTICAL_TTF, 0).unwrap();
+    let vertical_metrics = font.vertical_metrics();
+    assert_eq!(vertical_metrics.ascent, 500.0);
+    assert_eq!(vertical_metrics.descent, -500.0);
+
+    let (a, b) = (
+        font.glyph_for_char('A').unwrap(),
+        font.glyph_for_char('B').unwrap(),
+    );
+    assert_eq!(font.vertical_advance(a), Ok(1000.0));
+    assert_eq!(font.vertical_advance(b), Ok(600.0));
+    assert_eq!(font.vertical_origin(a), Ok(Vector2F::new(500.0, 850.0)));
+    assert_eq!(font.vertical_origin(b), Ok(Vector2F::new(250.0, 550.0)));
+    assert_eq!(
+        font.vertical_advance(font.glyph_count()),
+        Err(GlyphLoadingError::NoSuchGlyph)
+    );
+
+    // `VORG` takes precedence over `vmtx`, with a default for glyphs it doesn't list.
+    let font = Font::from_path(FILE_PATH_VERTICAL_ORIGIN_TTF, 0).unwrap();
+    assert_eq!(font.vertical_origin(a), Ok(Vector2F::new(500.0, 880.0)));
+    assert_eq!(font.vertical_origin(b), Ok(Vector2F::new(250.0, 550.0)));
+
+    // Fonts without vertical metrics get them synthesized from the ascent and descent.
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    let metrics = font.metrics();
+    let height = metrics.ascent - metrics.descent;
+    let vertical_metrics = font.vertical_metrics();
+    assert_eq!(vertical_metrics.ascent, height * 0.5);
+    assert_eq!(vertical_metrics.descent, -height * 0.5);
+    let glyph = font.glyph_for_char('a').unwrap();
+    assert_eq!(font.vertical_advance(glyph), Ok(height));
+    assert_eq!(
+        font.vertical_origin(glyph),
+        Ok(Vector2F::new(
+            font.advance(glyph).unwrap().x() * 0.5,
+            metrics.ascent
+        ))
+    );
 }
 
 #[cfg(all(
@@ -753,7 +1172,57 @@ pub fn rasterize_glyph_bilevel_offset() {
     check_L_shape(&canvas);
 }
 
+#[test]
+pub fn rasterize_skewed_glyph() {
+    let mut file = File::open(FILE_PATH_INCONSOLATA_TTF).unwrap();
+    let font = Font::from_file(&mut file, 0).unwrap();
+    let glyph_id = font.glyph_for_char('|').unwrap();
+    let size = 64.0;
+    // Slants the glyph to the right, as the Y axis points down.
+    let skew = Transform2F {
+        matrix: Matrix2x2F::row_major(1.0, -0.5, 0.0, 1.0),
+        vector: Vector2F::zero(),
+    };
+    let raster_rect = font
+        .raster_bounds(
+            glyph_id,
+            size,
+            skew,
+            HintingOptions::None,
+            RasterizationOptions::GrayscaleAa,
+        )
+        .unwrap();
+    let mut canvas = Canvas::new(raster_rect.size(), Format::A8);
+    font.rasterize_glyph(
+        &mut canvas,
+        glyph_id,
+        size,
+        Transform2F::from_translation(-raster_rect.origin().to_f32()) * skew,
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+    )
+    .unwrap();
+
+    // The vertical bar must lean right: ink near the top sits to the right of ink near the bottom.
+    let ink_center = |y: i32| {
+        let row = &canvas.pixels[(y as usize * canvas.stride)..][..canvas.size.x() as usize];
+        let (sum, weight) = row
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(sum, weight), (x, &value)| {
+                (sum + x as f32 * value as f32, weight + value as f32)
+            });
+        assert!(weight > 0.0);
+        sum / weight
+    };
+    let height = canvas.size.y();
+    let (top, bottom) = (ink_center(height / 4), ink_center(height * 3 / 4));
+    let expected = 0.5 * (height * 3 / 4 - height / 4) as f32;
+    assert!((top - bottom - expected).abs() < 2.0, "{} {}", top, bottom);
+}
+
 #[cfg(all(
+    not(feature = "loader-opentype-default"),
     feature = "source",
     any(
         not(any(target_os = "macos", target_os = "ios", target_family = "windows")),
@@ -797,13 +1266,13 @@ pub fn rasterize_glyph_with_full_hinting() {
         top_row = &canvas.pixels[canvas.stride..(2 * canvas.stride)];
     }
 
-    assert!(top_row.iter().any(|&value| value == 0xff));
+    assert!(top_row.contains(&0xff));
     for y in (0..(canvas.size.y() as usize)).rev() {
         let bottom_row = &canvas.pixels[(y * canvas.stride)..((y + 1) * canvas.stride)];
         if bottom_row.iter().all(|&value| value == 0) {
             continue;
         }
-        assert!(bottom_row.iter().any(|&value| value == 0xff));
+        assert!(bottom_row.contains(&0xff));
         break;
     }
 }
@@ -862,6 +1331,179 @@ pub fn rasterize_glyph_with_full_hinting_subpixel() {
     check_L_shape(&canvas);
 }
 
+#[test]
+pub fn build_gamma_tables() {
+    let identity = GammaLut::new(0.0, 1.0, 1.0);
+    for luminance in 0..=255 {
+        let table = identity.table(luminance);
+        assert!((0..=255).all(|coverage| table[coverage as usize] == coverage));
+    }
+
+    let gamma_lut = GammaLut::new(0.0, 2.2, 2.2);
+    for luminance in 0..=255 {
+        let table = gamma_lut.table(luminance);
+        assert_eq!((table[0], table[255]), (0, 255));
+        assert!(table.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+    // Dark text on a light background gets less coverage, and light text on a dark one more.
+    assert!(gamma_lut.table(0)[128] < 128);
+    assert!(gamma_lut.table(255)[128] > 128);
+
+    // Contrast raises partial coverage of dark text, and doesn't change full coverage.
+    let contrast_lut = GammaLut::new(0.5, 2.2, 2.2);
+    assert!(contrast_lut.table(0)[128] > gamma_lut.table(0)[128]);
+    assert_eq!(contrast_lut.table(0)[255], 255);
+
+    // sRGB is close to a gamma of 2.2.
+    let srgb_lut = GammaLut::new(0.0, 0.0, 0.0);
+    assert!((srgb_lut.table(0)[128] as i32 - gamma_lut.table(0)[128] as i32).abs() <= 8);
+}
+
+#[test]
+pub fn rasterize_glyph_with_gamma() {
+    let mut file = File::open(TEST_FONT_FILE_PATH).unwrap();
+    let font = Font::from_file(&mut file, 0).unwrap();
+    let glyph_id = font.glyph_for_char('a').unwrap();
+    let size = 24.0;
+    let gamma_lut = GammaLut::new(0.25, 0.0, 0.0);
+
+    for &(format, rasterization_options) in &[
+        (Format::A8, RasterizationOptions::GrayscaleAa),
+        (Format::Rgb24, RasterizationOptions::SubpixelAa),
+    ] {
+        let raster_rect = font
+            .raster_bounds(
+                glyph_id,
+                size,
+                Transform2F::default(),
+                HintingOptions::None,
+                rasterization_options,
+            )
+            .unwrap();
+        let transform = Transform2F::from_translation(-raster_rect.origin().to_f32());
+        let mut plain_canvas = Canvas::new(raster_rect.size(), format);
+        font.rasterize_glyph(
+            &mut plain_canvas,
+            glyph_id,
+            size,
+            transform,
+            HintingOptions::None,
+            rasterization_options,
+        )
+        .unwrap();
+
+        // Red text: the red channel is corrected as light text and the others as dark text.
+        let color = RgbaColor::new(255, 0, 0, 255);
+        let mut canvas = Canvas::new(raster_rect.size(), format);
+        font.rasterize_glyph_with_gamma(
+            &mut canvas,
+            glyph_id,
+            size,
+            transform,
+            HintingOptions::None,
+            rasterization_options,
+            &gamma_lut,
+            color,
+        )
+        .unwrap();
+
+        let tables: Vec<_> = match format {
+            // Pure red is about half as light as white, once encoded as sRGB.
+            Format::A8 => vec![gamma_lut.table(127)],
+            _ => vec![gamma_lut.table(255), gamma_lut.table(0), gamma_lut.table(0)],
+        };
+        assert!(plain_canvas
+            .pixels
+            .iter()
+            .any(|&value| value > 0 && value < 255));
+        for (index, (&value, &plain_value)) in
+            canvas.pixels.iter().zip(&plain_canvas.pixels).enumerate()
+        {
+            assert_eq!(value, tables[index % tables.len()][plain_value as usize]);
+        }
+
+        // What is already drawn is kept.
+        let mut canvas = Canvas::new(raster_rect.size(), format);
+        canvas.pixels.iter_mut().for_each(|value| *value = 255);
+        font.rasterize_glyph_with_gamma(
+            &mut canvas,
+            glyph_id,
+            size,
+            transform,
+            HintingOptions::None,
+            rasterization_options,
+            &gamma_lut,
+            color,
+        )
+        .unwrap();
+        assert!(canvas.pixels.iter().all(|&value| value == 255));
+    }
+}
+
+#[test]
+pub fn rasterize_glyph_with_gamma_onto_rgba32() {
+    let mut file = File::open(TEST_FONT_FILE_PATH).unwrap();
+    let font = Font::from_file(&mut file, 0).unwrap();
+    let glyph_id = font.glyph_for_char('a').unwrap();
+    let size = 24.0;
+    let gamma_lut = GammaLut::new(0.25, 0.0, 0.0);
+    let raster_rect = font
+        .raster_bounds(
+            glyph_id,
+            size,
+            Transform2F::default(),
+            HintingOptions::None,
+            RasterizationOptions::GrayscaleAa,
+        )
+        .unwrap();
+    let transform = Transform2F::from_translation(-raster_rect.origin().to_f32());
+    let mut plain_canvas = Canvas::new(raster_rect.size(), Format::Rgba32);
+    font.rasterize_glyph(
+        &mut plain_canvas,
+        glyph_id,
+        size,
+        transform,
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+    )
+    .unwrap();
+
+    // `Rgba32` is premultiplied, so no corrected channel may exceed the corrected alpha, which is
+    // corrected for the luminance of the text.
+    let color = RgbaColor::new(255, 0, 0, 255);
+    let mut glyph_canvas = Canvas::new(raster_rect.size(), Format::Rgba32);
+    glyph_canvas.pixels.copy_from_slice(&plain_canvas.pixels);
+    gamma_lut.preblend(&mut glyph_canvas, color);
+    let luminance_table = gamma_lut.table(127);
+    for (pixel, plain_pixel) in glyph_canvas
+        .pixels
+        .chunks(4)
+        .zip(plain_canvas.pixels.chunks(4))
+    {
+        assert_eq!(pixel[3], luminance_table[plain_pixel[3] as usize]);
+        assert!(pixel[..3].iter().all(|&value| value <= pixel[3]));
+    }
+
+    // Compositing onto an opaque background keeps it opaque.
+    let mut canvas = Canvas::new(raster_rect.size(), Format::Rgba32);
+    for pixel in canvas.pixels.chunks_mut(4) {
+        pixel.copy_from_slice(&[255, 255, 255, 255]);
+    }
+    font.rasterize_glyph_with_gamma(
+        &mut canvas,
+        glyph_id,
+        size,
+        transform,
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+        &gamma_lut,
+        color,
+    )
+    .unwrap();
+    assert!(canvas.pixels.chunks(4).all(|pixel| pixel[3] == 255));
+    assert!(canvas.pixels.chunks(4).any(|pixel| pixel[1] < 255));
+}
+
 #[cfg(all(feature = "source", target_family = "windows"))]
 #[test]
 pub fn rasterize_glyph() {
@@ -942,6 +1584,82 @@ pub fn rasterize_empty_glyph_on_empty_canvas() {
     .unwrap();
 }
 
+#[test]
+pub fn rasterize_glyph_to_every_format() {
+    let mut file = File::open(TEST_FONT_FILE_PATH).unwrap();
+    let font = Font::from_file(&mut file, 0).unwrap();
+    let glyph_id = font.glyph_for_char('a').unwrap();
+    let size = 24.0;
+    let raster_rect = font
+        .raster_bounds(
+            glyph_id,
+            size,
+            Transform2F::default(),
+            HintingOptions::None,
+            RasterizationOptions::GrayscaleAa,
+        )
+        .unwrap();
+    let rasterize = |format, offset: Vector2I, rasterization_options| {
+        let mut canvas = Canvas::new(raster_rect.size(), format);
+        font.rasterize_glyph(
+            &mut canvas,
+            glyph_id,
+            size,
+            Transform2F::from_translation((offset - raster_rect.origin()).to_f32()),
+            HintingOptions::None,
+            rasterization_options,
+        )
+        .unwrap();
+        canvas
+    };
+
+    for &rasterization_options in &[
+        RasterizationOptions::GrayscaleAa,
+        RasterizationOptions::Bilevel,
+    ] {
+        let width = raster_rect.width() as usize;
+        let whole_coverage = rasterize(Format::A8, Vector2I::zero(), rasterization_options);
+        assert!(whole_coverage.pixels.iter().any(|&value| value > 0));
+
+        // The glyph can also hang off the top left of the canvas, so that only part of it is
+        // drawn.
+        for &offset in &[Vector2I::zero(), Vector2I::new(-3, -5)] {
+            let coverage = rasterize(Format::A8, offset, rasterization_options);
+            let rgba = rasterize(Format::Rgba32Straight, offset, rasterization_options);
+            let bgra = rasterize(Format::Bgra32, offset, rasterization_options);
+            let premultiplied = rasterize(Format::Rgba32, offset, rasterization_options);
+            let bilevel = rasterize(Format::A1, offset, rasterization_options);
+            assert_eq!(bilevel.stride, width.div_ceil(8));
+
+            for (index, &value) in coverage.pixels.iter().enumerate() {
+                let (x, y) = (index % width, index / width);
+                let (whole_x, whole_y) = (x as i32 - offset.x(), y as i32 - offset.y());
+                if whole_x < raster_rect.width() && whole_y < raster_rect.height() {
+                    let whole_index = whole_y as usize * width + whole_x as usize;
+                    assert_eq!(value, whole_coverage.pixels[whole_index]);
+                }
+
+                // Coverage becomes white, with the coverage as alpha.
+                let start = index * 4;
+                let white = if value == 0 { 0 } else { 255 };
+                assert_eq!(
+                    rgba.pixels[start..(start + 4)],
+                    [white, white, white, value]
+                );
+                assert_eq!(
+                    bgra.pixels[start..(start + 4)],
+                    [white, white, white, value]
+                );
+                assert_eq!(premultiplied.pixels[start..(start + 4)], [value; 4]);
+
+                // `A1` pixels are set where they are at least half covered.
+                let bit = bilevel.pixels[y * bilevel.stride + x / 8] & (0x80 >> (x % 8)) != 0;
+                assert_eq!(bit, value >= 0x80);
+            }
+        }
+    }
+}
+
 #[cfg(feature = "source")]
 #[test]
 pub fn font_transform() {
@@ -993,26 +1711,470 @@ fn load_fonts_from_opentype_collection() {
     );
 }
 
+#[cfg(all(
+    feature = "source",
+    not(any(target_family = "windows", target_os = "macos", target_os = "ios"))
+))]
 #[test]
-fn get_glyph_count() {
-    let font = Font::from_path(TEST_FONT_FILE_PATH, 0).unwrap();
-    assert_eq!(font.glyph_count(), 3084);
+fn select_named_instance_of_variable_font() {
+    let handle = Handle::from_path(FILE_PATH_VARIABLE_TTF.into(), 0);
+    let source = MemSource::from_fonts(vec![handle].into_iter()).unwrap();
+    let postscript_names: Vec<String> = source
+        .all_fonts()
+        .unwrap()
+        .iter()
+        .map(|handle| handle.load().unwrap().postscript_name().unwrap())
+        .collect();
+    assert_eq!(
+        postscript_names,
+        [
+            "FontKitVariable-Regular",
+            "FontKitVariable-Thin",
+            "FontKitVariable-Bold",
+            "FontKitVariable-BoldCondensed",
+        ]
+    );
+
+    let font = source
+        .select_best_match(
+            &[FamilyName::Title("Font Kit Variable".to_owned())],
+            Properties::new()
+                .weight(Weight::BOLD)
+                .stretch(Stretch::CONDENSED),
+        )
+        .unwrap()
+        .load()
+        .unwrap();
+    assert_eq!(
+        font.postscript_name().unwrap(),
+        "FontKitVariable-BoldCondensed"
+    );
+    assert_eq!(font.full_name(), "Font Kit Variable Bold Condensed");
+    assert_eq!(font.properties().weight, Weight::BOLD);
+    assert_eq!(font.properties().stretch, Stretch::CONDENSED);
 }
 
-// The initial off-curve point used to cause an assertion in the FreeType backend.
 #[test]
-fn get_glyph_outline_eb_garamond_exclam() {
-    let mut file = File::open(FILE_PATH_EB_GARAMOND_TTF).unwrap();
-    let font = Font::from_file(&mut file, 0).unwrap();
-    let glyph = font.glyph_for_char('!').expect("No glyph for char!");
-    let mut outline_builder = OutlineBuilder::new();
-    font.outline(glyph, HintingOptions::None, &mut outline_builder)
-        .unwrap();
+fn get_color_palettes_and_layers() {
+    let font = Font::from_path(FILE_PATH_COLOR_TTF, 0).unwrap();
+    assert_eq!(
+        font.color_palettes(),
+        vec![
+            ColorPalette {
+                colors: vec![RgbaColor::new(255, 0, 0, 255), RgbaColor::new(0, 0, 0, 128)],
+            },
+            ColorPalette {
+                colors: vec![RgbaColor::new(0, 255, 0, 255), RgbaColor::new(0, 0, 0, 128)],
+            },
+        ]
+    );
 
-    // The TrueType spec doesn't specify the rounding method for midpoints, as far as I can tell.
-    // So we are lenient and accept either values rounded down (what Core Text provides if the
-    // first point is off-curve, it seems) or precise floating-point values (what our FreeType
-    // loader provides).
+    let glyph_id = font.glyph_for_char('A').unwrap();
+    assert_eq!(
+        font.color_glyph_layers(glyph_id).unwrap(),
+        vec![
+            ColorLayer {
+                glyph_id: 4,
+                color: LayerColor::Palette(0),
+            },
+            ColorLayer {
+                glyph_id: 5,
+                color: LayerColor::Foreground,
+            },
+        ]
+    );
+    assert_eq!(
+        font.color_glyph_layers(font.glyph_for_char('B').unwrap()),
+        None
+    );
+}
+
+#[test]
+fn rasterize_color_glyph() {
+    let font = Font::from_path(FILE_PATH_COLOR_TTF, 0).unwrap();
+    let foreground_color = RgbaColor::new(0, 0, 255, 255);
+    let rasterize = |character, palette_index| {
+        let mut canvas = Canvas::new(Vector2I::splat(10), Format::Rgba32);
+        font.rasterize_color_glyph(
+            &mut canvas,
+            font.glyph_for_char(character).unwrap(),
+            10.0,
+            Transform2F::from_translation(Vector2F::new(0.0, 10.0)),
+            HintingOptions::None,
+            RasterizationOptions::GrayscaleAa,
+            palette_index,
+            foreground_color,
+        )
+        .unwrap();
+        canvas
+    };
+    let pixel = |canvas: &Canvas, x: usize, y: usize| {
+        let start = y * canvas.stride + x * 4;
+        canvas.pixels[start..(start + 4)].to_vec()
+    };
+
+    // The left half of "A" is drawn in the foreground color on top of a square filled with the
+    // first palette entry.
+    let canvas = rasterize('A', 0);
+    assert_eq!(pixel(&canvas, 2, 5), [0, 0, 255, 255]);
+    assert_eq!(pixel(&canvas, 7, 5), [255, 0, 0, 255]);
+
+    let canvas = rasterize('A', 1);
+    assert_eq!(pixel(&canvas, 2, 5), [0, 0, 255, 255]);
+    assert_eq!(pixel(&canvas, 7, 5), [0, 255, 0, 255]);
+
+    // Out-of-range palettes fall back to the first palette.
+    let canvas = rasterize('A', 7);
+    assert_eq!(pixel(&canvas, 7, 5), [255, 0, 0, 255]);
+
+    // Other RGBA formats get the same colors, in their own channel order.
+    let mut canvas = Canvas::new(Vector2I::splat(10), Format::Bgra32);
+    font.rasterize_color_glyph(
+        &mut canvas,
+        font.glyph_for_char('A').unwrap(),
+        10.0,
+        Transform2F::from_translation(Vector2F::new(0.0, 10.0)),
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+        0,
+        foreground_color,
+    )
+    .unwrap();
+    assert_eq!(pixel(&canvas, 2, 5), [255, 0, 0, 255]);
+    assert_eq!(pixel(&canvas, 7, 5), [0, 0, 255, 255]);
+
+    // Glyphs without color layers are filled with the foreground color.
+    let canvas = rasterize('B', 0);
+    assert_eq!(pixel(&canvas, 2, 5), [0, 0, 255, 255]);
+    assert_eq!(pixel(&canvas, 7, 5), [0, 0, 0, 0]);
+
+    // Translucent colors are premultiplied, like the rest of the canvas.
+    let mut canvas = Canvas::new(Vector2I::splat(10), Format::Rgba32);
+    font.rasterize_color_glyph(
+        &mut canvas,
+        font.glyph_for_char('B').unwrap(),
+        10.0,
+        Transform2F::from_translation(Vector2F::new(0.0, 10.0)),
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+        0,
+        RgbaColor::new(0, 0, 255, 128),
+    )
+    .unwrap();
+    assert_eq!(pixel(&canvas, 2, 5), [0, 0, 128, 128]);
+}
+
+#[test]
+fn rasterize_color_glyph_paint_graph() {
+    let font = Font::from_path(FILE_PATH_COLOR_V1_TTF, 0).unwrap();
+    assert!(font.has_color_glyph_paint(font.glyph_for_char('A').unwrap()));
+    assert!(!font.has_color_glyph_paint(font.glyph_for_char(' ').unwrap()));
+
+    let foreground_color = RgbaColor::new(255, 255, 255, 255);
+    let rasterize = |character| {
+        let mut canvas = Canvas::new(Vector2I::splat(10), Format::Rgba32);
+        font.rasterize_color_glyph(
+            &mut canvas,
+            font.glyph_for_char(character).unwrap(),
+            10.0,
+            Transform2F::from_translation(Vector2F::new(0.0, 10.0)),
+            HintingOptions::None,
+            RasterizationOptions::GrayscaleAa,
+            0,
+            foreground_color,
+        )
+        .unwrap();
+        canvas
+    };
+    let pixel = |canvas: &Canvas, x: usize, y: usize| {
+        let start = y * canvas.stride + x * 4;
+        canvas.pixels[start..(start + 4)].to_vec()
+    };
+
+    // "A" is a horizontal red-to-blue linear gradient, with its bottom right quarter covered by
+    // a translated square in the foreground color.
+    let canvas = rasterize('A');
+    assert_eq!(pixel(&canvas, 0, 2), [242, 0, 13, 255]);
+    assert_eq!(pixel(&canvas, 4, 2), [140, 0, 115, 255]);
+    assert_eq!(pixel(&canvas, 9, 2), [13, 0, 242, 255]);
+    assert_eq!(pixel(&canvas, 7, 7), [255, 255, 255, 255]);
+
+    // "B" composites a green left half onto a red bottom half with the "source in" mode.
+    let canvas = rasterize('B');
+    assert_eq!(pixel(&canvas, 2, 7), [0, 255, 0, 255]);
+    assert_eq!(pixel(&canvas, 2, 2), [0, 0, 0, 0]);
+    assert_eq!(pixel(&canvas, 7, 7), [0, 0, 0, 0]);
+
+    // "C" is "A" rotated by 90 degrees counterclockwise, so the gradient runs from bottom to top
+    // and the foreground square moves to the top right.
+    let canvas = rasterize('C');
+    assert_eq!(pixel(&canvas, 2, 9), [242, 0, 13, 255]);
+    assert_eq!(pixel(&canvas, 2, 0), [13, 0, 242, 255]);
+    assert_eq!(pixel(&canvas, 7, 2), [255, 255, 255, 255]);
+
+    // "D" sweeps counterclockwise from red to blue, starting from the right of the center.
+    let canvas = rasterize('D');
+    assert!(pixel(&canvas, 9, 4)[0] > 240);
+    assert!(pixel(&canvas, 9, 5)[2] > 240);
+    assert_eq!(pixel(&canvas, 0, 5), [123, 0, 132, 255]);
+
+    // "E" is a radial gradient from red in the center to blue at the edges.
+    let canvas = rasterize('E');
+    assert_eq!(pixel(&canvas, 5, 5), [219, 0, 36, 255]);
+    assert_eq!(pixel(&canvas, 0, 0), [0, 0, 255, 255]);
+}
+
+#[test]
+fn get_fixed_sizes() {
+    let font = Font::from_path(FILE_PATH_CBDT_TTF, 0).unwrap();
+    let sizes = font.fixed_sizes();
+    assert_eq!(sizes.len(), 2);
+    let mut pixels_per_em: Vec<f32> = sizes.iter().map(|size| size.pixels_per_em.y()).collect();
+    pixels_per_em.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(pixels_per_em, [8.0, 16.0]);
+
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    assert_eq!(font.fixed_sizes(), Vec::<FixedSize>::new());
+}
+
+#[test]
+fn get_embedded_image() {
+    let font = Font::from_path(FILE_PATH_CBDT_TTF, 0).unwrap();
+    let (a, b, c) = (
+        font.glyph_for_char('A').unwrap(),
+        font.glyph_for_char('B').unwrap(),
+        font.glyph_for_char('C').unwrap(),
+    );
+
+    let image = font.embedded_image(a, 16.0).unwrap();
+    assert_eq!(image.format, EmbeddedImageFormat::Png);
+    assert!(image.data.starts_with(b"\x89PNG"));
+    assert_eq!(image.size, Vector2I::splat(16));
+    assert_eq!(image.origin, Vector2I::new(0, -16));
+    assert_eq!(
+        image.strike,
+        BitmapStrike {
+            pixels_per_em: Vector2I::splat(16),
+            pixels_per_inch: 72,
+            bit_depth: 32,
+        }
+    );
+
+    // The nearest strike wins, and the larger one when two are equally near.
+    let strike_size = |glyph_id, size| {
+        font.embedded_image(glyph_id, size)
+            .map(|image| image.strike.pixels_per_em.x())
+    };
+    assert_eq!(strike_size(a, 6.0), Some(8));
+    assert_eq!(strike_size(a, 8.0), Some(8));
+    assert_eq!(strike_size(a, 11.0), Some(8));
+    assert_eq!(strike_size(a, 12.0), Some(16));
+    assert_eq!(strike_size(a, 100.0), Some(16));
+    assert_eq!(strike_size(b, 8.0), Some(16));
+    assert_eq!(strike_size(c, 16.0), None);
+
+    let image = font.embedded_image(b, 16.0).unwrap();
+    assert_eq!(
+        image.format,
+        EmbeddedImageFormat::Raw {
+            bit_depth: 32,
+            bit_aligned: false,
+        }
+    );
+    assert_eq!(image.data, [0, 0, 128, 128].repeat(16));
+
+    let font = Font::from_path(FILE_PATH_SBIX_TTF, 0).unwrap();
+    let image = font.embedded_image(a, 20.0).unwrap();
+    assert_eq!(image.format, EmbeddedImageFormat::Png);
+    assert_eq!(image.size, Vector2I::splat(20));
+    assert_eq!(image.origin, Vector2I::new(0, -20));
+    assert_eq!(image.strike.pixels_per_em, Vector2I::splat(20));
+    assert_eq!(
+        font.embedded_image(a, 30.0).unwrap().strike.pixels_per_em,
+        Vector2I::splat(40)
+    );
+
+    // "B" is a duplicate of "A".
+    assert_eq!(font.embedded_image(b, 20.0).unwrap().data, image.data);
+    assert_eq!(font.embedded_image(c, 20.0), None);
+}
+
+#[test]
+fn get_embedded_image_from_sparse_index_subtables() {
+    // Index subtables of formats 4 and 5 list the glyphs that they hold by glyph ID, which need not
+    // be the first glyph that the subtable covers.
+    let font = Font::from_path(FILE_PATH_CBDT_INDEX_TTF, 0).unwrap();
+    let (a, b) = (
+        font.glyph_for_char('A').unwrap(),
+        font.glyph_for_char('B').unwrap(),
+    );
+
+    let image = font.embedded_image(a, 8.0).unwrap();
+    assert_eq!(image.format, EmbeddedImageFormat::Png);
+    assert!(image.data.starts_with(b"\x89PNG"));
+    assert_eq!(image.size, Vector2I::splat(8));
+    assert_eq!(image.origin, Vector2I::new(0, -8));
+    assert_eq!(image.strike.pixels_per_em, Vector2I::splat(8));
+
+    // The 16 pixel per em strike covers "A" but only holds an image for "B".
+    let image = font.embedded_image(a, 16.0).unwrap();
+    assert_eq!(image.strike.pixels_per_em, Vector2I::splat(8));
+    let image = font.embedded_image(b, 16.0).unwrap();
+    assert_eq!(image.strike.pixels_per_em, Vector2I::splat(16));
+    assert_eq!(image.data, [0, 0, 128, 128].repeat(16));
+}
+
+#[test]
+fn get_embedded_images_with_corrupt_strike_count() {
+    let mut font_data = vec![];
+    File::open(FILE_PATH_SBIX_TTF)
+        .unwrap()
+        .read_to_end(&mut font_data)
+        .unwrap();
+    let sbix_offset = font_table_offset(&font_data, b"sbix");
+    font_data[(sbix_offset + 4)..(sbix_offset + 8)].copy_from_slice(&[0xff; 4]);
+
+    let font = Font::from_bytes(Arc::new(font_data), 0).unwrap();
+    let a = font.glyph_for_char('A').unwrap();
+    assert_eq!(font.embedded_image(a, 20.0), None);
+    assert_eq!(font.fixed_sizes(), Vec::<FixedSize>::new());
+}
+
+#[test]
+fn rasterize_embedded_image() {
+    let rasterize = |font: &Font, character, size: i32| {
+        let mut canvas = Canvas::new(Vector2I::splat(size), Format::Rgba32);
+        font.rasterize_color_glyph(
+            &mut canvas,
+            font.glyph_for_char(character).unwrap(),
+            size as f32,
+            Transform2F::from_translation(Vector2F::new(0.0, size as f32)),
+            HintingOptions::None,
+            RasterizationOptions::GrayscaleAa,
+            0,
+            RgbaColor::BLACK,
+        )
+        .unwrap();
+        canvas
+    };
+    let pixel = |canvas: &Canvas, x: usize, y: usize| {
+        let start = y * canvas.stride + x * 4;
+        canvas.pixels[start..(start + 4)].to_vec()
+    };
+
+    // Each strike is drawn at its own size, and scaled up or down to other sizes.
+    let font = Font::from_path(FILE_PATH_CBDT_TTF, 0).unwrap();
+    let canvas = rasterize(&font, 'A', 8);
+    assert_eq!(pixel(&canvas, 4, 4), [0, 255, 0, 255]);
+    let canvas = rasterize(&font, 'A', 16);
+    assert_eq!(pixel(&canvas, 4, 4), [255, 0, 0, 255]);
+    assert_eq!(pixel(&canvas, 4, 12), [0, 0, 255, 255]);
+    let canvas = rasterize(&font, 'A', 32);
+    assert_eq!(pixel(&canvas, 8, 8), [255, 0, 0, 255]);
+    assert_eq!(pixel(&canvas, 8, 24), [0, 0, 255, 255]);
+
+    // Uncompressed images are premultiplied.
+    let canvas = rasterize(&font, 'B', 16);
+    assert_eq!(pixel(&canvas, 1, 14), [128, 0, 0, 128]);
+    assert_eq!(pixel(&canvas, 1, 10), [0, 0, 0, 0]);
+
+    // Straight alpha canvases get the color back undivided.
+    let mut canvas = Canvas::new(Vector2I::splat(16), Format::Rgba32Straight);
+    font.rasterize_color_glyph(
+        &mut canvas,
+        font.glyph_for_char('B').unwrap(),
+        16.0,
+        Transform2F::from_translation(Vector2F::new(0.0, 16.0)),
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+        0,
+        RgbaColor::BLACK,
+    )
+    .unwrap();
+    assert_eq!(pixel(&canvas, 1, 14), [255, 0, 0, 128]);
+
+    // Bitmap glyphs can also be rasterized without color, at the sizes of their strikes.
+    let mut canvas = Canvas::new(Vector2I::splat(16), Format::A8);
+    font.rasterize_glyph(
+        &mut canvas,
+        font.glyph_for_char('A').unwrap(),
+        16.0,
+        Transform2F::from_translation(Vector2F::new(0.0, 16.0)),
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+    )
+    .unwrap();
+    assert!(canvas.pixels[4 * 16 + 8] > 0);
+    assert!(canvas.pixels[12 * 16 + 8] > 0);
+
+    let font = Font::from_path(FILE_PATH_SBIX_TTF, 0).unwrap();
+    let canvas = rasterize(&font, 'A', 10);
+    assert_eq!(pixel(&canvas, 0, 0), [255, 0, 0, 255]);
+    assert_eq!(pixel(&canvas, 9, 9), [255, 0, 0, 255]);
+    let canvas = rasterize(&font, 'B', 20);
+    assert_eq!(pixel(&canvas, 10, 10), [255, 0, 0, 255]);
+}
+
+#[test]
+fn get_svg_glyph_document() {
+    let font = Font::from_path(FILE_PATH_SVG_TTF, 0).unwrap();
+    let (space, a, b, c) = (
+        font.glyph_for_char(' ').unwrap(),
+        font.glyph_for_char('A').unwrap(),
+        font.glyph_for_char('B').unwrap(),
+        font.glyph_for_char('C').unwrap(),
+    );
+    assert!(font.has_svg_glyph(a));
+    assert!(font.has_svg_glyph(c));
+    assert!(!font.has_svg_glyph(space));
+    assert_eq!(font.svg_glyph_document(space), None);
+
+    let document = font.svg_glyph_document(a).unwrap();
+    assert_eq!(document.glyph_ids, a..=a);
+    let text = String::from_utf8(document.document).unwrap();
+    assert!(text.starts_with("<svg"));
+    assert!(text.contains("id=\"glyph2\""));
+
+    // "B" and "C" share a compressed document.
+    let document = font.svg_glyph_document(b).unwrap();
+    assert_eq!(document.glyph_ids, b..=c);
+    let text = String::from_utf8(document.document.clone()).unwrap();
+    assert!(text.starts_with("<svg"));
+    assert!(text.contains("id=\"glyph3\""));
+    assert!(text.contains("id=\"glyph4\""));
+    assert_eq!(
+        font.svg_glyph_document(c),
+        Some(SvgGlyphDocument {
+            document: document.document,
+            glyph_ids: b..=c,
+        })
+    );
+
+    // Fonts without an `SVG ` table have no SVG glyphs.
+    let font = Font::from_path(FILE_PATH_COLOR_TTF, 0).unwrap();
+    assert!(!font.has_svg_glyph(font.glyph_for_char('A').unwrap()));
+}
+
+#[test]
+fn get_glyph_count() {
+    let font = Font::from_path(TEST_FONT_FILE_PATH, 0).unwrap();
+    assert_eq!(font.glyph_count(), 3084);
+}
+
+// The initial off-curve point used to cause an assertion in the FreeType backend.
+#[test]
+fn get_glyph_outline_eb_garamond_exclam() {
+    let mut file = File::open(FILE_PATH_EB_GARAMOND_TTF).unwrap();
+    let font = Font::from_file(&mut file, 0).unwrap();
+    let glyph = font.glyph_for_char('!').expect("No glyph for char!");
+    let mut outline_builder = OutlineBuilder::new();
+    font.outline(glyph, HintingOptions::None, &mut outline_builder)
+        .unwrap();
+
+    // The TrueType spec doesn't specify the rounding method for midpoints, as far as I can tell.
+    // So we are lenient and accept either values rounded down (what Core Text provides if the
+    // first point is off-curve, it seems) or precise floating-point values (what our FreeType
+    // loader provides).
     let mut outline = outline_builder.into_outline();
     for contour in &mut outline.contours {
         for position in &mut contour.positions {
@@ -1193,7 +2355,18 @@ fn get_glyph_outline_inconsolata_J() {
     );
 }
 
+// Returns the offset of a table in the data of a font file.
+fn font_table_offset(font_data: &[u8], table_tag: &[u8; 4]) -> usize {
+    let table_count = u16::from_be_bytes([font_data[4], font_data[5]]) as usize;
+    let record = font_data[12..(12 + table_count * 16)]
+        .chunks(16)
+        .find(|record| &record[..4] == table_tag)
+        .unwrap();
+    u32::from_be_bytes([record[8], record[9], record[10], record[11]]) as usize
+}
+
 // Makes sure that a canvas has an "L" shape in it. This is used to test rasterization.
+#[cfg(feature = "source")]
 #[allow(non_snake_case)]
 fn check_L_shape(canvas: &Canvas) {
     // Find any empty rows at the start.
@@ -1299,6 +2472,7 @@ fn stride_pixel_start(pixels: &[u8]) -> Option<u32> {
     None
 }
 
+#[cfg(feature = "source")]
 fn stripe_width(pixels: &[u8]) -> Option<u32> {
     let mut x = 0;
     // Find the initial empty part.
@@ -1321,3 +2495,1009 @@ fn stripe_width(pixels: &[u8]) -> Option<u32> {
     assert_eq!(x, pixels.len());
     Some(stripe_width)
 }
+
+#[cfg(feature = "source")]
+#[test]
+fn select_best_match_with_synthesis() {
+    let handles = vec![
+        Handle::from_path(TEST_FONT_FILE_PATH.into(), 0),
+        Handle::from_path(FILE_PATH_EB_GARAMOND_ITALIC_OTF.into(), 0),
+    ];
+    let family_name = handles[0].load().unwrap().family_name();
+    let source = MemSource::from_fonts(handles.into_iter()).unwrap();
+    let select = |properties: &Properties| {
+        let best_match = source
+            .select_best_match_with_synthesis(&[FamilyName::Title(family_name.clone())], properties)
+            .unwrap();
+        let postscript_name = best_match.handle.load().unwrap().postscript_name().unwrap();
+        (postscript_name, best_match.synthesis)
+    };
+
+    assert_eq!(
+        select(&Properties::new()),
+        ("EBGaramond12-Regular".to_owned(), Synthesis::default())
+    );
+    assert_eq!(
+        select(Properties::new().weight(Weight::BOLD)),
+        (
+            "EBGaramond12-Regular".to_owned(),
+            Synthesis {
+                bold: true,
+                oblique: false
+            }
+        )
+    );
+    assert_eq!(
+        select(Properties::new().weight(Weight::BOLD).style(Style::Italic)),
+        (
+            "EBGaramond12-Italic".to_owned(),
+            Synthesis {
+                bold: true,
+                oblique: false
+            }
+        )
+    );
+    assert_eq!(
+        select(
+            Properties::new()
+                .weight(Weight::MEDIUM)
+                .style(Style::Oblique)
+        ),
+        ("EBGaramond12-Italic".to_owned(), Synthesis::default())
+    );
+}
+
+#[test]
+fn synthesize_bold_and_oblique() {
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    let glyph_id = font.glyph_for_char('l').unwrap();
+    let units_per_em = font.metrics().units_per_em as f32;
+    let bounds = font.typographic_bounds(glyph_id).unwrap();
+    let advance = font.advance(glyph_id).unwrap();
+
+    let outline_bounds = |font: &SyntheticFont| {
+        let mut builder = OutlineBuilder::new();
+        font.outline(glyph_id, HintingOptions::None, &mut builder)
+            .unwrap();
+        let outline = builder.into_outline();
+        let mut points = outline
+            .contours
+            .iter()
+            .flat_map(|contour| contour.positions.iter());
+        let first = *points.next().unwrap();
+        points.fold(RectF::new(first, Vector2F::zero()), |bounds, &point| {
+            bounds.union_point(point)
+        })
+    };
+
+    let bold = SyntheticFont::new(
+        font.clone(),
+        Synthesis {
+            bold: true,
+            oblique: false,
+        },
+    );
+    let strength = units_per_em / 24.0;
+    assert_eq!(bold.embolden_strength(), strength);
+    assert_eq!(bold.properties().weight, Weight::BOLD);
+    assert_eq!(
+        bold.advance(glyph_id).unwrap(),
+        advance + Vector2F::new(strength, 0.0)
+    );
+    // 'l' is a single stroke with straight sides, so it grows by exactly the strength, keeping its
+    // left and bottom edges in place.
+    let bold_bounds = outline_bounds(&bold);
+    assert!((bold_bounds.width() - (bounds.width() + strength)).abs() < 1.0);
+    assert!((bold_bounds.min_x() - bounds.min_x()).abs() < 1.0);
+    assert!((bold_bounds.min_y() - bounds.min_y()).abs() < 1.0);
+
+    let oblique = SyntheticFont::new(
+        font.clone(),
+        Synthesis {
+            bold: false,
+            oblique: true,
+        },
+    );
+    assert_eq!(oblique.properties().style, Style::Oblique);
+    assert_eq!(oblique.advance(glyph_id).unwrap(), advance);
+    // Points are slanted about the baseline.
+    let outline = |font: &SyntheticFont| {
+        let mut builder = OutlineBuilder::new();
+        font.outline(glyph_id, HintingOptions::None, &mut builder)
+            .unwrap();
+        builder.into_outline()
+    };
+    let skew = 14.0f32.to_radians().tan();
+    let regular_outline = outline(&SyntheticFont::new(font.clone(), Synthesis::default()));
+    let oblique_outline = outline(&oblique);
+    for (regular, oblique) in regular_outline.contours[0]
+        .positions
+        .iter()
+        .zip(oblique_outline.contours[0].positions.iter())
+    {
+        assert!((oblique.x() - (regular.x() + regular.y() * skew)).abs() < 0.01);
+        assert_eq!(oblique.y(), regular.y());
+    }
+    let oblique_bounds = oblique.typographic_bounds(glyph_id).unwrap();
+    assert!(oblique_bounds.max_x() > bounds.max_x() + bounds.max_y() * skew - 1.0);
+}
+
+#[test]
+fn rasterize_synthetic_glyphs_within_raster_bounds() {
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    let glyph_id = font.glyph_for_char('a').unwrap();
+    let size = 32.0;
+
+    let coverage = |synthesis: Synthesis| {
+        let font = SyntheticFont::new(font.clone(), synthesis);
+        let raster_rect = font
+            .raster_bounds(
+                glyph_id,
+                size,
+                Transform2F::default(),
+                HintingOptions::None,
+                RasterizationOptions::GrayscaleAa,
+            )
+            .unwrap();
+
+        // Draw with a one-pixel margin and check that nothing lands in it.
+        let mut canvas = Canvas::new(raster_rect.size() + Vector2I::splat(2), Format::A8);
+        font.rasterize_glyph(
+            &mut canvas,
+            glyph_id,
+            size,
+            Transform2F::from_translation(Vector2F::splat(1.0) - raster_rect.origin().to_f32()),
+            HintingOptions::None,
+            RasterizationOptions::GrayscaleAa,
+        )
+        .unwrap();
+        let width = canvas.size.x() as usize;
+        let height = canvas.size.y() as usize;
+        for (index, &value) in canvas.pixels.iter().enumerate() {
+            let (x, y) = (index % width, index / width);
+            if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                assert_eq!(value, 0);
+            }
+        }
+        (
+            raster_rect,
+            canvas.pixels.iter().map(|&value| value as u32).sum::<u32>(),
+        )
+    };
+
+    let (regular_rect, regular_coverage) = coverage(Synthesis::default());
+    let (bold_rect, bold_coverage) = coverage(Synthesis {
+        bold: true,
+        oblique: false,
+    });
+    let (oblique_rect, oblique_coverage) = coverage(Synthesis {
+        bold: false,
+        oblique: true,
+    });
+    assert!(bold_rect.width() > regular_rect.width());
+    assert!(bold_coverage > regular_coverage * 5 / 4);
+    assert!(oblique_rect.width() > regular_rect.width());
+    assert!((oblique_coverage as f32 / regular_coverage as f32 - 1.0).abs() < 0.05);
+}
+
+#[cfg(all(
+    not(feature = "loader-opentype-default"),
+    any(
+        not(any(target_os = "macos", target_os = "ios", target_family = "windows")),
+        feature = "loader-freetype-default"
+    )
+))]
+#[test]
+fn rasterize_synthetic_bold_glyph_with_hinting() {
+    let font = SyntheticFont::new(
+        Font::from_path(FILE_PATH_INCONSOLATA_TTF, 0).unwrap(),
+        Synthesis {
+            bold: true,
+            oblique: false,
+        },
+    );
+    let glyph_id = font.font().glyph_for_char('i').unwrap();
+    let size = 10.0;
+
+    let rasterize = |hinting_options: HintingOptions| {
+        let raster_rect = font
+            .raster_bounds(
+                glyph_id,
+                size,
+                Transform2F::default(),
+                hinting_options,
+                RasterizationOptions::GrayscaleAa,
+            )
+            .unwrap();
+        let mut canvas = Canvas::new(raster_rect.size(), Format::A8);
+        font.rasterize_glyph(
+            &mut canvas,
+            glyph_id,
+            size,
+            Transform2F::from_translation(-raster_rect.origin().to_f32()),
+            hinting_options,
+            RasterizationOptions::GrayscaleAa,
+        )
+        .unwrap();
+        (raster_rect, canvas.pixels)
+    };
+
+    // The emboldened outline is grid-fitted first, so hinting must change the result.
+    assert_ne!(
+        rasterize(HintingOptions::None),
+        rasterize(HintingOptions::Full(size))
+    );
+}
+
+// Builds a contour from its path commands: a point alone is a line, and two or three points are a
+// quadratic or cubic curve.
+fn contour_from_segments(start: Vector2F, segments: &[&[Vector2F]]) -> Contour {
+    let mut builder = OutlineBuilder::new();
+    builder.move_to(start);
+    for segment in segments {
+        match **segment {
+            [to] => builder.line_to(to),
+            [ctrl, to] => builder.quadratic_curve_to(ctrl, to),
+            [ctrl0, ctrl1, to] => builder.cubic_curve_to(LineSegment2F::new(ctrl0, ctrl1), to),
+            _ => unreachable!(),
+        }
+    }
+    builder.close();
+    builder.into_outline().contours.remove(0)
+}
+
+fn square_contour(origin: Vector2F, size: f32) -> Contour {
+    contour_from_segments(
+        origin,
+        &[
+            &[origin + Vector2F::new(size, 0.0)],
+            &[origin + Vector2F::splat(size)],
+            &[origin + Vector2F::new(0.0, size)],
+        ],
+    )
+}
+
+#[test]
+fn get_exact_outline_bounds() {
+    let mut outline = Outline::new();
+    assert_eq!(outline.bounds(), RectF::default());
+
+    // The control points reach twice as far as the curves.
+    outline.contours.push(contour_from_segments(
+        Vector2F::zero(),
+        &[&[Vector2F::new(50.0, 100.0), Vector2F::new(100.0, 0.0)]],
+    ));
+    outline.contours.push(contour_from_segments(
+        Vector2F::zero(),
+        &[&[
+            Vector2F::new(0.0, -100.0),
+            Vector2F::new(100.0, -100.0),
+            Vector2F::new(100.0, 0.0),
+        ]],
+    ));
+    assert_eq!(
+        outline.bounds(),
+        RectF::from_points(Vector2F::new(0.0, -75.0), Vector2F::new(100.0, 50.0))
+    );
+
+    // The bounds of a glyph lie within its control box.
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    let glyph_id = font.glyph_for_char('o').unwrap();
+    let mut builder = OutlineBuilder::new();
+    font.outline(glyph_id, HintingOptions::None, &mut builder)
+        .unwrap();
+    let bounds = builder.into_outline().bounds();
+    let control_box = font.typographic_bounds(glyph_id).unwrap();
+    assert!(control_box.contains_rect(bounds));
+
+    // The bounds sink gives the same result between a loader and a consumer.
+    let mut sink = BoundsSink::new();
+    font.outline(glyph_id, HintingOptions::None, &mut sink)
+        .unwrap();
+    assert_eq!(sink.bounds(), bounds);
+}
+
+#[test]
+fn transform_and_flatten_outline() {
+    let mut outline = Outline::new();
+    outline.contours.push(contour_from_segments(
+        Vector2F::zero(),
+        &[&[Vector2F::new(50.0, 100.0), Vector2F::new(100.0, 0.0)]],
+    ));
+    let mut transformed = outline.clone();
+    transformed.transform(&Transform2F::from_translation(Vector2F::new(10.0, 0.0)));
+    assert_eq!(
+        transformed.contours[0].positions,
+        [
+            Vector2F::new(10.0, 0.0),
+            Vector2F::new(60.0, 100.0),
+            Vector2F::new(110.0, 0.0),
+        ]
+    );
+
+    // The same transform through a sink, as a loader would send it.
+    let mut builder = OutlineBuilder::new();
+    outline.copy_to(&mut TransformingSink::new(
+        &mut builder,
+        Transform2F::from_translation(Vector2F::new(10.0, 0.0)),
+    ));
+    assert_eq!(builder.into_outline(), transformed);
+
+    let flattened = outline.flatten(0.5);
+    let contour = &flattened.contours[0];
+    assert!(contour.positions.len() > 4);
+    assert!(contour.flags.iter().all(|flags| flags.is_empty()));
+    for &position in &contour.positions {
+        // The curve is the parabola `y = x (100 - x) / 50`.
+        let y = position.x() * (100.0 - position.x()) / 50.0;
+        assert!((position.y() - y).abs() <= 0.5);
+    }
+    assert!((flattened.area() - outline.area()).abs() < outline.area().abs() * 0.05);
+
+    let mut builder = OutlineBuilder::new();
+    outline.copy_to(&mut FlatteningSink::new(&mut builder, 0.5));
+    assert_eq!(builder.into_outline(), flattened);
+}
+
+#[test]
+fn convert_outline_to_cubics() {
+    let outline = Outline {
+        contours: vec![contour_from_segments(
+            Vector2F::zero(),
+            &[&[Vector2F::new(30.0, 60.0), Vector2F::new(90.0, 0.0)]],
+        )],
+    };
+    let contour = &outline.to_cubics().contours[0];
+    assert_eq!(
+        contour.flags,
+        [
+            PointFlags::empty(),
+            PointFlags::CONTROL_POINT_0,
+            PointFlags::CONTROL_POINT_1,
+            PointFlags::empty(),
+        ]
+    );
+    let expected = [(0.0, 0.0), (20.0, 40.0), (50.0, 40.0), (90.0, 0.0)];
+    for (position, &(x, y)) in contour.positions.iter().zip(expected.iter()) {
+        assert!((*position - Vector2F::new(x, y)).length() < 0.001);
+    }
+
+    let font = Font::from_path(FILE_PATH_EB_GARAMOND_TTF, 0).unwrap();
+    let mut builder = OutlineBuilder::new();
+    font.outline(
+        font.glyph_for_char('g').unwrap(),
+        HintingOptions::None,
+        &mut builder,
+    )
+    .unwrap();
+    let outline = builder.into_outline();
+    let cubics = outline.to_cubics();
+    for contour in &cubics.contours {
+        for (index, flags) in contour.flags.iter().enumerate() {
+            if flags.contains(PointFlags::CONTROL_POINT_0) {
+                assert_eq!(contour.flags[index + 1], PointFlags::CONTROL_POINT_1);
+            }
+        }
+    }
+    let (bounds, cubic_bounds) = (outline.bounds(), cubics.bounds());
+    assert!((bounds.origin() - cubic_bounds.origin()).length() < 0.01);
+    assert!((bounds.lower_right() - cubic_bounds.lower_right()).length() < 0.01);
+    assert!((cubics.area() - outline.area()).abs() < 1.0);
+}
+
+#[test]
+fn reverse_and_normalize_outline_winding() {
+    let square = square_contour(Vector2F::zero(), 10.0);
+    assert_eq!(square.area(), 100.0);
+    let mut reversed = square.clone();
+    reversed.reverse();
+    assert_eq!(reversed.area(), -100.0);
+    assert_eq!(reversed.positions[0], square.positions[0]);
+
+    // Cubic control points swap places, so the curve keeps its shape.
+    let curve = contour_from_segments(
+        Vector2F::zero(),
+        &[
+            &[Vector2F::new(10.0, 0.0)],
+            &[
+                Vector2F::new(15.0, 5.0),
+                Vector2F::new(15.0, 10.0),
+                Vector2F::new(10.0, 15.0),
+            ],
+            &[Vector2F::new(5.0, 20.0), Vector2F::new(0.0, 15.0)],
+        ],
+    );
+    let mut reversed = curve.clone();
+    reversed.reverse();
+    assert_eq!(
+        reversed.flags,
+        [
+            PointFlags::empty(),
+            PointFlags::empty(),
+            PointFlags::CONTROL_POINT_0,
+            PointFlags::empty(),
+            PointFlags::CONTROL_POINT_0,
+            PointFlags::CONTROL_POINT_1,
+            PointFlags::empty(),
+        ]
+    );
+    assert!((reversed.area() + curve.area()).abs() < 0.001);
+    reversed.reverse();
+    assert_eq!(reversed, curve);
+
+    // The outer contour runs clockwise and the hole counterclockwise, whichever way they started.
+    let mut outline = Outline {
+        contours: vec![
+            square_contour(Vector2F::zero(), 30.0),
+            square_contour(Vector2F::splat(10.0), 10.0),
+        ],
+    };
+    assert_eq!(outline.area(), 1000.0);
+    outline.normalize_winding();
+    assert_eq!(outline.contours[0].area(), -900.0);
+    assert_eq!(outline.contours[1].area(), 100.0);
+    assert_eq!(outline.area(), -800.0);
+
+    // TrueType glyphs are already oriented that way, and CFF glyphs are the other way around.
+    let glyph_outline = |path| {
+        let font = Font::from_path(path, 0).unwrap();
+        let mut builder = OutlineBuilder::new();
+        font.outline(
+            font.glyph_for_char('o').unwrap(),
+            HintingOptions::None,
+            &mut builder,
+        )
+        .unwrap();
+        builder.into_outline()
+    };
+    let truetype = glyph_outline(FILE_PATH_EB_GARAMOND_TTF);
+    let mut normalized = truetype.clone();
+    normalized.normalize_winding();
+    assert_eq!(normalized, truetype);
+    let mut cff = glyph_outline(TEST_FONT_FILE_PATH);
+    assert!(cff.area() > 0.0);
+    cff.normalize_winding();
+    assert!(cff.area() < 0.0);
+    assert!((cff.area() - truetype.area()).abs() < truetype.area().abs() * 0.01);
+}
+
+#[test]
+fn test_points_in_outline() {
+    let mut outline = Outline {
+        contours: vec![
+            square_contour(Vector2F::zero(), 30.0),
+            square_contour(Vector2F::splat(10.0), 10.0),
+        ],
+    };
+    // Both contours run counterclockwise, so the inner one adds to the winding.
+    assert_eq!(outline.winding_number(Vector2F::splat(15.0)), 2);
+    assert_eq!(outline.winding_number(Vector2F::splat(5.0)), 1);
+    assert_eq!(outline.winding_number(Vector2F::splat(35.0)), 0);
+    // Points level with a corner count once.
+    assert_eq!(outline.winding_number(Vector2F::new(5.0, 10.0)), 1);
+
+    outline.normalize_winding();
+    assert!(!outline.contains_point(Vector2F::splat(15.0)));
+    assert!(outline.contains_point(Vector2F::splat(5.0)));
+    assert!(!outline.contains_point(Vector2F::new(-1.0, 5.0)));
+
+    // Points inside the bulge of a curve, but outside its end points.
+    let outline = Outline {
+        contours: vec![contour_from_segments(
+            Vector2F::zero(),
+            &[&[
+                Vector2F::new(0.0, 100.0),
+                Vector2F::new(100.0, 100.0),
+                Vector2F::new(100.0, 0.0),
+            ]],
+        )],
+    };
+    assert!(outline.contains_point(Vector2F::new(50.0, 70.0)));
+    assert!(!outline.contains_point(Vector2F::new(50.0, 80.0)));
+    assert!(outline.contains_point(Vector2F::new(5.0, 30.0)));
+    assert!(!outline.contains_point(Vector2F::new(1.0, 30.0)));
+}
+
+#[test]
+fn write_outline_as_svg_path_data() {
+    let outline = Outline {
+        contours: vec![contour_from_segments(
+            Vector2F::zero(),
+            &[
+                &[Vector2F::new(100.0, 0.0)],
+                &[Vector2F::new(100.0, 50.0), Vector2F::new(50.0, 100.0)],
+                &[
+                    Vector2F::new(25.0, 100.0),
+                    Vector2F::new(0.0, 75.0),
+                    Vector2F::new(0.0, 50.0),
+                ],
+            ],
+        )],
+    };
+    assert_eq!(
+        outline.to_svg_path_data(&SvgPathOptions::default()),
+        "M0 0 L100 0 Q100 50 50 100 C25 100 0 75 0 50 Z"
+    );
+    let options = SvgPathOptions {
+        scale: 0.5,
+        flip_y: true,
+    };
+    assert_eq!(
+        outline.to_svg_path_data(&options),
+        "M0 0 L50 0 Q50 -25 25 -50 C12.5 -50 0 -37.5 0 -25 Z"
+    );
+
+    // This glyph is the rectangle (50, 0)-(450, 500).
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    let glyph_id = font.glyph_for_char('o').unwrap();
+    let mut sink = SvgPathSink::new(&options);
+    font.outline(glyph_id, HintingOptions::None, &mut sink)
+        .unwrap();
+    let path_data = sink.into_path_data();
+    assert!(path_data.starts_with('M'));
+    assert!(path_data.ends_with('Z'));
+    let outline = Outline::from_svg_path_data(&path_data).unwrap();
+    assert_eq!(
+        outline.bounds(),
+        RectF::from_points(Vector2F::new(25.0, -250.0), Vector2F::new(225.0, 0.0))
+    );
+}
+
+#[test]
+fn read_outline_from_svg_path_data() {
+    let outline = Outline::from_svg_path_data("M0 0 L100 0 Q100 50 50 100 Z").unwrap();
+    assert_eq!(
+        Outline::from_svg_path_data(&outline.to_svg_path_data(&SvgPathOptions::default())),
+        Ok(outline.clone())
+    );
+
+    // Relative commands, implicit repeats, and numbers run together, as minifiers write them.
+    let relative = Outline::from_svg_path_data("m0,0 100,0q0 50-50 100z").unwrap();
+    assert_eq!(relative, outline);
+
+    // Horizontal and vertical lines, and smooth curves, which reflect the previous control point.
+    let outline =
+        Outline::from_svg_path_data("M0 0H10V10h-10v-10zM20 0Q25 5 30 0T40 0S50 10 60 0").unwrap();
+    assert_eq!(outline.contours.len(), 2);
+    assert_eq!(
+        outline.contours[0].positions,
+        [
+            Vector2F::new(0.0, 0.0),
+            Vector2F::new(10.0, 0.0),
+            Vector2F::new(10.0, 10.0),
+            Vector2F::new(0.0, 10.0),
+            Vector2F::new(0.0, 0.0),
+        ]
+    );
+    assert_eq!(
+        outline.contours[1].positions,
+        [
+            Vector2F::new(20.0, 0.0),
+            Vector2F::new(25.0, 5.0),
+            Vector2F::new(30.0, 0.0),
+            Vector2F::new(35.0, -5.0),
+            Vector2F::new(40.0, 0.0),
+            Vector2F::new(40.0, 0.0),
+            Vector2F::new(50.0, 10.0),
+            Vector2F::new(60.0, 0.0),
+        ]
+    );
+    assert_eq!(outline.contours[1].flags[5], PointFlags::CONTROL_POINT_0);
+
+    // Two half-circle arcs make a circle of radius 50 around (50, 50).
+    let outline =
+        Outline::from_svg_path_data("M0 50 A50 50 0 0 1 100 50 a50 50 0 1 1 -100 0z").unwrap();
+    let bounds = outline.bounds();
+    assert!((bounds.origin() - Vector2F::zero()).length() < 0.01);
+    assert!((bounds.lower_right() - Vector2F::splat(100.0)).length() < 0.01);
+    let circle_area = std::f32::consts::PI * 50.0 * 50.0;
+    assert!((outline.area().abs() - circle_area).abs() < circle_area * 0.001);
+    assert!(outline.contains_point(Vector2F::splat(50.0)));
+    assert!(!outline.contains_point(Vector2F::splat(5.0)));
+
+    assert_eq!(Outline::from_svg_path_data(""), Ok(Outline::new()));
+    assert_eq!(
+        Outline::from_svg_path_data("L10 10"),
+        Err(SvgPathError::MissingMoveTo)
+    );
+    assert_eq!(
+        Outline::from_svg_path_data("M0 0 L10"),
+        Err(SvgPathError::MissingArguments(5))
+    );
+    assert_eq!(
+        Outline::from_svg_path_data("M0 0 X10 10"),
+        Err(SvgPathError::UnexpectedCharacter(5))
+    );
+}
+
+#[test]
+fn get_stroked_outline() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    assert_eq!(font.metrics().units_per_em, 1000);
+    // This glyph is the rectangle (50, 0)-(450, 500).
+    let glyph_id = font.glyph_for_char('o').unwrap();
+    let stroke = |line_join, miter_limit| {
+        let stroke_style = StrokeStyle {
+            line_join,
+            miter_limit,
+            ..StrokeStyle::new(20.0)
+        };
+        font.stroked_outline(glyph_id, HintingOptions::None, &stroke_style)
+            .unwrap()
+    };
+    let bounds = |contour: &Contour| {
+        let mut points = contour.positions.iter();
+        let first = *points.next().unwrap();
+        points.fold(RectF::new(first, Vector2F::zero()), |bounds, &point| {
+            bounds.union_point(point)
+        })
+    };
+
+    // Each contour has a border on either side.
+    let outline = stroke(LineJoin::Miter, 4.0);
+    assert_eq!(outline.contours.len(), 2);
+    assert_eq!(
+        bounds(&outline.contours[0]).union_rect(bounds(&outline.contours[1])),
+        RectF::from_points(Vector2F::new(40.0, -10.0), Vector2F::new(460.0, 510.0))
+    );
+    assert!(
+        outline.contours[0]
+            .positions
+            .contains(&Vector2F::new(40.0, -10.0))
+            || outline.contours[1]
+                .positions
+                .contains(&Vector2F::new(40.0, -10.0))
+    );
+
+    // Right angles need a miter limit of at least √2.
+    for outline in [stroke(LineJoin::Bevel, 4.0), stroke(LineJoin::Miter, 1.4)] {
+        for contour in &outline.contours {
+            assert!(!contour.positions.contains(&Vector2F::new(40.0, -10.0)));
+        }
+    }
+
+    // Round joins stay within half the width of the rectangle.
+    let outline = stroke(LineJoin::Round, 4.0);
+    let rect = RectF::from_points(Vector2F::new(50.0, 0.0), Vector2F::new(450.0, 500.0));
+    for contour in &outline.contours {
+        for &position in &contour.positions {
+            let nearest = position.clamp(rect.origin(), rect.lower_right());
+            assert!((position - nearest).length() <= 10.01);
+        }
+    }
+
+    let outline = font
+        .stroked_outline(glyph_id, HintingOptions::None, &StrokeStyle::new(0.0))
+        .unwrap();
+    assert!(outline.contours.is_empty());
+}
+
+#[test]
+fn rasterize_glyph_stroke() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    // At 100 pixels per em, this glyph is the rectangle (5, -50)-(45, 0) in pixels.
+    let glyph_id = font.glyph_for_char('o').unwrap();
+    let stroke_style = StrokeStyle {
+        line_join: LineJoin::Miter,
+        ..StrokeStyle::new(2.0)
+    };
+    let raster_rect = font
+        .stroke_raster_bounds(
+            glyph_id,
+            100.0,
+            Transform2F::default(),
+            &stroke_style,
+            HintingOptions::None,
+            RasterizationOptions::GrayscaleAa,
+        )
+        .unwrap();
+    assert_eq!(
+        raster_rect,
+        RectI::from_points(Vector2I::new(4, -51), Vector2I::new(46, 1))
+    );
+
+    let mut canvas = Canvas::new(raster_rect.size(), Format::A8);
+    font.rasterize_glyph_stroke(
+        &mut canvas,
+        glyph_id,
+        100.0,
+        Transform2F::from_translation(-raster_rect.origin().to_f32()),
+        &stroke_style,
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+    )
+    .unwrap();
+    let pixel = |x: i32, y: i32| canvas.pixels[y as usize * canvas.stride + x as usize];
+    // The stroke covers the two pixels either side of each edge, and nothing else.
+    for y in 0..raster_rect.height() {
+        for x in 0..raster_rect.width() {
+            let on_stroke = x <= 1 || y <= 1 || x >= 40 || y >= 50;
+            assert_eq!(pixel(x, y), if on_stroke { 255 } else { 0 }, "{} {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn rasterize_glyph_sdf() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    // At 20 pixels per em, this glyph is the rectangle (1, -10)-(9, 0) in pixels.
+    let glyph_id = font.glyph_for_char('o').unwrap();
+    let field = font.rasterize_glyph_sdf(glyph_id, 20.0, 4.0).unwrap();
+    assert_eq!(field.canvas.format, Format::A8);
+    assert_eq!(
+        field.bounds,
+        RectI::from_points(Vector2I::new(-3, -14), Vector2I::new(13, 4))
+    );
+    assert_eq!(field.canvas.size, field.bounds.size());
+
+    for y in 0..field.bounds.height() {
+        for x in 0..field.bounds.width() {
+            let point =
+                (field.bounds.origin() + Vector2I::new(x, y)).to_f32() + Vector2F::splat(0.5);
+            let inside = (point.x() - 1.0)
+                .min(9.0 - point.x())
+                .min(point.y() + 10.0)
+                .min(-point.y());
+            let outside = Vector2F::new(
+                (1.0 - point.x()).max(point.x() - 9.0).max(0.0),
+                (-10.0 - point.y()).max(point.y()).max(0.0),
+            )
+            .length();
+            let distance = if inside > 0.0 { inside } else { -outside };
+            let expected = ((0.5 + distance / 8.0).clamp(0.0, 1.0) * 255.0).round() as u8;
+            let value = field.canvas.pixels[y as usize * field.canvas.stride + x as usize];
+            assert_eq!(value, expected, "{} {}", x, y);
+        }
+    }
+
+    let space = font.glyph_for_char(' ').unwrap();
+    let field = font.rasterize_glyph_sdf(space, 20.0, 4.0).unwrap();
+    assert_eq!(field.canvas.size, Vector2I::default());
+}
+
+#[test]
+fn rasterize_glyph_msdf() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    let glyph_id = font.glyph_for_char('o').unwrap();
+    let sdf = font.rasterize_glyph_sdf(glyph_id, 20.0, 4.0).unwrap();
+    let msdf = font.rasterize_glyph_msdf(glyph_id, 20.0, 4.0).unwrap();
+    assert_eq!(msdf.canvas.format, Format::Rgb24);
+    assert_eq!(msdf.bounds, sdf.bounds);
+
+    let size = msdf.bounds.size();
+    let median = |x: i32, y: i32| {
+        let offset = y as usize * msdf.canvas.stride + x as usize * 3;
+        let pixel = &msdf.canvas.pixels[offset..offset + 3];
+        font_kit::sdf::median(pixel[0], pixel[1], pixel[2])
+    };
+    for y in 0..size.y() {
+        for x in 0..size.x() {
+            let distance = sdf.canvas.pixels[y as usize * sdf.canvas.stride + x as usize];
+            // Inside and alongside the edges, the median is the true distance. Beyond the
+            // corners, it keeps the corners sharp instead of rounding them off.
+            let beyond_corner = !(4..12).contains(&x) && !(4..14).contains(&y);
+            if beyond_corner {
+                assert!(
+                    median(x, y) >= distance && median(x, y) < 128,
+                    "{} {}",
+                    x,
+                    y
+                );
+            } else {
+                assert_eq!(median(x, y), distance, "{} {}", x, y);
+            }
+        }
+    }
+    // Diagonally beyond a corner, the distance is to the nearer of the two edges.
+    assert_eq!(median(1, 1), sdf.canvas.pixels[sdf.canvas.stride + 4]);
+
+    // Curved glyphs agree with the rasterizer about which pixels are inside.
+    let font = Font::from_path(TEST_FONT_FILE_PATH, 0).unwrap();
+    let glyph_id = font.glyph_for_char('e').unwrap();
+    let msdf = font.rasterize_glyph_msdf(glyph_id, 32.0, 2.0).unwrap();
+    let mut canvas = Canvas::new(msdf.bounds.size(), Format::A8);
+    font.rasterize_glyph(
+        &mut canvas,
+        glyph_id,
+        32.0,
+        Transform2F::from_translation(-msdf.bounds.origin().to_f32()),
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+    )
+    .unwrap();
+    for y in 0..msdf.bounds.height() as usize {
+        for x in 0..msdf.bounds.width() as usize {
+            let coverage = canvas.pixels[y * canvas.stride + x];
+            let pixel = &msdf.canvas.pixels[y * msdf.canvas.stride + x * 3..][..3];
+            let inside = font_kit::sdf::median(pixel[0], pixel[1], pixel[2]) >= 128;
+            if coverage == 0 || coverage == 255 {
+                assert_eq!(inside, coverage == 255, "{} {}", x, y);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "atlas")]
+fn atlas_glyph_key(atlas: &GlyphAtlas, font_id: FontId, character: char) -> GlyphKey {
+    GlyphKey {
+        font: font_id,
+        glyph_id: atlas
+            .font(font_id)
+            .unwrap()
+            .glyph_for_char(character)
+            .unwrap(),
+        point_size: 20.0,
+        subpixel_offset: Vector2F::default(),
+        hinting_options: HintingOptions::None,
+        rasterization_options: RasterizationOptions::GrayscaleAa,
+    }
+}
+
+#[cfg(feature = "atlas")]
+#[test]
+pub fn pack_glyphs_into_atlas() {
+    let font = Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap();
+    let mut atlas = GlyphAtlas::new(AtlasOptions {
+        page_size: Vector2I::new(32, 16),
+        ..AtlasOptions::default()
+    });
+    let font_id = atlas.add_font(font.clone());
+
+    // 'A' is 12×14 pixels at 20 points, drawn from 14 pixels above the baseline.
+    let key = atlas_glyph_key(&atlas, font_id, 'A');
+    let entry = atlas.get(&key).unwrap();
+    assert_eq!(entry.page, 0);
+    assert_eq!(
+        entry.rect,
+        RectI::new(Vector2I::splat(1), Vector2I::new(12, 14))
+    );
+    assert_eq!(entry.bearing, Vector2I::new(0, -14));
+    assert_eq!(
+        entry.uv_rect,
+        RectF::new(
+            Vector2F::new(1.0 / 32.0, 1.0 / 16.0),
+            Vector2F::new(0.375, 0.875)
+        )
+    );
+    assert_eq!(atlas.get(&key).unwrap(), entry);
+    assert_eq!(atlas.len(), 1);
+
+    // The page holds the same pixels as rasterizing the glyph directly.
+    let mut canvas = Canvas::new(entry.rect.size(), Format::A8);
+    font.rasterize_glyph(
+        &mut canvas,
+        key.glyph_id,
+        20.0,
+        Transform2F::from_translation(Vector2F::new(0.0, 14.0)),
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+    )
+    .unwrap();
+    let page = atlas.page(0).unwrap();
+    for y in 0..14 {
+        let start = (y + 1) * page.stride + 1;
+        assert_eq!(
+            page.pixels[start..start + 12],
+            canvas.pixels[y * canvas.stride..y * canvas.stride + 12]
+        );
+    }
+
+    // The padded slot is reported dirty once.
+    let dirty_region = DirtyRegion {
+        page: 0,
+        rect: RectI::new(Vector2I::default(), Vector2I::new(14, 16)),
+    };
+    assert_eq!(atlas.take_dirty_regions(), vec![dirty_region]);
+    assert!(atlas.take_dirty_regions().is_empty());
+
+    // A space takes up no room.
+    let space = atlas.get(&atlas_glyph_key(&atlas, font_id, ' ')).unwrap();
+    assert!(space.is_empty());
+    assert!(atlas.take_dirty_regions().is_empty());
+
+    // Subpixel offsets are quantized to quarter pixels.
+    let mut shifted_key = key;
+    shifted_key.subpixel_offset = Vector2F::new(0.3, 0.0);
+    let shifted = atlas.get(&shifted_key).unwrap();
+    assert_ne!(shifted.rect, entry.rect);
+    shifted_key.subpixel_offset = Vector2F::new(0.45, 0.0);
+    assert_eq!(atlas.get(&shifted_key).unwrap(), shifted);
+}
+
+#[cfg(feature = "atlas")]
+#[test]
+pub fn evict_least_recently_used_atlas_glyphs() {
+    // The page has room for two 'A'-sized glyphs side by side.
+    let mut atlas = GlyphAtlas::new(AtlasOptions {
+        page_size: Vector2I::new(32, 16),
+        ..AtlasOptions::default()
+    });
+    let font_id = atlas.add_font(Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap());
+    let (a, v, t) = (
+        atlas_glyph_key(&atlas, font_id, 'A'),
+        atlas_glyph_key(&atlas, font_id, 'V'),
+        atlas_glyph_key(&atlas, font_id, 'T'),
+    );
+
+    atlas.get(&a).unwrap();
+    let v_entry = atlas.get(&v).unwrap();
+    assert_eq!(atlas.get(&t), Err(AtlasError::Full));
+
+    // 'V' is the least recently used glyph once 'A' is used again, so 'T' takes its place.
+    atlas.begin_frame();
+    atlas.take_dirty_regions();
+    let a_entry = atlas.get(&a).unwrap();
+    let t_entry = atlas.get(&t).unwrap();
+    assert_eq!(t_entry.rect, v_entry.rect);
+    assert_eq!(atlas.len(), 2);
+    assert_eq!(
+        atlas.take_dirty_regions(),
+        vec![DirtyRegion {
+            page: 0,
+            rect: RectI::new(Vector2I::new(14, 0), Vector2I::new(14, 16)),
+        }]
+    );
+
+    // Glyphs used during the current frame are never evicted.
+    assert_eq!(atlas.get(&v), Err(AtlasError::Full));
+    assert_eq!(atlas.get(&a).unwrap(), a_entry);
+    assert_eq!(atlas.get(&t).unwrap(), t_entry);
+}
+
+#[cfg(feature = "atlas")]
+#[test]
+pub fn reuse_evicted_atlas_space() {
+    // The page has room for three 'A'-sized glyphs side by side.
+    let mut atlas = GlyphAtlas::new(AtlasOptions {
+        page_size: Vector2I::new(42, 16),
+        ..AtlasOptions::default()
+    });
+    let font_id = atlas.add_font(Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap());
+    let (a, v, t, o) = (
+        atlas_glyph_key(&atlas, font_id, 'A'),
+        atlas_glyph_key(&atlas, font_id, 'V'),
+        atlas_glyph_key(&atlas, font_id, 'T'),
+        atlas_glyph_key(&atlas, font_id, 'o'),
+    );
+    atlas.get(&a).unwrap();
+    atlas.get(&v).unwrap();
+    atlas.get(&t).unwrap();
+
+    // 'o' goes where 'V' was, in the middle of the shelf.
+    atlas.begin_frame();
+    atlas.get(&a).unwrap();
+    atlas.get(&t).unwrap();
+    let o_entry = atlas.get(&o).unwrap();
+    assert_eq!(
+        o_entry.rect,
+        RectI::new(Vector2I::new(15, 1), Vector2I::new(8, 10))
+    );
+    assert_eq!(atlas.len(), 3);
+
+    // Shelves that become empty are merged, so a taller glyph fits in their place.
+    let mut atlas = GlyphAtlas::new(AtlasOptions {
+        page_size: Vector2I::new(14, 40),
+        ..AtlasOptions::default()
+    });
+    let font_id = atlas.add_font(Font::from_path(FILE_PATH_KERNING_TTF, 0).unwrap());
+    let (a, t, o) = (
+        atlas_glyph_key(&atlas, font_id, 'A'),
+        atlas_glyph_key(&atlas, font_id, 'T'),
+        atlas_glyph_key(&atlas, font_id, 'o'),
+    );
+    let mut shifted_o = o;
+    shifted_o.subpixel_offset = Vector2F::new(0.5, 0.0);
+    atlas.get(&o).unwrap();
+    atlas.get(&shifted_o).unwrap();
+    atlas.get(&t).unwrap();
+    assert_eq!(atlas.get(&a), Err(AtlasError::Full));
+
+    atlas.begin_frame();
+    atlas.get(&t).unwrap();
+    let a_entry = atlas.get(&a).unwrap();
+    assert_eq!(
+        a_entry.rect,
+        RectI::new(Vector2I::splat(1), Vector2I::new(12, 14))
+    );
+    assert_eq!(atlas.len(), 2);
+}